@@ -0,0 +1,206 @@
+//! 처리량 벤치마크 (feature: bench)
+//!
+//! 핫 패스의 성능 회귀를 잡기 위한 criterion 벤치마크입니다.
+//! - 청킹 속도 (MarkdownChunker)
+//! - RRF 통합 (대량 후보)
+//! - FTS5 검색 지연
+//! - LanceDB 검색 지연 (벡터 수별)
+//!
+//! ## 실행
+//! ```bash
+//! cargo bench --features bench
+//!
+//! # Lance 벡터 수 지정 (기본: 10000)
+//! PALANK_BENCH_VECTORS=10000,100000,1000000 cargo bench --features bench -- lance
+//! ```
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use palank_rag::knowledge::{
+    rrf_fuse, ChunkConfig, Chunker, FtsSearchResult, KnowledgeStore, LanceVectorStore,
+    MarkdownChunker, NewDocument, SearchResult, VectorEntry, VectorStore, EMBEDDING_DIMENSION,
+};
+use tempfile::TempDir;
+
+/// Lance 벡터 수 환경변수
+const VECTORS_ENV: &str = "PALANK_BENCH_VECTORS";
+
+/// Lance 삽입 배치 크기
+const INSERT_BATCH: usize = 10_000;
+
+// ============================================================================
+// Fixtures
+// ============================================================================
+
+/// 결정적 의사 난수 생성기 (xorshift)
+struct Rng(u64);
+
+impl Rng {
+    fn next_f32(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    fn vector(&mut self) -> Vec<f32> {
+        (0..EMBEDDING_DIMENSION).map(|_| self.next_f32()).collect()
+    }
+}
+
+/// 섹션/코드 블록이 섞인 Markdown 문서 생성
+fn markdown_document(sections: usize) -> String {
+    let mut text = String::new();
+    for i in 0..sections {
+        text.push_str(&format!("## Section {}\n\n", i));
+        for p in 0..4 {
+            text.push_str(&format!(
+                "Paragraph {} of section {} explains hybrid retrieval with keyword and vector \
+                 search, reciprocal rank fusion, and chunking strategies for RAG pipelines.\n\n",
+                p, i
+            ));
+        }
+        text.push_str("```rust\nfn main() {\n    println!(\"hello\");\n}\n```\n\n");
+    }
+    text
+}
+
+/// 벤치마크할 Lance 벡터 수 목록
+fn vector_sizes() -> Vec<usize> {
+    std::env::var(VECTORS_ENV)
+        .ok()
+        .map(|v| {
+            v.split(',')
+                .filter_map(|s| s.trim().parse().ok())
+                .collect::<Vec<usize>>()
+        })
+        .filter(|sizes| !sizes.is_empty())
+        .unwrap_or_else(|| vec![10_000])
+}
+
+// ============================================================================
+// Benchmarks
+// ============================================================================
+
+fn bench_chunking(c: &mut Criterion) {
+    let mut group = c.benchmark_group("chunking");
+
+    for sections in [10, 100, 1000] {
+        let text = markdown_document(sections);
+        let chunker = MarkdownChunker::new(ChunkConfig::default());
+
+        group.throughput(Throughput::Bytes(text.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(sections), &text, |b, text| {
+            b.iter(|| chunker.chunk(black_box(text)))
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_rrf_fusion(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rrf_fuse");
+
+    for size in [1_000, 10_000, 100_000] {
+        let fts: Vec<FtsSearchResult> = (0..size)
+            .map(|i| FtsSearchResult {
+                doc_id: i as i64,
+                title: None,
+                content_snippet: String::new(),
+                bm25_score: -(i as f64),
+            })
+            .collect();
+        // 절반은 FTS와 겹치는 문서
+        let vector: Vec<SearchResult> = (0..size)
+            .map(|i| SearchResult {
+                doc_id: (i + size / 2) as i64,
+                chunk_index: 0,
+                chunk_text: String::new(),
+                similarity: 1.0 / (1.0 + i as f32),
+            })
+            .collect();
+
+        group.throughput(Throughput::Elements((size * 2) as u64));
+        group.bench_function(BenchmarkId::from_parameter(size), |b| {
+            b.iter(|| rrf_fuse(black_box(&fts), black_box(&vector), 10))
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_fts_search(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fts_search");
+
+    for docs in [1_000, 10_000] {
+        let dir = TempDir::new().expect("temp dir");
+        let store = KnowledgeStore::open(&dir.path().join("bench.db")).expect("open store");
+        let body = markdown_document(2);
+
+        for i in 0..docs {
+            store
+                .add_document(NewDocument {
+                    url: format!("bench://doc/{}", i),
+                    title: Some(format!("Document {}", i)),
+                    content: format!("{}\n\nunique token doc{}", body, i),
+                    framework: None,
+                    ..Default::default()
+                })
+                .expect("add document");
+        }
+
+        group.bench_function(BenchmarkId::from_parameter(docs), |b| {
+            b.iter(|| store.search_fts(black_box("hybrid retrieval fusion"), 10, None))
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_lance_search(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().expect("tokio runtime");
+    let mut group = c.benchmark_group("lance_search");
+    group.sample_size(20);
+
+    for size in vector_sizes() {
+        let dir = TempDir::new().expect("temp dir");
+        let store = runtime
+            .block_on(LanceVectorStore::open(&dir.path().join("bench.lance")))
+            .expect("open lance");
+
+        let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+        let mut inserted = 0;
+        while inserted < size {
+            let batch: Vec<VectorEntry> = (inserted..(inserted + INSERT_BATCH).min(size))
+                .map(|i| VectorEntry {
+                    doc_id: i as i64,
+                    chunk_index: 0,
+                    chunk_text: format!("chunk {}", i),
+                    embedding: rng.vector(),
+                })
+                .collect();
+            inserted += batch.len();
+            runtime
+                .block_on(store.insert_batch(&batch))
+                .expect("insert batch");
+        }
+
+        let query = rng.vector();
+        group.bench_function(BenchmarkId::from_parameter(size), |b| {
+            b.to_async(&runtime)
+                .iter(|| store.search(black_box(&query), 10))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_chunking,
+    bench_rrf_fusion,
+    bench_fts_search,
+    bench_lance_search
+);
+criterion_main!(benches);
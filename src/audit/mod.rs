@@ -0,0 +1,277 @@
+//! 네트워크 감사 모듈 - 외부 통신 엔드포인트 목록
+//!
+//! palank-rag는 텔레메트리를 전송하지 않습니다.
+//! 현재 설정에서 기기 밖으로 나갈 수 있는 모든 요청 대상을 나열하여
+//! "오프라인 RAG" 설치를 보안 검토할 때 근거로 사용할 수 있게 합니다.
+
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::daemon::JobAction;
+use crate::embedding::{OversizeStrategy, GEMINI_EMBED_URL};
+use crate::extractor::image::GEMINI_VISION_URL;
+use crate::generation::GEMINI_GENERATE_URL;
+
+/// 웹 페이지를 스크랩하는 명령어 (모두 `[url_policy]`를 따름)
+const SCRAPER_TRIGGER: &str = "ingest --url (--changelog 포함), ingest --bookmarks --scrape, \
+                               chunk-preview --url, daemon (refresh/ingest 작업)";
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// 외부 통신 엔드포인트
+#[derive(Debug, Clone, Serialize)]
+pub struct OutboundEndpoint {
+    /// 요청을 보내는 구성 요소
+    pub component: &'static str,
+    /// 대상 엔드포인트 (URL 또는 도메인 패턴)
+    pub endpoint: String,
+    /// 전송되는 데이터
+    pub sends: &'static str,
+    /// 요청이 발생하는 명령어
+    pub trigger: String,
+    /// 현재 설정에서 실제로 호출 가능한지 (API 키 등)
+    pub active: bool,
+}
+
+// ============================================================================
+// Audit
+// ============================================================================
+
+/// 현재 설정이 접속할 수 있는 외부 엔드포인트 목록
+///
+/// # Arguments
+/// * `config` - 로드된 설정 (URL 정책)
+/// * `has_api_key` - Gemini API 키 설정 여부
+pub fn outbound_endpoints(config: &Config, has_api_key: bool) -> Vec<OutboundEndpoint> {
+    // 스크린샷 감시(watch 명령, 데몬의 [[watch]] 폴더)도 이미지를 Vision으로 보냄
+    let mut vision_trigger =
+        "ingest --file/--dir/--bib (이미지, --skip-images 미지정 시), watch".to_string();
+    if !config.watch.is_empty() {
+        let dirs: Vec<String> = config
+            .watch
+            .iter()
+            .map(|w| w.dir.display().to_string())
+            .collect();
+        vision_trigger.push_str(&format!(", daemon ([[watch]] 폴더: {})", dirs.join(", ")));
+    }
+
+    let mut endpoints = vec![
+        OutboundEndpoint {
+            component: "embedding",
+            endpoint: GEMINI_EMBED_URL.to_string(),
+            sends: "청크 텍스트, 검색 쿼리",
+            trigger: "ingest, query".to_string(),
+            active: has_api_key,
+        },
+        OutboundEndpoint {
+            component: "vision",
+            endpoint: GEMINI_VISION_URL.to_string(),
+            sends: "이미지 파일 (base64)",
+            trigger: vision_trigger,
+            active: has_api_key,
+        },
+        OutboundEndpoint {
+            component: "generation",
+            endpoint: GEMINI_GENERATE_URL.to_string(),
+            sends: "질문, 검색된 문서 청크",
+            trigger: "ask (--context-only 미지정 시)".to_string(),
+            active: has_api_key,
+        },
+        OutboundEndpoint {
+            component: "sql",
+            endpoint: "--conn으로 지정한 Postgres 서버 (postgres://...)".to_string(),
+            sends: "SQL 쿼리, 접속 계정 (TLS 없이 연결)",
+            trigger: "ingest --sql --conn postgres://...".to_string(),
+            active: cfg!(feature = "postgres"),
+        },
+    ];
+
+    if config.embedding.oversize == OversizeStrategy::Summarize {
+        endpoints.push(OutboundEndpoint {
+            component: "summarize",
+            endpoint: GEMINI_GENERATE_URL.to_string(),
+            sends: "입력 한도를 넘는 청크 원문",
+            trigger: "ingest, reembed, repair, watch, daemon (oversize = summarize)".to_string(),
+            active: has_api_key,
+        });
+    }
+
+    let policy = &config.url_policy;
+    if policy.allow_domains.is_empty() {
+        endpoints.push(OutboundEndpoint {
+            component: "scraper",
+            endpoint: if policy.allow_private_networks {
+                "사용자가 지정한 모든 URL (내부망 포함)".to_string()
+            } else {
+                "사용자가 지정한 모든 공개 URL (내부망 차단)".to_string()
+            },
+            sends: "HTTP GET 요청",
+            trigger: SCRAPER_TRIGGER.to_string(),
+            active: true,
+        });
+    } else {
+        for domain in &policy.allow_domains {
+            endpoints.push(OutboundEndpoint {
+                component: "scraper",
+                endpoint: format!("https://{}/* (하위 도메인 포함)", domain),
+                sends: "HTTP GET 요청",
+                trigger: SCRAPER_TRIGGER.to_string(),
+                active: true,
+            });
+        }
+    }
+
+    // 데몬 예약 작업이 실제로 요청할 대상 (정책 검사는 위 scraper와 같음)
+    for job in &config.jobs {
+        match job.action {
+            JobAction::Refresh { ref framework, .. } => endpoints.push(OutboundEndpoint {
+                component: "daemon",
+                endpoint: match framework {
+                    Some(framework) => format!("저장된 웹 문서 URL ({} 태그)", framework),
+                    None => "저장된 모든 웹 문서 URL".to_string(),
+                },
+                sends: "HTTP GET 요청",
+                trigger: "daemon (refresh 작업)".to_string(),
+                active: true,
+            }),
+            JobAction::Ingest { ref urls, .. } => {
+                endpoints.extend(urls.iter().map(|url| OutboundEndpoint {
+                    component: "daemon",
+                    endpoint: url.clone(),
+                    sends: "HTTP GET 요청",
+                    trigger: "daemon (ingest 작업)".to_string(),
+                    active: true,
+                }));
+            }
+            JobAction::Compact | JobAction::Backup { .. } => {}
+        }
+    }
+
+    if let Some(ref webhook) = config.notify.webhook {
+        endpoints.push(OutboundEndpoint {
+            component: "notify",
+            endpoint: webhook.clone(),
+            sends: "이벤트 요약 (수집 건수, 실패 파일 경로와 오류, 작업 이름)",
+            trigger: "daemon, watch".to_string(),
+            active: true,
+        });
+    }
+
+    endpoints
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outbound_endpoints_default() {
+        let endpoints = outbound_endpoints(&Config::default(), false);
+
+        assert_eq!(endpoints.len(), 5);
+        assert!(endpoints.iter().any(|e| e.endpoint == GEMINI_EMBED_URL && !e.active));
+        assert!(endpoints.iter().any(|e| e.component == "scraper" && e.active));
+    }
+
+    #[test]
+    fn test_outbound_endpoints_allow_list() {
+        let mut config = Config::default();
+        config.url_policy.allow_domains = vec!["docs.rs".to_string(), "react.dev".to_string()];
+
+        let scraper: Vec<_> = outbound_endpoints(&config, true)
+            .into_iter()
+            .filter(|e| e.component == "scraper")
+            .collect();
+
+        assert_eq!(scraper.len(), 2);
+        assert!(scraper[0].endpoint.contains("docs.rs"));
+    }
+
+    #[test]
+    fn test_outbound_endpoints_summarize() {
+        let mut config = Config::default();
+        config.embedding.oversize = OversizeStrategy::Summarize;
+
+        let endpoints = outbound_endpoints(&config, true);
+        assert!(endpoints
+            .iter()
+            .any(|e| e.component == "summarize" && e.endpoint == GEMINI_GENERATE_URL && e.active));
+    }
+
+    #[test]
+    fn test_outbound_endpoints_daemon_jobs() {
+        let config: Config = toml::from_str(
+            r#"
+            [[jobs]]
+            name = "refresh-docs"
+            schedule = "@daily"
+            action = "refresh"
+            framework = "react"
+            changelog = true
+
+            [[jobs]]
+            name = "feeds"
+            schedule = "@hourly"
+            action = "ingest"
+            urls = ["https://blog.example.com/feed"]
+
+            [[jobs]]
+            name = "compact"
+            schedule = "@weekly"
+            action = "compact"
+            "#,
+        )
+        .unwrap();
+
+        let endpoints = outbound_endpoints(&config, false);
+        let daemon: Vec<_> = endpoints
+            .iter()
+            .filter(|e| e.component == "daemon")
+            .collect();
+        assert_eq!(daemon.len(), 2);
+        assert!(daemon[0].endpoint.contains("react"));
+        assert_eq!(daemon[1].endpoint, "https://blog.example.com/feed");
+
+        // 북마크 스크랩과 변경 기록도 scraper 항목에 포함
+        let scraper = endpoints.iter().find(|e| e.component == "scraper").unwrap();
+        assert!(scraper.trigger.contains("--bookmarks --scrape"));
+        assert!(scraper.trigger.contains("--changelog"));
+    }
+
+    #[test]
+    fn test_outbound_endpoints_watch() {
+        let config: Config = toml::from_str(
+            r#"
+            [[watch]]
+            dir = "/home/me/Screenshots"
+            "#,
+        )
+        .unwrap();
+
+        let endpoints = outbound_endpoints(&config, true);
+        let vision = endpoints.iter().find(|e| e.component == "vision").unwrap();
+        assert!(vision.trigger.contains("watch"));
+        assert!(vision.trigger.contains("/home/me/Screenshots"));
+
+        let sql = endpoints.iter().find(|e| e.component == "sql").unwrap();
+        assert!(sql.trigger.contains("postgres://"));
+        assert_eq!(sql.active, cfg!(feature = "postgres"));
+    }
+
+    #[test]
+    fn test_outbound_endpoints_webhook() {
+        let mut config = Config::default();
+        config.notify.webhook = Some("https://hooks.example.com/kb".to_string());
+
+        let endpoints = outbound_endpoints(&config, false);
+        assert!(endpoints
+            .iter()
+            .any(|e| e.component == "notify" && e.endpoint == "https://hooks.example.com/kb"));
+    }
+}
@@ -0,0 +1,601 @@
+//! BibTeX 파서
+//!
+//! `@article{key, field = {value}, ...}` 형식의 항목을 읽습니다.
+//! `@string` 매크로, `#` 연결, 중첩 중괄호, 흔한 LaTeX 악센트/이스케이프를 처리하고
+//! Zotero/Better BibTeX/JabRef의 `file` 필드에서 연결 파일 경로를 추출합니다.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use super::{BibEntry, Citation};
+
+/// BibTeX 텍스트에서 항목 파싱
+///
+/// 형식이 깨진 항목은 건너뜁니다 (`@comment`, `@preamble`도 무시).
+pub fn parse_bibtex(text: &str) -> Vec<BibEntry> {
+    let mut parser = Parser {
+        chars: text.chars().collect(),
+        pos: 0,
+        strings: HashMap::new(),
+    };
+    let mut entries = Vec::new();
+
+    while parser.skip_past('@') {
+        let entry_type = parser.read_ident().to_lowercase();
+        parser.skip_ws();
+        let close = match parser.bump() {
+            Some('{') => '}',
+            Some('(') => ')',
+            _ => continue,
+        };
+
+        match entry_type.as_str() {
+            "comment" | "preamble" => parser.skip_block(close),
+            "string" => {
+                if let Some((name, value)) = parser.read_field() {
+                    parser.strings.insert(name, value);
+                }
+                parser.skip_block(close);
+            }
+            _ => {
+                if let Some((key, fields)) = parser.read_entry(close) {
+                    entries.push(build_entry(entry_type, key, &fields));
+                }
+            }
+        }
+    }
+
+    entries
+}
+
+// ============================================================================
+// Parser
+// ============================================================================
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    /// `@string` 매크로
+    strings: HashMap<String, String>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        Some(c)
+    }
+
+    fn skip_ws(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    /// 지정 문자 다음 위치로 이동 (없으면 false)
+    fn skip_past(&mut self, target: char) -> bool {
+        while let Some(c) = self.bump() {
+            if c == target {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn read_ident(&mut self) -> String {
+        let mut ident = String::new();
+        while let Some(c) = self.peek() {
+            if c.is_alphanumeric() || matches!(c, '_' | '-' | ':' | '.' | '+' | '/') {
+                ident.push(c);
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+        ident
+    }
+
+    /// 여는 괄호 다음부터 짝이 맞는 닫는 괄호 다음까지 건너뜀
+    fn skip_block(&mut self, close: char) {
+        let mut depth = 0usize;
+        while let Some(c) = self.bump() {
+            match c {
+                '{' => depth += 1,
+                '}' if depth > 0 => depth -= 1,
+                c if c == close && depth == 0 => return,
+                _ => {}
+            }
+        }
+    }
+
+    /// 항목 본문 읽기: `key, name = value, ...` + 닫는 괄호
+    fn read_entry(&mut self, close: char) -> Option<(String, HashMap<String, String>)> {
+        let mut key = String::new();
+        loop {
+            match self.peek()? {
+                ',' => {
+                    self.pos += 1;
+                    break;
+                }
+                c if c == close => break,
+                c => {
+                    key.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+
+        let mut fields = HashMap::new();
+        loop {
+            self.skip_ws();
+            match self.peek()? {
+                ',' => self.pos += 1,
+                c if c == close => {
+                    self.pos += 1;
+                    break;
+                }
+                _ => {
+                    let (name, value) = self.read_field()?;
+                    fields.insert(name, value);
+                }
+            }
+        }
+
+        Some((key.trim().to_string(), fields))
+    }
+
+    /// `name = value` 읽기 (이름은 소문자)
+    fn read_field(&mut self) -> Option<(String, String)> {
+        self.skip_ws();
+        let name = self.read_ident().to_lowercase();
+        self.skip_ws();
+        if name.is_empty() || self.bump()? != '=' {
+            return None;
+        }
+        Some((name, self.read_value()?))
+    }
+
+    /// 값 읽기: `{...}`, `"..."`, 숫자/매크로, `#` 연결
+    fn read_value(&mut self) -> Option<String> {
+        let mut value = String::new();
+
+        loop {
+            self.skip_ws();
+            match self.peek()? {
+                '{' => {
+                    self.pos += 1;
+                    value.push_str(&self.read_delimited('}'));
+                }
+                '"' => {
+                    self.pos += 1;
+                    value.push_str(&self.read_delimited('"'));
+                }
+                _ => {
+                    let token = self.read_ident();
+                    if token.is_empty() {
+                        return None;
+                    }
+                    let expanded = self.strings.get(&token.to_lowercase()).cloned();
+                    value.push_str(&expanded.unwrap_or(token));
+                }
+            }
+
+            self.skip_ws();
+            if self.peek() == Some('#') {
+                self.pos += 1;
+            } else {
+                return Some(value);
+            }
+        }
+    }
+
+    /// 구분자 안의 원문 읽기 (안쪽 중괄호는 유지)
+    fn read_delimited(&mut self, end: char) -> String {
+        let mut text = String::new();
+        let mut depth = 0usize;
+
+        while let Some(c) = self.bump() {
+            match c {
+                '\\' => {
+                    text.push(c);
+                    if let Some(next) = self.bump() {
+                        text.push(next);
+                    }
+                }
+                '{' => {
+                    depth += 1;
+                    text.push(c);
+                }
+                '}' if depth > 0 => {
+                    depth -= 1;
+                    text.push(c);
+                }
+                c if c == end && depth == 0 => break,
+                c => text.push(c),
+            }
+        }
+
+        text
+    }
+}
+
+// ============================================================================
+// Entry Building
+// ============================================================================
+
+fn build_entry(entry_type: String, key: String, fields: &HashMap<String, String>) -> BibEntry {
+    let field = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|n| fields.get(*n))
+            .map(|v| clean_latex(v))
+            .filter(|v| !v.is_empty())
+    };
+
+    let year = field(&["year", "date"]).and_then(|v| {
+        let digits: String = v.chars().filter(char::is_ascii_digit).take(4).collect();
+        digits.parse().ok()
+    });
+
+    let doi = field(&["doi"]).map(|doi| {
+        doi.trim_start_matches("https://doi.org/")
+            .trim_start_matches("http://dx.doi.org/")
+            .to_string()
+    });
+
+    let citation = Citation {
+        key,
+        entry_type,
+        title: field(&["title"]),
+        authors: fields
+            .get("author")
+            .or_else(|| fields.get("editor"))
+            .map(|a| parse_authors(a))
+            .unwrap_or_default(),
+        year,
+        venue: field(&[
+            "journal",
+            "journaltitle",
+            "booktitle",
+            "publisher",
+            "school",
+            "institution",
+        ]),
+        doi,
+        url: field(&["url"]),
+    };
+
+    BibEntry {
+        citation,
+        abstract_text: field(&["abstract"]),
+        files: fields.get("file").map(|f| parse_file_field(f)).unwrap_or_default(),
+    }
+}
+
+/// 저자 필드를 "성, 이름" 목록으로 변환
+///
+/// `and`로 구분하며, 중괄호로 감싼 단체명(`{World Health Organization}`)은 그대로 둡니다.
+fn parse_authors(raw: &str) -> Vec<String> {
+    split_top_level_and(raw)
+        .into_iter()
+        .filter_map(|name| {
+            let name = name.trim();
+            if name.is_empty() || name.eq_ignore_ascii_case("others") {
+                return None;
+            }
+
+            // 단체명
+            if name.starts_with('{') && name.ends_with('}') {
+                return Some(clean_latex(name));
+            }
+
+            let cleaned = clean_latex(name);
+            if cleaned.contains(',') {
+                return Some(cleaned);
+            }
+
+            // "이름 성" -> "성, 이름"
+            match cleaned.rsplit_once(' ') {
+                Some((given, family)) => Some(format!("{}, {}", family, given)),
+                None => Some(cleaned),
+            }
+        })
+        .collect()
+}
+
+/// 중괄호 밖의 ` and `로 분리
+fn split_top_level_and(raw: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    let words: Vec<&str> = raw.split_whitespace().collect();
+
+    for word in words {
+        if depth == 0 && word.eq_ignore_ascii_case("and") {
+            names.push(std::mem::take(&mut current));
+            continue;
+        }
+
+        for c in word.chars() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+
+    names.push(current);
+    names
+}
+
+/// `file` 필드에서 파일 경로 추출
+///
+/// - Zotero/JabRef: `설명:경로:MIME` 항목을 `;`로 구분 (`\:`는 이스케이프된 콜론)
+/// - Better BibTeX: 경로만 (`files/12/paper.pdf`)
+fn parse_file_field(raw: &str) -> Vec<PathBuf> {
+    split_unescaped(raw, ';')
+        .into_iter()
+        .filter_map(|item| {
+            let parts = split_unescaped(&item, ':');
+            let path = match parts.as_slice() {
+                [path] => path.clone(),
+                // Windows 드라이브 문자가 이스케이프되지 않은 경우 (C:\...)
+                [drive, rest] if drive.len() == 1 => format!("{}:{}", drive, rest),
+                [_, path] => path.clone(),
+                [_, path, ..] => path.clone(),
+                [] => return None,
+            };
+            let path = unescape_path(path.trim());
+            (!path.is_empty()).then(|| PathBuf::from(path))
+        })
+        .collect()
+}
+
+/// 백슬래시로 이스케이프되지 않은 구분자로 분리 (이스케이프는 유지)
+fn split_unescaped(text: &str, sep: char) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        let current = parts.last_mut().expect("parts is never empty");
+        match c {
+            '\\' => {
+                current.push(c);
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            c if c == sep => parts.push(String::new()),
+            c => current.push(c),
+        }
+    }
+
+    parts
+}
+
+/// 경로의 BibTeX 이스케이프 해제 (`\:` → `:`, `\\` → `\`)
+fn unescape_path(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    let mut chars = path.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(next) = chars.next() {
+                out.push(next);
+            }
+        } else if c != '{' && c != '}' {
+            out.push(c);
+        }
+    }
+
+    out
+}
+
+// ============================================================================
+// LaTeX Cleanup
+// ============================================================================
+
+/// LaTeX 마크업을 일반 텍스트로 변환
+///
+/// 중괄호 제거, 악센트(`\"o` → ö), 이스케이프(`\&` → &), 대시(`--` → –),
+/// 서식 명령(`\emph{x}` → x) 처리 후 공백을 정리합니다.
+pub(crate) fn clean_latex(raw: &str) -> String {
+    let chars: Vec<char> = raw.chars().collect();
+    let mut out = String::with_capacity(raw.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        i += 1;
+
+        match c {
+            '{' | '}' => {}
+            '~' => out.push(' '),
+            '-' if chars.get(i) == Some(&'-') => {
+                if chars.get(i + 1) == Some(&'-') {
+                    out.push('—');
+                    i += 2;
+                } else {
+                    out.push('–');
+                    i += 1;
+                }
+            }
+            '\\' => {
+                let Some(&next) = chars.get(i) else { break };
+                i += 1;
+
+                if matches!(next, '"' | '\'' | '`' | '^' | '~' | '=' | '.') {
+                    // 악센트: \"o, \"{o}
+                    while chars.get(i) == Some(&'{') {
+                        i += 1;
+                    }
+                    if let Some(&letter) = chars.get(i) {
+                        out.push(accented(next, letter).unwrap_or(letter));
+                        i += 1;
+                    }
+                } else if next.is_ascii_alphabetic() {
+                    // 명령: \ss, \emph{...}, \c{c}
+                    let start = i - 1;
+                    while chars.get(i).is_some_and(char::is_ascii_alphabetic) {
+                        i += 1;
+                    }
+                    let command: String = chars[start..i].iter().collect();
+                    if let Some(symbol) = latex_symbol(&command) {
+                        out.push(symbol);
+                    }
+                    if chars.get(i) == Some(&' ') {
+                        i += 1;
+                    }
+                } else {
+                    // 이스케이프: \&, \%, \_, \$, \#
+                    out.push(next);
+                }
+            }
+            c => out.push(c),
+        }
+    }
+
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// 악센트 명령 + 문자 → 합성 문자
+fn accented(accent: char, letter: char) -> Option<char> {
+    let c = match (accent, letter) {
+        ('"', 'a') => 'ä',
+        ('"', 'o') => 'ö',
+        ('"', 'u') => 'ü',
+        ('"', 'e') => 'ë',
+        ('"', 'i') => 'ï',
+        ('"', 'A') => 'Ä',
+        ('"', 'O') => 'Ö',
+        ('"', 'U') => 'Ü',
+        ('\'', 'a') => 'á',
+        ('\'', 'e') => 'é',
+        ('\'', 'i') => 'í',
+        ('\'', 'o') => 'ó',
+        ('\'', 'u') => 'ú',
+        ('\'', 'E') => 'É',
+        ('`', 'a') => 'à',
+        ('`', 'e') => 'è',
+        ('`', 'i') => 'ì',
+        ('`', 'o') => 'ò',
+        ('`', 'u') => 'ù',
+        ('^', 'a') => 'â',
+        ('^', 'e') => 'ê',
+        ('^', 'i') => 'î',
+        ('^', 'o') => 'ô',
+        ('^', 'u') => 'û',
+        ('~', 'n') => 'ñ',
+        ('~', 'a') => 'ã',
+        ('~', 'o') => 'õ',
+        ('~', 'N') => 'Ñ',
+        _ => return None,
+    };
+    Some(c)
+}
+
+/// 인자 없는 LaTeX 기호 명령
+fn latex_symbol(command: &str) -> Option<char> {
+    let c = match command {
+        "ss" => 'ß',
+        "o" => 'ø',
+        "O" => 'Ø',
+        "aa" => 'å',
+        "AA" => 'Å',
+        "ae" => 'æ',
+        "AE" => 'Æ',
+        "l" => 'ł',
+        "L" => 'Ł',
+        "i" => 'i',
+        _ => return None,
+    };
+    Some(c)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"
+@string{ir = "Information Retrieval Journal"}
+
+@comment{ exported by Zotero }
+
+@article{smith2020hybrid,
+  title = {{Hybrid} Retrieval with {RRF}: A Study},
+  author = {Smith, John and Ada M\"{u}ller and {World Health Organization}},
+  journal = ir # " (Special Issue)",
+  year = 2020,
+  doi = {https://doi.org/10.1000/xyz},
+  abstract = {We combine BM25 and dense vectors -- cheaply.},
+  file = {Full Text PDF:files/12/smith2020.pdf:application/pdf;Snapshot:files/12/page.html:text/html}
+}
+
+@inproceedings(lee2021,
+  title = "Chunking \& Ranking",
+  author = "Lee, Kim",
+  booktitle = {Proc. of SIGIR},
+  date = {2021-07-11},
+  file = {C\:\\Papers\\lee2021.pdf}
+)
+"#;
+
+    #[test]
+    fn test_parse_bibtex() {
+        let entries = parse_bibtex(SAMPLE);
+        assert_eq!(entries.len(), 2);
+
+        let smith = &entries[0].citation;
+        assert_eq!(smith.key, "smith2020hybrid");
+        assert_eq!(smith.entry_type, "article");
+        assert_eq!(smith.title.as_deref(), Some("Hybrid Retrieval with RRF: A Study"));
+        assert_eq!(
+            smith.authors,
+            vec!["Smith, John", "Müller, Ada", "World Health Organization"]
+        );
+        assert_eq!(smith.year, Some(2020));
+        assert_eq!(
+            smith.venue.as_deref(),
+            Some("Information Retrieval Journal (Special Issue)")
+        );
+        assert_eq!(smith.doi.as_deref(), Some("10.1000/xyz"));
+        assert_eq!(
+            entries[0].abstract_text.as_deref(),
+            Some("We combine BM25 and dense vectors – cheaply.")
+        );
+        assert_eq!(
+            entries[0].files,
+            vec![
+                PathBuf::from("files/12/smith2020.pdf"),
+                PathBuf::from("files/12/page.html")
+            ]
+        );
+
+        let lee = &entries[1].citation;
+        assert_eq!(lee.title.as_deref(), Some("Chunking & Ranking"));
+        assert_eq!(lee.year, Some(2021));
+        assert_eq!(lee.venue.as_deref(), Some("Proc. of SIGIR"));
+        assert_eq!(entries[1].files, vec![PathBuf::from(r"C:\Papers\lee2021.pdf")]);
+    }
+
+    #[test]
+    fn test_clean_latex() {
+        assert_eq!(clean_latex(r"Stra\ss e \emph{und} Caf\'e"), "Straße und Café");
+        assert_eq!(clean_latex("pages 1--10, a---b"), "pages 1–10, a—b");
+        assert_eq!(clean_latex(r"50\% of {NLP}~tasks"), "50% of NLP tasks");
+    }
+}
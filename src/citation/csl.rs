@@ -0,0 +1,142 @@
+//! CSL JSON 파서 (Zotero "CSL JSON" 내보내기)
+//!
+//! ref: https://citeproc-js.readthedocs.io/en/latest/csl-json/markup.html
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::{BibEntry, Citation};
+
+/// CSL JSON 배열에서 항목 파싱
+pub fn parse_csl_json(text: &str) -> Result<Vec<BibEntry>> {
+    let items: Vec<CslItem> = serde_json::from_str(text).context("Failed to parse CSL JSON")?;
+    Ok(items.into_iter().map(CslItem::into_entry).collect())
+}
+
+// ============================================================================
+// CSL Types
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+struct CslItem {
+    id: serde_json::Value,
+    #[serde(rename = "type", default)]
+    item_type: String,
+    title: Option<String>,
+    #[serde(default)]
+    author: Vec<CslName>,
+    #[serde(default)]
+    editor: Vec<CslName>,
+    issued: Option<CslDate>,
+    #[serde(rename = "container-title")]
+    container_title: Option<String>,
+    publisher: Option<String>,
+    #[serde(rename = "DOI")]
+    doi: Option<String>,
+    #[serde(rename = "URL")]
+    url: Option<String>,
+    #[serde(rename = "abstract")]
+    abstract_text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CslName {
+    family: Option<String>,
+    given: Option<String>,
+    /// 단체명 등 분리되지 않은 이름
+    literal: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CslDate {
+    #[serde(rename = "date-parts", default)]
+    date_parts: Vec<Vec<serde_json::Value>>,
+}
+
+impl CslItem {
+    fn into_entry(self) -> BibEntry {
+        let key = match self.id {
+            serde_json::Value::String(id) => id,
+            other => other.to_string(),
+        };
+
+        // 연도는 숫자 또는 문자열로 올 수 있음
+        let year = self
+            .issued
+            .and_then(|d| d.date_parts.into_iter().next())
+            .and_then(|parts| parts.into_iter().next())
+            .and_then(|y| match y {
+                serde_json::Value::Number(n) => n.as_i64().map(|n| n as i32),
+                serde_json::Value::String(s) => s.parse().ok(),
+                _ => None,
+            });
+
+        let names = if self.author.is_empty() {
+            self.editor
+        } else {
+            self.author
+        };
+
+        BibEntry {
+            citation: Citation {
+                key,
+                entry_type: self.item_type,
+                title: self.title,
+                authors: names.into_iter().filter_map(CslName::into_author).collect(),
+                year,
+                venue: self.container_title.or(self.publisher),
+                doi: self.doi,
+                url: self.url,
+            },
+            abstract_text: self.abstract_text,
+            files: Vec::new(),
+        }
+    }
+}
+
+impl CslName {
+    /// "성, 이름" 형식으로 변환
+    fn into_author(self) -> Option<String> {
+        match (self.family, self.given, self.literal) {
+            (Some(family), Some(given), _) => Some(format!("{}, {}", family, given)),
+            (Some(family), None, _) => Some(family),
+            (None, _, literal) => literal,
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csl_json() {
+        let json = r#"[
+            {
+                "id": "http://zotero.org/users/1/items/ABCD",
+                "type": "article-journal",
+                "title": "Dense Passage Retrieval",
+                "author": [
+                    {"family": "Karpukhin", "given": "Vladimir"},
+                    {"literal": "Facebook AI"}
+                ],
+                "issued": {"date-parts": [["2020", 11]]},
+                "container-title": "EMNLP",
+                "DOI": "10.18653/v1/2020.emnlp-main.550"
+            }
+        ]"#;
+
+        let entries = parse_csl_json(json).unwrap();
+        assert_eq!(entries.len(), 1);
+
+        let citation = &entries[0].citation;
+        assert_eq!(citation.authors, vec!["Karpukhin, Vladimir", "Facebook AI"]);
+        assert_eq!(citation.year, Some(2020));
+        assert_eq!(citation.venue.as_deref(), Some("EMNLP"));
+        assert_eq!(citation.short(), "Karpukhin & Facebook AI, 2020");
+    }
+}
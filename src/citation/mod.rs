@@ -0,0 +1,218 @@
+//! 인용 모듈 - BibTeX / Zotero 서지 정보
+//!
+//! BibTeX(.bib, Zotero/Better BibTeX 내보내기 포함)와 CSL JSON(.json, Zotero 내보내기)
+//! 파일에서 서지 항목을 읽어 문서에 인용 정보(저자, 연도, DOI)를 붙입니다.
+//! 검색/ask 결과에서 출처를 인용 형식으로 보여줄 때 사용합니다.
+
+mod bibtex;
+mod csl;
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+pub use bibtex::parse_bibtex;
+pub use csl::parse_csl_json;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// 문서 인용 정보
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Citation {
+    /// 인용 키 (BibTeX citekey, CSL id)
+    pub key: String,
+    /// 항목 유형 (article, book, inproceedings 등)
+    pub entry_type: String,
+    pub title: Option<String>,
+    /// 저자 목록 ("성, 이름" 형식)
+    pub authors: Vec<String>,
+    pub year: Option<i32>,
+    /// 학술지/학회/출판사
+    pub venue: Option<String>,
+    pub doi: Option<String>,
+    pub url: Option<String>,
+}
+
+/// 서지 파일의 한 항목
+#[derive(Debug, Clone, Default)]
+pub struct BibEntry {
+    pub citation: Citation,
+    /// 초록 (연결 파일이 없을 때 문서 본문으로 사용)
+    pub abstract_text: Option<String>,
+    /// 연결된 파일 (Zotero `file` 필드, 서지 파일 기준 상대 경로는 해석됨)
+    pub files: Vec<PathBuf>,
+}
+
+// ============================================================================
+// Citation
+// ============================================================================
+
+impl Citation {
+    /// 본문 인용 형식 (예: `Smith & Doe, 2020`, `Smith et al., 2020`)
+    pub fn short(&self) -> String {
+        let names: Vec<&str> = self.authors.iter().map(|a| family_name(a)).collect();
+        let who = match names.as_slice() {
+            [] => self.title.clone().unwrap_or_else(|| self.key.clone()),
+            [one] => one.to_string(),
+            [first, second] => format!("{} & {}", first, second),
+            [first, ..] => format!("{} et al.", first),
+        };
+
+        match self.year {
+            Some(year) => format!("{}, {}", who, year),
+            None => format!("{}, n.d.", who),
+        }
+    }
+
+    /// 참고문헌 형식 (APA 유사)
+    ///
+    /// 예: `Smith, J., & Doe, A. (2020). Title. Journal. https://doi.org/10.1000/xyz`
+    pub fn format(&self) -> String {
+        let mut parts = Vec::new();
+
+        if !self.authors.is_empty() {
+            parts.push(join_authors(&self.authors));
+        }
+        parts.push(match self.year {
+            Some(year) => format!("({}).", year),
+            None => "(n.d.).".to_string(),
+        });
+        if let Some(ref title) = self.title {
+            parts.push(format!("{}.", title.trim_end_matches('.')));
+        }
+        if let Some(ref venue) = self.venue {
+            parts.push(format!("{}.", venue.trim_end_matches('.')));
+        }
+        if let Some(link) = self.link() {
+            parts.push(link);
+        }
+
+        parts.join(" ")
+    }
+
+    /// DOI 링크 (없으면 URL)
+    pub fn link(&self) -> Option<String> {
+        self.doi
+            .as_ref()
+            .map(|doi| format!("https://doi.org/{}", doi))
+            .or_else(|| self.url.clone())
+    }
+}
+
+/// "성, 이름"에서 성 추출 (쉼표가 없으면 단체명으로 보고 그대로)
+fn family_name(author: &str) -> &str {
+    match author.split_once(',') {
+        Some((family, _)) => family.trim(),
+        None => author.trim(),
+    }
+}
+
+/// 저자 목록을 "Smith, J., Doe, A., & Lee, K." 형식으로
+fn join_authors(authors: &[String]) -> String {
+    let formatted: Vec<String> = authors
+        .iter()
+        .map(|author| match author.split_once(',') {
+            Some((family, given)) => {
+                let initials: Vec<String> = given
+                    .split_whitespace()
+                    .filter_map(|g| g.chars().next())
+                    .map(|c| format!("{}.", c))
+                    .collect();
+                if initials.is_empty() {
+                    family.trim().to_string()
+                } else {
+                    format!("{}, {}", family.trim(), initials.join(" "))
+                }
+            }
+            None => author.clone(),
+        })
+        .collect();
+
+    match formatted.as_slice() {
+        [] => String::new(),
+        [one] => one.clone(),
+        [init @ .., last] => format!("{}, & {}", init.join(", "), last),
+    }
+}
+
+// ============================================================================
+// Loading
+// ============================================================================
+
+/// 서지 파일 로드 (확장자로 형식 결정: .bib → BibTeX, .json → CSL JSON)
+///
+/// 연결 파일의 상대 경로는 서지 파일이 있는 폴더 기준으로 해석합니다.
+pub fn load_bibliography(path: &Path) -> Result<Vec<BibEntry>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read bibliography: {:?}", path))?;
+
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+
+    let mut entries = match ext.as_str() {
+        "bib" | "bibtex" => parse_bibtex(&text),
+        "json" => parse_csl_json(&text)?,
+        _ => anyhow::bail!("Unsupported bibliography format: {} (.bib 또는 .json)", ext),
+    };
+
+    let base = path.parent().unwrap_or(Path::new("."));
+    for entry in &mut entries {
+        for file in &mut entry.files {
+            if file.is_relative() {
+                *file = base.join(&*file);
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn citation(authors: &[&str]) -> Citation {
+        Citation {
+            key: "smith2020".to_string(),
+            entry_type: "article".to_string(),
+            title: Some("Hybrid Retrieval.".to_string()),
+            authors: authors.iter().map(|a| a.to_string()).collect(),
+            year: Some(2020),
+            venue: Some("Journal of IR".to_string()),
+            doi: Some("10.1000/xyz".to_string()),
+            url: None,
+        }
+    }
+
+    #[test]
+    fn test_short_citation() {
+        assert_eq!(citation(&["Smith, John"]).short(), "Smith, 2020");
+        assert_eq!(
+            citation(&["Smith, John", "Doe, Ada"]).short(),
+            "Smith & Doe, 2020"
+        );
+        assert_eq!(
+            citation(&["Smith, John", "Doe, Ada", "Lee, Kim"]).short(),
+            "Smith et al., 2020"
+        );
+    }
+
+    #[test]
+    fn test_format_citation() {
+        assert_eq!(
+            citation(&["Smith, John Paul", "Doe, Ada"]).format(),
+            "Smith, J. P., & Doe, A. (2020). Hybrid Retrieval. Journal of IR. \
+             https://doi.org/10.1000/xyz"
+        );
+    }
+}
@@ -0,0 +1,242 @@
+//! CLI 오류 분류 - 종료 코드와 `--error-format json`
+//!
+//! 감싸는 스크립트나 GUI가 한국어 메시지를 정규식으로 맞추지 않고도 실패 원인에 따라
+//! 대응할 수 있도록 오류 체인을 유형별로 분류하여 서로 다른 종료 코드로 끝냅니다.
+//!
+//! | 종료 코드 | 유형 | 예 |
+//! |---|---|---|
+//! | 1 | `other` | 그 밖의 모든 오류 |
+//! | 2 | - | 잘못된 명령줄 인자 (clap) |
+//! | 3 | `config` | API 키 없음/거부됨, 설정 파일 파싱 실패 |
+//! | 4 | `quota` | 일일 할당량 소진, 실행당 수집 한도(`[limits]`) 초과 |
+//! | 5 | `network` | API/URL 연결 실패, 임베딩 API 연속 실패 |
+//! | 6 | `store-corrupted` | SQLite/LanceDB 손상 (`repair` 필요) |
+//! | 7 | `not-found` | 없는 문서 ID/URL, 없는 파일 |
+
+use std::process::ExitCode;
+
+use clap::ValueEnum;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::embedding::{CircuitOpen, EmbeddingApiError, QuotaExhausted};
+use crate::knowledge::StoreCorrupted;
+use crate::limits::LimitExceeded;
+
+/// 설정 오류 (API 키 없음 등)
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{0}")]
+pub struct ConfigError(pub String);
+
+/// 대상 없음 (문서 ID/URL, 파일)
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{0}")]
+pub struct NotFound(pub String);
+
+// ============================================================================
+// ErrorKind
+// ============================================================================
+
+/// 오류 유형
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ErrorKind {
+    Config,
+    Quota,
+    Network,
+    StoreCorrupted,
+    NotFound,
+    Other,
+}
+
+impl ErrorKind {
+    /// 오류 체인으로 유형 결정 (context로 감싼 경우 포함)
+    pub fn of(error: &anyhow::Error) -> Self {
+        if QuotaExhausted::is_cause_of(error) || LimitExceeded::is_cause_of(error) {
+            return Self::Quota;
+        }
+        if StoreCorrupted::is_cause_of(error) {
+            return Self::StoreCorrupted;
+        }
+
+        error
+            .chain()
+            .find_map(|cause| {
+                if cause.is::<ConfigError>() || cause.is::<toml::de::Error>() {
+                    Some(Self::Config)
+                } else if cause
+                    .downcast_ref::<EmbeddingApiError>()
+                    .is_some_and(EmbeddingApiError::is_auth_error)
+                {
+                    Some(Self::Config)
+                } else if cause.is::<NotFound>() {
+                    Some(Self::NotFound)
+                } else if cause.is::<CircuitOpen>() || cause.is::<reqwest::Error>() {
+                    Some(Self::Network)
+                } else if is_sqlite_corruption(cause) {
+                    Some(Self::StoreCorrupted)
+                } else {
+                    None
+                }
+            })
+            .unwrap_or(Self::Other)
+    }
+
+    /// 프로세스 종료 코드 (2는 clap 인자 오류용)
+    pub fn exit_code(self) -> u8 {
+        match self {
+            Self::Other => 1,
+            Self::Config => 3,
+            Self::Quota => 4,
+            Self::Network => 5,
+            Self::StoreCorrupted => 6,
+            Self::NotFound => 7,
+        }
+    }
+}
+
+fn is_sqlite_corruption(cause: &(dyn std::error::Error + 'static)) -> bool {
+    use rusqlite::ErrorCode;
+
+    matches!(
+        cause.downcast_ref::<rusqlite::Error>(),
+        Some(rusqlite::Error::SqliteFailure(e, _))
+            if matches!(e.code, ErrorCode::DatabaseCorrupt | ErrorCode::NotADatabase)
+    )
+}
+
+// ============================================================================
+// Reporting
+// ============================================================================
+
+/// 오류 출력 형식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ErrorFormat {
+    /// 사람이 읽는 메시지 (원인 체인 포함)
+    #[default]
+    Text,
+    /// 한 줄 JSON (`{"error": {"kind", "exit_code", "message", "causes"}}`)
+    Json,
+}
+
+/// JSON 오류 출력
+#[derive(Debug, Serialize)]
+struct ErrorReport {
+    error: ErrorBody,
+}
+
+#[derive(Debug, Serialize)]
+struct ErrorBody {
+    kind: ErrorKind,
+    exit_code: u8,
+    message: String,
+    /// 원인 체인 (바깥 context 다음부터)
+    causes: Vec<String>,
+}
+
+fn render_json(error: &anyhow::Error) -> String {
+    let kind = ErrorKind::of(error);
+    let report = ErrorReport {
+        error: ErrorBody {
+            kind,
+            exit_code: kind.exit_code(),
+            message: error.to_string(),
+            causes: error.chain().skip(1).map(|c| c.to_string()).collect(),
+        },
+    };
+    serde_json::to_string(&report)
+        .unwrap_or_else(|_| format!("{{\"error\":{:?}}}", error.to_string()))
+}
+
+/// 오류를 stderr에 출력하고 유형별 종료 코드 반환
+pub fn report_error(error: &anyhow::Error, format: ErrorFormat) -> ExitCode {
+    match format {
+        ErrorFormat::Text => eprintln!("Error: {:?}", error),
+        ErrorFormat::Json => eprintln!("{}", render_json(error)),
+    }
+    ExitCode::from(ErrorKind::of(error).exit_code())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_errors() {
+        let quota = anyhow::Error::new(QuotaExhausted { keys: 2 }).context("임베딩 실패");
+        assert_eq!(ErrorKind::of(&quota), ErrorKind::Quota);
+
+        let missing = anyhow::Error::new(NotFound("ID 3인 문서를 찾을 수 없습니다".into()));
+        assert_eq!(ErrorKind::of(&missing), ErrorKind::NotFound);
+
+        let config = toml::from_str::<toml::Table>("= broken").unwrap_err();
+        let config = anyhow::Error::new(config).context("설정 파일 로드 실패");
+        assert_eq!(ErrorKind::of(&config), ErrorKind::Config);
+
+        assert_eq!(ErrorKind::of(&anyhow::anyhow!("unknown")), ErrorKind::Other);
+    }
+
+    #[tokio::test]
+    async fn test_classify_embedding_errors() {
+        // 임베더가 전송 실패를 감싸는 방식 그대로 (reqwest 오류가 원인으로 남음)
+        let refused = reqwest::Client::new()
+            .post("http://127.0.0.1:1/embed")
+            .send()
+            .await
+            .unwrap_err();
+        let network = anyhow::Error::new(refused)
+            .context("Failed to send embedding request")
+            .context("Failed to embed chunk");
+        assert_eq!(ErrorKind::of(&network), ErrorKind::Network);
+
+        let revoked = anyhow::Error::new(EmbeddingApiError {
+            status: 400,
+            message: "INVALID_ARGUMENT: API key not valid. Please pass a valid API key.".into(),
+        })
+        .context("Failed to embed chunk");
+        assert_eq!(ErrorKind::of(&revoked), ErrorKind::Config);
+
+        let denied = anyhow::Error::new(EmbeddingApiError {
+            status: 403,
+            message: "PERMISSION_DENIED: Method doesn't allow unregistered callers".into(),
+        });
+        assert_eq!(ErrorKind::of(&denied), ErrorKind::Config);
+
+        let server = anyhow::Error::new(EmbeddingApiError {
+            status: 500,
+            message: "INTERNAL: Internal error".into(),
+        });
+        assert_eq!(ErrorKind::of(&server), ErrorKind::Other);
+    }
+
+    #[test]
+    fn test_exit_codes_are_distinct() {
+        let kinds = [
+            ErrorKind::Config,
+            ErrorKind::Quota,
+            ErrorKind::Network,
+            ErrorKind::StoreCorrupted,
+            ErrorKind::NotFound,
+            ErrorKind::Other,
+        ];
+        let codes: std::collections::HashSet<u8> = kinds.iter().map(|k| k.exit_code()).collect();
+        assert_eq!(codes.len(), kinds.len());
+        assert!(!codes.contains(&0) && !codes.contains(&2));
+    }
+
+    #[test]
+    fn test_render_json() {
+        let error = anyhow::Error::new(ConfigError("API 키가 설정되지 않았습니다".into()))
+            .context("ask 실패");
+        let json: serde_json::Value = serde_json::from_str(&render_json(&error)).unwrap();
+
+        assert_eq!(json["error"]["kind"], "config");
+        assert_eq!(json["error"]["exit_code"], 3);
+        assert_eq!(json["error"]["message"], "ask 실패");
+        assert_eq!(json["error"]["causes"][0], "API 키가 설정되지 않았습니다");
+    }
+}
@@ -4,16 +4,60 @@
 //!
 //! palank-rag CLI 명령어 정의 및 구현
 
-use std::path::PathBuf;
+use std::collections::BTreeSet;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
 
 use anyhow::{bail, Context, Result};
-use clap::{Parser, Subcommand};
-
-use crate::collector::{CollectionStats, CollectorConfig, FileCollector, FileType};
-use crate::embedding::has_api_key;
-use crate::extractor::ContentExtractor;
-use crate::knowledge::{get_data_dir, HybridRetriever, KnowledgeStore, NewDocument};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+use crate::audit::outbound_endpoints;
+use crate::citation::load_bibliography;
+use crate::collector::{
+    load_failures, CollectionStats, CollectorConfig, FailureKind, FileCollector, FileType,
+    IngestReport, INGEST_ERRORS_FILE,
+};
+use crate::config::{config_path, Config};
+use crate::connector::{
+    fetch_rows, parse_bookmarks, parse_conversations, Bookmark, RowTemplate, SqlConnection,
+};
+use crate::daemon::{Daemon, Shutdown, BACKUPS_DIR};
+use crate::embedding::{
+    find_api_key, has_api_key, keyring_api_key, remove_api_key, store_api_key, ApiKeySource,
+    CircuitBreaker, CircuitOpen, EmbeddingConfig, GeminiEmbedding, OversizeStrategy,
+    QuotaExhausted,
+};
+use crate::extractor::{content_length, ContentExtractor, ContentFilter};
+use crate::generation::GeminiGenerator;
+use crate::i18n::{self, Lang, Msg};
+use crate::knowledge::{
+    changelog_document, check_integrity, collection_dir, compare_rankings, create_bundle,
+    get_data_dir, latest_backup, open_bundle, pair_framework, query_id, quickfix_lines,
+    read_manifest, resolve_collections, restore_backup, search_collections, set_aside,
+    validate_chunks, AlfredOutput, ChunkConfig, ChunkKind, ContextOptions, ExplainedResult,
+    FusionConfig, HybridRetriever, HybridSearchResult, KnowledgeStore, LanceVectorStore,
+    MarkdownChunker, NewDocument, PreparedDocuments, Provenance, RaycastOutput, ResultExplanation,
+    ScoreNormalization, SearchOptions, SearchReport, SourceCitation, VectorHealthIssue,
+    VectorStoreDescription, SQLITE_FILE, TRACES_DIR, VECTORS_DIR,
+};
+use crate::limits::{BudgetCounter, DiskGuard, EmbeddingBudget, LimitExceeded};
+use crate::notify::Notifier;
+use crate::opener::{load_last_results, save_last_results, OpenTarget, LAST_RESULTS_FILE};
 use crate::scraper::WebScraper;
+use crate::service::{self, ServiceSpec};
+use crate::tokens::Tokenizer;
+use crate::watch::{watch_state_path, ScreenshotWatcher, SCREENSHOT_TAG};
+
+mod error;
+
+pub use error::{report_error, ConfigError, ErrorFormat, ErrorKind, NotFound};
+
+/// 폴더 수집 시 한 번에 저장할 문서 수
+const INGEST_BATCH_SIZE: usize = 32;
+
+/// `--collection`으로 고른 컬렉션의 저장소 디렉토리 (실행 시작 시 한 번 설정)
+static STORE_DIR: OnceLock<PathBuf> = OnceLock::new();
 
 // ============================================================================
 // CLI Definition
@@ -25,8 +69,18 @@ use crate::scraper::WebScraper;
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
+
+    /// 오류 출력 형식 (json이면 stderr에 한 줄 JSON, 종료 코드는 유형별로 같음)
+    #[arg(long, global = true, value_enum, default_value_t = ErrorFormat::Text)]
+    pub error_format: ErrorFormat,
+
+    /// 대상 컬렉션 (기본: default) - 처음 수집할 때 만들어지며, 여러 컬렉션은 query --collections로 함께 검색
+    #[arg(long, global = true, value_name = "NAME")]
+    pub collection: Option<String>,
 }
 
+// 실행당 한 번만 파싱되므로 변형 크기 차이는 문제되지 않음
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand)]
 pub enum Commands {
     /// URL, 파일, 또는 폴더를 지식베이스에 추가
@@ -47,7 +101,25 @@ pub enum Commands {
         #[arg(short, long)]
         dir: Option<PathBuf>,
 
-        /// 프레임워크 태그
+        /// 이 git 리비전 이후 변경된 파일만 수집 (--dir 아래, 커밋 안 된 수정/새 파일 포함)
+        #[arg(long, value_name = "REV", requires = "dir")]
+        git_changed: Option<String>,
+
+        /// 서지 파일 (.bib BibTeX/Zotero, .json CSL JSON) - 연결 파일/초록을 인용 정보와 함께 수집
+        #[arg(long)]
+        bib: Option<PathBuf>,
+
+        #[command(flatten)]
+        sql: SqlIngestArgs,
+
+        #[command(flatten)]
+        bookmarks: BookmarkIngestArgs,
+
+        /// ChatGPT/Claude 대화 내보내기 (conversations.json 또는 압축을 푼 폴더) - 대화마다 문서 하나
+        #[arg(long, value_name = "FILE")]
+        conversations: Option<PathBuf>,
+
+        /// 프레임워크 태그 (react@18처럼 버전 지정, 버전이 없으면 URL/경로의 /v18/, /3.12/ 등에서 찾아 붙임)
         #[arg(short, long)]
         framework: Option<String>,
 
@@ -59,9 +131,33 @@ pub enum Commands {
         #[arg(long)]
         skip_pdfs: bool,
 
+        /// CSV/TSV를 행 단위 "열: 값" 문서로 수집 (N행씩 묶음)
+        #[arg(long, value_name = "N")]
+        table_rows: Option<usize>,
+
+        /// 본문 최소 길이 (글자/숫자 수, 0이면 끔) - 더 짧은 추출 결과는 건너뜀 [기본: 설정 파일 또는 50]
+        #[arg(long, value_name = "N")]
+        min_chars: Option<usize>,
+
         /// 강제 재수집 (이미 존재하는 파일도 덮어쓰기)
         #[arg(long)]
         force: bool,
+
+        /// 임베딩까지 끝난 파일은 건너뛰고 이어서 수집 (할당량 소진 등으로 중단된 --file/--dir 수집)
+        #[arg(long, conflicts_with = "force")]
+        resume: bool,
+
+        /// 직전 파일 수집에서 실패한 파일만 다시 수집 (~/.palank-rag/ingest-errors.json)
+        #[arg(long, conflicts_with_all = ["url", "text", "file", "dir", "bib"])]
+        errors_only: bool,
+
+        /// URL 재수집 시 이전 버전과의 변경 사항을 날짜별 문서로 별도 저장
+        #[arg(long, requires = "url")]
+        changelog: bool,
+
+        /// 만료 기간 (예: 30m, 12h, 7d, 2w) - 지나면 검색에서 제외되고 compact 시 삭제
+        #[arg(long, value_parser = parse_ttl)]
+        ttl: Option<chrono::Duration>,
     },
 
     /// 지식베이스 검색
@@ -73,7 +169,83 @@ pub enum Commands {
         #[arg(short, long, default_value = "5")]
         limit: usize,
 
-        /// 프레임워크 필터 (현재 미구현)
+        /// 프레임워크 필터 (react는 모든 버전, react@18은 18.x만)
+        #[arg(short, long)]
+        framework: Option<String>,
+
+        /// 선호 버전 (예: react@19) - 같은 버전 문서를 올리고 같은 프레임워크의 다른 버전을 내림
+        #[arg(long, value_name = "FRAMEWORK@VERSION")]
+        prefer_version: Option<String>,
+
+        /// 결과마다 순위 근거 표시 (검색 경로별 순위, RRF 계산, 일치 키워드)
+        #[arg(long)]
+        explain: bool,
+
+        /// 검색 트레이스(후보 목록, 점수, 통합 결과)를 JSON으로 저장 (~/.palank-rag/traces)
+        #[arg(long)]
+        trace: bool,
+
+        /// 점수 정규화 방식 (rank, min_max, z_score) - 설정 파일의 `[search]`보다 우선
+        #[arg(long, value_parser = parse_normalization)]
+        normalization: Option<ScoreNormalization>,
+
+        /// 문서 임베딩으로 가까운 문서 N개를 먼저 고른 뒤 그 안에서 청크 검색 (대규모 코퍼스용)
+        #[arg(long, value_name = "N")]
+        two_stage: Option<usize>,
+
+        /// 벡터 검색 시간 예산 (밀리초) - 넘으면 키워드 검색 결과만 표시
+        #[arg(long, value_name = "MS")]
+        time_budget: Option<u64>,
+
+        /// 검색 결과를 Markdown 보고서로 저장 (쿼리, 시각, 순위별 결과와 스니펫)
+        #[arg(long, value_name = "FILE")]
+        export: Option<PathBuf>,
+
+        /// 출력 형식 (alfred: Script Filter JSON, raycast: palank-rag 고유 JSON, quickfix: 코드 위치 목록만 출력)
+        #[arg(long, value_enum, default_value_t = QueryFormat::Text)]
+        format: QueryFormat,
+
+        /// 코드 청크만 검색 (예제 코드, 소스 파일)
+        #[arg(long, conflicts_with = "only_prose")]
+        only_code: bool,
+
+        /// 설명문 청크만 검색 (코드 제외)
+        #[arg(long)]
+        only_prose: bool,
+
+        /// 여러 컬렉션을 동시에 검색하여 통합 (default: 기본 지식베이스, all: 모든 컬렉션)
+        #[arg(
+            long,
+            value_delimiter = ',',
+            value_name = "A,B,C",
+            conflicts_with_all = ["explain", "trace", "export"]
+        )]
+        collections: Vec<String>,
+
+        /// 가져오지 않고 번들 파일(`bundle create`로 만든 읽기 전용 스냅샷)을 검색
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["collections", "trace"])]
+        bundle: Option<PathBuf>,
+    },
+
+    /// 같은 쿼리를 두 검색 설정으로 실행하여 순위 비교
+    Compare {
+        /// 검색 쿼리
+        #[arg(short, long)]
+        query: String,
+
+        /// 설정 A (TOML 파일 경로 또는 "vector_weight=2,rrf_k=20" 형식, 생략 시 기본값)
+        #[arg(long)]
+        config_a: Option<String>,
+
+        /// 설정 B (TOML 파일 경로 또는 "vector_weight=2,rrf_k=20" 형식, 생략 시 기본값)
+        #[arg(long)]
+        config_b: Option<String>,
+
+        /// 결과 개수 제한
+        #[arg(short, long, default_value = "10")]
+        limit: usize,
+
+        /// 프레임워크 필터
         #[arg(short, long)]
         framework: Option<String>,
     },
@@ -87,6 +259,10 @@ pub enum Commands {
         /// 결과 개수 제한
         #[arg(short, long, default_value = "20")]
         limit: usize,
+
+        /// 게시일이 이후인 문서만 (예: 2024-01-01, 30d) - 게시일을 알 수 없는 문서 제외
+        #[arg(long, value_parser = parse_since)]
+        since: Option<chrono::DateTime<chrono::Utc>>,
     },
 
     /// 문서 삭제
@@ -100,36 +276,398 @@ pub enum Commands {
         id: Option<i64>,
     },
 
+    /// 문서 고정 (ask 컨텍스트에 항상 포함)
+    Pin {
+        /// 고정할 문서 URL
+        #[arg(short, long)]
+        url: Option<String>,
+
+        /// 고정할 문서 ID
+        #[arg(short, long)]
+        id: Option<i64>,
+    },
+
+    /// 문서 고정 해제
+    Unpin {
+        /// 고정 해제할 문서 URL
+        #[arg(short, long)]
+        url: Option<String>,
+
+        /// 고정 해제할 문서 ID
+        #[arg(short, long)]
+        id: Option<i64>,
+    },
+
+    /// 고정된 문서 목록
+    Pins,
+
+    /// 문서 상세 정보 (메타데이터, 수집 출처)
+    Show {
+        /// 문서 URL
+        #[arg(short, long)]
+        url: Option<String>,
+
+        /// 문서 ID
+        #[arg(short, long)]
+        id: Option<i64>,
+    },
+
+    /// 검색 결과 판정 기록 (다음 검색부터 순위에 반영)
+    Feedback {
+        /// 쿼리 ID (query 결과 끝에 표시)
+        query_id: String,
+
+        /// 판정할 문서 ID
+        doc_id: i64,
+
+        /// 관련 있는 결과
+        #[arg(long, required_unless_present = "irrelevant", conflicts_with = "irrelevant")]
+        relevant: bool,
+
+        /// 관련 없는 결과
+        #[arg(long)]
+        irrelevant: bool,
+    },
+
+    /// 검색 결과 원본 열기 (웹 문서는 브라우저, 파일은 $EDITOR)
+    Open {
+        /// 마지막 query 결과 번호 (1부터)
+        #[arg(required_unless_present = "id", conflicts_with = "id")]
+        index: Option<usize>,
+
+        /// 문서 ID로 열기
+        #[arg(short, long)]
+        id: Option<i64>,
+    },
+
+    /// 지식베이스를 바탕으로 질문에 답변
+    Ask {
+        /// 질문
+        question: String,
+
+        /// 컨텍스트에 넣을 검색 결과 수
+        #[arg(short, long, default_value = "5")]
+        limit: usize,
+
+        /// 프레임워크 필터 (react는 모든 버전, react@18은 18.x만)
+        #[arg(short, long)]
+        framework: Option<String>,
+
+        /// 선호 버전 (예: react@19) - 같은 버전 문서를 올리고 같은 프레임워크의 다른 버전을 내림
+        #[arg(long, value_name = "FRAMEWORK@VERSION")]
+        prefer_version: Option<String>,
+
+        /// 고정 문서를 컨텍스트에 포함하지 않음
+        #[arg(long)]
+        no_pins: bool,
+
+        /// 컨텍스트 최대 토큰 수 (추정치)
+        #[arg(long)]
+        max_tokens: Option<usize>,
+
+        /// 점수 정규화 방식 (rank, min_max, z_score) - 설정 파일의 `[search]`보다 우선
+        #[arg(long, value_parser = parse_normalization)]
+        normalization: Option<ScoreNormalization>,
+
+        /// 답변을 생성하지 않고 구성된 컨텍스트만 출력
+        #[arg(long)]
+        context_only: bool,
+
+        /// 답변과 인용 번호별 출처(citations)를 JSON으로 출력
+        #[arg(long)]
+        json: bool,
+
+        /// 검색 트레이스를 JSON으로 저장 (~/.palank-rag/traces)
+        #[arg(long)]
+        trace: bool,
+    },
+
     /// 상태 확인
+    Status {
+        /// 벡터 테이블별 행 수, 인덱스, 조각 수, 데이터셋 버전, 디스크 사용량 표시
+        #[arg(short, long)]
+        verbose: bool,
+    },
+
+    /// 저장소 정리 (만료 문서 삭제 + DB 최적화)
+    Compact,
+
+    /// 저장소 무결성 검사 및 복구 (백업 복원, FTS 재생성, 누락 벡터 재임베딩)
+    Repair {
+        /// 검사만 하고 복구하지 않음
+        #[arg(long)]
+        check: bool,
+
+        /// 복원할 백업 디렉토리 (기본: ~/.palank-rag/backups의 최신 백업)
+        #[arg(long, value_name = "DIR")]
+        backup: Option<PathBuf>,
+    },
+
+    /// 읽기 전용 번들 (지식베이스 스냅샷 파일) 관리
+    Bundle {
+        #[command(subcommand)]
+        action: BundleAction,
+    },
+
+    /// 저장된 문서를 현재 청킹 설정으로 다시 청킹하고 임베딩
+    Reembed {
+        /// 현재와 다른 청커/설정으로 청킹된 문서만 (청커 기록이 없는 문서 포함)
+        #[arg(long)]
+        changed_chunker: bool,
+
+        /// 현재와 다른 임베딩 모델(버전)로 만든 벡터가 있는 문서만 (모델 기록이 없는 문서 포함)
+        #[arg(long)]
+        outdated_model: bool,
+
+        /// 이번 실행에서 다시 임베딩할 최대 문서 수 (일일 할당량 안에서 나누어 진행)
+        #[arg(long, value_name = "N")]
+        max_docs: Option<usize>,
+    },
+
+    /// 추출과 청킹만 실행하여 청크 미리보기 (임베딩/저장 없음)
+    ChunkPreview {
+        /// 미리볼 파일
+        #[arg(
+            short,
+            long,
+            value_name = "PATH",
+            required_unless_present = "url",
+            conflicts_with = "url"
+        )]
+        file: Option<PathBuf>,
+
+        /// 미리볼 URL
+        #[arg(short, long)]
+        url: Option<String>,
+
+        /// 최소 청크 크기 (바이트, 기본: 200)
+        #[arg(long)]
+        min: Option<usize>,
+
+        /// 최대 청크 크기 (바이트, 기본: 1200)
+        #[arg(long)]
+        max: Option<usize>,
+
+        /// 청크 간 오버랩 (바이트, 기본: 100)
+        #[arg(long)]
+        overlap: Option<usize>,
+
+        /// CSV/TSV를 N행씩 묶어서 추출 (ingest --table-rows와 동일)
+        #[arg(long, value_name = "N")]
+        table_rows: Option<usize>,
+
+        /// 청크 전체 출력 (기본: 첫 줄만)
+        #[arg(long)]
+        full: bool,
+    },
+
+    /// 스크린샷 폴더를 감시하여 새 이미지를 OCR 후 수집
+    Watch {
+        /// 감시할 폴더
+        dir: PathBuf,
+
+        /// 확인 주기 (초)
+        #[arg(long, default_value = "10")]
+        interval: u64,
+
+        /// 수집 문서 태그
+        #[arg(long, default_value = SCREENSHOT_TAG)]
+        tag: String,
+
+        /// 한 번만 확인하고 종료
+        #[arg(long)]
+        once: bool,
+    },
+
+    /// 설정 파일의 예약 작업과 감시 폴더를 계속 실행
+    Daemon {
+        /// 예약 작업과 다음 실행 시각만 출력
+        #[arg(long)]
+        list: bool,
+
+        /// 지정한 작업을 즉시 한 번 실행하고 종료
+        #[arg(long, value_name = "NAME")]
+        run: Option<String>,
+    },
+
+    /// 데몬/감시를 로그인 시 자동 실행되는 서비스로 등록 (systemd 사용자 유닛, Windows 작업 스케줄러)
+    Service {
+        #[command(subcommand)]
+        action: ServiceAction,
+    },
+
+    /// API 키 관리 (OS 키링)
+    Auth {
+        #[command(subcommand)]
+        action: AuthAction,
+    },
+
+    /// 외부로 통신하는 모든 엔드포인트 나열 (텔레메트리 없음)
+    AuditNetwork {
+        /// JSON으로 출력
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// `ingest --sql` 옵션
+#[derive(Args, Default)]
+pub struct SqlIngestArgs {
+    /// 수집할 SQL 쿼리 (SELECT, 결과 행마다 문서 1개)
+    #[arg(long, requires = "conn")]
+    sql: Option<String>,
+
+    /// SQL 연결 (SQLite 파일 경로/sqlite://경로, postgres://...)
+    #[arg(long, requires = "sql")]
+    conn: Option<String>,
+
+    /// 본문 템플릿 (예: "Q: {question}\nA: {answer}", 기본: "열: 값" 줄)
+    #[arg(long, requires = "sql")]
+    template: Option<String>,
+
+    /// 제목 템플릿 (예: "{question}")
+    #[arg(long, requires = "sql")]
+    title_template: Option<String>,
+
+    /// URL 템플릿 (예: "https://shop.example.com/p/{sku}", 기본: 쿼리 해시 + 첫 열 값)
+    #[arg(long, requires = "sql")]
+    url_template: Option<String>,
+}
+
+/// `ingest --bookmarks` 옵션
+#[derive(Args, Default)]
+pub struct BookmarkIngestArgs {
+    /// 브라우저 북마크 파일 (Netscape HTML 내보내기 또는 Chrome Bookmarks JSON)
+    #[arg(long)]
+    bookmarks: Option<PathBuf>,
+
+    /// 각 북마크 URL의 본문을 스크랩 (없으면 제목/URL/폴더만 저장)
+    #[arg(long, requires = "bookmarks")]
+    scrape: bool,
+
+    /// 동시 스크랩 수
+    #[arg(long, default_value = "4", requires = "scrape")]
+    concurrency: usize,
+
+    /// 이 폴더 경로로 시작하는 북마크만 수집 (예: "Dev/Rust")
+    #[arg(long, requires = "bookmarks")]
+    folder: Option<String>,
+}
+
+/// `query --format` 출력 형식
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum QueryFormat {
+    /// 사람이 읽는 텍스트
+    Text,
+    /// Alfred Script Filter JSON
+    Alfred,
+    /// Raycast 확장용 JSON (palank-rag 고유 형식, `RaycastOutput` 참고)
+    Raycast,
+    /// vim/VS Code quickfix (`파일:줄:열: 메시지`, 소스 코드 결과만)
+    Quickfix,
+}
+
+/// `auth` 하위 명령어
+#[derive(Subcommand)]
+pub enum AuthAction {
+    /// OS 키링에 Gemini API 키 저장
+    Set {
+        /// API 키 (생략 시 표준 입력에서 읽음 - 셸 기록에 남지 않음)
+        key: Option<String>,
+    },
+
+    /// OS 키링에서 API 키 삭제
+    Remove,
+
+    /// API 키 설정 상태 및 출처 확인
     Status,
 }
 
+/// `bundle` 하위 명령어
+#[derive(Subcommand)]
+pub enum BundleAction {
+    /// 현재 지식베이스를 번들 파일 하나로 묶기 (SQLite + 벡터 + 매니페스트)
+    Create {
+        /// 만들 번들 파일 (예: project.palank, 이미 있으면 실패)
+        out: PathBuf,
+    },
+}
+
+/// `service` 하위 명령어
+#[derive(Subcommand)]
+pub enum ServiceAction {
+    /// 서비스 등록 후 시작 (기본: `daemon`)
+    Install {
+        /// `daemon` 대신 이 폴더를 감시 (`watch <DIR>`)
+        #[arg(long)]
+        watch: Option<PathBuf>,
+
+        /// 등록하지 않고 생성될 유닛/스크립트 내용만 출력
+        #[arg(long)]
+        print: bool,
+    },
+
+    /// 서비스 중지 및 등록 해제
+    Uninstall,
+}
+
 // ============================================================================
 // CLI Runner
 // ============================================================================
 
 /// CLI 명령어 실행
 pub async fn run(cli: Cli) -> Result<()> {
+    // 설정 오류는 명령에서 다시 보고하므로 여기서는 무시
+    let configured_lang = Config::load().ok().and_then(|config| config.lang);
+    i18n::set_lang(Lang::resolve(configured_lang));
+
+    if let Some(ref name) = cli.collection {
+        let dir = collection_dir(&get_data_dir(), name)?;
+        let _ = STORE_DIR.set(dir);
+    }
+
     match cli.command {
         Commands::Ingest {
             url,
             text,
             file,
             dir,
+            git_changed,
+            bib,
+            sql,
+            bookmarks,
+            conversations,
             framework,
             skip_images,
             skip_pdfs,
+            table_rows,
+            min_chars,
             force,
+            resume,
+            errors_only,
+            changelog,
+            ttl,
         } => {
             cmd_ingest(
                 url,
                 text,
                 file,
                 dir,
+                git_changed,
+                bib,
+                sql,
+                bookmarks,
+                conversations,
                 framework,
                 skip_images,
                 skip_pdfs,
+                table_rows,
+                min_chars,
                 force,
+                resume,
+                errors_only,
+                changelog,
+                ttl,
             )
             .await
         }
@@ -137,10 +675,143 @@ pub async fn run(cli: Cli) -> Result<()> {
             query,
             limit,
             framework,
-        } => cmd_query(&query, limit, framework).await,
-        Commands::List { framework, limit } => cmd_list(framework, limit).await,
+            prefer_version,
+            explain,
+            trace,
+            normalization,
+            two_stage,
+            time_budget,
+            export,
+            format,
+            only_code,
+            only_prose,
+            collections,
+            bundle,
+        } => {
+            let chunk_kind = match (only_code, only_prose) {
+                (true, _) => Some(ChunkKind::Code),
+                (_, true) => Some(ChunkKind::Prose),
+                _ => None,
+            };
+            let config = Config::load().context("설정 파일 로드 실패")?;
+            let options = SearchOptions {
+                limit,
+                framework,
+                prefer_version,
+                fusion: search_fusion(config.search, normalization),
+                two_stage,
+                time_budget_ms: time_budget,
+                chunk_kind,
+                boilerplate: config.boilerplate,
+                ..Default::default()
+            };
+            if !collections.is_empty() {
+                return cmd_query_collections(&query, &options, &collections, format).await;
+            }
+            cmd_query(
+                &query,
+                &options,
+                explain,
+                trace,
+                export.as_deref(),
+                bundle.as_deref(),
+                format,
+            )
+            .await
+        }
+        Commands::Compare {
+            query,
+            config_a,
+            config_b,
+            limit,
+            framework,
+        } => cmd_compare(&query, config_a, config_b, limit, framework).await,
+        Commands::List {
+            framework,
+            limit,
+            since,
+        } => cmd_list(framework, limit, since).await,
         Commands::Delete { url, id } => cmd_delete(url, id).await,
-        Commands::Status => cmd_status().await,
+        Commands::Pin { url, id } => cmd_set_pinned(url, id, true),
+        Commands::Unpin { url, id } => cmd_set_pinned(url, id, false),
+        Commands::Pins => cmd_pins(),
+        Commands::Show { url, id } => cmd_show(url, id),
+        Commands::Feedback {
+            query_id,
+            doc_id,
+            relevant,
+            irrelevant: _,
+        } => cmd_feedback(&query_id, doc_id, relevant),
+        Commands::Open { index, id } => cmd_open(index, id),
+        Commands::Ask {
+            question,
+            limit,
+            framework,
+            prefer_version,
+            no_pins,
+            max_tokens,
+            normalization,
+            context_only,
+            json,
+            trace,
+        } => {
+            let config = Config::load().context("설정 파일 로드 실패")?;
+            let search = SearchOptions {
+                limit,
+                framework,
+                prefer_version,
+                fusion: search_fusion(config.search, normalization),
+                boilerplate: config.boilerplate,
+                ..Default::default()
+            };
+            cmd_ask(
+                &question,
+                search,
+                !no_pins,
+                max_tokens,
+                context_only,
+                json,
+                trace,
+            )
+            .await
+        }
+        Commands::Status { verbose } => cmd_status(verbose).await,
+        Commands::Compact => cmd_compact().await,
+        Commands::Repair { check, backup } => cmd_repair(check, backup).await,
+        Commands::Bundle { action } => cmd_bundle(action).await,
+        Commands::Reembed {
+            changed_chunker,
+            outdated_model,
+            max_docs,
+        } => cmd_reembed(changed_chunker, outdated_model, max_docs).await,
+        Commands::ChunkPreview {
+            file,
+            url,
+            min,
+            max,
+            overlap,
+            table_rows,
+            full,
+        } => {
+            let defaults = ChunkConfig::default();
+            let config = ChunkConfig {
+                min_characters: min.unwrap_or(defaults.min_characters),
+                max_characters: max.unwrap_or(defaults.max_characters),
+                overlap_characters: overlap.unwrap_or(defaults.overlap_characters),
+                ..defaults
+            };
+            cmd_chunk_preview(file, url, config, table_rows, full).await
+        }
+        Commands::Watch {
+            dir,
+            interval,
+            tag,
+            once,
+        } => cmd_watch(dir, interval, tag, once).await,
+        Commands::Daemon { list, run } => cmd_daemon(list, run).await,
+        Commands::Service { action } => cmd_service(action),
+        Commands::Auth { action } => cmd_auth(action),
+        Commands::AuditNetwork { json } => cmd_audit_network(json),
     }
 }
 
@@ -157,64 +828,141 @@ async fn cmd_ingest(
     text: Option<String>,
     file: Option<PathBuf>,
     dir: Option<PathBuf>,
+    git_changed: Option<String>,
+    bib: Option<PathBuf>,
+    sql: SqlIngestArgs,
+    bookmarks: BookmarkIngestArgs,
+    conversations: Option<PathBuf>,
     framework: Option<String>,
     skip_images: bool,
     skip_pdfs: bool,
-    _force: bool,
+    table_rows: Option<usize>,
+    min_chars: Option<usize>,
+    force: bool,
+    resume: bool,
+    errors_only: bool,
+    changelog: bool,
+    ttl: Option<chrono::Duration>,
 ) -> Result<()> {
     // API 키 확인
     if !has_api_key() {
-        bail!(
-            "API 키가 설정되지 않았습니다.\n\n\
-             설정 방법:\n  \
-             export GEMINI_API_KEY=your-api-key\n  \
-             또는\n  \
-             export GOOGLE_AI_API_KEY=your-api-key\n\n\
-             API 키 발급: https://aistudio.google.com/app/apikey"
-        );
+        return Err(ConfigError(Msg::ApiKeyHelp.text().into()).into());
+    }
+
+    let expires_at = ttl.map(|ttl| chrono::Utc::now() + ttl);
+    let content_filter = match min_chars {
+        Some(n) => ContentFilter::new(n),
+        None => Config::load().context("설정 파일 로드 실패")?.content_filter,
+    };
+
+    // 서지 파일 수집
+    if let Some(ref bib_path) = bib {
+        return cmd_ingest_bibliography(bib_path, framework, force, expires_at).await;
+    }
+
+    // SQL 쿼리 결과 수집
+    if sql.sql.is_some() {
+        return cmd_ingest_sql(sql, framework, expires_at).await;
+    }
+
+    // 북마크 수집
+    if bookmarks.bookmarks.is_some() {
+        return cmd_ingest_bookmarks(bookmarks, framework, content_filter, expires_at).await;
+    }
+
+    // AI 대화 내보내기 수집
+    if let Some(ref path) = conversations {
+        return cmd_ingest_conversations(path, framework, expires_at).await;
     }
 
     // 파일/폴더 수집
-    if file.is_some() || dir.is_some() {
-        return cmd_ingest_files(file, dir, framework, skip_images, skip_pdfs).await;
+    if file.is_some() || dir.is_some() || errors_only {
+        let table_rows = table_rows.filter(|&n| n > 0);
+        return cmd_ingest_files(
+            file,
+            dir,
+            git_changed,
+            framework,
+            skip_images,
+            skip_pdfs,
+            table_rows,
+            content_filter,
+            resume,
+            errors_only,
+            expires_at,
+        )
+        .await;
     }
 
     // URL 또는 텍스트 수집 (기존 로직)
-    let retriever = HybridRetriever::new()
-        .await
-        .context("HybridRetriever 초기화 실패")?;
+    let retriever = ingest_retriever().await?;
 
-    let (content, source_url, title) = if let Some(ref url_str) = url {
+    let (content, source_url, title, metadata) = if let Some(ref url_str) = url {
         // URL에서 콘텐츠 스크랩
-        println!("[*] URL 스크래핑 중: {}", url_str);
+        println!("{}", Msg::IngestScraping.format(&[url_str]));
 
-        let scraper = WebScraper::new().context("WebScraper 생성 실패")?;
+        let config = Config::load().context("설정 파일 로드 실패")?;
+        let scraper = WebScraper::with_policy(config.url_policy)
+            .context("WebScraper 생성 실패")?
+            .with_titles(config.titles);
         let scraped = scraper
             .scrape(url_str)
             .await
             .context("URL 스크래핑 실패")?;
 
+        if !content_filter.accepts(&scraped.content) {
+            let length = content_length(&scraped.content);
+            let min_chars = content_filter.min_chars;
+            println!(
+                "{}",
+                Msg::IngestUrlTooShort.format(&[&length, &min_chars, url_str])
+            );
+            return Ok(());
+        }
+
         let content = if let Some(ref title) = scraped.title {
             format!("# {}\n\n{}", title, scraped.content)
         } else {
             scraped.content
         };
+        let metadata = (!scraped.metadata.is_empty()).then_some(scraped.metadata);
 
-        (content, url_str.clone(), scraped.title)
+        (content, url_str.clone(), scraped.title, metadata)
     } else if let Some(ref text_content) = text {
         // 직접 입력된 텍스트
-        (text_content.clone(), "direct-input".to_string(), None)
+        (text_content.clone(), "direct-input".to_string(), None, None)
     } else {
-        bail!("--url, --text, --file, --dir 중 하나를 지정해야 합니다");
+        bail!(
+            "--url, --text, --file, --dir, --bib, --sql, --bookmarks, --conversations 중 하나를 지정해야 합니다"
+        );
     };
 
-    println!("[*] 문서 저장 및 임베딩 생성 중...");
+    println!("{}", Msg::IngestSaving.text());
 
+    let provenance = if url.is_some() {
+        Provenance::current("url").with_extractor("scraper")
+    } else {
+        Provenance::current("text")
+    };
     let doc = NewDocument {
         url: source_url.clone(),
         title,
         content,
-        framework,
+        framework: pair_framework(framework.as_deref(), &source_url),
+        expires_at,
+        citation: None,
+        metadata,
+        provenance: Some(provenance),
+    };
+
+    // 변경 이력: 저장 전에 이전 버전과 비교
+    let changes = if changelog {
+        retriever
+            .store()
+            .get_by_url(&source_url)?
+            .and_then(|previous| changelog_document(&previous, &doc, chrono::Utc::now()))
+    } else {
+        None
     };
 
     let doc_id = retriever
@@ -222,19 +970,41 @@ async fn cmd_ingest(
         .await
         .context("문서 추가 실패")?;
 
-    println!("[OK] 문서가 추가되었습니다 (ID: {})", doc_id);
-    println!("     URL: {}", source_url);
+    println!("{}", Msg::IngestDocAdded.format(&[&doc_id]));
+    println!("{}", Msg::IngestUrl.format(&[&source_url]));
+
+    if changelog {
+        match changes {
+            Some(changes) => {
+                let changes_url = changes.url.clone();
+                let changes_id = retriever
+                    .add_document(changes)
+                    .await
+                    .context("변경 이력 문서 추가 실패")?;
+                println!("{}", Msg::IngestChangesAdded.format(&[&changes_id]));
+                println!("{}", Msg::IngestUrl.format(&[&changes_url]));
+            }
+            None => println!("{}", Msg::IngestNoChanges.text()),
+        }
+    }
 
     Ok(())
 }
 
 /// 파일/폴더 수집 명령어
+#[allow(clippy::too_many_arguments)]
 async fn cmd_ingest_files(
     file: Option<PathBuf>,
     dir: Option<PathBuf>,
+    git_changed: Option<String>,
     framework: Option<String>,
     skip_images: bool,
     skip_pdfs: bool,
+    table_rows: Option<usize>,
+    content_filter: ContentFilter,
+    resume: bool,
+    errors_only: bool,
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
 ) -> Result<()> {
     let config = CollectorConfig {
         skip_images,
@@ -243,327 +1013,2546 @@ async fn cmd_ingest_files(
     };
 
     let collector = FileCollector::new(config);
-    let extractor = ContentExtractor::from_env();
-    let retriever = HybridRetriever::new()
-        .await
-        .context("HybridRetriever 초기화 실패")?;
+    let extractor = ContentExtractor::from_env().with_table_rows(table_rows);
+    let app_config = Config::load().context("설정 파일 로드 실패")?;
+    // 키별 사용량을 마지막에 출력하기 위해 임베더를 공유
+    let embedder = Arc::new(GeminiEmbedding::from_env().context("임베더 생성 실패")?);
+    let retriever = HybridRetriever::with_embedder(
+        &store_dir(),
+        Box::new(EmbeddingBudget::new(
+            CircuitBreaker::new(embedder.clone()),
+            app_config.limits.max_embeddings,
+        )),
+    )
+    .await
+    .context("HybridRetriever 초기화 실패")?;
+    let disk = DiskGuard::new(&store_dir(), app_config.limits.max_disk_growth_mb);
+    let retriever = apply_embedding_config(retriever, &app_config.embedding)?;
 
     // 파일 수집
-    let files = if let Some(ref file_path) = file {
+    let collection = if errors_only {
+        // 직전 수집의 실패 파일 (재시도로 해결될 수 있는 것만)
+        let errors_path = store_dir().join(INGEST_ERRORS_FILE);
+        if !errors_path.exists() {
+            println!("{}", Msg::IngestNoFailureList.text());
+            return Ok(());
+        }
+        let paths: Vec<PathBuf> = load_failures(&errors_path)?
+            .into_iter()
+            .filter(|f| f.kind.is_retryable())
+            .map(|f| f.path)
+            .collect();
+        println!("{}", Msg::IngestRetryingFailures.format(&[&paths.len()]));
+        collector.collect_paths(&paths)
+    } else if let Some(ref file_path) = file {
         // 단일 파일
-        match collector.collect_file(file_path)? {
-            Some(f) => vec![f],
-            None => {
-                println!("[!] 지원하지 않는 파일 형식: {:?}", file_path);
-                return Ok(());
-            }
+        if !file_path.is_file() {
+            return Err(NotFound(Msg::FileNotFound.format(&[&file_path.display()])).into());
         }
+        collector.collect_paths(&[std::path::absolute(file_path)?])
     } else if let Some(ref dir_path) = dir {
-        // 폴더 재귀
-        collector.collect_directory(dir_path)?
+        match git_changed {
+            // git 리비전 이후 변경분
+            Some(ref rev) => {
+                let collection = collector
+                    .collect_git_changed(dir_path, rev)
+                    .context("git 변경 파일 조회 실패")?;
+                let changed = collection.files.len()
+                    + collection.oversized.len()
+                    + collection.unsupported.len();
+                println!("{}", Msg::IngestGitChanged.format(&[rev, &changed]));
+                collection
+            }
+            // 폴더 재귀
+            None => collector.collect_directory_detailed(dir_path)?,
+        }
     } else {
         bail!("--file 또는 --dir를 지정해야 합니다");
     };
 
-    if files.is_empty() {
-        println!("[!] 수집할 파일이 없습니다.");
-        return Ok(());
-    }
-
+    let mut report = IngestReport::default();
+    for oversized in &collection.oversized {
+        let size = format_bytes(oversized.size as usize);
+        let max_size = format_bytes(collector.max_file_size() as usize);
+        println!(
+            "{}",
+            Msg::IngestTooLarge.format(&[&oversized.path.display(), &size, &max_size])
+        );
+        report.fail(
+            &oversized.path,
+            FailureKind::TooLarge,
+            "file size limit exceeded",
+        );
+    }
+    // 직접 지정한 파일의 지원하지 않는 확장자는 실패, 폴더 수집에서는 건너뜀으로 집계
+    let unsupported_skipped = if dir.is_some() {
+        collection.unsupported.len()
+    } else {
+        for path in &collection.unsupported {
+            println!("{}", Msg::IngestUnsupported.format(&[&path.display()]));
+            report.fail(
+                path,
+                FailureKind::UnsupportedFormat,
+                "unsupported file extension",
+            );
+        }
+        0
+    };
+
+    let files = collection.files;
+    if files.is_empty() {
+        println!("{}", Msg::IngestNoFiles.text());
+        return finish_ingest_report(&report, unsupported_skipped);
+    }
+
     // 통계 표시
     let stats = CollectionStats::from_files(&files);
-    println!("[*] 수집 대상: {} 파일", stats.total_files);
+    println!("{}", Msg::IngestTargets.format(&[&stats.total_files]));
+    println!(
+        "{}",
+        Msg::IngestFileTypes.format(&[&stats.text_files, &stats.image_files, &stats.pdf_files])
+    );
+    let total_size = format_bytes(stats.total_size as usize);
+    println!("{}", Msg::IngestTotalSize.format(&[&total_size]));
+    println!();
+
+    // 이미지 파일 경고
+    if stats.image_files > 0 && !skip_images {
+        println!("{}", Msg::IngestVisionWarning.format(&[&stats.image_files]));
+    }
+
+    // 파일별 처리 (추출한 문서는 INGEST_BATCH_SIZE 단위로 일괄 저장)
+    let mut pending_docs: Vec<NewDocument> = Vec::new();
+    let mut pending_files: Vec<(PathBuf, usize)> = Vec::new();
+
+    for (i, collected_file) in files.iter().enumerate() {
+        let file_name = collected_file
+            .path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown");
+        let file_url = format!("file://{}", collected_file.path.display());
+        let file_framework = pair_framework(framework.as_deref(), &file_url);
+
+        // 남은 문서는 루프 뒤에서 저장됨
+        if resume && retriever.is_ingested(&file_url).await? {
+            report.skipped += 1;
+            continue;
+        }
+
+        let type_str = match collected_file.file_type {
+            FileType::Text => "TXT",
+            FileType::Image => "IMG",
+            FileType::Pdf => "PDF",
+        };
+
+        print!(
+            "[{}/{}] [{}] {}... ",
+            i + 1,
+            files.len(),
+            type_str,
+            file_name
+        );
+
+        // 콘텐츠 추출
+        let contents = match extractor
+            .extract(&collected_file.path, collected_file.file_type)
+            .await
+        {
+            Ok(c) => c,
+            Err(e) => {
+                println!("{}", Msg::IngestItemFailed.format(&[&e]));
+                let kind = FailureKind::of_extraction_error(&e);
+                report.fail(&collected_file.path, kind, format!("{:#}", e));
+                continue;
+            }
+        };
+
+        // 문서 제목 (설정된 우선순위, 없으면 파일명) - 페이지/행 표시는 뒤에 붙임
+        let base_title = contents
+            .first()
+            .and_then(|c| app_config.titles.for_text(&c.text, Some(file_name)))
+            .unwrap_or_else(|| file_name.to_string());
+
+        // 빈 페이지, 몇 단어뿐인 페이지, 바이너리 잔해 제외 (표의 행 묶음은 유지)
+        let extracted = contents.len();
+        let contents: Vec<_> = contents
+            .into_iter()
+            .filter(|c| content_filter.accepts_content(c))
+            .collect();
+        let too_short = extracted - contents.len();
+        report.too_short += too_short;
+        if contents.is_empty() {
+            println!("{}", Msg::IngestItemTooShort.text());
+            continue;
+        }
+
+        // 각 콘텐츠 저장 (PDF는 페이지별, CSV/TSV 구조화 모드는 행 묶음별)
+        let doc_count = contents.len();
+        for content in contents {
+            let (title, url) = match (content.metadata.page_number, content.metadata.row_range) {
+                (Some(page), _) => (format!("{} (Page {})", base_title, page), file_url.clone()),
+                (None, Some((first, last))) if first == last => (
+                    format!("{} (Row {})", base_title, first),
+                    format!("{}#row={}", file_url, first),
+                ),
+                (None, Some((first, last))) => (
+                    format!("{} (Rows {}-{})", base_title, first, last),
+                    format!("{}#rows={}-{}", file_url, first, last),
+                ),
+                (None, None) => (base_title.clone(), file_url.clone()),
+            };
+
+            let provenance = Provenance::current("file").with_extractor(content.extractor_name());
+            pending_docs.push(NewDocument {
+                url,
+                title: Some(title),
+                content: content.text,
+                framework: file_framework.clone(),
+                expires_at,
+                citation: None,
+                metadata: None,
+                provenance: Some(provenance),
+            });
+        }
+
+        if too_short > 0 {
+            println!("{}", Msg::IngestExtractedSkipped.format(&[&too_short]));
+        } else {
+            println!("{}", Msg::IngestExtracted.text());
+        }
+        pending_files.push((collected_file.path.clone(), doc_count));
+
+        if pending_docs.len() >= INGEST_BATCH_SIZE || i + 1 == files.len() {
+            let flushed = flush_ingest_batch(
+                &retriever,
+                &disk,
+                &mut pending_docs,
+                &mut pending_files,
+                &mut report,
+            )
+            .await;
+            if let Err(e) = flushed {
+                finish_ingest_report(&report, unsupported_skipped)?;
+                return Err(e);
+            }
+        }
+    }
+
+    // 마지막 파일이 추출 실패한 경우 남은 문서 저장
+    let flushed = flush_ingest_batch(
+        &retriever,
+        &disk,
+        &mut pending_docs,
+        &mut pending_files,
+        &mut report,
+    )
+    .await;
+    finish_ingest_report(&report, unsupported_skipped)?;
+    flushed?;
+
+    // API 키 순환 사용 시 키별 사용량
+    if embedder.key_count() > 1 {
+        println!("{}", Msg::IngestKeyUsage.text());
+        for usage in embedder.key_usage() {
+            let line = Msg::IngestKeyUsageLine.format(&[
+                &usage.key_hint,
+                &usage.requests,
+                &usage.successes,
+                &usage.rate_limited,
+            ]);
+            println!("{}", line);
+        }
+    }
+
+    Ok(())
+}
+
+/// 서지 파일 수집 명령어 (ingest --bib)
+///
+/// 항목마다 연결 파일(PDF 등)이 있으면 파일 내용을, 없으면 초록을 문서로 저장하고
+/// 인용 정보를 함께 기록합니다. 이미 수집된 파일은 `--force` 없이는 인용 정보만 갱신합니다.
+async fn cmd_ingest_bibliography(
+    bib_path: &Path,
+    framework: Option<String>,
+    force: bool,
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
+) -> Result<()> {
+    let entries = load_bibliography(bib_path).context("서지 파일 로드 실패")?;
+    if entries.is_empty() {
+        println!("{}", Msg::IngestNoBibEntries.text());
+        return Ok(());
+    }
+    println!("{}", Msg::IngestBibEntries.format(&[&entries.len()]));
+
+    let collector = FileCollector::new(CollectorConfig::default());
+    let extractor = ContentExtractor::from_env();
+    let retriever = ingest_retriever().await?;
+
+    let mut added = 0;
+    let mut updated = 0;
+    let mut skipped = 0;
+    let mut error_count = 0;
+
+    for (i, entry) in entries.iter().enumerate() {
+        let citation = &entry.citation;
+        print!("[{}/{}] {}... ", i + 1, entries.len(), citation.short());
+
+        // 연결 파일 중 수집 가능한 첫 파일 (읽기 실패는 이 항목만 실패로 처리)
+        let mut collected = None;
+        let mut collect_error = None;
+        for path in entry.files.iter().filter(|p| p.exists()) {
+            match collector.collect_file(path) {
+                Ok(Some(f)) => {
+                    collected = Some(f);
+                    break;
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    collect_error = Some(e);
+                    break;
+                }
+            }
+        }
+        if let Some(e) = collect_error {
+            println!("{}", Msg::IngestItemFailed.format(&[&e]));
+            error_count += 1;
+            continue;
+        }
+
+        let doc = if let Some(collected_file) = collected {
+            let url = format!("file://{}", collected_file.path.display());
+
+            // 이미 수집된 파일은 인용 정보만 갱신
+            if !force {
+                if let Some(existing) = retriever.store().get_by_url(&url)? {
+                    retriever.store().set_citation(existing.id, Some(citation))?;
+                    println!("{}", Msg::IngestCitationUpdated.format(&[&existing.id]));
+                    updated += 1;
+                    continue;
+                }
+            }
+
+            let contents = match extractor
+                .extract(&collected_file.path, collected_file.file_type)
+                .await
+            {
+                Ok(c) => c,
+                Err(e) => {
+                    println!("{}", Msg::IngestItemFailed.format(&[&e]));
+                    error_count += 1;
+                    continue;
+                }
+            };
+
+            let provenance = Provenance::current("bibtex")
+                .with_extractor(contents.first().map_or("text", |c| c.extractor_name()));
+            // 인용 단위로 검색되도록 페이지를 하나의 문서로 합침
+            let content = contents
+                .into_iter()
+                .map(|c| c.text)
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            let title = citation.title.clone().or_else(|| {
+                collected_file
+                    .path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+            });
+
+            NewDocument {
+                url,
+                title,
+                content,
+                framework: framework.clone(),
+                expires_at,
+                citation: Some(citation.clone()),
+                metadata: None,
+                provenance: Some(provenance),
+            }
+        } else if let Some(ref abstract_text) = entry.abstract_text {
+            let title = citation.title.clone().unwrap_or_else(|| citation.key.clone());
+            NewDocument {
+                url: citation
+                    .link()
+                    .unwrap_or_else(|| format!("bibtex:{}", citation.key)),
+                content: format!("# {}\n\n{}\n\n{}", title, citation.format(), abstract_text),
+                title: Some(title),
+                framework: framework.clone(),
+                expires_at,
+                citation: Some(citation.clone()),
+                metadata: None,
+                provenance: Some(Provenance::current("bibtex").with_extractor("abstract")),
+            }
+        } else {
+            println!("{}", Msg::IngestBibSkipped.text());
+            skipped += 1;
+            continue;
+        };
+
+        match retriever.add_document(doc).await {
+            Ok(doc_id) => {
+                println!("{}", Msg::IngestItemAdded.format(&[&doc_id]));
+                added += 1;
+            }
+            Err(e) => {
+                println!("{}", Msg::IngestItemFailed.format(&[&e]));
+                error_count += 1;
+            }
+        }
+    }
+
+    println!();
+    println!(
+        "{}",
+        Msg::IngestBibDone.format(&[&added, &updated, &skipped, &error_count])
+    );
+
+    Ok(())
+}
+
+/// SQL 수집 명령어 (ingest --sql --conn)
+///
+/// 쿼리 결과의 각 행을 템플릿으로 문서화하여 INGEST_BATCH_SIZE 단위로 저장합니다.
+/// 같은 URL의 행은 재수집 시 갱신됩니다.
+async fn cmd_ingest_sql(
+    args: SqlIngestArgs,
+    framework: Option<String>,
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
+) -> Result<()> {
+    let (Some(query), Some(conn_str)) = (args.sql, args.conn) else {
+        bail!("--sql과 --conn을 함께 지정해야 합니다");
+    };
+    let conn = SqlConnection::parse(&conn_str)?;
+    // 셸에서 입력한 "\n"은 줄바꿈으로 해석
+    let template = RowTemplate {
+        content: args.template.map(|t| t.replace("\\n", "\n")),
+        title: args.title_template,
+        url: args.url_template,
+    };
+
+    println!("{}", Msg::IngestRunningSql.text());
+    let rows = fetch_rows(&conn, &query).await.context("SQL 쿼리 실패")?;
+    if rows.is_empty() {
+        println!("{}", Msg::IngestNoRows.text());
+        return Ok(());
+    }
+    println!("{}", Msg::IngestRows.format(&[&rows.len()]));
+
+    // 템플릿 오류는 첫 행에서 바로 드러나도록 전체를 먼저 변환
+    let source_key = format!("{}\n{}", conn_str, query);
+    let mut docs = Vec::with_capacity(rows.len());
+    for (i, row) in rows.iter().enumerate() {
+        let mut doc = template
+            .to_document(row, &source_key)
+            .with_context(|| format!("{}번째 행 변환 실패", i + 1))?;
+        doc.framework = framework.clone();
+        doc.expires_at = expires_at;
+        doc.provenance = Some(Provenance::current("sql"));
+        docs.push(doc);
+    }
+
+    let retriever = ingest_retriever().await?;
+    let disk = ingest_disk_guard()?;
+
+    let (success_count, error_count) = save_in_batches(&retriever, &disk, &docs).await?;
+
+    println!();
+    println!(
+        "{}",
+        Msg::IngestDone.format(&[&success_count, &error_count])
+    );
+
+    Ok(())
+}
+
+/// 북마크 수집 명령어 (ingest --bookmarks)
+///
+/// 북마크 폴더 경로를 프레임워크 태그로 사용합니다 (`--framework`로 덮어쓰기 가능).
+/// `--scrape` 시 스크랩에 실패한 북마크는 제목/URL/폴더만 저장합니다.
+async fn cmd_ingest_bookmarks(
+    args: BookmarkIngestArgs,
+    framework: Option<String>,
+    content_filter: ContentFilter,
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
+) -> Result<()> {
+    use futures::StreamExt;
+
+    let Some(path) = args.bookmarks else {
+        bail!("--bookmarks를 지정해야 합니다");
+    };
+    let text = std::fs::read_to_string(&path)
+        .with_context(|| format!("북마크 파일 읽기 실패: {:?}", path))?;
+
+    // 웹 URL만, 폴더 필터 적용, 같은 URL은 처음 것만
+    let mut seen = std::collections::HashSet::new();
+    let bookmarks: Vec<Bookmark> = parse_bookmarks(&text)?
+        .into_iter()
+        .filter(|b| b.is_web())
+        .filter(|b| match args.folder {
+            Some(ref prefix) => b
+                .folder_path()
+                .is_some_and(|f| f == *prefix || f.starts_with(&format!("{}/", prefix))),
+            None => true,
+        })
+        .filter(|b| seen.insert(b.url.clone()))
+        .collect();
+
+    if bookmarks.is_empty() {
+        println!("{}", Msg::IngestNoBookmarks.text());
+        return Ok(());
+    }
+    println!("{}", Msg::IngestBookmarks.format(&[&bookmarks.len()]));
+
+    let mut docs = Vec::with_capacity(bookmarks.len());
+    let mut scrape_failed = 0;
+    let mut too_short = 0;
+
+    if args.scrape {
+        let config = Config::load().context("설정 파일 로드 실패")?;
+        // 가져오기 전에 검사하여 범위를 잘못 잡은 스크랩이 시작되지 않게 함
+        config.limits.check_pages(bookmarks.len())?;
+        let scraper = WebScraper::with_policy(config.url_policy)
+            .context("WebScraper 생성 실패")?
+            .with_titles(config.titles);
+        let total = bookmarks.len();
+
+        let mut results = futures::stream::iter(bookmarks.iter())
+            .map(|bookmark| {
+                let scraper = &scraper;
+                async move { (bookmark, scraper.scrape(&bookmark.url).await) }
+            })
+            .buffered(args.concurrency.max(1))
+            .enumerate();
+
+        while let Some((i, (bookmark, result))) = results.next().await {
+            match result {
+                Ok(scraped) if !content_filter.accepts(&scraped.content) => {
+                    let line =
+                        Msg::IngestBookmarkTooShort.format(&[&(i + 1), &total, &bookmark.url]);
+                    println!("{}", line);
+                    too_short += 1;
+                    docs.push(bookmark.to_document());
+                }
+                Ok(scraped) => {
+                    let line =
+                        Msg::IngestBookmarkScraped.format(&[&(i + 1), &total, &bookmark.url]);
+                    println!("{}", line);
+                    let mut doc = bookmark.to_document_with(Some(&scraped.content));
+                    doc.metadata = (!scraped.metadata.is_empty()).then_some(scraped.metadata);
+                    doc.provenance =
+                        Some(Provenance::current("bookmarks").with_extractor("scraper"));
+                    docs.push(doc);
+                }
+                Err(e) => {
+                    let line = Msg::IngestBookmarkScrapeFailed.format(&[
+                        &(i + 1),
+                        &total,
+                        &bookmark.url,
+                        &e,
+                    ]);
+                    println!("{}", line);
+                    scrape_failed += 1;
+                    docs.push(bookmark.to_document());
+                }
+            }
+        }
+    } else {
+        docs.extend(bookmarks.iter().map(Bookmark::to_document));
+    }
+
+    for doc in &mut docs {
+        if framework.is_some() {
+            doc.framework = framework.clone();
+        }
+        doc.framework = pair_framework(doc.framework.as_deref(), &doc.url);
+        doc.expires_at = expires_at;
+        doc.provenance.get_or_insert_with(|| Provenance::current("bookmarks"));
+    }
+
+    let retriever = ingest_retriever().await?;
+    let disk = ingest_disk_guard()?;
+
+    let (success_count, error_count) = save_in_batches(&retriever, &disk, &docs).await?;
+
+    println!();
+    let summary = Msg::IngestBookmarksDone.format(&[
+        &success_count,
+        &error_count,
+        &scrape_failed,
+        &too_short,
+    ]);
+    println!("{}", summary);
+
+    Ok(())
+}
+
+/// AI 대화 수집 명령어 (ingest --conversations)
+///
+/// 대화마다 문서 하나로 저장하며, 서비스 이름(chatgpt/claude)을 프레임워크 태그로 씁니다.
+/// 대화 URL이 같으므로 새로 내보낸 파일을 다시 수집하면 기존 대화를 갱신합니다.
+async fn cmd_ingest_conversations(
+    path: &Path,
+    framework: Option<String>,
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
+) -> Result<()> {
+    // 내보내기 압축을 푼 폴더를 지정해도 됨
+    let path = if path.is_dir() {
+        path.join("conversations.json")
+    } else {
+        path.to_path_buf()
+    };
+    let text = std::fs::read_to_string(&path)
+        .with_context(|| format!("대화 내보내기 파일 읽기 실패: {:?}", path))?;
+    let conversations = parse_conversations(&text)?;
+
+    if conversations.is_empty() {
+        println!("{}", Msg::IngestNoConversations.text());
+        return Ok(());
+    }
+    let turns: usize = conversations.iter().map(|c| c.turns.len()).sum();
+    println!(
+        "{}",
+        Msg::IngestConversations.format(&[&conversations.len(), &turns])
+    );
+
+    let docs: Vec<NewDocument> = conversations
+        .iter()
+        .map(|conversation| {
+            let mut doc = conversation.to_document();
+            if framework.is_some() {
+                doc.framework = framework.clone();
+            }
+            doc.expires_at = expires_at;
+            doc.provenance = Some(Provenance::current("conversations"));
+            doc
+        })
+        .collect();
+
+    let retriever = ingest_retriever().await?;
+    let disk = ingest_disk_guard()?;
+
+    let (success_count, error_count) = save_in_batches(&retriever, &disk, &docs).await?;
+
+    println!();
+    println!(
+        "{}",
+        Msg::IngestDone.format(&[&success_count, &error_count])
+    );
+
+    Ok(())
+}
+
+/// 문서를 INGEST_BATCH_SIZE 단위로 저장하고 (성공, 실패) 문서 수 반환
+///
+/// 실행당 한도(`[limits]`)를 넘으면 남은 배치를 저장하지 않고 오류를 돌려줍니다.
+async fn save_in_batches(
+    retriever: &HybridRetriever,
+    disk: &DiskGuard,
+    docs: &[NewDocument],
+) -> Result<(usize, usize)> {
+    let mut success_count = 0;
+    let mut error_count = 0;
+    for batch in docs.chunks(INGEST_BATCH_SIZE) {
+        println!("{}", Msg::IngestSavingDocs.format(&[&batch.len()]));
+        match retriever.add_documents(batch).await {
+            Ok(ids) => success_count += ids.len(),
+            Err(e) if LimitExceeded::is_cause_of(&e) => {
+                print_limit_stop(success_count);
+                return Err(e);
+            }
+            Err(e) => {
+                println!("{}", Msg::IngestStoreFailed.format(&[&e]));
+                error_count += batch.len();
+            }
+        }
+        if let Err(e) = disk.check() {
+            print_limit_stop(success_count);
+            return Err(e.into());
+        }
+    }
+    Ok((success_count, error_count))
+}
+
+/// 대기 중인 문서를 한 번에 저장하고 성공/실패 집계
+///
+/// 배치 저장이 실패하면 배치의 문서는 하나도 저장되지 않으므로(`HybridRetriever::add_documents`),
+/// 수집을 중단할 오류가 아니면 파일별로 다시 저장해 실패한 파일만 실패로 집계합니다.
+/// 이미 임베딩한 문서는 다시 임베딩하지 않고 저장 단계만 다시 시도하며, 임베딩이 실패한
+/// 파일부터만 파일별로 다시 임베딩합니다.
+async fn flush_ingest_batch(
+    retriever: &HybridRetriever,
+    disk: &DiskGuard,
+    pending_docs: &mut Vec<NewDocument>,
+    pending_files: &mut Vec<(PathBuf, usize)>,
+    report: &mut IngestReport,
+) -> Result<()> {
+    if pending_files.is_empty() {
+        return Ok(());
+    }
+
     println!(
-        "    텍스트: {}, 이미지: {}, PDF: {}",
-        stats.text_files, stats.image_files, stats.pdf_files
+        "{}",
+        Msg::IngestSavingFiles.format(&[&pending_files.len(), &pending_docs.len()])
     );
-    println!("    총 크기: {}", format_bytes(stats.total_size as usize));
+    let docs = std::mem::take(pending_docs);
+    let files = std::mem::take(pending_files);
+
+    let mut ranges = Vec::with_capacity(files.len());
+    let mut start = 0;
+    for (_, count) in &files {
+        ranges.push(start..start + count);
+        start += count;
+    }
+
+    // 1. 임베딩을 마친 파일 저장 (중간에 임베딩이 실패해도 앞쪽 파일은 저장)
+    let mut prepared = retriever.prepare_documents(&docs).await;
+    let embed_error = prepared.take_error();
+    let ready = ranges
+        .iter()
+        .take_while(|range| range.end <= prepared.embedded())
+        .count();
+    if ready > 0 {
+        let (ready_files, ready_ranges) = (&files[..ready], &ranges[..ready]);
+        store_prepared_files(retriever, &prepared, ready_files, ready_ranges, report).await?;
+    }
+
+    // 2. 임베딩이 실패한 파일부터는 파일별로 다시 수집
+    if let Some(e) = embed_error {
+        let rest = &files[ready..];
+        if rest.len() == 1 || stops_ingest(&e) {
+            let kind = FailureKind::of_store_error(&e);
+            for (path, _) in rest {
+                report.fail(path, kind, format!("{:#}", e));
+            }
+            return report_store_failure(e, report);
+        }
+
+        println!("{}", Msg::IngestEmbedRetry.format(&[&e]));
+        for (i, ((path, _), range)) in rest.iter().zip(&ranges[ready..]).enumerate() {
+            let Err(e) = retriever.add_documents(&docs[range.clone()]).await else {
+                report.succeeded += 1;
+                continue;
+            };
+
+            let kind = FailureKind::of_store_error(&e);
+            if stops_ingest(&e) {
+                for (path, _) in &rest[i..] {
+                    report.fail(path, kind, format!("{:#}", e));
+                }
+                return report_store_failure(e, report);
+            }
+            report.fail(path, kind, format!("{:#}", e));
+            println!(
+                "{}",
+                Msg::IngestFileStoreFailed.format(&[&path.display(), &e])
+            );
+        }
+    }
+
+    if let Err(e) = disk.check() {
+        print_limit_stop(report.succeeded);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+/// 임베딩을 마친 파일들을 한 번에 저장하고, 실패하면 임베딩을 재사용해 파일별로 다시 저장
+async fn store_prepared_files(
+    retriever: &HybridRetriever,
+    prepared: &PreparedDocuments,
+    files: &[(PathBuf, usize)],
+    ranges: &[Range<usize>],
+    report: &mut IngestReport,
+) -> Result<()> {
+    let all = ranges[0].start..ranges[ranges.len() - 1].end;
+    match retriever.store_prepared(prepared, all).await {
+        Ok(_) => report.succeeded += files.len(),
+        Err(e) if files.len() == 1 || stops_ingest(&e) => {
+            let kind = FailureKind::of_store_error(&e);
+            for (path, _) in files {
+                report.fail(path, kind, format!("{:#}", e));
+            }
+            return report_store_failure(e, report);
+        }
+        Err(e) => {
+            println!("{}", Msg::IngestBatchRetry.format(&[&e]));
+            for (i, ((path, _), range)) in files.iter().zip(ranges).enumerate() {
+                let Err(e) = retriever.store_prepared(prepared, range.clone()).await else {
+                    report.succeeded += 1;
+                    continue;
+                };
+
+                let kind = FailureKind::of_store_error(&e);
+                if stops_ingest(&e) {
+                    for (path, _) in &files[i..] {
+                        report.fail(path, kind, format!("{:#}", e));
+                    }
+                    return report_store_failure(e, report);
+                }
+                report.fail(path, kind, format!("{:#}", e));
+                println!(
+                    "{}",
+                    Msg::IngestFileStoreFailed.format(&[&path.display(), &e])
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 남은 파일도 모두 실패할 오류인지 (할당량 소진, 실행당 한도 초과, 임베딩 API 장애)
+fn stops_ingest(e: &anyhow::Error) -> bool {
+    QuotaExhausted::is_cause_of(e) || LimitExceeded::is_cause_of(e) || CircuitOpen::is_cause_of(e)
+}
+
+/// 저장 실패 안내 (수집을 중단할 오류면 `Err`)
+fn report_store_failure(e: anyhow::Error, report: &IngestReport) -> Result<()> {
+    if QuotaExhausted::is_cause_of(&e) {
+        // 남은 파일도 모두 실패하므로 바로 중단
+        println!("{}", Msg::IngestQuotaStop.text());
+        println!("{}", Msg::IngestQuotaResume.format(&[&report.succeeded]));
+        return Err(e);
+    }
+    if LimitExceeded::is_cause_of(&e) {
+        print_limit_stop(report.succeeded);
+        return Err(e);
+    }
+    if CircuitOpen::is_cause_of(&e) {
+        // API 장애: 남은 파일마다 실패를 반복하지 않고 중단
+        println!("{}", Msg::IngestCircuitStop.text());
+        println!("{}", Msg::IngestCircuitResume.format(&[&report.succeeded]));
+        return Err(e);
+    }
+
+    println!("{}", Msg::IngestStoreFailed.format(&[&e]));
+    Ok(())
+}
+
+/// 실행당 한도(`[limits]`) 초과로 중단할 때 안내
+fn print_limit_stop(succeeded: usize) {
+    println!("{}", Msg::IngestLimitStop.text());
+    println!("{}", Msg::IngestLimitHint.format(&[&succeeded]));
+}
+
+/// 파일 수집 요약 출력 (실패 유형별 집계) 및 실패 목록 저장
+fn finish_ingest_report(report: &IngestReport, unsupported_skipped: usize) -> Result<()> {
     println!();
+    println!(
+        "{}",
+        Msg::IngestDone.format(&[&report.succeeded, &report.failures.len()])
+    );
+    if report.skipped > 0 {
+        println!("{}", Msg::IngestSkippedResume.format(&[&report.skipped]));
+    }
+    if unsupported_skipped > 0 {
+        println!(
+            "{}",
+            Msg::IngestSkippedUnsupported.format(&[&unsupported_skipped])
+        );
+    }
+    if report.too_short > 0 {
+        println!(
+            "{}",
+            Msg::IngestSkippedTooShort.format(&[&report.too_short])
+        );
+    }
+
+    let counts = report.counts();
+    if !counts.is_empty() {
+        println!("{}", Msg::IngestFailureKinds.text());
+        for (kind, count) in counts {
+            println!("    {}: {}", kind.label(), count);
+        }
+    }
+
+    let errors_path = store_dir().join(INGEST_ERRORS_FILE);
+    report
+        .save_failures(&errors_path)
+        .context("실패 목록 저장 실패")?;
+    if report.failures.iter().any(|f| f.kind.is_retryable()) {
+        println!(
+            "{}",
+            Msg::IngestFailureList.format(&[&errors_path.display()])
+        );
+        println!("{}", Msg::IngestRetryHint.text());
+    }
+
+    Ok(())
+}
+
+/// 검색 명령어 (query)
+///
+/// 하이브리드 검색 (FTS5 + 벡터)을 사용하여 지식베이스를 검색합니다.
+async fn cmd_query(
+    query: &str,
+    options: &SearchOptions,
+    explain: bool,
+    trace: bool,
+    export: Option<&Path>,
+    bundle: Option<&Path>,
+    format: QueryFormat,
+) -> Result<()> {
+    if !has_api_key() {
+        return Err(ConfigError(Msg::ApiKeyMissing.text().into()).into());
+    }
+
+    // 런처 출력은 stdout에 JSON만 씀
+    let text_output = format == QueryFormat::Text;
+    if text_output {
+        println!("{}", Msg::Searching.format(&[&query]));
+    }
+
+    let retriever = match bundle {
+        Some(path) => open_bundle_retriever(path, text_output).await?,
+        None => open_retriever(trace, text_output).await?,
+    };
+
+    let results = if explain {
+        retriever
+            .search_explained(query, options)
+            .await
+            .context("검색 실패")?
+    } else {
+        retriever
+            .search_with(query, options)
+            .await
+            .context("검색 실패")?
+            .into_iter()
+            .map(|result| ExplainedResult {
+                result,
+                explanation: ResultExplanation::default(),
+            })
+            .collect()
+    };
+
+    let plain: Vec<HybridSearchResult> = results.iter().map(|e| e.result.clone()).collect();
+    // `open <번호>`용 결과 목록 (저장 실패는 검색을 막지 않음)
+    if let Err(e) = save_last_results(&get_data_dir().join(LAST_RESULTS_FILE), &plain) {
+        tracing::warn!("검색 결과 목록 저장 실패: {}", e);
+    }
+    if let Some(path) = export {
+        let report = SearchReport {
+            query,
+            timestamp: chrono::Utc::now(),
+            results: &plain,
+        };
+        std::fs::write(path, report.render())
+            .with_context(|| format!("보고서 저장 실패: {:?}", path))?;
+        if text_output {
+            println!("{}", Msg::ReportSaved.format(&[&path.display()]));
+        }
+    }
+
+    match format {
+        QueryFormat::Text => {}
+        QueryFormat::Alfred => {
+            println!("{}", serde_json::to_string(&AlfredOutput::from_results(&plain))?);
+            return Ok(());
+        }
+        QueryFormat::Raycast => {
+            println!("{}", serde_json::to_string(&RaycastOutput::from_results(&plain))?);
+            return Ok(());
+        }
+        QueryFormat::Quickfix => {
+            for line in quickfix_lines(&plain) {
+                println!("{}", line);
+            }
+            return Ok(());
+        }
+    }
+
+    if plain.first().is_some_and(|r| r.partial) {
+        let budget = options.time_budget_ms.unwrap_or_default();
+        println!("{}", Msg::PartialResults.format(&[&budget]));
+    }
+
+    if results.is_empty() {
+        println!("{}", Msg::NoResults.text());
+        return Ok(());
+    }
+
+    println!("{}", Msg::ResultsHeader.format(&[&results.len()]));
+
+    for (i, ExplainedResult { result, explanation }) in results.iter().enumerate() {
+        let method_str = match result.method {
+            crate::knowledge::SearchMethod::Vector => "VEC",
+            crate::knowledge::SearchMethod::Fts => "FTS",
+            crate::knowledge::SearchMethod::Hybrid => "HYB",
+        };
+
+        let score = format!("{:.4}", result.rrf_score);
+        println!(
+            "{}",
+            Msg::ResultLine.format(&[&(i + 1), &method_str, &score, &result.doc_id])
+        );
+
+        if let Some(ref title) = result.title {
+            println!("{}", Msg::LabelTitle.format(&[title]));
+        }
+
+        println!("   URL: {}", result.url);
+
+        if let Some(ref location) = result.location {
+            let location = format!(
+                "{}:{}-{}",
+                location.path.display(),
+                location.start_line,
+                location.end_line
+            );
+            println!("{}", Msg::LabelLocation.format(&[&location]));
+        }
+
+        if let Some(ref citation) = result.citation {
+            println!("{}", Msg::LabelCitation.format(&[&citation.format()]));
+        }
+
+        // 청크 텍스트 또는 스니펫 출력
+        if let Some(ref chunk) = result.chunk_text {
+            let chunk = truncate_text(chunk, 200);
+            println!("{}", Msg::LabelContent.format(&[&chunk]));
+        } else if let Some(ref snippet) = result.snippet {
+            let snippet = truncate_text(snippet, 200);
+            println!("{}", Msg::LabelSnippet.format(&[&snippet]));
+        }
+
+        if explain {
+            print_explanation(explanation);
+        }
+
+        println!();
+    }
+
+    let id = query_id(query);
+    println!("{}", Msg::QueryId.format(&[&id]));
+    // 피드백은 내 지식베이스의 문서 ID로 기록하므로 번들 결과에는 안내하지 않음
+    if bundle.is_none() {
+        println!("{}", Msg::FeedbackHint.format(&[&id]));
+    }
+    println!("{}", Msg::OpenHint.text());
+
+    Ok(())
+}
+
+/// 여러 컬렉션 검색 (query --collections)
+///
+/// 컬렉션마다 검색기를 열어 동시에 검색하고, 결과마다 컬렉션 이름을 붙여 출력합니다.
+async fn cmd_query_collections(
+    query: &str,
+    options: &SearchOptions,
+    collections: &[String],
+    format: QueryFormat,
+) -> Result<()> {
+    if !has_api_key() {
+        return Err(ConfigError(Msg::ApiKeyMissing.text().into()).into());
+    }
+
+    let data_dir = get_data_dir();
+    let names = resolve_collections(&data_dir, collections)?;
+    if names.is_empty() {
+        return Err(NotFound(Msg::NoCollections.text().into()).into());
+    }
+
+    let text_output = format == QueryFormat::Text;
+    if text_output {
+        let label = names.join(", ");
+        println!(
+            "{}",
+            Msg::SearchingCollections.format(&[&names.len(), &label, &query])
+        );
+    }
+
+    let mut retrievers = Vec::with_capacity(names.len());
+    for name in &names {
+        let dir = collection_dir(&data_dir, name)?;
+        if !dir.join(SQLITE_FILE).is_file() {
+            let message = Msg::CollectionNotFound.format(&[name, &dir.display()]);
+            return Err(NotFound(message).into());
+        }
+        let retriever = HybridRetriever::with_data_dir(&dir)
+            .await
+            .with_context(|| format!("컬렉션 {} 검색기 초기화 실패", name))?;
+        retrievers.push((name.clone(), retriever));
+    }
+
+    let targets: Vec<(String, &HybridRetriever)> = retrievers
+        .iter()
+        .map(|(name, retriever)| (name.clone(), retriever))
+        .collect();
+    let hits = search_collections(&targets, query, options)
+        .await
+        .context("검색 실패")?;
+
+    let plain: Vec<HybridSearchResult> = hits.iter().map(|hit| hit.result.clone()).collect();
+    if let Err(e) = save_last_results(&data_dir.join(LAST_RESULTS_FILE), &plain) {
+        tracing::warn!("검색 결과 목록 저장 실패: {}", e);
+    }
+
+    match format {
+        QueryFormat::Text => {}
+        QueryFormat::Alfred => {
+            let output = AlfredOutput::from_results(&plain);
+            println!("{}", serde_json::to_string(&output)?);
+            return Ok(());
+        }
+        QueryFormat::Raycast => {
+            let output = RaycastOutput::from_results(&plain);
+            println!("{}", serde_json::to_string(&output)?);
+            return Ok(());
+        }
+        QueryFormat::Quickfix => {
+            for line in quickfix_lines(&plain) {
+                println!("{}", line);
+            }
+            return Ok(());
+        }
+    }
+
+    if hits.is_empty() {
+        println!("{}", Msg::NoResults.text());
+        return Ok(());
+    }
+
+    println!("{}", Msg::ResultsHeader.format(&[&hits.len()]));
+
+    for (i, hit) in hits.iter().enumerate() {
+        let result = &hit.result;
+        let score = format!("{:.4}", hit.score);
+        println!(
+            "{}",
+            Msg::ResultLine.format(&[&(i + 1), &hit.collection, &score, &result.doc_id])
+        );
+
+        if let Some(ref title) = result.title {
+            println!("{}", Msg::LabelTitle.format(&[title]));
+        }
+        println!("   URL: {}", result.url);
+
+        if let Some(ref chunk) = result.chunk_text {
+            let chunk = truncate_text(chunk, 200);
+            println!("{}", Msg::LabelContent.format(&[&chunk]));
+        } else if let Some(ref snippet) = result.snippet {
+            let snippet = truncate_text(snippet, 200);
+            println!("{}", Msg::LabelSnippet.format(&[&snippet]));
+        }
+
+        println!();
+    }
+
+    println!("{}", Msg::OpenHint.text());
+
+    Ok(())
+}
+
+/// 검색 결과의 순위 근거 출력 (query --explain)
+fn print_explanation(explanation: &ResultExplanation) {
+    println!("{}", Msg::ExplainHeader.text());
+
+    match explanation.fts {
+        Some(ref fts) => {
+            let terms = if fts.matched_terms.is_empty() {
+                "-".to_string()
+            } else {
+                fts.matched_terms.join(", ")
+            };
+            let rank = format!("{:<3}", fts.rank);
+            let bm25 = format!("{:.3}", fts.bm25_score);
+            let contribution = format!("{:+.4}", fts.contribution);
+            println!(
+                "{}",
+                Msg::ExplainFts.format(&[&rank, &bm25, &contribution, &terms])
+            );
+        }
+        None => println!("{}", Msg::ExplainFtsMissing.text()),
+    }
+
+    match explanation.vector {
+        Some(ref vector) => {
+            let rank = format!("{:<3}", vector.rank);
+            let similarity = format!("{:.4}", vector.similarity);
+            let contribution = format!("{:+.4}", vector.contribution);
+            let args: [&dyn std::fmt::Display; 4] =
+                [&rank, &similarity, &vector.chunk_index, &contribution];
+            println!("{}", Msg::ExplainVector.format(&args));
+        }
+        None => println!("{}", Msg::ExplainVectorMissing.text()),
+    }
+
+    println!("     RRF  {}", explanation.arithmetic());
+}
+
+/// 비교 명령어 (compare)
+///
+/// 같은 쿼리를 두 RRF 설정으로 검색하고 순위 변화를 나란히 출력합니다.
+async fn cmd_compare(
+    query: &str,
+    config_a: Option<String>,
+    config_b: Option<String>,
+    limit: usize,
+    framework: Option<String>,
+) -> Result<()> {
+    if !has_api_key() {
+        return Err(ConfigError(Msg::ApiKeyMissing.text().into()).into());
+    }
+
+    let fusion_a = parse_fusion_config(config_a.as_deref()).context("설정 A 읽기 실패")?;
+    let fusion_b = parse_fusion_config(config_b.as_deref()).context("설정 B 읽기 실패")?;
+
+    println!("[*] 비교 중: \"{}\"", query);
+    println!("    A: {}", describe_fusion(&fusion_a));
+    println!("    B: {}", describe_fusion(&fusion_b));
+
+    let retriever = HybridRetriever::with_data_dir(&store_dir())
+        .await
+        .context("HybridRetriever 초기화 실패")?;
+    let boilerplate = Config::load().context("설정 파일 로드 실패")?.boilerplate;
+
+    let options_a = SearchOptions {
+        limit,
+        framework: framework.clone(),
+        prefer_version: None,
+        fusion: fusion_a,
+        two_stage: None,
+        time_budget_ms: None,
+        chunk_kind: None,
+        boilerplate,
+    };
+    let options_b = SearchOptions {
+        limit,
+        framework,
+        prefer_version: None,
+        fusion: fusion_b,
+        two_stage: None,
+        time_budget_ms: None,
+        chunk_kind: None,
+        boilerplate,
+    };
+
+    // 두 설정은 같은 쿼리 임베딩을 씀 (임베딩 API 호출 1회)
+    let embedding = retriever
+        .embed_query(query)
+        .await
+        .context("쿼리 임베딩 실패")?;
+    let results_a = retriever
+        .search_with_embedding(query, &embedding, &options_a)
+        .await
+        .context("설정 A 검색 실패")?;
+    let results_b = retriever
+        .search_with_embedding(query, &embedding, &options_b)
+        .await
+        .context("설정 B 검색 실패")?;
+
+    let diff = compare_rankings(&results_a, &results_b);
+
+    if diff.changes.is_empty() {
+        println!("\n[!] 검색 결과가 없습니다.");
+        return Ok(());
+    }
+
+    println!(
+        "\n[OK] 겹침: {}/{} 건, 1위 {}\n",
+        diff.overlap,
+        results_a.len().max(results_b.len()),
+        if diff.same_top { "동일" } else { "변경" }
+    );
+    println!("  {:>4}  {:>4}  {:>5}  문서", "A", "B", "변화");
+
+    for change in &diff.changes {
+        let rank = |r: Option<usize>| r.map_or_else(|| "-".to_string(), |r| r.to_string());
+        let movement = match change.delta() {
+            Some(0) => "=".to_string(),
+            Some(d) => format!("{:+}", d),
+            None if change.rank_a.is_none() => "new".to_string(),
+            None => "out".to_string(),
+        };
+        let label = change
+            .title
+            .as_ref()
+            .map(|t| truncate_text(t, 40))
+            .unwrap_or_else(|| change.url.clone());
+
+        println!(
+            "  {:>4}  {:>4}  {:>5}  #{} {}",
+            rank(change.rank_a),
+            rank(change.rank_b),
+            movement,
+            change.doc_id,
+            label
+        );
+    }
+
+    Ok(())
+}
+
+/// 목록 명령어 (list)
+///
+/// 저장된 문서 목록을 조회합니다.
+async fn cmd_list(
+    framework: Option<String>,
+    limit: usize,
+    since: Option<chrono::DateTime<chrono::Utc>>,
+) -> Result<()> {
+    let store = open_store()?;
+
+    let docs = store
+        .list_documents(limit, framework.as_deref(), since)
+        .context("문서 목록 조회 실패")?;
+
+    if docs.is_empty() {
+        println!("{}", Msg::NoDocuments.text());
+        return Ok(());
+    }
+
+    println!("{}", Msg::DocumentsHeader.format(&[&docs.len()]));
+
+    for doc in docs {
+        let fw = doc.framework.as_deref().unwrap_or("-");
+        let title_display = doc
+            .title
+            .as_ref()
+            .map(|t| truncate_text(t, 40))
+            .unwrap_or_else(|| "-".to_string());
+
+        println!("  #{:<4} [{}] {}", doc.id, fw, title_display);
+        println!("        URL: {}", doc.url);
+        println!(
+            "        {} | {} chars",
+            doc.created_at.format("%Y-%m-%d %H:%M"),
+            doc.content_length
+        );
+        if let Some(published_at) = doc.published_at {
+            let published_at = published_at.format("%Y-%m-%d");
+            println!("{}", Msg::Published.format(&[&published_at]));
+        }
+        if let Some(expires_at) = doc.expires_at {
+            let state = if doc.is_expired() {
+                Msg::Expired
+            } else {
+                Msg::ExpiresAt
+            };
+            println!("{}", state.format(&[&expires_at.format("%Y-%m-%d %H:%M")]));
+        }
+        println!();
+    }
+
+    Ok(())
+}
+
+/// 삭제 명령어 (delete)
+///
+/// ID 또는 URL로 문서를 삭제합니다.
+async fn cmd_delete(url: Option<String>, id: Option<i64>) -> Result<()> {
+    let store = open_store()?;
+
+    let doc_id = resolve_doc_id(&store, url, id)?;
+
+    // 문서 존재 확인
+    let doc = store.get_document(doc_id).context("문서 조회 실패")?;
+
+    if doc.is_none() {
+        return Err(NotFound(Msg::DocNotFound.format(&[&doc_id])).into());
+    }
+
+    // 삭제 수행 (벡터 삭제도 필요하지만 HybridRetriever가 필요)
+    // 현재는 SQLite만 삭제 (벡터는 남아있음)
+    let deleted = store.delete_document(doc_id).context("문서 삭제 실패")?;
+
+    if deleted {
+        println!("{}", Msg::DocDeleted.format(&[&doc_id]));
+        println!("{}", Msg::VectorCleanupNote.text());
+    } else {
+        println!("{}", Msg::NothingToDelete.text());
+    }
+
+    Ok(())
+}
+
+/// 고정/고정 해제 명령어 (pin, unpin)
+fn cmd_set_pinned(url: Option<String>, id: Option<i64>, pinned: bool) -> Result<()> {
+    let store = open_store()?;
+    let doc_id = resolve_doc_id(&store, url, id)?;
+
+    if !store
+        .set_pinned(doc_id, pinned)
+        .context("문서 고정 변경 실패")?
+    {
+        return Err(NotFound(Msg::DocNotFound.format(&[&doc_id])).into());
+    }
+
+    if pinned {
+        println!("{}", Msg::DocPinned.format(&[&doc_id]));
+    } else {
+        println!("{}", Msg::DocUnpinned.format(&[&doc_id]));
+    }
+
+    Ok(())
+}
+
+/// 문서 상세 명령어 (show)
+fn cmd_show(url: Option<String>, id: Option<i64>) -> Result<()> {
+    let store = open_store()?;
+    let doc_id = resolve_doc_id(&store, url, id)?;
+    let doc = store
+        .get_document(doc_id)
+        .context("문서 조회 실패")?
+        .ok_or_else(|| NotFound(Msg::DocNotFound.format(&[&doc_id])))?;
+
+    let or_dash = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+    println!("{}", Msg::ShowHeader.format(&[&doc.id]));
+    println!("{}", Msg::ShowTitle.format(&[&or_dash(&doc.title)]));
+    println!("  URL: {}", doc.url);
+    println!("{}", Msg::ShowFramework.format(&[&or_dash(&doc.framework)]));
+    let ingested = doc.created_at.format("%Y-%m-%d %H:%M");
+    println!("{}", Msg::ShowIngested.format(&[&ingested]));
+    let length = doc.content.chars().count();
+    println!("{}", Msg::ShowLength.format(&[&length]));
+    if doc.pinned {
+        println!("{}", Msg::ShowPinned.text());
+    }
+    if let Some(expires_at) = doc.expires_at {
+        let state = if doc.is_expired() {
+            Msg::ShowExpired
+        } else {
+            Msg::ShowExpiresAt
+        };
+        println!("{}", state.format(&[&expires_at.format("%Y-%m-%d %H:%M")]));
+    }
+    if let Some(ref citation) = doc.citation {
+        println!("{}", Msg::ShowCitation.format(&[&citation.format()]));
+    }
+
+    println!("{}", Msg::ShowProvenance.text());
+    match doc.provenance {
+        Some(ref provenance) => {
+            let extractor = or_dash(&provenance.extractor);
+            let command = or_dash(&provenance.command);
+            let host = or_dash(&provenance.hostname);
+            let version = &provenance.tool_version;
+            println!("{}", Msg::ProvenanceSource.format(&[&provenance.source]));
+            println!("{}", Msg::ProvenanceExtractor.format(&[&extractor]));
+            println!("{}", Msg::ProvenanceCommand.format(&[&command]));
+            println!("{}", Msg::ProvenanceHost.format(&[&host]));
+            println!("{}", Msg::ProvenanceVersion.format(&[version]));
+        }
+        None => println!("{}", Msg::ProvenanceMissing.text()),
+    }
+
+    let content = truncate_text(&doc.content, 200);
+    println!("{}", Msg::ShowContent.format(&[&content]));
+
+    Ok(())
+}
+
+/// 검색 피드백 명령어 (feedback)
+fn cmd_feedback(query_id: &str, doc_id: i64, relevant: bool) -> Result<()> {
+    let store = open_store()?;
+
+    if !store
+        .add_feedback(query_id, doc_id, relevant)
+        .context("피드백 저장 실패")?
+    {
+        return Err(NotFound(Msg::DocNotFound.format(&[&doc_id])).into());
+    }
+
+    let verdict = if relevant { "관련" } else { "무관" };
+    println!("[OK] 쿼리 {}: 문서 #{} {} 판정 저장", query_id, doc_id, verdict);
+
+    Ok(())
+}
+
+/// 결과 열기 명령어 (open)
+fn cmd_open(index: Option<usize>, id: Option<i64>) -> Result<()> {
+    let target = match (index, id) {
+        (_, Some(doc_id)) => {
+            let store = open_store()?;
+            let doc = store
+                .get_document(doc_id)?
+                .ok_or_else(|| NotFound(Msg::DocNotFound.format(&[&doc_id])))?;
+            OpenTarget {
+                doc_id,
+                url: doc.url,
+                line: None,
+            }
+        }
+        (Some(index), None) => {
+            let targets = load_last_results(&get_data_dir().join(LAST_RESULTS_FILE))
+                .context(Msg::OpenNoLastResults.text())?;
+            if index == 0 || index > targets.len() {
+                bail!(Msg::OpenIndexOutOfRange.format(&[&targets.len()]));
+            }
+            targets[index - 1].clone()
+        }
+        (None, None) => bail!(Msg::OpenTargetMissing.text()),
+    };
+
+    let editor = std::env::var("EDITOR").ok().filter(|e| !e.trim().is_empty());
+    println!("{}", Msg::Opening.format(&[&target.doc_id, &target.url]));
+    target.open(editor.as_deref())
+}
+
+/// 고정 문서 목록 명령어 (pins)
+fn cmd_pins() -> Result<()> {
+    let store = open_store()?;
+    let docs = store.list_pinned().context("고정 문서 조회 실패")?;
+
+    if docs.is_empty() {
+        println!("{}", Msg::NoPins.text());
+        println!("{}", Msg::PinHint.text());
+        return Ok(());
+    }
+
+    println!("{}", Msg::PinsHeader.format(&[&docs.len()]));
+
+    for doc in docs {
+        let fw = doc.framework.as_deref().unwrap_or("-");
+        let title_display = doc
+            .title
+            .as_ref()
+            .map(|t| truncate_text(t, 40))
+            .unwrap_or_else(|| "-".to_string());
+
+        println!("  #{:<4} [{}] {}", doc.id, fw, title_display);
+        println!("        URL: {}", doc.url);
+    }
+
+    Ok(())
+}
+
+/// 질문 명령어 (ask)
+///
+/// 검색 결과와 고정 문서로 컨텍스트를 구성하고 Gemini로 답변을 생성합니다.
+/// 답변의 `[n]` 인용 번호는 출처 목록(`--json`이면 `citations`)의 번호와 같습니다.
+async fn cmd_ask(
+    question: &str,
+    search: SearchOptions,
+    include_pinned: bool,
+    max_tokens: Option<usize>,
+    context_only: bool,
+    json: bool,
+    trace: bool,
+) -> Result<()> {
+    if !has_api_key() {
+        return Err(ConfigError(Msg::ApiKeyMissing.text().into()).into());
+    }
+
+    let retriever = open_retriever(trace, !json && !context_only).await?;
+
+    let options = ContextOptions {
+        search,
+        include_pinned,
+        max_tokens,
+        ..Default::default()
+    };
+    let context = retriever
+        .build_context(question, &options)
+        .await
+        .context("컨텍스트 구성 실패")?;
+
+    if context.is_empty() && !json {
+        println!("[!] 관련 문서를 찾지 못했습니다.");
+        return Ok(());
+    }
+
+    if context_only {
+        if json {
+            let output = AskOutput {
+                question,
+                answer: None,
+                context: Some(context.render()),
+                citations: context.citations(),
+            };
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        } else {
+            print!("{}", context.render());
+        }
+        return Ok(());
+    }
+
+    if !json {
+        println!(
+            "[*] 컨텍스트: {} 청크 / ~{} 토큰",
+            context.chunks.len(),
+            context.token_count(&*options.tokenizer)
+        );
+        println!("[*] 답변 생성 중...\n");
+    }
+    // 찾은 문서가 없으면 답변 없이 빈 citations만 출력
+    let answer = if context.is_empty() {
+        None
+    } else {
+        let generator = GeminiGenerator::from_env()?;
+        let answer = generator
+            .answer(question, &context)
+            .await
+            .context("답변 생성 실패")?;
+        Some(answer.trim().to_string())
+    };
+
+    if json {
+        let output = AskOutput {
+            question,
+            answer,
+            context: None,
+            citations: context.citations(),
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    println!("{}\n", answer.unwrap_or_default());
+    println!("출처:");
+    for source in context.citations() {
+        let pin = if source.pinned { " [고정]" } else { "" };
+        match source.citation {
+            Some(ref citation) => println!("  [{}] {}{}", source.marker, citation.format(), pin),
+            None => println!("  [{}] {}{}", source.marker, source.url, pin),
+        }
+    }
+
+    Ok(())
+}
+
+/// `ask --json` 출력
+#[derive(serde::Serialize)]
+struct AskOutput<'a> {
+    question: &'a str,
+    /// 생성된 답변 (`--context-only`이거나 찾은 문서가 없으면 생략)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    answer: Option<String>,
+    /// 렌더링된 컨텍스트 (`--context-only`일 때)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    context: Option<String>,
+    citations: Vec<SourceCitation>,
+}
+
+/// 감시 명령어 (watch)
+///
+/// 폴더의 새 이미지를 주기적으로 Gemini Vision으로 추출하여 수집합니다.
+/// 실패한 파일은 다음 주기에 다시 시도합니다.
+async fn cmd_watch(dir: PathBuf, interval: u64, tag: String, once: bool) -> Result<()> {
+    if !has_api_key() {
+        return Err(ConfigError(Msg::ApiKeyMissing.text().into()).into());
+    }
+
+    let config = Config::load()?;
+    let shutdown = Shutdown::new();
+    let state_path = watch_state_path(&store_dir(), &dir);
+    let mut watcher = ScreenshotWatcher::new(&dir, state_path)?
+        .with_tag(tag)
+        .with_content_filter(config.content_filter)
+        .with_shutdown(shutdown.clone());
+    let extractor = ContentExtractor::from_env();
+    let notifier = Notifier::new(config.notify);
+    let (retriever, budget) = resident_retriever().await?;
+
+    println!(
+        "[*] 감시 중: {} (주기 {}초, 기록 {} 건)",
+        watcher.dir().display(),
+        interval,
+        watcher.seen_count()
+    );
+    shutdown.listen_for_signals();
+
+    loop {
+        budget.reset();
+        let pass = watcher.ingest_new(&retriever, &extractor).await?;
+        for (path, doc_id) in &pass.added {
+            println!("[OK] {} (Doc #{})", path.display(), doc_id);
+        }
+        for (path, error) in &pass.failed {
+            println!("[!] {} 실패: {}", path.display(), error);
+        }
+        for (path, error) in &pass.gave_up {
+            println!(
+                "[!] {} 실패 (다시 시도하지 않음): {}",
+                path.display(),
+                error
+            );
+        }
+        for path in &pass.too_short {
+            println!("[!] {} 본문이 너무 짧아 건너뜀", path.display());
+        }
+        for event in pass.events(watcher.dir()) {
+            notifier.notify(&event).await;
+        }
+
+        if once {
+            println!(
+                "[OK] 완료: 추가 {}, 짧음 {}, 실패 {}",
+                pass.added.len(),
+                pass.too_short.len(),
+                pass.failed.len() + pass.gave_up.len()
+            );
+            return Ok(());
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(std::time::Duration::from_secs(interval.max(1))) => {}
+            _ = shutdown.wait() => {}
+        }
+        if shutdown.is_requested() {
+            println!("[*] 감시 종료 (기록 {} 건)", watcher.seen_count());
+            return Ok(());
+        }
+    }
+}
+
+/// 데몬 명령어 (daemon)
+///
+/// config.toml의 `[[jobs]]`, `[[watch]]` 항목을 실행합니다.
+async fn cmd_daemon(list: bool, run: Option<String>) -> Result<()> {
+    let config = Config::load()?;
+    let (retriever, budget) = resident_retriever().await?;
+    let mut daemon = Daemon::new(retriever, &store_dir(), config)?.with_embedding_budget(budget);
+
+    if list {
+        if daemon.jobs().is_empty() {
+            println!("[*] 예약 작업 없음 ({})", config_path().display());
+        }
+        for job in daemon.jobs() {
+            let next = job
+                .next_run
+                .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_else(|| "-".to_string());
+            println!("  {} [{}] 다음 실행: {}", job.config.name, job.config.schedule, next);
+        }
+        for dir in daemon.watch_dirs() {
+            println!("  감시: {}", dir.display());
+        }
+        return Ok(());
+    }
+
+    if let Some(name) = run {
+        let summary = daemon.run_job_by_name(&name).await?;
+        println!("[OK] {}: {}", name, summary);
+        return Ok(());
+    }
+
+    if daemon.jobs().is_empty() && daemon.watch_dirs().is_empty() {
+        bail!(
+            "실행할 작업이 없습니다. {} 에 [[jobs]] 또는 [[watch]] 항목을 추가하세요.",
+            config_path().display()
+        );
+    }
+
+    println!(
+        "[*] 데몬 시작: 작업 {} 개, 감시 폴더 {} 개",
+        daemon.jobs().len(),
+        daemon.watch_dirs().len()
+    );
+    daemon.run().await
+}
+
+/// 서비스 명령어 (service)
+fn cmd_service(action: ServiceAction) -> Result<()> {
+    match action {
+        ServiceAction::Install { watch, print } => {
+            let args = match watch {
+                Some(dir) => {
+                    let dir = dir
+                        .canonicalize()
+                        .with_context(|| format!("감시 폴더를 찾을 수 없습니다: {:?}", dir))?;
+                    vec!["watch".to_string(), dir.to_string_lossy().into_owned()]
+                }
+                None => vec!["daemon".to_string()],
+            };
+            let spec = ServiceSpec::current(args)?;
+
+            if print {
+                if cfg!(windows) {
+                    print!("{}", service::windows_script(&spec));
+                } else {
+                    print!("{}", service::systemd_unit(&spec));
+                }
+                return Ok(());
+            }
+
+            let path = service::install(&spec)?;
+            println!("[OK] 서비스 등록 및 시작: {}", path.display());
+            println!("    로그: {}", spec.log_path.display());
+            // 서비스에는 셸 환경 변수가 전달되지 않으므로 키링에 있는 키만 쓸 수 있음
+            if keyring_api_key().is_none() {
+                match find_api_key() {
+                    Some((_, source)) => println!(
+                        "[!] API 키가 {} 에만 있습니다: 서비스에는 셸 환경 변수가 전달되지 않습니다",
+                        source
+                    ),
+                    None => println!("[!] API 키 미설정: 서비스는 OS 키링의 키를 사용합니다"),
+                }
+                println!("    키링에 저장: palank-rag auth set");
+            }
+        }
+        ServiceAction::Uninstall => {
+            if service::uninstall()? {
+                println!("[OK] 서비스를 중지하고 등록 해제했습니다");
+            } else {
+                println!("[!] 등록된 서비스가 없습니다");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 상태 명령어 (status)
+///
+/// 시스템 상태를 확인합니다.
+async fn cmd_status(verbose: bool) -> Result<()> {
+    println!("palank-rag v{}", env!("CARGO_PKG_VERSION"));
+    println!();
+
+    // 데이터 디렉토리 (--collection 지정 시 컬렉션 디렉토리)
+    let data_dir = store_dir();
+    println!("{}", Msg::StatusDataDir.format(&[&data_dir.display()]));
+
+    // API 키 상태
+    if let Some((_, source)) = find_api_key() {
+        println!("{}", Msg::StatusApiKeySet.format(&[&source]));
+    } else {
+        println!("{}", Msg::StatusApiKeyMissing.text());
+        println!("{}", Msg::ApiKeySetupHint.text());
+    }
+
+    // 문서 수 및 통계
+    match open_store() {
+        Ok(store) => match store.stats() {
+            Ok(stats) => {
+                println!("{}", Msg::StatusDocuments.format(&[&stats.document_count]));
+                let bytes = format_bytes(stats.total_content_bytes);
+                println!("{}", Msg::StatusContentBytes.format(&[&bytes]));
+            }
+            Err(e) => {
+                println!("{}", Msg::StatusStatsFailed.format(&[&e]));
+            }
+        },
+        Err(e) => {
+            println!("{}", Msg::StatusStoreOpenFailed.format(&[&e]));
+        }
+    }
+
+    // 벡터 스토어 상태 (API 키가 있을 때만)
+    if has_api_key() {
+        match HybridRetriever::with_data_dir(&store_dir()).await {
+            Ok(retriever) => match retriever.stats().await {
+                Ok(stats) => {
+                    println!("{}", Msg::StatusVectorIndex.format(&[&stats.vector_count]));
+                }
+                Err(e) => {
+                    tracing::debug!("벡터 통계 조회 실패: {}", e);
+                }
+            },
+            Err(e) => {
+                tracing::debug!("HybridRetriever 초기화 실패: {}", e);
+            }
+        }
+    }
+
+    // 벡터 테이블 상세 (임베딩 API 없이 LanceDB만 열기)
+    if verbose {
+        let description = match LanceVectorStore::open(&data_dir.join(VECTORS_DIR)).await {
+            Ok(vector) => vector.describe().await,
+            Err(e) => Err(e),
+        };
+        match description {
+            Ok(description) => print_vector_description(&description),
+            Err(e) => println!("{}", Msg::StatusDescribeFailed.format(&[&e])),
+        }
+    }
+
+    Ok(())
+}
+
+/// `status --verbose` 벡터 테이블 통계와 정리/재생성 권장 출력
+fn print_vector_description(description: &VectorStoreDescription) {
+    for table in &description.tables {
+        let disk = format_bytes(table.disk_bytes as usize);
+        println!(
+            "{}",
+            Msg::StatusVectorTable.format(&[
+                &table.name,
+                &table.rows,
+                &table.version,
+                &table.fragments,
+                &disk
+            ])
+        );
+
+        if table.indices.is_empty() {
+            println!("{}", Msg::StatusVectorNoIndex.text());
+        }
+        let rows = |n: Option<usize>| n.map_or("-".to_string(), |n| n.to_string());
+        for index in &table.indices {
+            // 종류, 컬럼, 거리 함수, 조각 수
+            let mut params = vec![index.index_type.clone(), index.columns.join(", ")];
+            if let Some(ref distance) = index.distance_type {
+                params.push(format!("distance={}", distance));
+            }
+            if let Some(parts) = index.parts {
+                params.push(format!("parts={}", parts));
+            }
+            let params = params.join(", ");
+            let indexed = rows(index.indexed_rows);
+            let unindexed = rows(index.unindexed_rows);
+            println!(
+                "{}",
+                Msg::StatusVectorIndexDetail.format(&[&index.name, &params, &indexed, &unindexed])
+            );
+        }
+    }
+
+    let issues = description.issues();
+    if issues.is_empty() {
+        println!("{}", Msg::StatusVectorHealthy.text());
+    }
+    for issue in issues {
+        let line = match issue {
+            VectorHealthIssue::Fragmented { table, fragments } => {
+                Msg::StatusVectorFragmented.format(&[&table, &fragments])
+            }
+            VectorHealthIssue::StaleIndex { table, unindexed } => {
+                Msg::StatusVectorStaleIndex.format(&[&table, &unindexed])
+            }
+            VectorHealthIssue::Unindexed { table, rows } => {
+                Msg::StatusVectorUnindexed.format(&[&table, &rows])
+            }
+        };
+        println!("{}", line);
+    }
+}
+
+/// 정리 명령어 (compact)
+///
+/// 만료된 문서와 벡터를 삭제하고 SQLite/LanceDB를 최적화합니다.
+async fn cmd_compact() -> Result<()> {
+    let retriever = HybridRetriever::with_data_dir(&store_dir())
+        .await
+        .context("HybridRetriever 초기화 실패")?;
+
+    println!("[*] 저장소 정리 중...");
+    let report = retriever.compact().await.context("저장소 정리 실패")?;
+
+    println!("[OK] 정리 완료");
+    println!("     만료 문서 삭제: {} 건", report.expired_documents);
+
+    Ok(())
+}
+
+/// 번들 명령어 (bundle)
+async fn cmd_bundle(action: BundleAction) -> Result<()> {
+    match action {
+        BundleAction::Create { out } => {
+            println!("[*] 번들 만드는 중: {}", out.display());
+            let manifest = create_bundle(&store_dir(), &out)
+                .await
+                .context("번들 생성 실패")?;
+            let size = std::fs::metadata(&out).map(|m| m.len()).unwrap_or(0);
+
+            println!("[OK] 번들 생성 완료: {}", out.display());
+            println!(
+                "     문서 {} 건, 청크 {} 개, {}",
+                manifest.documents,
+                manifest.chunks,
+                format_bytes(size as usize)
+            );
+            println!(
+                "     검색: palank-rag query \"...\" --bundle {}",
+                out.display()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// 복구 명령어 (repair)
+///
+/// SQLite/FTS5/LanceDB를 검사하고, 손상된 SQLite는 최신 백업으로 복원하며
+/// FTS5 인덱스를 다시 만들고 벡터가 없는 문서를 다시 임베딩합니다.
+async fn cmd_repair(check_only: bool, backup: Option<PathBuf>) -> Result<()> {
+    let data_dir = store_dir();
+
+    println!("{}", Msg::RepairChecking.format(&[&data_dir.display()]));
+    let report = check_integrity(&data_dir).await;
+    for problem in &report.sqlite {
+        println!("{}", Msg::RepairSqliteCorrupt.format(&[problem]));
+    }
+    if let Some(ref problem) = report.fts {
+        println!("{}", Msg::RepairFtsMismatch.format(&[problem]));
+    }
+    if let Some(ref problem) = report.vectors {
+        println!("{}", Msg::RepairVectorsCorrupt.format(&[problem]));
+    }
+    if report.is_healthy() {
+        println!("{}", Msg::RepairHealthy.text());
+    }
+    if check_only {
+        return Ok(());
+    }
+
+    // 1. SQLite 손상 → 백업 복원 (벡터도 같은 시점으로)
+    let restored = !report.sqlite.is_empty();
+    if restored {
+        let backup = backup
+            .or_else(|| latest_backup(&data_dir.join(BACKUPS_DIR)))
+            .context(Msg::RepairNoBackup.text())?;
+        println!("{}", Msg::RepairRestoring.format(&[&backup.display()]));
+        for moved in restore_backup(&backup, &data_dir)? {
+            println!("{}", Msg::RepairMovedAside.format(&[&moved.display()]));
+        }
+    } else if report.vectors.is_some() {
+        let moved = set_aside(&data_dir.join(VECTORS_DIR))?;
+        println!(
+            "{}",
+            Msg::RepairVectorsMovedAside.format(&[&moved.display()])
+        );
+    }
+
+    // 2. 정규화 이전에 수집한 문서의 제목/본문 정규화
+    let store = KnowledgeStore::open(&data_dir.join(SQLITE_FILE))?;
+    let normalized = store
+        .normalize_stored_text(None)
+        .context("본문 정규화 실패")?;
+    if normalized > 0 {
+        println!("{}", Msg::RepairNormalized.format(&[&normalized]));
+        println!("{}", Msg::RepairNormalizedHint.text());
+    }
+
+    // 3. FTS5 인덱스 재생성
+    if restored || report.fts.is_some() || normalized > 0 {
+        let count = store
+            .rebuild_fts_index()
+            .context("FTS5 인덱스 재생성 실패")?;
+        println!("{}", Msg::RepairFtsRebuilt.format(&[&count]));
+    }
+
+    // 4. 벡터 인덱스 동기화 (누락 문서 재임베딩)
+    if !has_api_key() {
+        println!("{}", Msg::RepairNoApiKey.text());
+        return Ok(());
+    }
+    let retriever = HybridRetriever::with_data_dir(&data_dir)
+        .await
+        .context("HybridRetriever 초기화 실패")?;
+    let config = Config::load().context("설정 파일 로드 실패")?;
+    let retriever = apply_embedding_config(retriever, &config.embedding)?;
+
+    println!("{}", Msg::RepairSyncing.text());
+    let reindex = retriever
+        .reindex_missing()
+        .await
+        .context("재임베딩 실패 (다시 실행하면 남은 문서부터 이어서 진행)")?;
+
+    println!("{}", Msg::RepairDone.text());
+    println!("{}", Msg::RepairReembedded.format(&[&reindex.reembedded]));
+    println!("{}", Msg::RepairOrphans.format(&[&reindex.orphans_removed]));
+    println!(
+        "{}",
+        Msg::RepairDocEmbeddings.format(&[&reindex.doc_embeddings])
+    );
+    println!(
+        "{}",
+        Msg::RepairFingerprinted.format(&[&reindex.fingerprinted])
+    );
+    println!(
+        "{}",
+        Msg::RepairChunkRows.format(&[&reindex.chunk_rows_removed])
+    );
+
+    Ok(())
+}
+
+/// 재임베딩 명령어 (reembed)
+///
+/// 청킹 설정(`[embedding]` 포함)을 바꾼 뒤 기존 문서에도 반영할 때 사용합니다.
+/// `--changed-chunker`면 문서마다 기록된 청커 지문이 현재와 다른 문서만 처리합니다.
+async fn cmd_reembed(
+    changed_chunker: bool,
+    outdated_model: bool,
+    max_docs: Option<usize>,
+) -> Result<()> {
+    if !has_api_key() {
+        return Err(ConfigError(Msg::ApiKeyMissing.text().into()).into());
+    }
+
+    let retriever = ingest_retriever().await?;
+    let fingerprint = retriever.chunker_fingerprint();
+    let model = retriever.embedding_model();
+    let store = retriever.store();
+
+    // 두 옵션을 함께 주면 어느 한쪽에 해당하는 문서 모두
+    let mut ids = if changed_chunker || outdated_model {
+        let mut ids = BTreeSet::new();
+        if changed_chunker {
+            ids.extend(store.ids_with_other_chunker(&fingerprint)?);
+        }
+        if outdated_model {
+            ids.extend(store.ids_with_other_model(&model)?);
+        }
+        ids.into_iter().collect::<Vec<_>>()
+    } else {
+        store.document_ids()?
+    };
+
+    if ids.is_empty() {
+        println!("{}", Msg::ReembedNothing.format(&[&fingerprint, &model]));
+        return Ok(());
+    }
+
+    if outdated_model {
+        println!("{}", Msg::ReembedModelCounts.text());
+        for (chunk_model, count) in store.chunk_model_counts()? {
+            let marker = if chunk_model == model {
+                Msg::ReembedCurrentModel.text()
+            } else {
+                ""
+            };
+            println!(
+                "{}",
+                Msg::ReembedModelLine.format(&[&chunk_model, &count, &marker])
+            );
+        }
+    }
 
-    // 이미지 파일 경고
-    if stats.image_files > 0 && !skip_images {
-        println!(
-            "[!] 이미지 {} 개를 Gemini Vision으로 처리합니다. API 호출이 발생합니다.",
-            stats.image_files
-        );
+    let total = ids.len();
+    if let Some(max_docs) = max_docs {
+        ids.truncate(max_docs);
     }
+    println!(
+        "{}",
+        Msg::ReembedTargets.format(&[&ids.len(), &total, &fingerprint, &model])
+    );
 
-    // 파일별 처리
-    let mut success_count = 0;
-    let mut error_count = 0;
+    // 문서 묶음마다 저장하므로 같은 옵션으로 다시 실행하면 남은 문서만 처리
+    let reembedded = match retriever.reembed_documents(&ids).await {
+        Ok(reembedded) => reembedded,
+        Err(e) if QuotaExhausted::is_cause_of(&e) || LimitExceeded::is_cause_of(&e) => {
+            println!("{}", Msg::ReembedQuotaStop.text());
+            println!("{}", Msg::ReembedQuotaResume.text());
+            return Err(e);
+        }
+        Err(e) => {
+            return Err(e).context(
+                "재임베딩 실패 (--changed-chunker/--outdated-model로 다시 실행하면 남은 문서부터 이어서 진행)",
+            );
+        }
+    };
 
-    for (i, collected_file) in files.iter().enumerate() {
-        let file_name = collected_file
-            .path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("unknown");
+    println!("{}", Msg::ReembedDone.format(&[&reembedded]));
+    if reembedded < total {
+        println!("{}", Msg::ReembedRemaining.format(&[&(total - reembedded)]));
+    }
 
-        let type_str = match collected_file.file_type {
-            FileType::Text => "TXT",
-            FileType::Image => "IMG",
-            FileType::Pdf => "PDF",
-        };
+    Ok(())
+}
 
-        print!(
-            "[{}/{}] [{}] {}... ",
-            i + 1,
-            files.len(),
-            type_str,
-            file_name
-        );
+/// API 키 관리 명령어 (auth)
+fn cmd_auth(action: AuthAction) -> Result<()> {
+    match action {
+        AuthAction::Set { key } => {
+            let key = match key {
+                Some(key) => key,
+                None => {
+                    eprint!("Gemini API 키 입력: ");
+                    let mut line = String::new();
+                    std::io::stdin()
+                        .read_line(&mut line)
+                        .context("API 키 입력 읽기 실패")?;
+                    line
+                }
+            };
 
-        // 콘텐츠 추출
-        let contents = match extractor
-            .extract(&collected_file.path, collected_file.file_type)
-            .await
-        {
-            Ok(c) => c,
-            Err(e) => {
-                println!("실패: {}", e);
-                error_count += 1;
-                continue;
+            let key = key.trim();
+            if key.is_empty() {
+                bail!("API 키가 비어 있습니다");
             }
-        };
 
-        // 각 콘텐츠 저장 (PDF는 페이지별)
-        for content in contents {
-            let title = if let Some(page) = content.metadata.page_number {
-                Some(format!("{} (Page {})", file_name, page))
+            store_api_key(key)?;
+            println!("[OK] API 키를 OS 키링에 저장했습니다 ({})", mask_secret(key));
+        }
+        AuthAction::Remove => {
+            if remove_api_key()? {
+                println!("[OK] OS 키링에서 API 키를 삭제했습니다");
             } else {
-                Some(file_name.to_string())
-            };
-
-            let doc = NewDocument {
-                url: format!("file://{}", collected_file.path.display()),
-                title,
-                content: content.text,
-                framework: framework.clone(),
-            };
-
-            match retriever.add_document(doc).await {
-                Ok(_) => {}
-                Err(e) => {
-                    println!("저장 실패: {}", e);
-                    error_count += 1;
-                    continue;
-                }
+                println!("[!] OS 키링에 저장된 API 키가 없습니다");
             }
         }
-
-        println!("완료");
-        success_count += 1;
+        AuthAction::Status => match find_api_key() {
+            Some((key, source)) => {
+                println!("[OK] API 키: 설정됨 ({})", mask_secret(&key));
+                println!("     출처: {}", source);
+                if matches!(source, ApiKeySource::Env(_)) && keyring_api_key().is_some() {
+                    println!("     (OS 키링에도 키가 있지만 환경변수가 우선합니다)");
+                }
+            }
+            None => {
+                println!("[!] API 키: 미설정");
+                println!("    설정: palank-rag auth set");
+            }
+        },
     }
 
-    println!();
-    println!(
-        "[OK] 완료: 성공 {}, 실패 {}",
-        success_count, error_count
-    );
-
     Ok(())
 }
 
-/// 검색 명령어 (query)
+/// 청크 미리보기 명령어 (chunk-preview)
 ///
-/// 하이브리드 검색 (FTS5 + 벡터)을 사용하여 지식베이스를 검색합니다.
-async fn cmd_query(query: &str, limit: usize, _framework: Option<String>) -> Result<()> {
-    if !has_api_key() {
+/// 추출과 청킹만 실행하여 청크별 크기, 예상 토큰 수, 제목 경로를 출력합니다.
+/// 임베딩 API를 호출하지 않으므로 할당량을 쓰지 않고 `ChunkConfig`를 조정할 수 있습니다.
+async fn cmd_chunk_preview(
+    file: Option<PathBuf>,
+    url: Option<String>,
+    config: ChunkConfig,
+    table_rows: Option<usize>,
+    full: bool,
+) -> Result<()> {
+    if config.min_characters > config.max_characters {
         bail!(
-            "API 키가 설정되지 않았습니다.\n\
-             설정: export GEMINI_API_KEY=your-key"
+            "--min({})은 --max({})보다 클 수 없습니다",
+            config.min_characters,
+            config.max_characters
         );
     }
 
-    println!("[*] 검색 중: \"{}\"", query);
-
-    let retriever = HybridRetriever::new()
-        .await
-        .context("HybridRetriever 초기화 실패")?;
+    // (섹션 이름, 텍스트) - PDF는 페이지별, CSV/TSV 구조화 모드는 행 묶음별로 따로 청킹됨
+    let sections: Vec<(Option<String>, String)> = if let Some(path) = file {
+        let file_type = FileType::from_path(&path)
+            .ok_or_else(|| anyhow::anyhow!("지원하지 않는 파일 형식: {}", path.display()))?;
+        if file_type == FileType::Image && !has_api_key() {
+            println!("[!] 이미지 추출에는 Vision API 키가 필요합니다 (GEMINI_API_KEY)");
+        }
 
-    let results = retriever.search(query, limit).await.context("검색 실패")?;
+        println!("[*] 파일 추출 중: {}", path.display());
+        let extractor = ContentExtractor::from_env().with_table_rows(table_rows);
+        let contents = extractor
+            .extract(&path, file_type)
+            .await
+            .with_context(|| format!("콘텐츠 추출 실패: {}", path.display()))?;
+
+        contents
+            .into_iter()
+            .map(|content| {
+                let label = match (content.metadata.page_number, content.metadata.row_range) {
+                    (Some(page), _) => Some(format!("Page {}", page)),
+                    (None, Some((first, last))) if first == last => Some(format!("Row {}", first)),
+                    (None, Some((first, last))) => Some(format!("Rows {}-{}", first, last)),
+                    (None, None) => None,
+                };
+                (label, content.text)
+            })
+            .collect()
+    } else if let Some(url) = url {
+        println!("[*] URL 스크래핑 중: {}", url);
+        let app_config = Config::load().context("설정 파일 로드 실패")?;
+        let scraper = WebScraper::with_policy(app_config.url_policy)
+            .context("WebScraper 생성 실패")?
+            .with_titles(app_config.titles);
+        let scraped = scraper
+            .scrape(&url)
+            .await
+            .with_context(|| format!("URL 스크래핑 실패: {}", url))?;
 
-    if results.is_empty() {
-        println!("\n[!] 검색 결과가 없습니다.");
-        return Ok(());
-    }
+        // ingest --url과 같은 형태로 제목을 본문 앞에 붙임
+        let content = match scraped.title {
+            Some(title) => format!("# {}\n\n{}", title, scraped.content),
+            None => scraped.content,
+        };
+        vec![(None, content)]
+    } else {
+        bail!("--file 또는 --url 중 하나를 지정해야 합니다");
+    };
 
-    println!("\n[OK] 검색 결과 ({} 건):\n", results.len());
+    println!(
+        "[*] 청크 설정: min {} / max {} / overlap {} (바이트)",
+        config.min_characters, config.max_characters, config.overlap_characters
+    );
 
-    for (i, result) in results.iter().enumerate() {
-        let method_str = match result.method {
-            crate::knowledge::SearchMethod::Vector => "VEC",
-            crate::knowledge::SearchMethod::Fts => "FTS",
-            crate::knowledge::SearchMethod::Hybrid => "HYB",
-        };
+    let chunker = MarkdownChunker::new(config.clone());
+    let mut sizes = Vec::new();
+    let mut total_tokens = 0;
+    let mut violations = 0;
 
-        println!(
-            "{}. [{}] [점수: {:.4}] Doc #{}",
-            i + 1,
-            method_str,
-            result.rrf_score,
-            result.doc_id
-        );
+    for (label, text) in &sections {
+        let chunks = chunker.chunk_with_headings(text);
 
-        if let Some(ref title) = result.title {
-            println!("   제목: {}", title);
+        println!();
+        match label {
+            Some(label) => println!("=== {} ({} 청크) ===", label, chunks.len()),
+            None => println!("=== {} 청크 ===", chunks.len()),
         }
 
-        println!("   URL: {}", result.url);
+        for (path, chunk) in &chunks {
+            let tokens = config.tokenizer.count(chunk);
+            sizes.push(chunk.len());
+            total_tokens += tokens;
+
+            println!(
+                "\n#{:<3} {:>5} 바이트 / {:>5} 자 / ~{} 토큰",
+                sizes.len(),
+                chunk.len(),
+                chunk.chars().count(),
+                tokens
+            );
+            if !path.is_empty() {
+                println!("     제목: {}", path.join(" > "));
+            }
 
-        // 청크 텍스트 또는 스니펫 출력
-        if let Some(ref chunk) = result.chunk_text {
-            println!("   내용: {}", truncate_text(chunk, 200));
-        } else if let Some(ref snippet) = result.snippet {
-            println!("   스니펫: {}", truncate_text(snippet, 200));
+            if full {
+                for line in chunk.lines() {
+                    println!("     | {}", line);
+                }
+            } else {
+                // 제목 경로를 이미 출력했으므로 본문 첫 줄 (본문이 없으면 제목 줄)
+                let mut lines = chunk.lines().map(str::trim).filter(|l| !l.is_empty());
+                let first = lines
+                    .clone()
+                    .find(|l| !l.starts_with('#'))
+                    .or_else(|| lines.next())
+                    .unwrap_or("");
+                let snippet: String = first.chars().take(80).collect();
+                let ellipsis = if first.chars().count() > 80 {
+                    "..."
+                } else {
+                    ""
+                };
+                println!("     {}{}", snippet, ellipsis);
+            }
         }
 
-        println!();
+        let plain: Vec<String> = chunks.into_iter().map(|(_, chunk)| chunk).collect();
+        if let Err(violation) = validate_chunks(text, &plain, &config) {
+            violations += 1;
+            let section = label.as_deref().unwrap_or("문서");
+            println!("\n[!] 청크 검증 실패 ({}): {}", section, violation);
+        }
+    }
+
+    println!();
+    if sizes.is_empty() {
+        println!("[!] 청크가 없습니다 (추출된 텍스트 없음)");
+        return Ok(());
+    }
+
+    println!(
+        "[OK] 청크 {}개, 예상 토큰 합계 ~{} (임베딩 호출 없음)",
+        sizes.len(),
+        total_tokens
+    );
+    println!(
+        "     크기: 평균 {} / 최소 {} / 최대 {} 바이트",
+        sizes.iter().sum::<usize>() / sizes.len(),
+        sizes.iter().min().unwrap_or(&0),
+        sizes.iter().max().unwrap_or(&0)
+    );
+    if violations > 0 {
+        println!(
+            "[!] 검증 실패 섹션 {}개 - --min/--max/--overlap 조정 필요",
+            violations
+        );
     }
 
     Ok(())
 }
 
-/// 목록 명령어 (list)
+/// 네트워크 감사 명령어 (audit-network)
 ///
-/// 저장된 문서 목록을 조회합니다.
-async fn cmd_list(framework: Option<String>, limit: usize) -> Result<()> {
-    let store = KnowledgeStore::open_default().context("KnowledgeStore 열기 실패")?;
+/// 현재 설정에서 기기 밖으로 나갈 수 있는 모든 요청 대상을 출력합니다.
+fn cmd_audit_network(json: bool) -> Result<()> {
+    let config = Config::load().context("설정 파일 로드 실패")?;
+    let endpoints = outbound_endpoints(&config, has_api_key());
 
-    let docs = store
-        .list_documents(limit, framework.as_deref())
-        .context("문서 목록 조회 실패")?;
-
-    if docs.is_empty() {
-        println!("[!] 저장된 문서가 없습니다.");
+    if json {
+        println!("{}", serde_json::to_string_pretty(&endpoints)?);
         return Ok(());
     }
 
-    println!("[OK] 저장된 문서 ({} 건):\n", docs.len());
-
-    for doc in docs {
-        let fw = doc.framework.as_deref().unwrap_or("-");
-        let title_display = doc
-            .title
-            .as_ref()
-            .map(|t| truncate_text(t, 40))
-            .unwrap_or_else(|| "-".to_string());
+    println!("[*] 외부 통신 엔드포인트 (설정: {})", config_path().display());
+    println!("    텔레메트리/사용 통계 전송: 없음");
+    println!();
 
-        println!("  #{:<4} [{}] {}", doc.id, fw, title_display);
-        println!("        URL: {}", doc.url);
-        println!(
-            "        {} | {} chars",
-            doc.created_at.format("%Y-%m-%d %H:%M"),
-            doc.content.len()
-        );
-        println!();
+    for endpoint in &endpoints {
+        let state = if endpoint.active { "활성" } else { "비활성" };
+        println!("[{}] {} ({})", endpoint.component, endpoint.endpoint, state);
+        println!("     전송: {}", endpoint.sends);
+        println!("     발생: {}", endpoint.trigger);
     }
 
     Ok(())
 }
 
-/// 삭제 명령어 (delete)
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// 검색 설정 인자 해석 (`compare --config-a/--config-b`)
 ///
-/// ID 또는 URL로 문서를 삭제합니다.
-async fn cmd_delete(url: Option<String>, id: Option<i64>) -> Result<()> {
-    let store = KnowledgeStore::open_default().context("KnowledgeStore 열기 실패")?;
-
-    let doc_id = if let Some(id) = id {
-        // ID로 삭제
-        id
-    } else if let Some(ref url_str) = url {
-        // URL로 문서 조회 후 삭제
-        let doc = store
-            .get_by_url(url_str)
-            .context("문서 조회 실패")?
-            .ok_or_else(|| anyhow::anyhow!("URL '{}'인 문서를 찾을 수 없습니다", url_str))?;
-        doc.id
+/// 파일 경로면 TOML 파일로 읽고, 아니면 쉼표로 구분한 `key=value` 목록으로 해석합니다.
+/// 알 수 없는 키(예: rerank, chunker)는 에러입니다.
+fn parse_fusion_config(spec: Option<&str>) -> Result<FusionConfig> {
+    let Some(spec) = spec else {
+        return Ok(FusionConfig::default());
+    };
+
+    let path = std::path::Path::new(spec);
+    let text = if path.is_file() {
+        std::fs::read_to_string(path).with_context(|| format!("Failed to read {:?}", path))?
     } else {
-        bail!("--id 또는 --url 중 하나를 지정해야 합니다");
+        spec.split(',').collect::<Vec<_>>().join("\n")
     };
 
-    // 문서 존재 확인
-    let doc = store.get_document(doc_id).context("문서 조회 실패")?;
+    let fusion: FusionConfig =
+        toml::from_str(&text).with_context(|| format!("잘못된 검색 설정: {}", spec))?;
+    fusion
+        .validate()
+        .with_context(|| format!("잘못된 검색 설정: {}", spec))?;
+    Ok(fusion)
+}
 
-    if doc.is_none() {
-        bail!("ID {}인 문서를 찾을 수 없습니다", doc_id);
+/// query/ask 검색 설정 (`[search]` 설정에 `--normalization` 적용)
+fn search_fusion(
+    mut fusion: FusionConfig,
+    normalization: Option<ScoreNormalization>,
+) -> FusionConfig {
+    if let Some(normalization) = normalization {
+        fusion.normalization = normalization;
     }
+    fusion
+}
 
-    // 삭제 수행 (벡터 삭제도 필요하지만 HybridRetriever가 필요)
-    // 현재는 SQLite만 삭제 (벡터는 남아있음)
-    let deleted = store.delete_document(doc_id).context("문서 삭제 실패")?;
+/// `--normalization` 값 파싱
+fn parse_normalization(value: &str) -> std::result::Result<ScoreNormalization, String> {
+    ScoreNormalization::parse(value)
+        .ok_or_else(|| format!("rank, min_max, z_score 중 하나여야 합니다: {}", value))
+}
 
-    if deleted {
-        println!("[OK] 문서 #{} 삭제됨", doc_id);
-        println!("     (주의: 벡터 인덱스는 별도로 정리가 필요할 수 있습니다)");
-    } else {
-        println!("[!] 삭제할 문서를 찾을 수 없습니다");
-    }
+/// 검색 설정 요약 문자열
+fn describe_fusion(fusion: &FusionConfig) -> String {
+    format!(
+        "rrf_k={}, fts_weight={}, vector_weight={}, normalization={}, feedback_weight={}",
+        fusion.rrf_k,
+        fusion.fts_weight,
+        fusion.vector_weight,
+        fusion.normalization.label(),
+        fusion.feedback_weight
+    )
+}
 
-    Ok(())
+/// 명령이 다룰 저장소 디렉토리 (`--collection`을 지정하지 않으면 데이터 디렉토리)
+///
+/// 설정 파일, 로그, 검색 트레이스, 직전 검색 결과는 컬렉션과 관계없이 데이터 디렉토리에 둡니다.
+fn store_dir() -> PathBuf {
+    STORE_DIR.get().cloned().unwrap_or_else(get_data_dir)
 }
 
-/// 상태 명령어 (status)
+/// 현재 컬렉션의 KnowledgeStore 열기
+fn open_store() -> Result<KnowledgeStore> {
+    KnowledgeStore::open(&store_dir().join(SQLITE_FILE)).context("KnowledgeStore 열기 실패")
+}
+
+/// 검색용 HybridRetriever 열기 (`--trace` 지정 시 트레이스 저장)
 ///
-/// 시스템 상태를 확인합니다.
-async fn cmd_status() -> Result<()> {
-    println!("palank-rag v{}", env!("CARGO_PKG_VERSION"));
-    println!();
+/// `text_output`이 아니면(JSON 등 기계가 읽는 출력) 안내 메시지를 stdout에 쓰지 않습니다.
+async fn open_retriever(trace: bool, text_output: bool) -> Result<HybridRetriever> {
+    let retriever = HybridRetriever::with_data_dir(&store_dir())
+        .await
+        .context("HybridRetriever 초기화 실패")?;
 
-    // 데이터 디렉토리
-    let data_dir = get_data_dir();
-    println!("[*] 데이터 디렉토리: {}", data_dir.display());
+    if !trace {
+        return Ok(retriever);
+    }
 
-    // API 키 상태
-    if has_api_key() {
-        println!("[OK] API 키: 설정됨");
-    } else {
-        println!("[!] API 키: 미설정");
-        println!("    설정: export GEMINI_API_KEY=your-key");
+    let dir = get_data_dir().join(TRACES_DIR);
+    if text_output {
+        println!("[*] 검색 트레이스 저장 위치: {}", dir.display());
     }
+    Ok(retriever.with_trace_dir(dir))
+}
 
-    // 문서 수 및 통계
-    match KnowledgeStore::open_default() {
-        Ok(store) => match store.stats() {
-            Ok(stats) => {
-                println!("[OK] 저장된 문서: {} 건", stats.document_count);
-                println!(
-                    "     총 콘텐츠: {} bytes",
-                    format_bytes(stats.total_content_bytes)
-                );
-            }
-            Err(e) => {
-                println!("[!] 통계 조회 실패: {}", e);
-            }
-        },
-        Err(e) => {
-            println!("[!] KnowledgeStore 열기 실패: {}", e);
-        }
+/// 번들 검색기 (처음 열 때 데이터 디렉토리의 `bundles/` 아래에 풀어 둠)
+async fn open_bundle_retriever(bundle: &Path, text_output: bool) -> Result<HybridRetriever> {
+    if !bundle.is_file() {
+        return Err(NotFound(format!("번들 파일이 없습니다: {}", bundle.display())).into());
+    }
+    let manifest = read_manifest(bundle)?;
+    if text_output {
+        let created = manifest.created_at.format("%Y-%m-%d %H:%M");
+        let label = bundle.display();
+        println!(
+            "{}",
+            Msg::SearchingBundle.format(&[&label, &manifest.documents, &created])
+        );
     }
 
-    // 벡터 스토어 상태 (API 키가 있을 때만)
-    if has_api_key() {
-        match HybridRetriever::new().await {
-            Ok(retriever) => match retriever.stats().await {
-                Ok(stats) => {
-                    println!("[OK] 벡터 인덱스: {} 청크", stats.vector_count);
-                }
-                Err(e) => {
-                    tracing::debug!("벡터 통계 조회 실패: {}", e);
-                }
-            },
-            Err(e) => {
-                tracing::debug!("HybridRetriever 초기화 실패: {}", e);
-            }
-        }
+    let dir = open_bundle(bundle, &get_data_dir()).await?;
+    HybridRetriever::with_data_dir(&dir)
+        .await
+        .context("번들 검색기 초기화 실패")
+}
+
+/// 수집용 검색기 (설정 파일의 `[embedding]` 옵션과 실행당 임베딩 한도 적용)
+async fn ingest_retriever() -> Result<HybridRetriever> {
+    let config = Config::load().context("설정 파일 로드 실패")?;
+    let embedder = GeminiEmbedding::from_env().context("임베더 생성 실패")?;
+    let embedder =
+        EmbeddingBudget::new(CircuitBreaker::new(embedder), config.limits.max_embeddings);
+    let retriever = HybridRetriever::with_embedder(&store_dir(), Box::new(embedder))
+        .await
+        .context("HybridRetriever 초기화 실패")?;
+    apply_embedding_config(retriever, &config.embedding)
+}
+
+/// 상주 명령(watch, daemon)용 검색기 (`[embedding]` 옵션과 임베딩 한도 적용)
+///
+/// 상주 프로세스는 작업 실행과 감시 주기를 한 번의 실행으로 세므로,
+/// 돌려준 사용량을 주기마다 되돌립니다.
+async fn resident_retriever() -> Result<(HybridRetriever, BudgetCounter)> {
+    let config = Config::load().context("설정 파일 로드 실패")?;
+    let embedder = GeminiEmbedding::from_env().context("임베더 생성 실패")?;
+    let embedder =
+        EmbeddingBudget::new(CircuitBreaker::new(embedder), config.limits.max_embeddings);
+    let budget = embedder.counter();
+    let retriever = HybridRetriever::with_embedder(&store_dir(), Box::new(embedder))
+        .await
+        .context("HybridRetriever 초기화 실패")?;
+    let retriever = apply_embedding_config(retriever, &config.embedding)?;
+    Ok((retriever, budget))
+}
+
+/// 저장소 증가량 검사 (설정 파일의 `[limits] max_disk_growth_mb`)
+fn ingest_disk_guard() -> Result<DiskGuard> {
+    let config = Config::load().context("설정 파일 로드 실패")?;
+    Ok(DiskGuard::new(&store_dir(), config.limits.max_disk_growth_mb))
+}
+
+/// 입력 한도 초과 청크 처리 방식 적용 (`summarize`는 요약 생성기도 연결)
+fn apply_embedding_config(
+    retriever: HybridRetriever,
+    config: &EmbeddingConfig,
+) -> Result<HybridRetriever> {
+    let retriever = retriever.with_oversize(config.oversize);
+    if config.oversize != OversizeStrategy::Summarize {
+        return Ok(retriever);
     }
 
-    Ok(())
+    let generator = GeminiGenerator::from_env().context("요약 생성기 생성 실패")?;
+    Ok(retriever.with_summarizer(generator))
 }
 
-// ============================================================================
-// Helper Functions
-// ============================================================================
+/// `--id` 또는 `--url`로 문서 ID 결정
+fn resolve_doc_id(store: &KnowledgeStore, url: Option<String>, id: Option<i64>) -> Result<i64> {
+    if let Some(id) = id {
+        return Ok(id);
+    }
+
+    let Some(url) = url else {
+        bail!("--id 또는 --url 중 하나를 지정해야 합니다");
+    };
+
+    let doc = store
+        .get_by_url(&url)
+        .context("문서 조회 실패")?
+        .ok_or_else(|| NotFound(Msg::UrlNotFound.format(&[&url])))?;
+
+    Ok(doc.id)
+}
 
 /// 텍스트 자르기 (UTF-8 안전)
 fn truncate_text(text: &str, max_chars: usize) -> String {
@@ -578,6 +3567,42 @@ fn truncate_text(text: &str, max_chars: usize) -> String {
     }
 }
 
+/// 만료 기간 파싱 (예: 30m, 12h, 7d, 2w)
+fn parse_ttl(value: &str) -> std::result::Result<chrono::Duration, String> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(|| format!("단위가 필요합니다 (m, h, d, w): {}", value))?;
+    let (amount, unit) = value.split_at(split);
+    let amount: i64 = amount
+        .parse()
+        .map_err(|_| format!("잘못된 기간: {}", value))?;
+
+    match unit {
+        "m" => Ok(chrono::Duration::minutes(amount)),
+        "h" => Ok(chrono::Duration::hours(amount)),
+        "d" => Ok(chrono::Duration::days(amount)),
+        "w" => Ok(chrono::Duration::weeks(amount)),
+        _ => Err(format!("지원하지 않는 단위: {} (m, h, d, w)", unit)),
+    }
+}
+
+/// 기준 시각 파싱 (날짜 2024-01-01 또는 지금부터의 기간 30d)
+fn parse_since(value: &str) -> std::result::Result<chrono::DateTime<chrono::Utc>, String> {
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d") {
+        return Ok(date.and_time(chrono::NaiveTime::MIN).and_utc());
+    }
+    parse_ttl(value)
+        .map(|ttl| chrono::Utc::now() - ttl)
+        .map_err(|_| format!("날짜(YYYY-MM-DD) 또는 기간(30d)이 필요합니다: {}", value))
+}
+
+/// 비밀 값 마스킹 (앞 4자만 표시)
+fn mask_secret(secret: &str) -> String {
+    let prefix: String = secret.chars().take(4).collect();
+    format!("{}****", prefix)
+}
+
 /// 바이트 크기 포맷팅
 fn format_bytes(bytes: usize) -> String {
     const KB: usize = 1024;
@@ -615,6 +3640,52 @@ mod tests {
         assert_eq!(format_bytes(1048576), "1.00 MB");
     }
 
+    #[test]
+    fn test_parse_ttl() {
+        assert_eq!(parse_ttl("30m").unwrap(), chrono::Duration::minutes(30));
+        assert_eq!(parse_ttl("7d").unwrap(), chrono::Duration::days(7));
+        assert!(parse_ttl("7").is_err());
+        assert!(parse_ttl("d").is_err());
+        assert!(parse_ttl("3y").is_err());
+    }
+
+    #[test]
+    fn test_parse_since() {
+        assert_eq!(
+            parse_since("2024-01-02").unwrap().to_rfc3339(),
+            "2024-01-02T00:00:00+00:00"
+        );
+        let week_ago = parse_since("7d").unwrap();
+        assert!(chrono::Utc::now() - week_ago >= chrono::Duration::days(7));
+        assert!(parse_since("yesterday").is_err());
+    }
+
+    #[test]
+    fn test_parse_fusion_config() {
+        assert_eq!(parse_fusion_config(None).unwrap(), FusionConfig::default());
+
+        let inline = parse_fusion_config(Some("vector_weight=2, rrf_k=20")).unwrap();
+        assert_eq!(inline.vector_weight, 2.0);
+        assert_eq!(inline.rrf_k, 20.0);
+        assert_eq!(inline.fts_weight, 1.0);
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("b.toml");
+        std::fs::write(&path, "fts_weight = 0.5\n").unwrap();
+        let from_file = parse_fusion_config(path.to_str()).unwrap();
+        assert_eq!(from_file.fts_weight, 0.5);
+
+        assert!(parse_fusion_config(Some("rerank=true")).is_err());
+        assert!(parse_fusion_config(Some("rrf_k=0")).is_err());
+        assert!(parse_fusion_config(Some("rrf_k=-60")).is_err());
+    }
+
+    #[test]
+    fn test_mask_secret() {
+        assert_eq!(mask_secret("AIzaSyExample"), "AIza****");
+        assert_eq!(mask_secret("ab"), "ab****");
+    }
+
     #[test]
     fn test_truncate_unicode() {
         let korean = "안녕하세요 세계";
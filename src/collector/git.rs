@@ -0,0 +1,142 @@
+//! git 변경 파일 목록 - `ingest --dir . --git-changed <rev>`
+//!
+//! 지정한 리비전 이후 바뀐 파일(커밋되지 않은 수정 포함)과 무시되지 않은 새 파일만 골라
+//! 전체 폴더를 다시 훑지 않고 수집합니다. pre-push 훅에서 프로젝트 지식베이스를 최신으로
+//! 유지하는 용도입니다. 삭제된 파일은 제외하며, 새 파일은 `.gitignore`를 따릅니다.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+/// `dir` 아래에서 `rev` 이후 변경된 파일 (절대 경로, 이름순)
+pub fn changed_files(dir: &Path, rev: &str) -> Result<Vec<PathBuf>> {
+    let dir = std::path::absolute(dir)?;
+    if !dir.is_dir() {
+        anyhow::bail!("Directory not found: {:?}", dir);
+    }
+    // 옵션으로 해석되지 않도록 막음
+    if rev.is_empty() || rev.starts_with('-') {
+        anyhow::bail!("Invalid git revision: {:?}", rev);
+    }
+
+    let commit = format!("{}^{{commit}}", rev);
+    git(&dir, &["rev-parse", "--verify", "--quiet", &commit])
+        .with_context(|| format!("Unknown git revision: {}", rev))?;
+
+    let diff = git(
+        &dir,
+        &[
+            "diff",
+            "--name-only",
+            "-z",
+            "--relative",
+            "--diff-filter=d",
+            rev,
+            "--",
+            ".",
+        ],
+    )?;
+    let untracked = git(
+        &dir,
+        &[
+            "ls-files",
+            "--others",
+            "--exclude-standard",
+            "-z",
+            "--",
+            ".",
+        ],
+    )?;
+
+    let mut paths: Vec<PathBuf> = split_nul(&diff)
+        .chain(split_nul(&untracked))
+        .map(|path| dir.join(path))
+        .filter(|path| path.is_file())
+        .collect();
+    paths.sort();
+    paths.dedup();
+
+    Ok(paths)
+}
+
+/// `-z` 출력 나누기
+fn split_nul(output: &str) -> impl Iterator<Item = &str> {
+    output.split('\0').filter(|path| !path.is_empty())
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn run_git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?}", args);
+    }
+
+    #[test]
+    fn test_changed_files() {
+        if Command::new("git").arg("--version").output().is_err() {
+            return;
+        }
+
+        let repo = TempDir::new().unwrap();
+        let root = repo.path();
+        run_git(root, &["init", "-q"]);
+        std::fs::create_dir(root.join("docs")).unwrap();
+        std::fs::write(root.join("docs/a.md"), "a").unwrap();
+        std::fs::write(root.join("docs/b.md"), "b").unwrap();
+        std::fs::write(root.join("docs/gone.md"), "gone").unwrap();
+        std::fs::write(root.join("other.md"), "other").unwrap();
+        std::fs::write(root.join(".gitignore"), "*.log\n").unwrap();
+        run_git(root, &["add", "-A"]);
+        run_git(root, &["commit", "-qm", "init"]);
+
+        // 수정, 삭제, 새 파일, 무시된 파일, 범위 밖 파일
+        std::fs::write(root.join("docs/a.md"), "a2").unwrap();
+        std::fs::remove_file(root.join("docs/gone.md")).unwrap();
+        std::fs::write(root.join("docs/new.md"), "new").unwrap();
+        std::fs::write(root.join("docs/debug.log"), "log").unwrap();
+        std::fs::write(root.join("other.md"), "other2").unwrap();
+
+        let changed = changed_files(&root.join("docs"), "HEAD").unwrap();
+        let names: Vec<_> = changed
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, vec!["a.md", "new.md"]);
+        assert!(changed.iter().all(|p| p.is_absolute()));
+
+        assert!(changed_files(root, "no-such-rev").is_err());
+        assert!(changed_files(root, "--output=x").is_err());
+    }
+}
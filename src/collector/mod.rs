@@ -3,6 +3,12 @@
 //! 로컬 파일 및 폴더를 수집하여 지식베이스에 추가합니다.
 //! .gitignore 패턴을 존중하고, 지원하는 확장자만 수집합니다.
 
+mod git;
+mod report;
+
+pub use git::changed_files;
+pub use report::{load_failures, FailureKind, IngestFailure, IngestReport, INGEST_ERRORS_FILE};
+
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
@@ -32,7 +38,7 @@ impl FileType {
             // 텍스트 파일
             "md" | "txt" | "rs" | "ts" | "tsx" | "js" | "jsx" | "py" | "json" | "toml" | "yaml"
             | "yml" | "html" | "css" | "scss" | "go" | "java" | "c" | "cpp" | "h" | "hpp"
-            | "sh" | "bash" | "zsh" | "sql" | "xml" | "csv" => Some(FileType::Text),
+            | "sh" | "bash" | "zsh" | "sql" | "xml" | "csv" | "tsv" => Some(FileType::Text),
 
             // 이미지 파일
             "png" | "jpg" | "jpeg" | "webp" | "gif" | "bmp" => Some(FileType::Image),
@@ -175,6 +181,13 @@ impl FileCollector {
 
     /// 폴더 재귀 수집
     pub fn collect_directory(&self, path: &Path) -> Result<Vec<CollectedFile>> {
+        Ok(self.collect_directory_detailed(path)?.files)
+    }
+
+    /// 폴더 재귀 수집 (제외된 파일 포함)
+    ///
+    /// 크기 초과 파일과 지원하지 않는 확장자 파일을 따로 돌려주어 수집 요약에 표시합니다.
+    pub fn collect_directory_detailed(&self, path: &Path) -> Result<Collection> {
         let abs_path = if path.is_absolute() {
             path.to_path_buf()
         } else {
@@ -189,7 +202,7 @@ impl FileCollector {
             anyhow::bail!("Not a directory: {:?}", abs_path);
         }
 
-        let mut files = Vec::new();
+        let mut collection = Collection::default();
 
         // ignore 크레이트로 .gitignore 지원
         let walker = WalkBuilder::new(&abs_path)
@@ -215,27 +228,71 @@ impl FileCollector {
 
             let file_path = entry.path().to_path_buf();
 
-            match CollectedFile::from_path(file_path) {
-                Ok(Some(file)) => {
-                    if self.should_include(&file) {
-                        files.push(file);
-                    }
-                }
-                Ok(None) => {} // 지원하지 않는 확장자
+            match CollectedFile::from_path(file_path.clone()) {
+                Ok(Some(file)) => collection.push(file, self),
+                Ok(None) => collection.unsupported.push(file_path),
                 Err(e) => {
                     tracing::warn!("Failed to collect file: {}", e);
                 }
             }
         }
 
-        tracing::info!("Collected {} files from {:?}", files.len(), abs_path);
-        Ok(files)
+        tracing::info!(
+            "Collected {} files from {:?}",
+            collection.files.len(),
+            abs_path
+        );
+        Ok(collection)
+    }
+
+    /// 지정한 파일 목록 수집 (없는 파일은 경고 후 제외)
+    pub fn collect_paths(&self, paths: &[PathBuf]) -> Collection {
+        let mut collection = Collection::default();
+
+        for path in paths {
+            if !path.is_file() {
+                tracing::warn!("File not found, skipping: {:?}", path);
+                continue;
+            }
+            match CollectedFile::from_path(path.clone()) {
+                Ok(Some(file)) => collection.push(file, self),
+                Ok(None) => collection.unsupported.push(path.clone()),
+                Err(e) => tracing::warn!("Failed to collect file: {}", e),
+            }
+        }
+
+        collection
+    }
+
+    /// git 리비전 이후 변경된 파일 수집 (`crate::collector::changed_files` 참고)
+    ///
+    /// 폴더 수집과 같이 숨김 파일은 `include_hidden`일 때만 포함합니다.
+    pub fn collect_git_changed(&self, dir: &Path, rev: &str) -> Result<Collection> {
+        let dir = std::path::absolute(dir)?;
+        let paths: Vec<PathBuf> = changed_files(&dir, rev)?
+            .into_iter()
+            .filter(|path| {
+                self.config.include_hidden || !is_hidden(path.strip_prefix(&dir).unwrap_or(path))
+            })
+            .collect();
+
+        Ok(self.collect_paths(&paths))
+    }
+
+    /// 최대 파일 크기를 넘는지
+    pub fn exceeds_size_limit(&self, file: &CollectedFile) -> bool {
+        self.config.max_file_size > 0 && file.size > self.config.max_file_size
+    }
+
+    /// 최대 파일 크기 (바이트, 0이면 제한 없음)
+    pub fn max_file_size(&self) -> u64 {
+        self.config.max_file_size
     }
 
     /// 파일이 필터 조건을 만족하는지 확인
     fn should_include(&self, file: &CollectedFile) -> bool {
         // 파일 크기 제한
-        if self.config.max_file_size > 0 && file.size > self.config.max_file_size {
+        if self.exceeds_size_limit(file) {
             tracing::debug!("Skipping large file: {:?} ({} bytes)", file.path, file.size);
             return false;
         }
@@ -270,6 +327,34 @@ impl FileCollector {
     }
 }
 
+/// 경로 중 숨김(`.`으로 시작하는) 조각이 있는지
+fn is_hidden(path: &Path) -> bool {
+    path.components()
+        .any(|c| c.as_os_str().to_string_lossy().starts_with('.'))
+}
+
+/// 폴더/파일 목록 수집 결과
+#[derive(Debug, Default)]
+pub struct Collection {
+    /// 수집 대상 파일
+    pub files: Vec<CollectedFile>,
+    /// 최대 크기를 넘어 제외된 파일
+    pub oversized: Vec<CollectedFile>,
+    /// 지원하지 않는 확장자라 제외된 파일
+    pub unsupported: Vec<PathBuf>,
+}
+
+impl Collection {
+    fn push(&mut self, file: CollectedFile, collector: &FileCollector) {
+        if collector.exceeds_size_limit(&file) {
+            tracing::debug!("Skipping large file: {:?} ({} bytes)", file.path, file.size);
+            self.oversized.push(file);
+        } else if collector.should_include(&file) {
+            self.files.push(file);
+        }
+    }
+}
+
 // ============================================================================
 // Statistics
 // ============================================================================
@@ -328,4 +413,11 @@ mod tests {
         assert!(!config.include_hidden);
         assert_eq!(config.max_file_size, 10 * 1024 * 1024);
     }
+
+    #[test]
+    fn test_is_hidden() {
+        assert!(is_hidden(Path::new(".github/workflows/ci.md")));
+        assert!(is_hidden(Path::new("docs/.draft.md")));
+        assert!(!is_hidden(Path::new("docs/guide.md")));
+    }
 }
@@ -0,0 +1,206 @@
+//! 파일 수집 결과 집계 - 실패 유형 분류
+//!
+//! "성공 180, 실패 20"만으로는 무엇을 고쳐야 할지 알 수 없으므로 실패를 유형별로 나눕니다.
+//! 실패 목록은 데이터 디렉토리에 저장해 두었다가 `ingest --errors-only`로 실패한 파일만
+//! 다시 수집합니다.
+
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::embedding::{CircuitOpen, QuotaExhausted};
+use crate::extractor::UnsupportedFormat;
+use crate::i18n::Msg;
+
+/// 마지막 파일 수집의 실패 목록 (데이터 디렉토리 기준)
+pub const INGEST_ERRORS_FILE: &str = "ingest-errors.json";
+
+/// 실패 유형
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FailureKind {
+    /// 지원하지 않는 형식 (확장자, UTF-8이 아닌 텍스트 등)
+    UnsupportedFormat,
+    /// 최대 파일 크기 초과
+    TooLarge,
+    /// 콘텐츠 추출 실패 (PDF 파싱, Vision API 등)
+    ExtractionFailed,
+    /// 임베딩 할당량 소진 또는 API 장애
+    EmbeddingQuota,
+    /// 저장 실패 (SQLite/LanceDB, 기타 임베딩 오류)
+    StoreError,
+}
+
+impl FailureKind {
+    /// 요약 출력 순서
+    pub const ALL: [Self; 5] = [
+        Self::UnsupportedFormat,
+        Self::TooLarge,
+        Self::ExtractionFailed,
+        Self::EmbeddingQuota,
+        Self::StoreError,
+    ];
+
+    /// 요약 표시 이름 (현재 출력 언어)
+    pub fn label(self) -> &'static str {
+        let msg = match self {
+            Self::UnsupportedFormat => Msg::FailureUnsupportedFormat,
+            Self::TooLarge => Msg::FailureTooLarge,
+            Self::ExtractionFailed => Msg::FailureExtraction,
+            Self::EmbeddingQuota => Msg::FailureEmbedding,
+            Self::StoreError => Msg::FailureStore,
+        };
+        msg.text()
+    }
+
+    /// 재시도로 해결될 수 있는지 (`--errors-only` 안내용)
+    pub fn is_retryable(self) -> bool {
+        matches!(
+            self,
+            Self::ExtractionFailed | Self::EmbeddingQuota | Self::StoreError
+        )
+    }
+
+    /// 추출 단계 오류 분류
+    pub fn of_extraction_error(error: &anyhow::Error) -> Self {
+        if UnsupportedFormat::is_cause_of(error) {
+            Self::UnsupportedFormat
+        } else {
+            Self::ExtractionFailed
+        }
+    }
+
+    /// 저장(임베딩 포함) 단계 오류 분류
+    pub fn of_store_error(error: &anyhow::Error) -> Self {
+        if QuotaExhausted::is_cause_of(error) || CircuitOpen::is_cause_of(error) {
+            Self::EmbeddingQuota
+        } else {
+            Self::StoreError
+        }
+    }
+}
+
+/// 실패한 파일
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IngestFailure {
+    pub path: PathBuf,
+    pub kind: FailureKind,
+    /// 오류 메시지 (원인 체인 포함)
+    pub error: String,
+}
+
+/// 파일 수집 결과
+#[derive(Debug, Default)]
+pub struct IngestReport {
+    /// 저장 완료한 파일 수
+    pub succeeded: usize,
+    /// 이미 수집되어 건너뛴 파일 수 (`--resume`)
+    pub skipped: usize,
+    /// 본문이 너무 짧아 건너뛴 콘텐츠 수 (페이지/행 묶음 단위)
+    pub too_short: usize,
+    /// 실패한 파일
+    pub failures: Vec<IngestFailure>,
+}
+
+impl IngestReport {
+    /// 실패 기록
+    pub fn fail(&mut self, path: &Path, kind: FailureKind, error: impl Display) {
+        self.failures.push(IngestFailure {
+            path: path.to_path_buf(),
+            kind,
+            error: error.to_string(),
+        });
+    }
+
+    /// 유형별 실패 수 (0건인 유형 제외, `FailureKind::ALL` 순서)
+    pub fn counts(&self) -> Vec<(FailureKind, usize)> {
+        FailureKind::ALL
+            .into_iter()
+            .map(|kind| {
+                let count = self.failures.iter().filter(|f| f.kind == kind).count();
+                (kind, count)
+            })
+            .filter(|(_, count)| *count > 0)
+            .collect()
+    }
+
+    /// 실패 목록 저장 (실패가 없으면 이전 목록 삭제)
+    pub fn save_failures(&self, path: &Path) -> Result<()> {
+        if self.failures.is_empty() {
+            if path.exists() {
+                std::fs::remove_file(path)
+                    .with_context(|| format!("Failed to remove {:?}", path))?;
+            }
+            return Ok(());
+        }
+
+        let json = serde_json::to_string_pretty(&self.failures)?;
+        std::fs::write(path, json).with_context(|| format!("Failed to write {:?}", path))
+    }
+}
+
+/// 저장된 실패 목록 읽기
+pub fn load_failures(path: &Path) -> Result<Vec<IngestFailure>> {
+    let json =
+        std::fs::read_to_string(path).with_context(|| format!("Failed to read {:?}", path))?;
+    serde_json::from_str(&json).with_context(|| format!("Failed to parse {:?}", path))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_errors() {
+        let unsupported = anyhow::Error::new(UnsupportedFormat("x".to_string())).context("read");
+        assert_eq!(
+            FailureKind::of_extraction_error(&unsupported),
+            FailureKind::UnsupportedFormat
+        );
+        assert_eq!(
+            FailureKind::of_extraction_error(&anyhow::anyhow!("bad pdf")),
+            FailureKind::ExtractionFailed
+        );
+
+        let quota = anyhow::Error::new(QuotaExhausted { keys: 1 }).context("embed");
+        assert_eq!(
+            FailureKind::of_store_error(&quota),
+            FailureKind::EmbeddingQuota
+        );
+        assert_eq!(
+            FailureKind::of_store_error(&anyhow::anyhow!("disk full")),
+            FailureKind::StoreError
+        );
+    }
+
+    #[test]
+    fn test_counts_and_roundtrip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(INGEST_ERRORS_FILE);
+
+        let mut report = IngestReport::default();
+        report.fail(Path::new("/a.pdf"), FailureKind::ExtractionFailed, "x");
+        report.fail(Path::new("/b.bin"), FailureKind::UnsupportedFormat, "y");
+        report.fail(Path::new("/c.pdf"), FailureKind::ExtractionFailed, "z");
+        assert_eq!(
+            report.counts(),
+            vec![
+                (FailureKind::UnsupportedFormat, 1),
+                (FailureKind::ExtractionFailed, 2)
+            ]
+        );
+
+        report.save_failures(&path).unwrap();
+        assert_eq!(load_failures(&path).unwrap(), report.failures);
+
+        // 실패가 없으면 이전 목록 삭제
+        IngestReport::default().save_failures(&path).unwrap();
+        assert!(!path.exists());
+    }
+}
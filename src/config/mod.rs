@@ -0,0 +1,326 @@
+//! 설정 모듈 - ~/.palank-rag/config.toml
+//!
+//! 파일이 없으면 기본값을 사용합니다. 모든 섹션은 선택 사항입니다.
+//!
+//! ```toml
+//! lang = "en"
+//!
+//! [url_policy]
+//! allow_domains = ["docs.rs"]
+//! deny_domains = []
+//! allow_private_networks = false
+//!
+//! [[jobs]]
+//! name = "nightly-compact"
+//! schedule = "0 3 * * *"
+//! action = "compact"
+//!
+//! [[watch]]
+//! dir = "~/Pictures/Screenshots"
+//!
+//! [notify]
+//! desktop = true
+//!
+//! [titles]
+//! order = ["front-matter", "h1", "og-title", "html-title", "filename"]
+//! ignore = ["Docs"]
+//!
+//! [embedding]
+//! oversize = "split"
+//!
+//! [content_filter]
+//! min_chars = 50
+//!
+//! [limits]
+//! max_pages = 500
+//! max_embeddings = 20000
+//! max_disk_growth_mb = 2048
+//!
+//! [boilerplate]
+//! mode = "demote"
+//! min_docs = 5
+//!
+//! [search]
+//! normalization = "min_max"
+//! vector_weight = 1.5
+//! ```
+//!
+//! `lang`은 CLI 출력 언어입니다 (`PALANK_RAG_LANG`이 우선, `crate::i18n` 참고).
+//! `jobs`/`watch` 항목은 `palank-rag daemon`에서 사용합니다 (`crate::daemon` 참고).
+//! `notify`는 데몬과 `watch` 명령의 알림 설정입니다 (`crate::notify` 참고).
+//! `titles`는 URL/파일 수집 시 문서 제목 우선순위입니다 (`crate::extractor::title` 참고).
+//! `embedding`은 입력 한도를 넘는 청크 처리 방식입니다 (`crate::embedding::OversizeStrategy` 참고).
+//! `content_filter`는 수집 시 건너뛸 짧은 본문 기준입니다 (`crate::extractor::filter` 참고).
+//! `limits`는 수집 한 번에 쓸 수 있는 페이지/임베딩/디스크 한도입니다 (`crate::limits` 참고).
+//! `boilerplate`는 검색 시 여러 문서에 반복되는 청크 처리 방식입니다 (`crate::knowledge::BoilerplateConfig` 참고).
+//! `search`는 query/ask의 RRF 가중치와 점수 정규화 방식입니다 (`crate::knowledge::FusionConfig` 참고).
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::daemon::{JobAction, JobConfig, WatchConfig};
+use crate::embedding::EmbeddingConfig;
+use crate::extractor::{ContentFilter, TitleRules};
+use crate::i18n::Lang;
+use crate::knowledge::{get_data_dir, BoilerplateConfig, FusionConfig};
+use crate::limits::ResourceLimits;
+use crate::notify::NotifyConfig;
+use crate::scraper::UrlPolicy;
+
+/// 설정 파일 이름
+const CONFIG_FILE: &str = "config.toml";
+
+// ============================================================================
+// Config
+// ============================================================================
+
+/// palank-rag 설정
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// CLI 출력 언어 (없으면 한국어)
+    pub lang: Option<Lang>,
+    /// URL 수집 정책 (스크랩/크롤 전 검사)
+    pub url_policy: UrlPolicy,
+    /// 데몬 예약 작업
+    pub jobs: Vec<JobConfig>,
+    /// 데몬 감시 폴더
+    pub watch: Vec<WatchConfig>,
+    /// 데몬/감시 이벤트 알림
+    pub notify: NotifyConfig,
+    /// 문서 제목 결정 규칙
+    pub titles: TitleRules,
+    /// 임베딩 입력 처리
+    pub embedding: EmbeddingConfig,
+    /// 최소 본문 길이 (수집 시 짧은 본문 건너뜀)
+    pub content_filter: ContentFilter,
+    /// 실행당 자원 사용 한도
+    pub limits: ResourceLimits,
+    /// 상용구 청크 억제 (검색 시)
+    pub boilerplate: BoilerplateConfig,
+    /// 검색 점수 통합 (RRF 가중치, 점수 정규화 방식)
+    pub search: FusionConfig,
+}
+
+impl Config {
+    /// 기본 위치에서 설정 로드 (~/.palank-rag/config.toml)
+    pub fn load() -> Result<Self> {
+        Self::load_from(&config_path())
+    }
+
+    /// 지정된 파일에서 설정 로드 (파일이 없으면 기본값)
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config: {:?}", path))?;
+
+        let mut config: Self =
+            toml::from_str(&text).with_context(|| format!("Failed to parse config: {:?}", path))?;
+        config
+            .search
+            .validate()
+            .with_context(|| format!("Invalid [search] in config: {:?}", path))?;
+        config.expand_paths();
+        Ok(config)
+    }
+
+    /// 경로 설정의 `~` 를 홈 디렉토리로 확장 (`[[watch]] dir`, 백업 `dir`)
+    fn expand_paths(&mut self) {
+        for watch in &mut self.watch {
+            watch.dir = expand_home(&watch.dir);
+        }
+        for job in &mut self.jobs {
+            if let JobAction::Backup { dir: Some(dir), .. } = &mut job.action {
+                *dir = expand_home(dir);
+            }
+        }
+    }
+}
+
+/// `~` 또는 `~/...` 경로를 홈 디렉토리 기준으로 확장 (홈을 모르면 그대로)
+pub fn expand_home(path: &Path) -> PathBuf {
+    let Ok(rest) = path.strip_prefix("~") else {
+        return path.to_path_buf();
+    };
+    match dirs::home_dir() {
+        Some(home) => home.join(rest),
+        None => path.to_path_buf(),
+    }
+}
+
+/// 설정 파일 경로 (~/.palank-rag/config.toml)
+pub fn config_path() -> PathBuf {
+    get_data_dir().join(CONFIG_FILE)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_expands_home() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(
+            &path,
+            r#"
+            [[jobs]]
+            name = "backup"
+            schedule = "@weekly"
+            action = "backup"
+            dir = "~/backups"
+
+            [[watch]]
+            dir = "~/Pictures/Screenshots"
+            "#,
+        )
+        .unwrap();
+
+        let config = Config::load_from(&path).unwrap();
+        let home = dirs::home_dir().unwrap();
+        assert_eq!(config.watch[0].dir, home.join("Pictures/Screenshots"));
+        assert_eq!(
+            config.jobs[0].action,
+            JobAction::Backup {
+                dir: Some(home.join("backups")),
+                keep: 7
+            }
+        );
+        assert_eq!(expand_home(Path::new("/tmp/~x")), PathBuf::from("/tmp/~x"));
+    }
+
+    #[test]
+    fn test_load_missing_file_uses_defaults() {
+        let dir = TempDir::new().unwrap();
+        let config = Config::load_from(&dir.path().join("config.toml")).unwrap();
+        assert!(config.url_policy.allow_domains.is_empty());
+        assert!(!config.url_policy.allow_private_networks);
+    }
+
+    #[test]
+    fn test_load_url_policy() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(
+            &path,
+            "[url_policy]\nallow_domains = [\"docs.rs\"]\nallow_private_networks = true\n",
+        )
+        .unwrap();
+
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(config.url_policy.allow_domains, vec!["docs.rs".to_string()]);
+        assert!(config.url_policy.deny_domains.is_empty());
+        assert!(config.url_policy.allow_private_networks);
+    }
+
+    #[test]
+    fn test_load_title_rules() {
+        use crate::extractor::TitleSource;
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, "[titles]\norder = [\"og-title\", \"filename\"]\n").unwrap();
+
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(
+            config.titles.order,
+            vec![TitleSource::OgTitle, TitleSource::Filename]
+        );
+        assert!(config.titles.ignore.is_empty());
+    }
+
+    #[test]
+    fn test_load_embedding_oversize() {
+        use crate::embedding::OversizeStrategy;
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, "[embedding]\noversize = \"truncate-tail\"\n").unwrap();
+
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(config.embedding.oversize, OversizeStrategy::TruncateTail);
+        assert_eq!(Config::default().embedding.oversize, OversizeStrategy::Average);
+    }
+
+    #[test]
+    fn test_load_content_filter() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, "[content_filter]\nmin_chars = 0\n").unwrap();
+
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(config.content_filter.min_chars, 0);
+        assert_eq!(
+            Config::default().content_filter.min_chars,
+            crate::extractor::DEFAULT_MIN_CONTENT_CHARS
+        );
+    }
+
+    #[test]
+    fn test_load_lang() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, "lang = \"en\"\n\n[content_filter]\nmin_chars = 10\n").unwrap();
+
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(config.lang, Some(Lang::En));
+        assert_eq!(config.content_filter.min_chars, 10);
+        assert_eq!(Config::default().lang, None);
+    }
+
+    #[test]
+    fn test_load_limits() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, "[limits]\nmax_pages = 20\nmax_disk_growth_mb = 0\n").unwrap();
+
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(config.limits.max_pages, 20);
+        assert_eq!(config.limits.max_disk_growth_mb, 0);
+        assert_eq!(
+            config.limits.max_embeddings,
+            crate::limits::DEFAULT_MAX_EMBEDDINGS
+        );
+    }
+
+    #[test]
+    fn test_load_boilerplate() {
+        use crate::knowledge::{BoilerplateMode, DEFAULT_BOILERPLATE_MIN_DOCS};
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, "[boilerplate]\nmode = \"exclude\"\n").unwrap();
+
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(config.boilerplate.mode, BoilerplateMode::Exclude);
+        assert_eq!(config.boilerplate.min_docs, DEFAULT_BOILERPLATE_MIN_DOCS);
+        assert_eq!(Config::default().boilerplate.mode, BoilerplateMode::Demote);
+    }
+
+    #[test]
+    fn test_load_search() {
+        use crate::knowledge::ScoreNormalization;
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, "[search]\nnormalization = \"z_score\"\n").unwrap();
+
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(config.search.normalization, ScoreNormalization::ZScore);
+        assert_eq!(config.search.rrf_k, FusionConfig::default().rrf_k);
+        assert_eq!(Config::default().search, FusionConfig::default());
+
+        std::fs::write(&path, "[search]\nrrf_k = 0\n").unwrap();
+        assert!(Config::load_from(&path).is_err());
+    }
+}
@@ -0,0 +1,282 @@
+//! 브라우저 북마크 커넥터 - `ingest --bookmarks`
+//!
+//! 브라우저에서 내보낸 북마크 파일을 읽어 URL 목록과 폴더 경로를 추출합니다.
+//! - Netscape HTML (`bookmarks.html`, Chrome/Firefox/Safari/Edge 내보내기)
+//! - Chrome `Bookmarks` JSON (프로필 폴더의 원본 파일)
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::knowledge::NewDocument;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// 북마크 항목
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bookmark {
+    pub url: String,
+    pub title: Option<String>,
+    /// 폴더 경로 (최상위부터)
+    pub folder: Vec<String>,
+    /// 추가 시각
+    pub added_at: Option<DateTime<Utc>>,
+}
+
+impl Bookmark {
+    /// 폴더 경로 문자열 (예: `Dev/Rust`, 최상위면 None)
+    pub fn folder_path(&self) -> Option<String> {
+        if self.folder.is_empty() {
+            None
+        } else {
+            Some(self.folder.join("/"))
+        }
+    }
+
+    /// 스크랩 없이 북마크 정보만으로 문서 생성
+    ///
+    /// 제목, URL, 폴더 경로만 담기므로 제목/폴더 이름으로 검색됩니다.
+    pub fn to_document(&self) -> NewDocument {
+        self.to_document_with(None)
+    }
+
+    /// 스크랩한 본문으로 문서 생성 (본문이 없으면 북마크 정보만)
+    pub fn to_document_with(&self, content: Option<&str>) -> NewDocument {
+        let title = self.title.clone().unwrap_or_else(|| self.url.clone());
+
+        let mut text = format!("# {}\n\n{}\n", title, self.url);
+        if let Some(folder) = self.folder_path() {
+            text.push_str(&format!("북마크 폴더: {}\n", folder.replace('/', " / ")));
+        }
+        if let Some(content) = content {
+            text.push('\n');
+            text.push_str(content);
+        }
+
+        NewDocument {
+            url: self.url.clone(),
+            title: Some(title),
+            content: text,
+            framework: self.folder_path(),
+            ..Default::default()
+        }
+    }
+
+    /// 수집 가능한 웹 URL인지 (`javascript:`, `place:`, `chrome://` 등 제외)
+    pub fn is_web(&self) -> bool {
+        self.url.starts_with("http://") || self.url.starts_with("https://")
+    }
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+/// 북마크 파일 파싱 (`{`로 시작하면 Chrome JSON, 그 밖에는 Netscape HTML)
+pub fn parse_bookmarks(text: &str) -> Result<Vec<Bookmark>> {
+    if text.trim_start().starts_with('{') {
+        parse_chrome_json(text)
+    } else {
+        Ok(parse_netscape_html(text))
+    }
+}
+
+/// Netscape 북마크 HTML 파싱
+///
+/// `<DT><H3>폴더</H3>` 다음의 `<DL>` ~ `</DL>`이 폴더 내용입니다.
+/// 형식이 느슨한 HTML이라 DOM 대신 태그 단위로 읽습니다.
+pub fn parse_netscape_html(html: &str) -> Vec<Bookmark> {
+    let tag = Regex::new(r"(?is)<h3[^>]*>(.*?)</h3>|<a\s([^>]*)>(.*?)</a>|<dl[\s>]|</dl>")
+        .expect("Invalid regex");
+    let attr = Regex::new(r#"([A-Za-z_-]+)\s*=\s*"([^"]*)""#).expect("Invalid regex");
+
+    let mut bookmarks = Vec::new();
+    // 열린 <DL>마다 폴더 이름 (최상위 DL은 None)
+    let mut stack: Vec<Option<String>> = Vec::new();
+    let mut pending_folder: Option<String> = None;
+
+    for caps in tag.captures_iter(html) {
+        if let Some(name) = caps.get(1) {
+            pending_folder = Some(decode_html(name.as_str()));
+        } else if let Some(attrs) = caps.get(2) {
+            let Some(url) = attribute(&attr, attrs.as_str(), "href") else {
+                continue;
+            };
+            let title = decode_html(caps.get(3).map_or("", |m| m.as_str()));
+            let added_at = attribute(&attr, attrs.as_str(), "add_date")
+                .and_then(|secs| secs.parse::<i64>().ok())
+                .and_then(|secs| DateTime::from_timestamp(secs, 0));
+
+            bookmarks.push(Bookmark {
+                url,
+                title: Some(title).filter(|t| !t.is_empty()),
+                folder: stack.iter().flatten().cloned().collect(),
+                added_at,
+            });
+        } else if caps[0].starts_with("</") {
+            stack.pop();
+        } else {
+            stack.push(pending_folder.take());
+        }
+    }
+
+    bookmarks
+}
+
+/// 태그 속성 값 (대소문자 무시, 큰따옴표 값)
+fn attribute(attr: &Regex, attrs: &str, name: &str) -> Option<String> {
+    attr.captures_iter(attrs)
+        .find(|caps| caps[1].eq_ignore_ascii_case(name))
+        .map(|caps| decode_html(&caps[2]))
+}
+
+/// HTML 엔티티 디코딩 및 태그 제거
+fn decode_html(text: &str) -> String {
+    scraper::Html::parse_fragment(text)
+        .root_element()
+        .text()
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+#[derive(Debug, Deserialize)]
+struct ChromeBookmarks {
+    roots: std::collections::BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChromeNode {
+    #[serde(rename = "type", default)]
+    node_type: String,
+    #[serde(default)]
+    name: String,
+    url: Option<String>,
+    date_added: Option<String>,
+    #[serde(default)]
+    children: Vec<ChromeNode>,
+}
+
+/// Chrome `Bookmarks` JSON 파싱
+///
+/// 최상위 루트(북마크바, 기타 북마크 등)는 폴더 경로에 포함하지 않습니다.
+pub fn parse_chrome_json(text: &str) -> Result<Vec<Bookmark>> {
+    let file: ChromeBookmarks =
+        serde_json::from_str(text).context("Failed to parse Chrome bookmarks JSON")?;
+
+    let mut bookmarks = Vec::new();
+    for root in file.roots.into_values() {
+        // roots에는 노드 외에 sync_transaction_version 같은 값도 있음
+        if let Ok(node) = serde_json::from_value::<ChromeNode>(root) {
+            for child in &node.children {
+                collect_chrome_node(child, &mut Vec::new(), &mut bookmarks);
+            }
+        }
+    }
+
+    Ok(bookmarks)
+}
+
+fn collect_chrome_node(node: &ChromeNode, path: &mut Vec<String>, out: &mut Vec<Bookmark>) {
+    match (node.node_type.as_str(), &node.url) {
+        ("url", Some(url)) => out.push(Bookmark {
+            url: url.clone(),
+            title: Some(node.name.clone()).filter(|n| !n.is_empty()),
+            folder: path.clone(),
+            added_at: node.date_added.as_deref().and_then(chrome_timestamp),
+        }),
+        ("folder", _) => {
+            path.push(node.name.clone());
+            for child in &node.children {
+                collect_chrome_node(child, path, out);
+            }
+            path.pop();
+        }
+        _ => {}
+    }
+}
+
+/// Chrome 시각 (1601-01-01 기준 마이크로초) 변환
+fn chrome_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    /// 1601-01-01부터 1970-01-01까지의 초
+    const WINDOWS_EPOCH_OFFSET_SECS: i64 = 11_644_473_600;
+
+    let micros: i64 = value.parse().ok()?;
+    if micros == 0 {
+        return None;
+    }
+    DateTime::from_timestamp(micros / 1_000_000 - WINDOWS_EPOCH_OFFSET_SECS, 0)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_netscape_html() {
+        let html = r#"<!DOCTYPE NETSCAPE-Bookmark-file-1>
+<TITLE>Bookmarks</TITLE>
+<H1>Bookmarks</H1>
+<DL><p>
+    <DT><H3 ADD_DATE="1600000000">Dev</H3>
+    <DL><p>
+        <DT><H3>Rust &amp; WASM</H3>
+        <DL><p>
+            <DT><A HREF="https://doc.rust-lang.org/book/" ADD_DATE="1700000000">The Book</A>
+        </DL><p>
+        <DT><A HREF="https://docs.rs/">Docs.rs</A>
+    </DL><p>
+    <DT><A HREF="javascript:void(0)">Bookmarklet</A>
+</DL><p>"#;
+
+        let bookmarks = parse_bookmarks(html).unwrap();
+        assert_eq!(bookmarks.len(), 3);
+
+        assert_eq!(bookmarks[0].url, "https://doc.rust-lang.org/book/");
+        assert_eq!(bookmarks[0].folder, vec!["Dev", "Rust & WASM"]);
+        assert_eq!(bookmarks[0].added_at.unwrap().timestamp(), 1_700_000_000);
+
+        assert_eq!(bookmarks[1].folder_path().as_deref(), Some("Dev"));
+        assert!(bookmarks[2].folder.is_empty());
+        assert!(!bookmarks[2].is_web());
+
+        let doc = bookmarks[0].to_document();
+        assert_eq!(doc.framework.as_deref(), Some("Dev/Rust & WASM"));
+        assert!(doc.content.contains("북마크 폴더: Dev / Rust & WASM"));
+    }
+
+    #[test]
+    fn test_parse_chrome_json() {
+        let json = r#"{
+            "checksum": "abc",
+            "roots": {
+                "bookmark_bar": {
+                    "type": "folder", "name": "Bookmarks bar",
+                    "children": [
+                        {"type": "folder", "name": "Papers", "children": [
+                            {"type": "url", "name": "RRF", "url": "https://example.com/rrf",
+                             "date_added": "13300000000000000"}
+                        ]},
+                        {"type": "url", "name": "Home", "url": "https://example.com/"}
+                    ]
+                },
+                "sync_transaction_version": "1"
+            },
+            "version": 1
+        }"#;
+
+        let bookmarks = parse_bookmarks(json).unwrap();
+        assert_eq!(bookmarks.len(), 2);
+        assert_eq!(bookmarks[0].folder, vec!["Papers"]);
+        assert_eq!(bookmarks[0].title.as_deref(), Some("RRF"));
+        assert!(bookmarks[0].added_at.is_some());
+        assert!(bookmarks[1].folder.is_empty());
+    }
+}
@@ -0,0 +1,581 @@
+//! AI 대화 내보내기 커넥터 - `ingest --conversations`
+//!
+//! ChatGPT/Claude에서 내보낸 `conversations.json`을 읽어 대화 하나를 문서 하나로 만듭니다.
+//! - ChatGPT: 메시지 트리(`mapping`)에서 마지막으로 본 가지(`current_node`)만 따라감
+//! - Claude: `chat_messages` 순서대로
+//!
+//! 질문 하나와 그 답변을 한 `##` 섹션으로 묶어, Markdown 청커가 서로 다른 질문/답변을
+//! 한 청크에 섞지 않도록 합니다. 답변 안의 `#`/`##` 제목은 섹션 아래로 내립니다.
+//! 대화 시작 시각은 게시일(`list --since`), 참여자는 작성자 메타데이터로 저장합니다.
+
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::knowledge::NewDocument;
+use crate::scraper::PageMetadata;
+
+/// 섹션 제목으로 쓰는 질문 첫 줄 최대 길이 (문자 수)
+const HEADING_CHARS: usize = 60;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// 대화를 내보낸 서비스
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConversationSource {
+    ChatGpt,
+    Claude,
+}
+
+impl ConversationSource {
+    /// 프레임워크 태그로 쓰는 이름
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ChatGpt => "chatgpt",
+            Self::Claude => "claude",
+        }
+    }
+
+    /// 표시 이름 (답변한 쪽 참여자 이름)
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ChatGpt => "ChatGPT",
+            Self::Claude => "Claude",
+        }
+    }
+}
+
+/// 발화자
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    User,
+    Assistant,
+}
+
+/// 대화의 발화 하나
+#[derive(Debug, Clone, PartialEq)]
+pub struct Turn {
+    pub role: Role,
+    pub text: String,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// 대화 하나
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conversation {
+    pub source: ConversationSource,
+    /// 서비스의 대화 ID
+    pub id: String,
+    pub title: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    /// 답변 모델 (ChatGPT `model_slug`, 기록이 없으면 None)
+    pub model: Option<String>,
+    pub turns: Vec<Turn>,
+}
+
+impl Conversation {
+    /// 서비스에서 대화를 여는 URL (문서 URL, 다시 가져오면 같은 문서를 갱신)
+    pub fn url(&self) -> String {
+        match self.source {
+            ConversationSource::ChatGpt => format!("https://chatgpt.com/c/{}", self.id),
+            ConversationSource::Claude => format!("https://claude.ai/chat/{}", self.id),
+        }
+    }
+
+    /// 참여자 (예: `사용자, ChatGPT (gpt-4o)`)
+    pub fn participants(&self) -> Vec<String> {
+        let assistant = match self.model {
+            Some(ref model) => format!("{} ({})", self.source.label(), model),
+            None => self.source.label().to_string(),
+        };
+        vec!["사용자".to_string(), assistant]
+    }
+
+    /// 문서로 변환 (질문/답변 쌍마다 `##` 섹션)
+    pub fn to_document(&self) -> NewDocument {
+        let title = self
+            .title
+            .clone()
+            .filter(|t| !t.trim().is_empty())
+            .unwrap_or_else(|| format!("{} 대화 {}", self.source.label(), self.id));
+        let participants = self.participants();
+
+        let mut text = format!("# {}\n\n", title);
+        let last_turn_at = self.turns.iter().rev().find_map(|t| t.created_at);
+        let dates: Vec<String> = [self.created_at, last_turn_at]
+            .iter()
+            .flatten()
+            .map(|at| at.format("%Y-%m-%d").to_string())
+            .collect();
+        let period = match dates.as_slice() {
+            [first, last] if first != last => format!(" · {} ~ {}", first, last),
+            [first, ..] => format!(" · {}", first),
+            [] => String::new(),
+        };
+        text.push_str(&format!("{} 대화{}\n", self.source.label(), period));
+        text.push_str(&format!("참여자: {}\n", participants.join(", ")));
+
+        let mut exchange = 0;
+        for (i, turn) in self.turns.iter().enumerate() {
+            if turn.role == Role::User || i == 0 {
+                exchange += 1;
+                let heading = match turn.role {
+                    Role::User => question_heading(&turn.text),
+                    Role::Assistant => String::new(),
+                };
+                text.push_str(&format!("\n## {}. {}\n", exchange, heading));
+            }
+
+            let speaker = match turn.role {
+                Role::User => participants[0].as_str(),
+                Role::Assistant => self.source.label(),
+            };
+            match turn.created_at {
+                Some(at) => {
+                    let at = at.format("%Y-%m-%d %H:%M");
+                    text.push_str(&format!("\n**{}** · {}\n\n", speaker, at));
+                }
+                None => text.push_str(&format!("\n**{}**\n\n", speaker)),
+            }
+            text.push_str(&demote_headings(turn.text.trim()));
+            text.push('\n');
+        }
+
+        NewDocument {
+            url: self.url(),
+            title: Some(title),
+            content: text,
+            framework: Some(self.source.as_str().to_string()),
+            metadata: Some(PageMetadata {
+                author: Some(participants.join(", ")),
+                published_at: self.created_at,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+}
+
+/// 질문 첫 줄로 섹션 제목 만들기
+fn question_heading(text: &str) -> String {
+    let line = text
+        .lines()
+        .map(|l| l.trim().trim_start_matches('#').trim())
+        .find(|l| !l.is_empty())
+        .unwrap_or_default();
+    if line.chars().count() <= HEADING_CHARS {
+        return line.to_string();
+    }
+    let cut: String = line.chars().take(HEADING_CHARS).collect();
+    format!("{}...", cut.trim_end())
+}
+
+/// 코드 블록 밖의 `#`/`##` 제목을 `###` 아래로 내림
+fn demote_headings(text: &str) -> String {
+    let mut in_code_block = false;
+    let mut lines = Vec::new();
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+        }
+        let level = line.chars().take_while(|&c| c == '#').count();
+        if !in_code_block && (1..=2).contains(&level) && line[level..].starts_with(' ') {
+            lines.push(format!("##{}", line));
+        } else {
+            lines.push(line.to_string());
+        }
+    }
+    lines.join("\n")
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+/// 대화 내보내기 JSON 파싱 (ChatGPT/Claude 자동 판별, 발화가 없는 대화는 제외)
+pub fn parse_conversations(text: &str) -> Result<Vec<Conversation>> {
+    let value: serde_json::Value =
+        serde_json::from_str(text).context("Failed to parse conversation export JSON")?;
+    let items = match value {
+        serde_json::Value::Array(items) => items,
+        single @ serde_json::Value::Object(_) => vec![single],
+        _ => anyhow::bail!("Conversation export must be a JSON array"),
+    };
+
+    let mut conversations = Vec::new();
+    for (i, item) in items.into_iter().enumerate() {
+        let conversation = if item.get("mapping").is_some() {
+            let raw: ChatGptConversation = serde_json::from_value(item)
+                .with_context(|| format!("Invalid ChatGPT conversation #{}", i + 1))?;
+            raw.into_conversation()
+        } else if item.get("chat_messages").is_some() {
+            let raw: ClaudeConversation = serde_json::from_value(item)
+                .with_context(|| format!("Invalid Claude conversation #{}", i + 1))?;
+            raw.into_conversation()
+        } else {
+            anyhow::bail!(
+                "Unrecognized conversation #{} (expected ChatGPT or Claude export)",
+                i + 1
+            );
+        };
+
+        if !conversation.turns.is_empty() {
+            conversations.push(conversation);
+        }
+    }
+
+    Ok(conversations)
+}
+
+fn role(name: &str) -> Option<Role> {
+    match name {
+        "user" | "human" => Some(Role::User),
+        "assistant" => Some(Role::Assistant),
+        // system, tool 등은 제외
+        _ => None,
+    }
+}
+
+fn unix_time(secs: Option<f64>) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp(secs? as i64, 0)
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatGptConversation {
+    title: Option<String>,
+    create_time: Option<f64>,
+    id: Option<String>,
+    conversation_id: Option<String>,
+    #[serde(default)]
+    mapping: HashMap<String, ChatGptNode>,
+    current_node: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatGptNode {
+    message: Option<ChatGptMessage>,
+    parent: Option<String>,
+    #[serde(default)]
+    children: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatGptMessage {
+    author: ChatGptAuthor,
+    content: Option<ChatGptContent>,
+    create_time: Option<f64>,
+    #[serde(default)]
+    metadata: ChatGptMetadata,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatGptAuthor {
+    role: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatGptContent {
+    #[serde(default)]
+    content_type: String,
+    #[serde(default)]
+    parts: Vec<serde_json::Value>,
+    text: Option<String>,
+    language: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ChatGptMetadata {
+    model_slug: Option<String>,
+    #[serde(default)]
+    is_visually_hidden_from_conversation: bool,
+}
+
+impl ChatGptContent {
+    /// 본문 텍스트 (이미지 등 문자열이 아닌 부분과 브라우징 결과 등은 제외)
+    fn text(&self) -> Option<String> {
+        let text = match self.content_type.as_str() {
+            "text" | "multimodal_text" => self
+                .parts
+                .iter()
+                .filter_map(|part| part.as_str())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            "code" => format!(
+                "```{}\n{}\n```",
+                self.language.as_deref().unwrap_or_default(),
+                self.text.as_deref()?.trim_end()
+            ),
+            _ => return None,
+        };
+        Some(text).filter(|t| !t.trim().is_empty())
+    }
+}
+
+impl ChatGptConversation {
+    /// 현재 가지의 메시지를 처음부터 순서대로
+    fn into_conversation(mut self) -> Conversation {
+        // current_node가 없으면 루트에서 첫 자식을 따라간 끝
+        let leaf = self.current_node.take().or_else(|| {
+            let mut node = self.mapping.iter().find(|(_, n)| n.parent.is_none())?.0;
+            while let Some(child) = self.mapping.get(node)?.children.first() {
+                node = child;
+            }
+            Some(node.clone())
+        });
+
+        // 지나간 노드는 꺼내므로 잘못된 파일의 순환 참조에서도 끝남
+        let mut path = Vec::new();
+        let mut next = leaf;
+        while let Some(id) = next {
+            let Some(node) = self.mapping.remove(&id) else {
+                break;
+            };
+            next = node.parent;
+            path.push(node.message);
+        }
+        path.reverse();
+
+        let mut model = None;
+        let mut turns = Vec::new();
+        for message in path.into_iter().flatten() {
+            if message.metadata.is_visually_hidden_from_conversation {
+                continue;
+            }
+            let Some(role) = role(&message.author.role) else {
+                continue;
+            };
+            let Some(text) = message.content.as_ref().and_then(ChatGptContent::text) else {
+                continue;
+            };
+            if role == Role::Assistant && message.metadata.model_slug.is_some() {
+                model = message.metadata.model_slug;
+            }
+            turns.push(Turn {
+                role,
+                text,
+                created_at: unix_time(message.create_time),
+            });
+        }
+
+        Conversation {
+            source: ConversationSource::ChatGpt,
+            id: self.conversation_id.or(self.id).unwrap_or_default(),
+            title: self.title,
+            created_at: unix_time(self.create_time),
+            model,
+            turns,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ClaudeConversation {
+    uuid: String,
+    name: Option<String>,
+    created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    chat_messages: Vec<ClaudeMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClaudeMessage {
+    sender: String,
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    content: Vec<ClaudeContent>,
+    created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClaudeContent {
+    #[serde(rename = "type", default)]
+    kind: String,
+    text: Option<String>,
+}
+
+impl ClaudeConversation {
+    fn into_conversation(self) -> Conversation {
+        let turns = self
+            .chat_messages
+            .into_iter()
+            .filter_map(|message| {
+                let role = role(&message.sender)?;
+                // 새 형식은 content 블록, 예전 형식은 text만 있음
+                let blocks: Vec<&str> = message
+                    .content
+                    .iter()
+                    .filter(|block| block.kind == "text")
+                    .filter_map(|block| block.text.as_deref())
+                    .collect();
+                let text = if blocks.is_empty() {
+                    message.text
+                } else {
+                    blocks.join("\n\n")
+                };
+                Some(Turn {
+                    role,
+                    text,
+                    created_at: message.created_at,
+                })
+                .filter(|turn| !turn.text.trim().is_empty())
+            })
+            .collect();
+
+        Conversation {
+            source: ConversationSource::Claude,
+            id: self.uuid,
+            title: self.name,
+            created_at: self.created_at,
+            model: None,
+            turns,
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_chatgpt_export() {
+        let json = r##"[{
+            "title": "RRF 가중치",
+            "create_time": 1714557600.5,
+            "id": "c-1",
+            "conversation_id": "c-1",
+            "current_node": "a2",
+            "mapping": {
+                "root": {"id": "root", "message": null, "parent": null, "children": ["sys"]},
+                "sys": {"id": "sys", "parent": "root", "children": ["u1"],
+                        "message": {"author": {"role": "system"},
+                                    "content": {"content_type": "text", "parts": [""]},
+                                    "metadata": {"is_visually_hidden_from_conversation": true}}},
+                "u1": {"id": "u1", "parent": "sys", "children": ["a1", "a2"],
+                       "message": {"author": {"role": "user"}, "create_time": 1714557601,
+                                   "content": {"content_type": "text", "parts": ["RRF k 값은 왜 60인가요?"]}}},
+                "a1": {"id": "a1", "parent": "u1", "children": [],
+                       "message": {"author": {"role": "assistant"},
+                                   "content": {"content_type": "text", "parts": ["버려진 답변"]}}},
+                "a2": {"id": "a2", "parent": "u1", "children": [],
+                       "message": {"author": {"role": "assistant"}, "create_time": 1714557700,
+                                   "metadata": {"model_slug": "gpt-4o"},
+                                   "content": {"content_type": "text", "parts": ["# 배경\n실험적으로 정한 값입니다."]}}}
+            }
+        }]"##;
+
+        let conversations = parse_conversations(json).unwrap();
+        assert_eq!(conversations.len(), 1);
+        let conversation = &conversations[0];
+        assert_eq!(conversation.source, ConversationSource::ChatGpt);
+        assert_eq!(conversation.model.as_deref(), Some("gpt-4o"));
+        // 현재 가지만, system 메시지 제외
+        assert_eq!(conversation.turns.len(), 2);
+        assert_eq!(
+            conversation.turns[1].text,
+            "# 배경\n실험적으로 정한 값입니다."
+        );
+
+        let doc = conversation.to_document();
+        assert_eq!(doc.url, "https://chatgpt.com/c/c-1");
+        assert_eq!(doc.framework.as_deref(), Some("chatgpt"));
+        assert!(doc.content.contains("참여자: 사용자, ChatGPT (gpt-4o)"));
+        assert!(doc.content.contains("## 1. RRF k 값은 왜 60인가요?"));
+        assert!(doc.content.contains("### 배경"));
+        assert!(!doc.content.contains("버려진 답변"));
+
+        let metadata = doc.metadata.unwrap();
+        assert_eq!(metadata.published_at.unwrap().timestamp(), 1_714_557_600);
+    }
+
+    #[test]
+    fn test_parse_claude_export() {
+        let json = r#"[
+            {"uuid": "9f1c", "name": "LanceDB 압축", "created_at": "2024-05-01T10:00:00.000000Z",
+             "chat_messages": [
+                {"sender": "human", "text": "compact는 언제 해야 하나요?",
+                 "created_at": "2024-05-01T10:00:05Z"},
+                {"sender": "assistant", "text": "",
+                 "content": [{"type": "text", "text": "조각이 많아지면 하세요."},
+                             {"type": "tool_use", "name": "search"}],
+                 "created_at": "2024-05-02T09:00:00Z"},
+                {"sender": "human", "text": "기준은요?"},
+                {"sender": "assistant", "text": "32개 정도입니다."}
+             ]},
+            {"uuid": "empty", "name": "", "chat_messages": []}
+        ]"#;
+
+        let conversations = parse_conversations(json).unwrap();
+        assert_eq!(conversations.len(), 1);
+        let conversation = &conversations[0];
+        assert_eq!(conversation.turns.len(), 4);
+        assert_eq!(conversation.turns[1].text, "조각이 많아지면 하세요.");
+
+        let doc = conversation.to_document();
+        assert_eq!(doc.url, "https://claude.ai/chat/9f1c");
+        assert!(doc
+            .content
+            .contains("Claude 대화 · 2024-05-01 ~ 2024-05-02"));
+        assert!(doc.content.contains("## 2. 기준은요?"));
+        assert!(doc.content.contains("**사용자** · 2024-05-01 10:00"));
+    }
+
+    #[test]
+    fn test_turn_aware_sections() {
+        use crate::knowledge::MarkdownChunker;
+
+        let conversation = Conversation {
+            source: ConversationSource::Claude,
+            id: "x".into(),
+            title: Some("대화".into()),
+            created_at: None,
+            model: None,
+            turns: vec![
+                Turn {
+                    role: Role::User,
+                    text: "첫 질문".into(),
+                    created_at: None,
+                },
+                Turn {
+                    role: Role::Assistant,
+                    text: "첫 답변 ".repeat(40),
+                    created_at: None,
+                },
+                Turn {
+                    role: Role::User,
+                    text: "두 번째 질문".into(),
+                    created_at: None,
+                },
+                Turn {
+                    role: Role::Assistant,
+                    text: "두 번째 답변".into(),
+                    created_at: None,
+                },
+            ],
+        };
+
+        let doc = conversation.to_document();
+        let chunks = MarkdownChunker::with_defaults().chunk_with_headings(&doc.content);
+        // 두 번째 질문과 첫 답변이 같은 섹션에 섞이지 않음
+        let second = chunks
+            .iter()
+            .find(|(_, chunk)| chunk.contains("두 번째 답변"))
+            .unwrap();
+        assert_eq!(second.0.last().map(String::as_str), Some("2. 두 번째 질문"));
+    }
+
+    #[test]
+    fn test_rejects_unknown_export() {
+        assert!(parse_conversations(r#"[{"foo": 1}]"#).is_err());
+        assert!(parse_conversations("42").is_err());
+        assert_eq!(question_heading(&"가".repeat(80)).chars().count(), 63);
+    }
+}
@@ -0,0 +1,14 @@
+//! 외부 소스 커넥터 모듈
+//!
+//! 파일/URL이 아닌 외부 데이터 소스에서 레코드를 읽어 지식베이스 문서로 변환합니다.
+//! - SQL: SQLite / Postgres 쿼리 결과 행을 템플릿으로 문서화
+//! - 북마크: 브라우저 북마크 내보내기 파일 (폴더 경로를 태그로)
+//! - AI 대화: ChatGPT/Claude 대화 내보내기 (대화마다 문서 하나)
+
+pub mod bookmarks;
+pub mod conversations;
+pub mod sql;
+
+pub use bookmarks::{parse_bookmarks, Bookmark};
+pub use conversations::{parse_conversations, Conversation, ConversationSource, Role, Turn};
+pub use sql::{fetch_rows, RowTemplate, SqlConnection, SqlRow};
@@ -0,0 +1,619 @@
+//! 데몬 모듈 - `daemon`
+//!
+//! 설정 파일(`config.toml`)의 예약 작업과 감시 폴더를 한 프로세스에서 계속 실행합니다.
+//! 플랫폼별 cron/작업 스케줄러 설정 없이 유지보수(재수집, 정리, 백업)를 자동화합니다.
+//!
+//! ```toml
+//! [[jobs]]
+//! name = "nightly-compact"
+//! schedule = "0 3 * * *"
+//! action = "compact"
+//!
+//! [[jobs]]
+//! name = "docs-refresh"
+//! schedule = "@every 12h"
+//! action = "refresh"
+//! framework = "nextjs"
+//! changelog = true
+//!
+//! [[jobs]]
+//! name = "weekly-backup"
+//! schedule = "@weekly"
+//! action = "backup"
+//! keep = 4
+//!
+//! [[watch]]
+//! dir = "~/Pictures/Screenshots"
+//! ```
+//!
+//! `[limits]` 한도는 작업 실행과 감시 주기마다 따로 적용합니다 (`crate::limits` 참고).
+
+mod schedule;
+mod shutdown;
+
+pub use schedule::Schedule;
+pub use shutdown::Shutdown;
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::extractor::ContentExtractor;
+use crate::knowledge::{
+    changelog_document, copy_dir, is_backup_dir, HybridRetriever, NewDocument, Provenance,
+    BACKUP_STAMP_FORMAT, SQLITE_FILE, VECTORS_DIR,
+};
+use crate::limits::{BudgetCounter, DiskGuard};
+use crate::normalize::normalize_document;
+use crate::notify::{Notifier, NotifyEvent};
+use crate::scraper::WebScraper;
+use crate::watch::{watch_state_path, ScreenshotWatcher, SCREENSHOT_TAG};
+
+/// 백업 디렉토리 이름 (데이터 디렉토리 기준)
+pub const BACKUPS_DIR: &str = "backups";
+
+/// 데몬 루프 확인 주기
+const TICK: Duration = Duration::from_secs(1);
+
+// ============================================================================
+// Config Types
+// ============================================================================
+
+/// 예약 작업 설정 (`[[jobs]]`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobConfig {
+    /// 작업 이름 (로그/`daemon --run`에 사용)
+    pub name: String,
+    /// 실행 주기 (cron 5필드 또는 `@every 6h`)
+    pub schedule: String,
+    #[serde(flatten)]
+    pub action: JobAction,
+}
+
+/// 예약 작업 동작
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "kebab-case")]
+pub enum JobAction {
+    /// 만료 문서 삭제 + DB 최적화
+    Compact,
+    /// 저장된 웹 문서 재수집 (본문이 바뀐 문서만 다시 임베딩)
+    Refresh {
+        /// 이 프레임워크 태그의 문서만
+        #[serde(default)]
+        framework: Option<String>,
+        /// 변경 사항을 날짜별 문서로 저장
+        #[serde(default)]
+        changelog: bool,
+    },
+    /// 지정한 URL 수집 (피드/인덱스 페이지 등)
+    Ingest {
+        urls: Vec<String>,
+        #[serde(default)]
+        framework: Option<String>,
+        #[serde(default)]
+        changelog: bool,
+    },
+    /// DB와 벡터 저장소 백업
+    Backup {
+        /// 백업 위치 (기본: `~/.palank-rag/backups`)
+        #[serde(default)]
+        dir: Option<PathBuf>,
+        /// 보관할 백업 수 (오래된 것부터 삭제)
+        #[serde(default = "default_backup_keep")]
+        keep: usize,
+    },
+}
+
+fn default_backup_keep() -> usize {
+    7
+}
+
+/// 감시 폴더 설정 (`[[watch]]`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WatchConfig {
+    pub dir: PathBuf,
+    /// 수집 문서 태그
+    #[serde(default = "default_watch_tag")]
+    pub tag: String,
+    /// 확인 주기 (초)
+    #[serde(default = "default_watch_interval")]
+    pub interval_secs: u64,
+}
+
+fn default_watch_tag() -> String {
+    SCREENSHOT_TAG.to_string()
+}
+
+fn default_watch_interval() -> u64 {
+    10
+}
+
+// ============================================================================
+// Daemon
+// ============================================================================
+
+/// 예약 작업과 다음 실행 시각
+pub struct ScheduledJob {
+    pub config: JobConfig,
+    pub schedule: Schedule,
+    /// 다음 실행 시각 (None이면 더 이상 실행되지 않음)
+    pub next_run: Option<DateTime<Local>>,
+}
+
+struct ActiveWatch {
+    watcher: ScreenshotWatcher,
+    interval: Duration,
+    last_run: Option<Instant>,
+}
+
+/// 예약 작업 + 폴더 감시 데몬
+pub struct Daemon {
+    retriever: HybridRetriever,
+    data_dir: PathBuf,
+    config: Config,
+    jobs: Vec<ScheduledJob>,
+    watches: Vec<ActiveWatch>,
+    extractor: ContentExtractor,
+    notifier: Notifier,
+    shutdown: Shutdown,
+    /// 검색기 임베더의 한도 사용량 (실행마다 되돌림)
+    budget: Option<BudgetCounter>,
+}
+
+impl Daemon {
+    /// 설정으로 데몬 생성 (주기 문자열과 감시 폴더를 검증)
+    pub fn new(retriever: HybridRetriever, data_dir: &Path, config: Config) -> Result<Self> {
+        let now = Local::now();
+        let shutdown = Shutdown::new();
+        let jobs = config
+            .jobs
+            .iter()
+            .map(|job| {
+                let schedule = Schedule::parse(&job.schedule)
+                    .with_context(|| format!("Invalid schedule for job '{}'", job.name))?;
+                Ok(ScheduledJob {
+                    next_run: schedule.next_after(now),
+                    config: job.clone(),
+                    schedule,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let watches = config
+            .watch
+            .iter()
+            .map(|watch| {
+                let state_path = watch_state_path(data_dir, &watch.dir);
+                Ok(ActiveWatch {
+                    watcher: ScreenshotWatcher::new(&watch.dir, state_path)?
+                        .with_tag(watch.tag.clone())
+                        .with_content_filter(config.content_filter)
+                        .with_shutdown(shutdown.clone()),
+                    interval: Duration::from_secs(watch.interval_secs.max(1)),
+                    last_run: None,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let notifier = Notifier::new(config.notify.clone());
+        Ok(Self {
+            retriever,
+            data_dir: data_dir.to_path_buf(),
+            config,
+            jobs,
+            watches,
+            extractor: ContentExtractor::from_env(),
+            notifier,
+            shutdown,
+            budget: None,
+        })
+    }
+
+    /// 검색기에 넘긴 `EmbeddingBudget`의 사용량 지정 (작업 실행과 감시 주기마다 한도를 새로 적용)
+    pub fn with_embedding_budget(mut self, budget: BudgetCounter) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// 새 실행 시작 (임베딩 한도 사용량 초기화)
+    fn start_run(&self) {
+        if let Some(ref budget) = self.budget {
+            budget.reset();
+        }
+    }
+
+    /// 예약 작업 목록
+    pub fn jobs(&self) -> &[ScheduledJob] {
+        &self.jobs
+    }
+
+    /// 감시 폴더 목록
+    pub fn watch_dirs(&self) -> Vec<&Path> {
+        self.watches.iter().map(|w| w.watcher.dir()).collect()
+    }
+
+    /// 종료 요청 핸들
+    pub fn shutdown(&self) -> Shutdown {
+        self.shutdown.clone()
+    }
+
+    /// 종료 신호를 받을 때까지 실행 (예약 시각이 된 작업과 주기가 된 감시 폴더를 처리)
+    ///
+    /// 신호를 받으면 처리 중인 문서까지 저장하고 반환합니다.
+    pub async fn run(&mut self) -> Result<()> {
+        self.shutdown.listen_for_signals();
+
+        let mut tick = tokio::time::interval(TICK);
+        while !self.shutdown.is_requested() {
+            tokio::select! {
+                _ = tick.tick() => {}
+                _ = self.shutdown.wait() => break,
+            }
+            self.run_due_jobs().await;
+            self.run_due_watches().await;
+        }
+
+        tracing::info!("Daemon stopped");
+        Ok(())
+    }
+
+    /// 이름으로 작업 즉시 실행
+    pub async fn run_job_by_name(&self, name: &str) -> Result<String> {
+        let job = self
+            .jobs
+            .iter()
+            .find(|j| j.config.name == name)
+            .ok_or_else(|| anyhow::anyhow!("Unknown job: {}", name))?;
+        self.run_action(&job.config.action).await
+    }
+
+    async fn run_due_jobs(&mut self) {
+        let now = Local::now();
+        for i in 0..self.jobs.len() {
+            if self.shutdown.is_requested() {
+                return;
+            }
+            if self.jobs[i].next_run.is_none_or(|t| t > now) {
+                continue;
+            }
+
+            let name = self.jobs[i].config.name.clone();
+            tracing::info!("Running job '{}'", name);
+            let event = match self.run_action(&self.jobs[i].config.action).await {
+                Ok(summary) => {
+                    tracing::info!("Job '{}' finished: {}", name, summary);
+                    NotifyEvent::JobFinished { job: name, summary }
+                }
+                Err(e) => {
+                    tracing::warn!("Job '{}' failed: {:#}", name, e);
+                    NotifyEvent::JobFailed {
+                        job: name,
+                        error: format!("{:#}", e),
+                    }
+                }
+            };
+            self.notifier.notify(&event).await;
+
+            let job = &mut self.jobs[i];
+            job.next_run = job.schedule.next_after(Local::now());
+        }
+    }
+
+    async fn run_due_watches(&mut self) {
+        for watch in &mut self.watches {
+            if self.shutdown.is_requested() {
+                return;
+            }
+            if watch.last_run.is_some_and(|t| t.elapsed() < watch.interval) {
+                continue;
+            }
+            watch.last_run = Some(Instant::now());
+            if let Some(ref budget) = self.budget {
+                budget.reset();
+            }
+
+            match watch
+                .watcher
+                .ingest_new(&self.retriever, &self.extractor)
+                .await
+            {
+                Ok(pass) => {
+                    for (path, doc_id) in &pass.added {
+                        tracing::info!("Watched file ingested: {:?} (id={})", path, doc_id);
+                    }
+                    for (path, error) in &pass.failed {
+                        tracing::warn!("Watched file failed: {:?}: {}", path, error);
+                    }
+                    for (path, error) in &pass.gave_up {
+                        tracing::warn!("Watched file failed, not retrying: {:?}: {}", path, error);
+                    }
+                    for path in &pass.too_short {
+                        tracing::info!("Watched file skipped (content too short): {:?}", path);
+                    }
+                    for event in pass.events(watch.watcher.dir()) {
+                        self.notifier.notify(&event).await;
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("Watch pass failed for {:?}: {:#}", watch.watcher.dir(), e);
+                    let event = NotifyEvent::IngestFailed {
+                        source: watch.watcher.dir().display().to_string(),
+                        count: 0,
+                        error: format!("{:#}", e),
+                    };
+                    self.notifier.notify(&event).await;
+                }
+            }
+        }
+    }
+
+    /// 작업 실행 후 요약 문자열 반환
+    async fn run_action(&self, action: &JobAction) -> Result<String> {
+        self.start_run();
+        match action {
+            JobAction::Compact => {
+                let report = self.retriever.compact().await?;
+                Ok(format!("만료 문서 {} 건 삭제", report.expired_documents))
+            }
+            JobAction::Refresh {
+                framework,
+                changelog,
+            } => {
+                let urls: Vec<(String, Option<String>)> = self
+                    .retriever
+                    .store()
+                    .list_documents(i64::MAX as usize, framework.as_deref(), None)?
+                    .into_iter()
+                    // 변경 이력 등 URL 조각(#)이 붙은 파생 문서는 제외
+                    .filter(|d| d.url.starts_with("http") && !d.url.contains('#'))
+                    .map(|d| (d.url, d.framework))
+                    .collect();
+                self.refresh_urls(&urls, *changelog).await
+            }
+            JobAction::Ingest {
+                urls,
+                framework,
+                changelog,
+            } => {
+                let urls: Vec<(String, Option<String>)> = urls
+                    .iter()
+                    .map(|url| (url.clone(), framework.clone()))
+                    .collect();
+                self.refresh_urls(&urls, *changelog).await
+            }
+            JobAction::Backup { dir, keep } => {
+                let root = dir
+                    .clone()
+                    .unwrap_or_else(|| self.data_dir.join(BACKUPS_DIR));
+                let target = backup_data(&self.retriever, &self.data_dir, &root)?;
+                let removed = prune_backups(&root, (*keep).max(1))?;
+                Ok(format!(
+                    "{} 에 백업 (오래된 백업 {} 개 삭제)",
+                    target.display(),
+                    removed
+                ))
+            }
+        }
+    }
+
+    /// URL 목록을 다시 스크랩하여 본문이 바뀐 문서만 저장
+    ///
+    /// 실행당 페이지 수와 저장소 증가량(`[limits]`)을 넘으면 `LimitExceeded`로 중단합니다.
+    /// 본문이 `[content_filter]`보다 짧은 페이지는 기존 문서를 덮어쓰지 않고 건너뜁니다.
+    async fn refresh_urls(
+        &self,
+        urls: &[(String, Option<String>)],
+        changelog: bool,
+    ) -> Result<String> {
+        self.config.limits.check_pages(urls.len())?;
+        let disk = DiskGuard::new(&self.data_dir, self.config.limits.max_disk_growth_mb);
+        let scraper = WebScraper::with_policy(self.config.url_policy.clone())?
+            .with_titles(self.config.titles.clone());
+        let (mut updated, mut unchanged, mut too_short, mut failed) = (0, 0, 0, 0);
+
+        for (url, framework) in urls {
+            if self.shutdown.is_requested() {
+                return Ok(format!(
+                    "종료 요청으로 중단 (갱신 {}, 변경 없음 {}, 짧음 {}, 실패 {})",
+                    updated, unchanged, too_short, failed
+                ));
+            }
+
+            let scraped = match scraper.scrape(url).await {
+                Ok(scraped) => scraped,
+                Err(e) => {
+                    tracing::warn!("Refresh failed for {}: {:#}", url, e);
+                    failed += 1;
+                    continue;
+                }
+            };
+            if !self.config.content_filter.accepts(&scraped.content) {
+                tracing::warn!("Refresh skipped for {}: content too short", url);
+                too_short += 1;
+                continue;
+            }
+
+            let content = match scraped.title {
+                Some(ref title) => format!("# {}\n\n{}", title, scraped.content),
+                None => scraped.content,
+            };
+            // 저장된 본문은 정규화되어 있으므로 같은 규칙으로 비교
+            let content = normalize_document(&content);
+            let previous = self.retriever.store().get_by_url(url)?;
+            if previous.as_ref().is_some_and(|p| p.content == content) {
+                unchanged += 1;
+                continue;
+            }
+
+            let doc = NewDocument {
+                url: url.clone(),
+                title: scraped.title,
+                content,
+                framework: framework.clone(),
+                expires_at: previous.as_ref().and_then(|p| p.expires_at),
+                citation: None,
+                metadata: (!scraped.metadata.is_empty()).then_some(scraped.metadata),
+                provenance: Some(Provenance::current("daemon").with_extractor("scraper")),
+            };
+            let changes = match (changelog, previous) {
+                (true, Some(ref previous)) => changelog_document(previous, &doc, Utc::now()),
+                _ => None,
+            };
+
+            self.retriever.add_document(doc).await?;
+            if let Some(changes) = changes {
+                self.retriever.add_document(changes).await?;
+            }
+            updated += 1;
+            disk.check()?;
+        }
+
+        Ok(format!(
+            "갱신 {}, 변경 없음 {}, 짧음 {}, 실패 {}",
+            updated, unchanged, too_short, failed
+        ))
+    }
+}
+
+// ============================================================================
+// Backup
+// ============================================================================
+
+/// DB 스냅샷과 벡터 저장소를 `<root>/<시각>/`에 복사
+pub fn backup_data(retriever: &HybridRetriever, data_dir: &Path, root: &Path) -> Result<PathBuf> {
+    let target = root.join(Local::now().format(BACKUP_STAMP_FORMAT).to_string());
+    std::fs::create_dir_all(&target)
+        .with_context(|| format!("Failed to create backup directory: {:?}", target))?;
+
+    retriever.store().backup_to(&target.join(SQLITE_FILE))?;
+
+    let vectors = data_dir.join(VECTORS_DIR);
+    if vectors.exists() {
+        copy_dir(&vectors, &target.join(VECTORS_DIR))?;
+    }
+
+    Ok(target)
+}
+
+/// 최근 `keep`개만 남기고 오래된 백업 삭제 (이름이 시각이라 이름순 = 시간순)
+///
+/// 이름이 백업 시각 형식인 디렉토리만 대상이며, 백업 위치의 다른 디렉토리는 건드리지 않습니다.
+pub fn prune_backups(root: &Path, keep: usize) -> Result<usize> {
+    let mut backups: Vec<PathBuf> = std::fs::read_dir(root)
+        .with_context(|| format!("Failed to read backup directory: {:?}", root))?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| is_backup_dir(p))
+        .collect();
+    backups.sort();
+
+    let excess = backups.len().saturating_sub(keep);
+    for old in &backups[..excess] {
+        std::fs::remove_dir_all(old)
+            .with_context(|| format!("Failed to remove old backup: {:?}", old))?;
+    }
+
+    Ok(excess)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_jobs_config() {
+        let config: Config = toml::from_str(
+            r#"
+            [[jobs]]
+            name = "compact"
+            schedule = "0 3 * * *"
+            action = "compact"
+
+            [[jobs]]
+            name = "docs"
+            schedule = "@every 12h"
+            action = "refresh"
+            framework = "nextjs"
+
+            [[jobs]]
+            name = "backup"
+            schedule = "@weekly"
+            action = "backup"
+
+            [[watch]]
+            dir = "/tmp/shots"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.jobs.len(), 3);
+        assert_eq!(config.jobs[0].action, JobAction::Compact);
+        assert_eq!(
+            config.jobs[1].action,
+            JobAction::Refresh {
+                framework: Some("nextjs".to_string()),
+                changelog: false
+            }
+        );
+        assert_eq!(
+            config.jobs[2].action,
+            JobAction::Backup { dir: None, keep: 7 }
+        );
+        assert_eq!(config.watch[0].tag, "screenshot");
+        assert_eq!(config.watch[0].interval_secs, 10);
+    }
+
+    #[test]
+    fn test_prune_backups() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in [
+            "20260101-030000",
+            "20260108-030000",
+            "20260115-030000",
+            "0-photos",
+        ] {
+            std::fs::create_dir(dir.path().join(name)).unwrap();
+        }
+
+        assert_eq!(prune_backups(dir.path(), 2).unwrap(), 1);
+        assert!(!dir.path().join("20260101-030000").exists());
+        assert!(dir.path().join("20260115-030000").exists());
+        // 백업이 아닌 디렉토리는 남김
+        assert!(dir.path().join("0-photos").exists());
+        assert_eq!(prune_backups(dir.path(), 2).unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_respects_page_limit() {
+        use crate::limits::LimitExceeded;
+        use crate::test_support::MockEmbedding;
+
+        let dir = tempfile::tempdir().unwrap();
+        let retriever = HybridRetriever::with_embedder(dir.path(), Box::new(MockEmbedding::new()))
+            .await
+            .unwrap();
+        let mut config = Config::default();
+        config.limits.max_pages = 1;
+        let daemon = Daemon::new(retriever, dir.path(), config).unwrap();
+
+        // 가져오기 전에 한도를 검사하므로 네트워크 요청 없이 실패
+        let action = JobAction::Ingest {
+            urls: vec![
+                "https://example.com/a".to_string(),
+                "https://example.com/b".to_string(),
+            ],
+            framework: None,
+            changelog: false,
+        };
+        let err = daemon.run_action(&action).await.unwrap_err();
+        assert!(LimitExceeded::is_cause_of(&err));
+    }
+}
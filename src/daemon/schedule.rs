@@ -0,0 +1,256 @@
+//! 작업 실행 주기 - cron 5필드 또는 `@every` 간격
+//!
+//! - `분 시 일 월 요일` (예: `0 3 * * *`, `*/15 9-18 * * 1-5`)
+//! - 별칭: `@hourly`, `@daily`, `@weekly`, `@monthly`
+//! - 고정 간격: `@every 30m`, `@every 6h`, `@every 1d`
+//!
+//! cron 시각은 로컬 시간대 기준입니다. 일/요일이 모두 지정되면 둘 중 하나만 맞아도 실행합니다.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Duration, Local, TimeZone, Timelike};
+
+/// 다음 실행 시각 탐색 한도 (4년, 2월 29일 같은 드문 조합 포함)
+const MAX_SEARCH_DAYS: i64 = 366 * 4;
+
+/// 실행 주기
+#[derive(Debug, Clone, PartialEq)]
+pub enum Schedule {
+    /// cron 5필드
+    Cron(CronFields),
+    /// 고정 간격 (이전 실행 기준)
+    Every(Duration),
+}
+
+/// cron 필드별 허용 값 (비트마스크)
+#[derive(Debug, Clone, PartialEq)]
+pub struct CronFields {
+    minutes: u64,
+    hours: u32,
+    days: u32,
+    months: u16,
+    weekdays: u8,
+    /// 일 필드가 `*`가 아닌지
+    days_restricted: bool,
+    /// 요일 필드가 `*`가 아닌지
+    weekdays_restricted: bool,
+}
+
+impl Schedule {
+    /// 주기 문자열 파싱
+    pub fn parse(spec: &str) -> Result<Self> {
+        let spec = spec.trim();
+
+        if let Some(interval) = spec.strip_prefix("@every") {
+            let interval = parse_interval(interval.trim())
+                .with_context(|| format!("Invalid interval in schedule: {}", spec))?;
+            return Ok(Self::Every(interval));
+        }
+
+        let expanded = match spec {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            other => other,
+        };
+
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields.as_slice() else {
+            anyhow::bail!("Schedule must have 5 fields (분 시 일 월 요일): {}", spec);
+        };
+
+        let context = || format!("Invalid schedule: {}", spec);
+        Ok(Self::Cron(CronFields {
+            minutes: parse_field(minute, 0, 59).with_context(context)?,
+            hours: parse_field(hour, 0, 23).with_context(context)? as u32,
+            days: parse_field(day, 1, 31).with_context(context)? as u32,
+            months: parse_field(month, 1, 12).with_context(context)? as u16,
+            // 7도 일요일로 허용
+            weekdays: {
+                let mask = parse_field(weekday, 0, 7).with_context(context)?;
+                ((mask | (mask >> 7)) & 0x7f) as u8
+            },
+            days_restricted: *day != "*",
+            weekdays_restricted: *weekday != "*",
+        }))
+    }
+
+    /// `after` 이후의 다음 실행 시각
+    ///
+    /// `@every`는 `after`에 간격을 더한 시각입니다.
+    pub fn next_after(&self, after: DateTime<Local>) -> Option<DateTime<Local>> {
+        match self {
+            Self::Every(interval) => Some(after + *interval),
+            Self::Cron(fields) => fields.next_after(after),
+        }
+    }
+}
+
+impl CronFields {
+    fn matches_day(&self, t: &DateTime<Local>) -> bool {
+        let day = self.days & (1 << t.day()) != 0;
+        let weekday = self.weekdays & (1 << t.weekday().num_days_from_sunday()) != 0;
+        match (self.days_restricted, self.weekdays_restricted) {
+            (true, true) => day || weekday,
+            _ => day && weekday,
+        }
+    }
+
+    fn next_after(&self, after: DateTime<Local>) -> Option<DateTime<Local>> {
+        // 다음 분의 0초부터 탐색
+        let mut t = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let limit = after + Duration::days(MAX_SEARCH_DAYS);
+
+        while t <= limit {
+            if self.months & (1 << t.month()) == 0 {
+                // 다음 달 1일 00:00
+                let (year, month) = if t.month() == 12 {
+                    (t.year() + 1, 1)
+                } else {
+                    (t.year(), t.month() + 1)
+                };
+                t = local_midnight(year, month, 1)?;
+                continue;
+            }
+            if !self.matches_day(&t) {
+                t = local_midnight(t.year(), t.month(), t.day())? + Duration::days(1);
+                continue;
+            }
+            if self.hours & (1 << t.hour()) == 0 {
+                t = t.with_minute(0)? + Duration::hours(1);
+                continue;
+            }
+            if self.minutes & (1 << t.minute()) == 0 {
+                t += Duration::minutes(1);
+                continue;
+            }
+            return Some(t);
+        }
+
+        None
+    }
+}
+
+/// 로컬 자정 (DST로 자정이 없으면 그 이후 가장 이른 시각)
+fn local_midnight(year: i32, month: u32, day: u32) -> Option<DateTime<Local>> {
+    let date = chrono::NaiveDate::from_ymd_opt(year, month, day)?;
+    (0..3).find_map(|hour| {
+        Local
+            .from_local_datetime(&date.and_hms_opt(hour, 0, 0)?)
+            .earliest()
+    })
+}
+
+/// cron 필드 하나를 비트마스크로 (`*`, `5`, `1-5`, `*/15`, `1-10/2`, `1,15,30`)
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64> {
+    let mut mask = 0u64;
+
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>().context("Invalid step")?),
+            None => (part, 1),
+        };
+        if step == 0 {
+            anyhow::bail!("Step must be positive: {}", part);
+        }
+
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((a, b)) => (a.parse()?, b.parse()?),
+                // `5/10`은 5부터 끝까지
+                None if step > 1 => (range.parse()?, max),
+                None => {
+                    let value = range.parse()?;
+                    (value, value)
+                }
+            },
+        };
+        if start < min || end > max || start > end {
+            anyhow::bail!("Value out of range {}-{}: {}", min, max, part);
+        }
+
+        for value in (start..=end).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+
+    Ok(mask)
+}
+
+/// 간격 파싱 (`30s`, `15m`, `6h`, `1d`, `2w`)
+fn parse_interval(s: &str) -> Result<Duration> {
+    let split = s
+        .find(|c: char| !c.is_ascii_digit())
+        .context("Missing unit (s, m, h, d, w)")?;
+    let (value, unit) = s.split_at(split);
+    let value: i64 = value.parse().context("Missing interval value")?;
+    if value <= 0 {
+        anyhow::bail!("Interval must be positive");
+    }
+
+    Ok(match unit {
+        "s" => Duration::seconds(value),
+        "m" => Duration::minutes(value),
+        "h" => Duration::hours(value),
+        "d" => Duration::days(value),
+        "w" => Duration::weeks(value),
+        _ => anyhow::bail!("Unknown unit: {} (s, m, h, d, w)", unit),
+    })
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local(s: &str) -> DateTime<Local> {
+        let naive = chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap();
+        Local.from_local_datetime(&naive).earliest().unwrap()
+    }
+
+    fn next(spec: &str, after: &str) -> String {
+        Schedule::parse(spec)
+            .unwrap()
+            .next_after(local(after))
+            .unwrap()
+            .format("%Y-%m-%d %H:%M")
+            .to_string()
+    }
+
+    #[test]
+    fn test_cron_next_after() {
+        assert_eq!(next("0 3 * * *", "2026-03-10 02:59"), "2026-03-10 03:00");
+        assert_eq!(next("0 3 * * *", "2026-03-10 03:00"), "2026-03-11 03:00");
+        assert_eq!(next("*/15 * * * *", "2026-03-10 10:07"), "2026-03-10 10:15");
+        // 2026-03-14는 토요일 → 월요일 09:00
+        assert_eq!(
+            next("0 9-18 * * 1-5", "2026-03-13 18:30"),
+            "2026-03-16 09:00"
+        );
+        assert_eq!(next("@monthly", "2026-12-15 00:00"), "2027-01-01 00:00");
+        // 일/요일 모두 지정: 13일 또는 금요일
+        assert_eq!(next("0 0 13 * 5", "2026-03-01 00:00"), "2026-03-06 00:00");
+        // 7은 일요일
+        assert_eq!(next("0 0 * * 7", "2026-03-10 00:00"), "2026-03-15 00:00");
+    }
+
+    #[test]
+    fn test_every_and_invalid() {
+        assert_eq!(
+            Schedule::parse("@every 6h").unwrap(),
+            Schedule::Every(Duration::hours(6))
+        );
+        assert!(Schedule::parse("0 3 * *").is_err());
+        assert!(Schedule::parse("60 * * * *").is_err());
+        assert!(Schedule::parse("*/0 * * * *").is_err());
+        assert!(Schedule::parse("@every 10").is_err());
+        assert!(Schedule::parse("0 0 30 2 *")
+            .unwrap()
+            .next_after(Local::now())
+            .is_none());
+    }
+}
@@ -0,0 +1,106 @@
+//! 종료 신호 처리
+//!
+//! Ctrl+C(SIGINT) 또는 SIGTERM을 받으면 새 작업을 시작하지 않고, 처리 중인 문서는
+//! 끝까지 저장한 뒤 루프를 빠져나옵니다. 문서 단위로 멈추므로 SQLite에는 있고
+//! 벡터는 없는 반쪽 문서가 남지 않습니다. 두 번째 신호는 즉시 종료합니다.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use tokio::sync::Notify;
+
+/// 종료 요청 핸들 (복제하여 작업 루프에 전달)
+#[derive(Debug, Clone, Default)]
+pub struct Shutdown {
+    requested: Arc<AtomicBool>,
+    notify: Arc<Notify>,
+}
+
+impl Shutdown {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 종료 신호 감시 태스크 시작
+    pub fn listen_for_signals(&self) {
+        let shutdown = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = wait_for_signal().await {
+                tracing::warn!("Failed to listen for shutdown signals: {}", e);
+                return;
+            }
+            tracing::info!("Shutdown requested, finishing in-flight work (repeat to force)");
+            shutdown.request();
+
+            if wait_for_signal().await.is_ok() {
+                tracing::warn!("Forced shutdown");
+                std::process::exit(130);
+            }
+        });
+    }
+
+    /// 종료 요청
+    pub fn request(&self) {
+        self.requested.store(true, Ordering::SeqCst);
+        self.notify.notify_waiters();
+    }
+
+    /// 종료가 요청되었는지 (문서 사이마다 확인)
+    pub fn is_requested(&self) -> bool {
+        self.requested.load(Ordering::SeqCst)
+    }
+
+    /// 종료 요청까지 대기
+    pub async fn wait(&self) {
+        loop {
+            // 확인 전에 등록해야 그 사이의 요청을 놓치지 않음
+            let notified = self.notify.notified();
+            if self.is_requested() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+#[cfg(unix)]
+async fn wait_for_signal() -> std::io::Result<()> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut terminate = signal(SignalKind::terminate())?;
+    tokio::select! {
+        result = tokio::signal::ctrl_c() => result,
+        _ = terminate.recv() => Ok(()),
+    }
+}
+
+#[cfg(not(unix))]
+async fn wait_for_signal() -> std::io::Result<()> {
+    tokio::signal::ctrl_c().await
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_wait_returns_after_request() {
+        let shutdown = Shutdown::new();
+        let waiter = tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move { shutdown.wait().await }
+        });
+
+        assert!(!shutdown.is_requested());
+        shutdown.request();
+        waiter.await.unwrap();
+
+        // 이미 요청된 뒤의 대기는 바로 반환
+        shutdown.wait().await;
+        assert!(shutdown.is_requested());
+    }
+}
@@ -0,0 +1,309 @@
+//! 임베딩 프로바이더 서킷 브레이커
+//!
+//! API가 죽었을 때 파일마다 재시도/백오프 주기를 끝까지 도는 것을 막습니다.
+//! 연속 실패가 `threshold`회에 이르면 회로를 열어(open) `cooldown` 동안 요청 없이
+//! 바로 실패시키고, 그 뒤 한 번의 시험 요청(half-open)이 성공하면 다시 닫습니다.
+//! 할당량 소진(429)과 요청 오류(4xx - 잘못된 키/요청)는 API가 응답한 것이므로 장애로 세지 않습니다.
+//!
+//! 하나의 브레이커를 검색기 전체가 공유하므로 수집 파이프라인 전체에 적용됩니다.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use thiserror::Error;
+
+use super::{EmbeddingApiError, EmbeddingProvider, QuotaExhausted};
+
+/// 회로를 여는 연속 실패 횟수
+const DEFAULT_THRESHOLD: u32 = 5;
+
+/// 회로가 열린 뒤 시험 요청까지 기다리는 시간
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// 회로가 열려 요청하지 않고 실패
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Embedding API unavailable after {failures} consecutive failures (circuit open, next probe in {}s)", retry_in.as_secs())]
+pub struct CircuitOpen {
+    /// 회로를 연 연속 실패 횟수
+    pub failures: u32,
+    /// 다음 시험 요청까지 남은 시간
+    pub retry_in: Duration,
+}
+
+impl CircuitOpen {
+    /// 오류 체인에 회로 열림이 있는지 (context로 감싼 경우 포함)
+    pub fn is_cause_of(error: &anyhow::Error) -> bool {
+        error.chain().any(|e| e.downcast_ref::<Self>().is_some())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    /// 정상 (연속 실패 횟수)
+    Closed { failures: u32 },
+    /// 차단 (이 시각까지)
+    Open { until: Instant },
+    /// 시험 요청 진행 중
+    HalfOpen,
+}
+
+/// 서킷 브레이커로 감싼 임베딩 프로바이더
+pub struct CircuitBreaker<P> {
+    inner: P,
+    state: Mutex<State>,
+    threshold: u32,
+    cooldown: Duration,
+}
+
+impl<P: EmbeddingProvider> CircuitBreaker<P> {
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            state: Mutex::new(State::Closed { failures: 0 }),
+            threshold: DEFAULT_THRESHOLD,
+            cooldown: DEFAULT_COOLDOWN,
+        }
+    }
+
+    /// 회로를 여는 연속 실패 횟수 지정
+    pub fn with_threshold(mut self, threshold: u32) -> Self {
+        self.threshold = threshold.max(1);
+        self
+    }
+
+    /// 시험 요청까지 대기 시간 지정
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// 회로가 열려 있는지
+    pub fn is_open(&self) -> bool {
+        !matches!(*self.state.lock().unwrap(), State::Closed { .. })
+    }
+
+    /// 요청 허용 여부 (열린 회로의 대기 시간이 지났으면 시험 요청 1회 허용)
+    ///
+    /// 시험 요청이면 `ProbeGuard`를 돌려주어, 결과를 기록하기 전에 취소되어도
+    /// 회로가 half-open에 머물지 않게 합니다.
+    fn acquire(&self) -> Result<ProbeGuard<'_>, CircuitOpen> {
+        let mut state = self.state.lock().unwrap();
+        match *state {
+            State::Closed { .. } => Ok(ProbeGuard { state: None }),
+            State::Open { until } if Instant::now() >= until => {
+                tracing::info!("Embedding circuit half-open, probing API");
+                *state = State::HalfOpen;
+                Ok(ProbeGuard {
+                    state: Some(&self.state),
+                })
+            }
+            State::Open { until } => Err(CircuitOpen {
+                failures: self.threshold,
+                retry_in: until.saturating_duration_since(Instant::now()),
+            }),
+            State::HalfOpen => Err(CircuitOpen {
+                failures: self.threshold,
+                retry_in: Duration::ZERO,
+            }),
+        }
+    }
+
+    /// 요청 결과 기록
+    fn record<T>(&self, result: &Result<T>) {
+        let mut state = self.state.lock().unwrap();
+
+        // 할당량 소진과 요청 오류(4xx)는 API가 응답한 것이므로 장애로 보지 않음
+        let failed = matches!(result, Err(e) if !QuotaExhausted::is_cause_of(e)
+            && !EmbeddingApiError::find(e).is_some_and(EmbeddingApiError::is_client_error));
+        if !failed {
+            if *state == State::HalfOpen {
+                tracing::info!("Embedding circuit closed, API recovered");
+            }
+            *state = State::Closed { failures: 0 };
+            return;
+        }
+
+        let failures = match *state {
+            State::Closed { failures } => failures + 1,
+            _ => self.threshold,
+        };
+        *state = if failures >= self.threshold {
+            tracing::warn!(
+                "Embedding circuit open after {} consecutive failures, pausing {:?}",
+                failures,
+                self.cooldown
+            );
+            State::Open {
+                until: Instant::now() + self.cooldown,
+            }
+        } else {
+            State::Closed { failures }
+        };
+    }
+}
+
+/// 진행 중인 시험 요청 (결과 기록 전에 drop되면 다음 요청이 다시 시험하도록 되돌림)
+///
+/// 시간 예산(`time_budget_ms`) 초과 등으로 embed future가 취소되면 `record`가 호출되지 않아
+/// 회로가 half-open에 머물러 이후 요청이 모두 `CircuitOpen`으로 실패하는 것을 막습니다.
+struct ProbeGuard<'a> {
+    state: Option<&'a Mutex<State>>,
+}
+
+impl ProbeGuard<'_> {
+    /// 결과를 기록했으므로 되돌리지 않음
+    fn complete(mut self) {
+        self.state = None;
+    }
+}
+
+impl Drop for ProbeGuard<'_> {
+    fn drop(&mut self) {
+        let Some(state) = self.state else {
+            return;
+        };
+        if let Ok(mut state) = state.lock() {
+            if *state == State::HalfOpen {
+                tracing::debug!("Embedding circuit probe cancelled, allowing a new probe");
+                *state = State::Open {
+                    until: Instant::now(),
+                };
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<P: EmbeddingProvider> EmbeddingProvider for CircuitBreaker<P> {
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        let probe = self.acquire()?;
+        let result = self.inner.embed(text).await;
+        self.record(&result);
+        probe.complete();
+        result
+    }
+
+    // embed_batch는 기본 구현(텍스트마다 embed)을 사용하여 텍스트마다 회로를 확인
+
+    fn dimension(&self) -> usize {
+        self.inner.dimension()
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn model_version(&self) -> String {
+        self.inner.model_version()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    /// 호출 수를 세고 `healthy`가 false면 실패하는 프로바이더 (`"bad"`는 400, `"hang"`은 무한 대기)
+    #[derive(Default)]
+    struct FlakyProvider {
+        healthy: AtomicBool,
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl EmbeddingProvider for FlakyProvider {
+        async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if text == "hang" {
+                std::future::pending::<()>().await;
+            }
+            if text == "bad" {
+                return Err(EmbeddingApiError {
+                    status: 400,
+                    message: "INVALID_ARGUMENT: bad request".to_string(),
+                }
+                .into());
+            }
+            if self.healthy.load(Ordering::SeqCst) {
+                Ok(vec![1.0])
+            } else {
+                anyhow::bail!("connection refused")
+            }
+        }
+
+        fn dimension(&self) -> usize {
+            1
+        }
+
+        fn name(&self) -> &str {
+            "flaky"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_opens_after_threshold() {
+        let breaker = CircuitBreaker::new(FlakyProvider::default()).with_threshold(2);
+
+        assert!(breaker.embed("a").await.is_err());
+        assert!(!breaker.is_open());
+        assert!(breaker.embed("b").await.is_err());
+        assert!(breaker.is_open());
+
+        // 열린 동안은 프로바이더를 호출하지 않음
+        let err = breaker.embed("c").await.unwrap_err();
+        assert!(CircuitOpen::is_cause_of(&err));
+        assert_eq!(breaker.inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_half_open_probe() {
+        let breaker = CircuitBreaker::new(FlakyProvider::default())
+            .with_threshold(1)
+            .with_cooldown(Duration::ZERO);
+
+        assert!(breaker.embed("a").await.is_err());
+        assert!(breaker.is_open());
+
+        // 시험 요청 실패 → 다시 열림
+        assert!(!CircuitOpen::is_cause_of(
+            &breaker.embed("b").await.unwrap_err()
+        ));
+        assert!(breaker.is_open());
+
+        // 시험 요청 성공 → 닫힘
+        breaker.inner.healthy.store(true, Ordering::SeqCst);
+        assert!(breaker.embed("c").await.is_ok());
+        assert!(!breaker.is_open());
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_probe_allows_new_probe() {
+        let breaker = CircuitBreaker::new(FlakyProvider::default())
+            .with_threshold(1)
+            .with_cooldown(Duration::ZERO);
+        assert!(breaker.embed("a").await.is_err());
+
+        // 시험 요청이 시간 초과로 취소됨
+        let probe = tokio::time::timeout(Duration::from_millis(10), breaker.embed("hang")).await;
+        assert!(probe.is_err());
+
+        breaker.inner.healthy.store(true, Ordering::SeqCst);
+        assert!(breaker.embed("b").await.is_ok());
+        assert!(!breaker.is_open());
+    }
+
+    #[tokio::test]
+    async fn test_client_errors_do_not_open() {
+        let breaker = CircuitBreaker::new(FlakyProvider::default()).with_threshold(1);
+
+        let err = breaker.embed("bad").await.unwrap_err();
+        assert!(EmbeddingApiError::find(&err).is_some());
+        assert!(!breaker.is_open());
+    }
+}
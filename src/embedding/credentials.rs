@@ -0,0 +1,59 @@
+//! API 키 보관 - OS 키링
+//!
+//! 환경변수 대신 OS 키링(macOS Keychain, Windows Credential Manager,
+//! Linux keyutils/Secret Service)에 Gemini API 키를 저장합니다.
+//! 공용 머신에서 셸 프로필이나 프로세스 환경에 키가 남지 않도록 하기 위함입니다.
+
+use anyhow::{Context, Result};
+
+/// 키링 서비스 이름
+const KEYRING_SERVICE: &str = "palank-rag";
+
+/// 키링 계정 이름
+const KEYRING_USER: &str = "gemini-api-key";
+
+/// 설정하면 키링을 조회하지 않는 환경변수 (테스트, 키링 접근이 느린 헤드리스 환경용)
+pub const KEYRING_DISABLE_ENV_VAR: &str = "PALANK_RAG_NO_KEYRING";
+
+/// 키링 엔트리
+fn entry() -> Result<keyring::Entry> {
+    keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER).context("Failed to access OS keyring")
+}
+
+/// 키링에 API 키 저장 (기존 값 덮어쓰기)
+pub fn store_api_key(key: &str) -> Result<()> {
+    entry()?
+        .set_password(key)
+        .context("Failed to store API key in OS keyring")
+}
+
+/// 키링에서 API 키 삭제
+///
+/// # Returns
+/// 삭제했으면 true, 저장된 키가 없었으면 false
+pub fn remove_api_key() -> Result<bool> {
+    match entry()?.delete_credential() {
+        Ok(()) => Ok(true),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(e).context("Failed to remove API key from OS keyring"),
+    }
+}
+
+/// 키링에 저장된 API 키 조회
+///
+/// `PALANK_RAG_NO_KEYRING`이 설정되었거나 키링을 사용할 수 없는 환경(헤드리스 서버 등)에서는
+/// None을 반환합니다.
+pub fn keyring_api_key() -> Option<String> {
+    if std::env::var_os(KEYRING_DISABLE_ENV_VAR).is_some_and(|v| !v.is_empty()) {
+        return None;
+    }
+
+    match entry().and_then(|e| e.get_password().map_err(Into::into)) {
+        Ok(key) if !key.is_empty() => Some(key),
+        Ok(_) => None,
+        Err(e) => {
+            tracing::debug!("No API key in OS keyring: {}", e);
+            None
+        }
+    }
+}
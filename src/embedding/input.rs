@@ -0,0 +1,259 @@
+//! 임베딩 입력 길이 검사 및 분할
+//!
+//! gemini-embedding-001은 입력이 2048 토큰을 넘으면 요청 전체를 거부합니다.
+//! 요청 전에 토큰 수를 보수적으로 추정하여, 넘치는 청크는 문단 → 줄 → 단어 → 문자
+//! 경계 순으로 나누어 각각 임베딩한 뒤 길이 가중 평균(L2 정규화)으로 하나의 벡터를 만듭니다.
+//! 다른 처리 방식은 `OversizeStrategy`로 고를 수 있습니다 (`ChunkConfig::oversize`).
+//! 토큰 수는 넘겨받은 `Tokenizer`로 셉니다 (`ChunkConfig::tokenizer`, 기본 `GeminiEstimator`).
+
+use serde::{Deserialize, Serialize};
+
+use crate::tokens::{count_tokens, Tokenizer};
+
+/// gemini-embedding-001 입력 토큰 한도
+/// source: https://ai.google.dev/gemini-api/docs/models#gemini-embedding
+pub const MAX_INPUT_TOKENS: usize = 2048;
+
+/// 분할 경계 (큰 단위부터)
+const SEPARATORS: [&str; 3] = ["\n\n", "\n", " "];
+
+/// 입력 한도를 넘는 청크 처리 방식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OversizeStrategy {
+    /// 나누어 임베딩한 뒤 길이 가중 평균 (청크 하나에 벡터 하나)
+    #[default]
+    Average,
+    /// 한도 이내의 하위 청크로 나누어 각각 저장
+    Split,
+    /// 앞부분을 잘라내고 뒷부분만 임베딩
+    TruncateHead,
+    /// 뒷부분을 잘라내고 앞부분만 임베딩
+    TruncateTail,
+    /// Gemini로 요약한 뒤 요약문을 임베딩 (저장되는 청크 본문은 원문)
+    Summarize,
+}
+
+/// 토큰 수 추정 (`crate::tokens::count_tokens`와 같은 보수적 기준)
+///
+/// 실제 토크나이저보다 크게 잡아 API 거부를 피합니다.
+pub fn estimate_tokens(text: &str) -> usize {
+    count_tokens(text)
+}
+
+/// 토큰 한도에 맞게 텍스트 분할 (한도 이내면 그대로 1개)
+pub fn split_for_embedding(
+    text: &str,
+    max_tokens: usize,
+    tokenizer: &dyn Tokenizer,
+) -> Vec<String> {
+    split_level(text, max_tokens, &SEPARATORS, tokenizer)
+        .into_iter()
+        .filter(|piece| !piece.trim().is_empty())
+        .collect()
+}
+
+fn split_level(
+    text: &str,
+    max_tokens: usize,
+    separators: &[&str],
+    tokenizer: &dyn Tokenizer,
+) -> Vec<String> {
+    if tokenizer.fits(text, max_tokens) {
+        return vec![text.to_string()];
+    }
+    let Some((separator, finer)) = separators.split_first() else {
+        return hard_split(text, max_tokens, tokenizer);
+    };
+
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+
+    for part in text.split(separator) {
+        let candidate = if current.is_empty() {
+            part.to_string()
+        } else {
+            format!("{}{}{}", current, separator, part)
+        };
+        if tokenizer.fits(&candidate, max_tokens) {
+            current = candidate;
+            continue;
+        }
+
+        if !current.is_empty() {
+            pieces.push(std::mem::take(&mut current));
+        }
+        if tokenizer.fits(part, max_tokens) {
+            current = part.to_string();
+        } else {
+            pieces.extend(split_level(part, max_tokens, finer, tokenizer));
+        }
+    }
+    if !current.is_empty() {
+        pieces.push(current);
+    }
+
+    pieces
+}
+
+/// 경계 없이 문자 단위로 자르기 (공백 없는 긴 문자열 등)
+///
+/// 조각마다 한도에 맞는 가장 긴 앞부분을 찾으므로 문자마다 전체를 다시 세지 않습니다.
+fn hard_split(text: &str, max_tokens: usize, tokenizer: &dyn Tokenizer) -> Vec<String> {
+    let mut pieces = Vec::new();
+    let mut rest = text;
+
+    while let Some(first) = rest.chars().next() {
+        // 한 글자도 한도에 맞지 않으면 한 글자씩 (진행 보장)
+        let end = match fitting_prefix(rest, max_tokens, tokenizer) {
+            0 => first.len_utf8(),
+            end => end,
+        };
+        pieces.push(rest[..end].to_string());
+        rest = &rest[end..];
+    }
+
+    pieces
+}
+
+/// 토큰 한도에 맞는 가장 긴 앞부분의 바이트 길이 (문자 경계)
+///
+/// 길이를 두 배씩 늘려 한도를 넘는 지점을 찾은 뒤 그 안에서 이분 탐색하므로,
+/// 토큰 계산 비용은 텍스트 전체가 아니라 찾은 앞부분 길이에 비례합니다.
+fn fitting_prefix(text: &str, max_tokens: usize, tokenizer: &dyn Tokenizer) -> usize {
+    let mut chars = text.char_indices().map(|(i, c)| i + c.len_utf8());
+    let mut ends: Vec<usize> = Vec::new();
+    let mut probe = 1;
+
+    loop {
+        ends.extend(chars.by_ref().take(probe - ends.len()));
+        let Some(&last) = ends.last() else {
+            return 0;
+        };
+        if !tokenizer.fits(&text[..last], max_tokens) {
+            break;
+        }
+        if last == text.len() {
+            return last;
+        }
+        probe *= 2;
+    }
+
+    let count = ends.partition_point(|&end| tokenizer.fits(&text[..end], max_tokens));
+    match count {
+        0 => 0,
+        n => ends[n - 1],
+    }
+}
+
+/// 토큰 한도에 맞게 앞부분을 잘라냄 (뒷부분 유지, 문자 경계)
+pub fn truncate_head(text: &str, max_tokens: usize, tokenizer: &dyn Tokenizer) -> String {
+    let bounds: Vec<usize> = text.char_indices().map(|(i, _)| i).collect();
+    // 한도 이내인 가장 긴 뒷부분의 시작 문자 위치
+    let start = bounds.partition_point(|&i| !tokenizer.fits(&text[i..], max_tokens));
+    bounds
+        .get(start)
+        .map_or_else(String::new, |&i| text[i..].to_string())
+}
+
+/// 토큰 한도에 맞게 뒷부분을 잘라냄 (앞부분 유지, 문자 경계)
+pub fn truncate_tail(text: &str, max_tokens: usize, tokenizer: &dyn Tokenizer) -> String {
+    text[..fitting_prefix(text, max_tokens, tokenizer)].to_string()
+}
+
+/// 가중 평균 벡터 (분할 조각의 임베딩을 하나로)
+///
+/// 평균 벡터는 조각 벡터보다 짧아지므로 L2 정규화하여 다른 청크의 벡터와 거리 척도를 맞춥니다.
+pub fn mean_pool(vectors: &[Vec<f32>], weights: &[f32]) -> Vec<f32> {
+    let dimension = vectors.first().map_or(0, Vec::len);
+    let total: f32 = weights.iter().sum();
+    let mut pooled = vec![0.0; dimension];
+
+    for (vector, weight) in vectors.iter().zip(weights) {
+        for (acc, value) in pooled.iter_mut().zip(vector) {
+            *acc += value * weight / total;
+        }
+    }
+
+    let norm = pooled.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        pooled.iter_mut().for_each(|v| *v /= norm);
+    }
+
+    pooled
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokens::{CharRatio, GeminiEstimator};
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 2);
+        assert_eq!(estimate_tokens("한국어"), 3);
+    }
+
+    #[test]
+    fn test_split_respects_limit() {
+        let paragraph = "word ".repeat(30);
+        let text = [paragraph.trim(); 4].join("\n\n");
+        let pieces = split_for_embedding(&text, 60, &GeminiEstimator);
+
+        assert!(pieces.len() > 1);
+        assert!(pieces.iter().all(|p| estimate_tokens(p) <= 60));
+        // 문단 경계에서 먼저 나눔
+        assert_eq!(pieces[0], paragraph.trim());
+
+        // 공백 없는 긴 문자열은 문자 단위로
+        let pieces = split_for_embedding(&"가".repeat(25), 10, &GeminiEstimator);
+        assert_eq!(pieces.len(), 3);
+        assert_eq!(pieces.concat(), "가".repeat(25));
+
+        // 긴 문자열도 조각마다 한도를 꽉 채움 (ASCII 3자당 1토큰 -> 6144자씩)
+        let long = "x".repeat(100_000);
+        let pieces = split_for_embedding(&long, MAX_INPUT_TOKENS, &GeminiEstimator);
+        assert_eq!(pieces.len(), 17);
+        assert_eq!(pieces[0].len(), 6144);
+        assert_eq!(pieces.concat(), long);
+
+        assert_eq!(
+            split_for_embedding("short", 60, &GeminiEstimator),
+            vec!["short".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_truncate() {
+        let gemini = GeminiEstimator;
+        let text = "abcdef가나";
+        assert_eq!(truncate_tail(text, 2, &gemini), "abcdef");
+        assert_eq!(truncate_head(text, 2, &gemini), "가나");
+        assert_eq!(truncate_head(text, 3, &gemini), "def가나");
+        assert_eq!(truncate_tail("short", 60, &gemini), "short");
+        assert_eq!(truncate_tail(text, 0, &gemini), "");
+        assert!(estimate_tokens(&truncate_tail(&"word ".repeat(100), 20, &gemini)) <= 20);
+
+        // 다른 토크나이저 기준으로 자름
+        let ratio = CharRatio::new("approx", 4.0);
+        assert_eq!(truncate_tail(text, 2, &ratio), "abcdef가나");
+        assert_eq!(truncate_head("abcdefghij", 2, &ratio), "cdefghij");
+    }
+
+    #[test]
+    fn test_mean_pool() {
+        let pooled = mean_pool(&[vec![1.0, 0.0], vec![0.0, 1.0]], &[3.0, 1.0]);
+        // 가중 평균 (0.75, 0.25)의 방향, 길이 1
+        assert!((pooled[0] / pooled[1] - 3.0).abs() < 1e-5);
+        let norm = pooled.iter().map(|v| v * v).sum::<f32>().sqrt();
+        assert!((norm - 1.0).abs() < 1e-5);
+
+        // 영벡터는 그대로
+        assert_eq!(mean_pool(&[vec![0.0, 0.0]], &[1.0]), vec![0.0, 0.0]);
+    }
+}
@@ -11,14 +11,33 @@
 //! let embedding = embedder.embed("Hello, world!").await?;
 //! ```
 
+mod breaker;
+mod credentials;
+mod input;
+mod quota;
+
+pub use breaker::{CircuitBreaker, CircuitOpen};
+pub use credentials::{keyring_api_key, remove_api_key, store_api_key, KEYRING_DISABLE_ENV_VAR};
+pub use input::{
+    estimate_tokens, split_for_embedding, truncate_head, truncate_tail, OversizeStrategy,
+    MAX_INPUT_TOKENS,
+};
+pub use quota::QuotaExhausted;
+
+use quota::parse_rate_limit;
+
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 use tokio::sync::Mutex;
 
+use crate::tokens::GeminiEstimator;
+
 // ============================================================================
 // EmbeddingProvider Trait
 // ============================================================================
@@ -45,6 +64,48 @@ pub trait EmbeddingProvider: Send + Sync {
 
     /// 프로바이더 이름
     fn name(&self) -> &str;
+
+    /// 모델 버전 (`이름/차원`) - 벡터 행마다 기록하여 모델을 바꾼 뒤 예전 벡터를 찾습니다
+    fn model_version(&self) -> String {
+        format!("{}/{}", self.name(), self.dimension())
+    }
+}
+
+/// 공유 프로바이더 (호출자가 사용량 등을 조회하면서 검색기에 넘길 때 사용)
+#[async_trait]
+impl<T: EmbeddingProvider + ?Sized> EmbeddingProvider for Arc<T> {
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        (**self).embed(text).await
+    }
+
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        (**self).embed_batch(texts).await
+    }
+
+    fn dimension(&self) -> usize {
+        (**self).dimension()
+    }
+
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn model_version(&self) -> String {
+        (**self).model_version()
+    }
+}
+
+// ============================================================================
+// Embedding Config
+// ============================================================================
+
+/// 임베딩 설정 (config.toml `[embedding]`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EmbeddingConfig {
+    /// 입력 한도를 넘는 청크 처리 방식
+    /// (`average`, `split`, `truncate-head`, `truncate-tail`, `summarize`)
+    pub oversize: OversizeStrategy,
 }
 
 // ============================================================================
@@ -53,7 +114,7 @@ pub trait EmbeddingProvider: Send + Sync {
 
 /// Gemini 임베딩 API 엔드포인트 (gemini-embedding-001 - MRL 지원)
 /// source: https://ai.google.dev/gemini-api/docs/embeddings
-const GEMINI_EMBED_URL: &str =
+pub(crate) const GEMINI_EMBED_URL: &str =
     "https://generativelanguage.googleapis.com/v1beta/models/gemini-embedding-001:embedContent";
 
 /// 기본 임베딩 차원
@@ -68,16 +129,87 @@ const MIN_DELAY_MS: u64 = 1000;
 const MAX_RETRIES: u32 = 3;
 /// 재시도 시 초기 백오프 (ms)
 const INITIAL_BACKOFF_MS: u64 = 2000;
+/// 서버가 안내한 대기 시간이 이보다 길면 기다리지 않고 실패
+const MAX_RETRY_WAIT: Duration = Duration::from_secs(120);
+/// 일일 할당량이 소진된 키를 다시 시도하기까지의 간격 (데몬처럼 오래 실행되는 경우)
+const QUOTA_RECHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
 
 /// Google Gemini 임베딩 구현체
 ///
+/// 여러 API 키가 주어지면 라운드 로빈으로 돌려 쓰고, 429 응답 시 즉시 다음 키로
+/// 넘어갑니다. Rate limit은 키마다 따로 적용되므로 키 수만큼 처리량이 늘어납니다.
+///
 /// source: https://ai.google.dev/gemini-api/docs/embeddings
 #[derive(Debug)]
 pub struct GeminiEmbedding {
-    api_key: String,
+    keys: Vec<ApiKeySlot>,
+    next_key: AtomicUsize,
     client: reqwest::Client,
     dimension: usize,
-    rate_limiter: Arc<Mutex<RateLimiter>>,
+}
+
+/// API 키별 상태 (Rate limiter + 사용량)
+#[derive(Debug)]
+struct ApiKeySlot {
+    api_key: String,
+    rate_limiter: Mutex<RateLimiter>,
+    requests: AtomicU64,
+    successes: AtomicU64,
+    rate_limited: AtomicU64,
+    /// 일일 할당량 소진 시각
+    quota_exhausted_at: std::sync::Mutex<Option<Instant>>,
+}
+
+impl ApiKeySlot {
+    fn new(api_key: String) -> Self {
+        Self {
+            api_key,
+            rate_limiter: Mutex::new(RateLimiter::new(RATE_LIMIT_RPM, RATE_LIMIT_WINDOW)),
+            requests: AtomicU64::new(0),
+            successes: AtomicU64::new(0),
+            rate_limited: AtomicU64::new(0),
+            quota_exhausted_at: std::sync::Mutex::new(None),
+        }
+    }
+
+    /// 일일 할당량 소진 상태인지 (`QUOTA_RECHECK_INTERVAL`이 지나면 다시 시도)
+    fn quota_exhausted(&self) -> bool {
+        self.quota_exhausted_at
+            .lock()
+            .unwrap()
+            .is_some_and(|at| at.elapsed() < QUOTA_RECHECK_INTERVAL)
+    }
+
+    fn mark_quota_exhausted(&self) {
+        *self.quota_exhausted_at.lock().unwrap() = Some(Instant::now());
+    }
+
+    fn usage(&self) -> KeyUsage {
+        let tail: String = {
+            let chars: Vec<char> = self.api_key.chars().collect();
+            chars[chars.len().saturating_sub(4)..].iter().collect()
+        };
+
+        KeyUsage {
+            key_hint: format!("****{}", tail),
+            requests: self.requests.load(Ordering::Relaxed),
+            successes: self.successes.load(Ordering::Relaxed),
+            rate_limited: self.rate_limited.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// API 키별 사용량
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct KeyUsage {
+    /// 키 식별용 힌트 (끝 4자리)
+    pub key_hint: String,
+    /// 전체 요청 수
+    pub requests: u64,
+    /// 성공한 요청 수
+    pub successes: u64,
+    /// 429 응답 수
+    pub rate_limited: u64,
 }
 
 /// Rate Limiter with minimum delay between requests
@@ -154,6 +286,19 @@ impl GeminiEmbedding {
     /// * `api_key` - Google AI API 키
     /// * `dimension` - 임베딩 차원 (768, 1536, 3072 중 선택)
     pub fn with_dimension(api_key: String, dimension: usize) -> Result<Self> {
+        Self::with_keys(vec![api_key], dimension)
+    }
+
+    /// 여러 API 키로 생성 (라운드 로빈 순환)
+    ///
+    /// # Arguments
+    /// * `api_keys` - Google AI API 키 목록 (1개 이상)
+    /// * `dimension` - 임베딩 차원 (768, 1536, 3072 중 선택)
+    pub fn with_keys(api_keys: Vec<String>, dimension: usize) -> Result<Self> {
+        if api_keys.is_empty() {
+            anyhow::bail!("At least one API key is required");
+        }
+
         // 유효한 차원 확인
         if ![768, 1536, 3072].contains(&dimension) {
             anyhow::bail!(
@@ -167,37 +312,65 @@ impl GeminiEmbedding {
             .build()
             .context("Failed to create HTTP client")?;
 
-        let rate_limiter = Arc::new(Mutex::new(RateLimiter::new(
-            RATE_LIMIT_RPM,
-            RATE_LIMIT_WINDOW,
-        )));
+        if api_keys.len() > 1 {
+            tracing::info!("Rotating {} Gemini API keys", api_keys.len());
+        }
 
         Ok(Self {
-            api_key,
+            keys: api_keys.into_iter().map(ApiKeySlot::new).collect(),
+            next_key: AtomicUsize::new(0),
             client,
             dimension,
-            rate_limiter,
         })
     }
 
     /// 환경변수에서 API 키를 읽어 생성
     ///
-    /// 우선순위: GEMINI_API_KEY > GOOGLE_AI_API_KEY
+    /// 우선순위: GEMINI_API_KEYS (쉼표 구분, 순환) > GEMINI_API_KEY > GOOGLE_AI_API_KEY > OS 키링
     pub fn from_env() -> Result<Self> {
-        let api_key = get_api_key()?;
-        Self::new(api_key)
+        Self::from_env_with_dimension(DEFAULT_DIMENSION)
     }
 
     /// 환경변수에서 API 키를 읽어 차원 지정하여 생성
     pub fn from_env_with_dimension(dimension: usize) -> Result<Self> {
-        let api_key = get_api_key()?;
-        Self::with_dimension(api_key, dimension)
+        let api_keys = get_api_keys()?;
+        Self::with_keys(api_keys, dimension)
     }
 
     /// 임베딩 차원 반환
     pub fn dimension(&self) -> usize {
         self.dimension
     }
+
+    /// 설정된 API 키 수
+    pub fn key_count(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// API 키별 사용량
+    pub fn key_usage(&self) -> Vec<KeyUsage> {
+        self.keys.iter().map(ApiKeySlot::usage).collect()
+    }
+
+    /// 다음 순서의 API 키 선택 (라운드 로빈, 일일 할당량이 소진된 키는 건너뜀)
+    fn next_slot(&self) -> &ApiKeySlot {
+        let start = self.next_key.fetch_add(1, Ordering::Relaxed);
+        (0..self.keys.len())
+            .map(|offset| &self.keys[(start + offset) % self.keys.len()])
+            .find(|slot| !slot.quota_exhausted())
+            .unwrap_or(&self.keys[start % self.keys.len()])
+    }
+
+    /// 모든 키의 일일 할당량이 소진되었으면 오류
+    fn check_quota(&self) -> Result<()> {
+        if self.keys.iter().all(ApiKeySlot::quota_exhausted) {
+            return Err(QuotaExhausted {
+                keys: self.keys.len(),
+            }
+            .into());
+        }
+        Ok(())
+    }
 }
 
 /// Gemini API 요청 본문
@@ -246,6 +419,33 @@ struct GeminiErrorDetail {
     status: String,
 }
 
+/// 임베딩 API 오류 응답 (429 제외 - `QuotaExhausted` 참고)
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Gemini API error ({status}): {message}")]
+pub struct EmbeddingApiError {
+    /// HTTP 상태 코드
+    pub status: u16,
+    /// API 오류 메시지 (`INVALID_ARGUMENT: ...`)
+    pub message: String,
+}
+
+impl EmbeddingApiError {
+    /// 오류 체인의 API 오류 (context로 감싼 경우 포함)
+    pub fn find(error: &anyhow::Error) -> Option<&Self> {
+        error.chain().find_map(|e| e.downcast_ref::<Self>())
+    }
+
+    /// 다시 시도해도 같은 결과인 요청 오류 (4xx - 잘못된 키, 잘못된 요청)
+    pub fn is_client_error(&self) -> bool {
+        (400..500).contains(&self.status)
+    }
+
+    /// API 키 문제 (잘못되었거나 폐기된 키, 권한 없음)
+    pub fn is_auth_error(&self) -> bool {
+        matches!(self.status, 401 | 403) || (self.status == 400 && self.message.contains("API key"))
+    }
+}
+
 #[async_trait]
 impl EmbeddingProvider for GeminiEmbedding {
     async fn embed(&self, text: &str) -> Result<Vec<f32>> {
@@ -254,6 +454,55 @@ impl EmbeddingProvider for GeminiEmbedding {
             return Ok(vec![0.0; self.dimension]);
         }
 
+        // 입력 한도 초과 시 나누어 임베딩 후 평균 (문서 전체 수집 실패 방지)
+        let pieces = split_for_embedding(text, MAX_INPUT_TOKENS, &GeminiEstimator);
+        if pieces.len() > 1 {
+            tracing::warn!(
+                "Input too long (~{} tokens > {}), embedding {} pieces and averaging",
+                estimate_tokens(text),
+                MAX_INPUT_TOKENS,
+                pieces.len()
+            );
+
+            let mut vectors = Vec::with_capacity(pieces.len());
+            for piece in &pieces {
+                vectors.push(self.embed_request(piece).await?);
+            }
+            let weights: Vec<f32> = pieces.iter().map(|p| estimate_tokens(p) as f32).collect();
+            return Ok(input::mean_pool(&vectors, &weights));
+        }
+
+        self.embed_request(text).await
+    }
+
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        // Gemini는 배치 API가 없으므로 순차 처리
+        // Rate limiter가 자동으로 조절함
+        let mut results = Vec::with_capacity(texts.len());
+
+        for (i, text) in texts.iter().enumerate() {
+            tracing::debug!("Embedding batch {}/{}", i + 1, texts.len());
+            results.push(self.embed(text).await?);
+        }
+
+        Ok(results)
+    }
+
+    fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    fn name(&self) -> &str {
+        "gemini-embedding-001"
+    }
+}
+
+impl GeminiEmbedding {
+    /// 단일 임베딩 API 호출 (입력은 토큰 한도 이내)
+    async fn embed_request(&self, text: &str) -> Result<Vec<f32>> {
+        // 할당량이 소진된 상태면 요청 없이 바로 실패 (파일마다 재시도 주기를 돌지 않도록)
+        self.check_quota()?;
+
         // 요청 본문 구성
         let request = EmbedRequest {
             model: "models/gemini-embedding-001".to_string(),
@@ -268,33 +517,43 @@ impl EmbeddingProvider for GeminiEmbedding {
 
         let mut last_error: Option<anyhow::Error> = None;
 
-        // 재시도 루프 (429 에러 시 지수 백오프)
-        for attempt in 0..=MAX_RETRIES {
-            // Rate limiting (매 시도마다)
+        // 429 시 다른 키로 즉시 넘어가는 횟수 (키가 하나면 0)
+        let rotations = self.keys.len() as u32 - 1;
+
+        // 재시도 루프 (429 에러 시 키 순환 후 지수 백오프)
+        for attempt in 0..=MAX_RETRIES + rotations {
+            let slot = self.next_slot();
+            let backoff_exp = attempt.saturating_sub(rotations);
+
+            // Rate limiting (키별, 매 시도마다)
             {
-                let mut limiter = self.rate_limiter.lock().await;
+                let mut limiter = slot.rate_limiter.lock().await;
                 limiter.acquire().await;
             }
+            slot.requests.fetch_add(1, Ordering::Relaxed);
 
             // API 호출 (API 키는 URL이 아닌 헤더로 전송 - 보안 강화)
             let response = match self
                 .client
                 .post(GEMINI_EMBED_URL)
-                .header("x-goog-api-key", &self.api_key)
+                .header("x-goog-api-key", &slot.api_key)
                 .json(&request)
                 .send()
                 .await
             {
                 Ok(resp) => resp,
                 Err(e) => {
-                    last_error = Some(anyhow::anyhow!("Failed to send embedding request: {}", e));
-                    if attempt < MAX_RETRIES {
-                        let backoff = Duration::from_millis(INITIAL_BACKOFF_MS * 2u64.pow(attempt));
+                    // reqwest 오류를 원인으로 남겨 네트워크 오류로 분류되게 함
+                    last_error =
+                        Some(anyhow::Error::new(e).context("Failed to send embedding request"));
+                    if attempt < MAX_RETRIES + rotations {
+                        let backoff =
+                            Duration::from_millis(INITIAL_BACKOFF_MS * 2u64.pow(backoff_exp));
                         tracing::warn!(
                             "Request failed, retrying in {:?} (attempt {}/{})",
                             backoff,
                             attempt + 1,
-                            MAX_RETRIES
+                            MAX_RETRIES + rotations
                         );
                         tokio::time::sleep(backoff).await;
                         continue;
@@ -304,6 +563,11 @@ impl EmbeddingProvider for GeminiEmbedding {
             };
 
             let status = response.status();
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string);
             let body = response
                 .text()
                 .await
@@ -311,36 +575,63 @@ impl EmbeddingProvider for GeminiEmbedding {
 
             // 성공
             if status.is_success() {
+                slot.successes.fetch_add(1, Ordering::Relaxed);
                 let embed_response: EmbedResponse =
                     serde_json::from_str(&body).context("Failed to parse embedding response")?;
                 return Ok(embed_response.embedding.values);
             }
 
-            // 429 Rate Limit 에러 - 재시도
+            // 429 Rate Limit 에러 - 다른 키로 순환 또는 재시도
             if status.as_u16() == 429 {
-                let backoff = Duration::from_millis(INITIAL_BACKOFF_MS * 2u64.pow(attempt));
+                slot.rate_limited.fetch_add(1, Ordering::Relaxed);
+                last_error = Some(anyhow::anyhow!("Rate limit exceeded (429)"));
+                let info = parse_rate_limit(retry_after.as_deref(), &body);
+
+                // 일일 할당량 소진: 이 키는 재시도해도 소용없음
+                if info.daily_quota {
+                    tracing::warn!("Daily quota exhausted on {}", slot.usage().key_hint);
+                    slot.mark_quota_exhausted();
+                    self.check_quota()?;
+                    continue;
+                }
+
+                if attempt < rotations {
+                    tracing::warn!("Rate limit hit (429) on {}, rotating key", slot.usage().key_hint);
+                    continue;
+                }
+
+                // 서버 안내 대기 시간 우선, 없으면 지수 백오프
+                let backoff = info.retry_after.unwrap_or_else(|| {
+                    Duration::from_millis(INITIAL_BACKOFF_MS * 2u64.pow(backoff_exp))
+                });
+                if backoff > MAX_RETRY_WAIT {
+                    anyhow::bail!(
+                        "Rate limit exceeded (429): API asked to retry after {:?}",
+                        backoff
+                    );
+                }
                 tracing::warn!(
                     "Rate limit hit (429), backing off {:?} (attempt {}/{})",
                     backoff,
-                    attempt + 1,
+                    backoff_exp + 1,
                     MAX_RETRIES
                 );
-                last_error = Some(anyhow::anyhow!("Rate limit exceeded (429)"));
 
-                if attempt < MAX_RETRIES {
+                if attempt < MAX_RETRIES + rotations {
                     tokio::time::sleep(backoff).await;
                     continue;
                 }
             } else {
                 // 다른 에러 - 즉시 실패
-                if let Ok(error) = serde_json::from_str::<GeminiError>(&body) {
-                    anyhow::bail!(
-                        "Gemini API error ({}): {}",
-                        error.error.status,
-                        error.error.message
-                    );
+                let message = match serde_json::from_str::<GeminiError>(&body) {
+                    Ok(error) => format!("{}: {}", error.error.status, error.error.message),
+                    Err(_) => body,
+                };
+                return Err(EmbeddingApiError {
+                    status: status.as_u16(),
+                    message,
                 }
-                anyhow::bail!("Gemini API error ({}): {}", status, body);
+                .into());
             }
         }
 
@@ -348,76 +639,106 @@ impl EmbeddingProvider for GeminiEmbedding {
         Err(last_error
             .unwrap_or_else(|| anyhow::anyhow!("Embedding failed after {} retries", MAX_RETRIES)))
     }
+}
 
-    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
-        // Gemini는 배치 API가 없으므로 순차 처리
-        // Rate limiter가 자동으로 조절함
-        let mut results = Vec::with_capacity(texts.len());
+// ============================================================================
+// API Key Management
+// ============================================================================
 
-        for (i, text) in texts.iter().enumerate() {
-            tracing::debug!("Embedding batch {}/{}", i + 1, texts.len());
-            results.push(self.embed(text).await?);
-        }
+/// 순환용 API 키 목록 환경변수 (쉼표 구분)
+const API_KEYS_ENV_VAR: &str = "GEMINI_API_KEYS";
 
-        Ok(results)
-    }
+/// API 키 환경변수 (우선순위 순)
+const API_KEY_ENV_VARS: [&str; 2] = ["GEMINI_API_KEY", "GOOGLE_AI_API_KEY"];
 
-    fn dimension(&self) -> usize {
-        self.dimension
-    }
+/// API 키 출처
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiKeySource {
+    /// 환경변수 (변수 이름)
+    Env(&'static str),
+    /// OS 키링 (`palank-rag auth set`)
+    Keyring,
+}
 
-    fn name(&self) -> &str {
-        "gemini-embedding-001"
+impl std::fmt::Display for ApiKeySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Env(name) => write!(f, "환경변수 {}", name),
+            Self::Keyring => write!(f, "OS 키링"),
+        }
     }
 }
 
-// ============================================================================
-// API Key Management
-// ============================================================================
-
-/// API 키 로드 (환경변수에서)
+/// API 키와 출처 조회
 ///
 /// 우선순위:
-/// 1. `GEMINI_API_KEY` 환경변수
-/// 2. `GOOGLE_AI_API_KEY` 환경변수
-pub fn get_api_key() -> Result<String> {
-    // 1. GEMINI_API_KEY 확인
-    if let Ok(key) = std::env::var("GEMINI_API_KEY") {
-        if !key.is_empty() {
-            tracing::debug!("Using API key from GEMINI_API_KEY");
-            return Ok(key);
-        }
+/// 1. `GEMINI_API_KEYS` 환경변수 (첫 번째 키)
+/// 2. `GEMINI_API_KEY` 환경변수
+/// 3. `GOOGLE_AI_API_KEY` 환경변수
+/// 4. OS 키링
+pub fn find_api_key() -> Option<(String, ApiKeySource)> {
+    if let Some(key) = env_api_keys().into_iter().next() {
+        return Some((key, ApiKeySource::Env(API_KEYS_ENV_VAR)));
     }
 
-    // 2. GOOGLE_AI_API_KEY 확인 (대체)
-    if let Ok(key) = std::env::var("GOOGLE_AI_API_KEY") {
-        if !key.is_empty() {
-            tracing::debug!("Using API key from GOOGLE_AI_API_KEY");
-            return Ok(key);
+    for name in API_KEY_ENV_VARS {
+        if let Ok(key) = std::env::var(name) {
+            if !key.is_empty() {
+                return Some((key, ApiKeySource::Env(name)));
+            }
         }
     }
 
-    anyhow::bail!(
-        "API key not found. Set GEMINI_API_KEY or GOOGLE_AI_API_KEY environment variable.\n\
-         Get your API key at: https://aistudio.google.com/app/apikey"
-    )
+    keyring_api_key().map(|key| (key, ApiKeySource::Keyring))
 }
 
-/// API 키 존재 여부 확인
-pub fn has_api_key() -> bool {
-    if let Ok(key) = std::env::var("GEMINI_API_KEY") {
-        if !key.is_empty() {
-            return true;
+/// API 키 로드 (환경변수 또는 OS 키링)
+pub fn get_api_key() -> Result<String> {
+    match find_api_key() {
+        Some((key, source)) => {
+            tracing::debug!("Using API key from {}", source);
+            Ok(key)
         }
+        None => anyhow::bail!(
+            "API key not found. Set GEMINI_API_KEY or GOOGLE_AI_API_KEY environment variable,\n\
+             or store it in the OS keyring with `palank-rag auth set`.\n\
+             Get your API key at: https://aistudio.google.com/app/apikey"
+        ),
     }
+}
+
+/// 순환에 사용할 API 키 목록 로드
+///
+/// `GEMINI_API_KEYS`(쉼표 구분)가 있으면 그 목록을, 없으면 단일 키를 반환합니다.
+pub fn get_api_keys() -> Result<Vec<String>> {
+    let keys = env_api_keys();
+    if !keys.is_empty() {
+        return Ok(keys);
+    }
+
+    Ok(vec![get_api_key()?])
+}
 
-    if let Ok(key) = std::env::var("GOOGLE_AI_API_KEY") {
-        if !key.is_empty() {
-            return true;
+/// `GEMINI_API_KEYS` 파싱 (빈 항목 제외, 중복 제거)
+fn env_api_keys() -> Vec<String> {
+    std::env::var(API_KEYS_ENV_VAR)
+        .map(|value| parse_api_keys(&value))
+        .unwrap_or_default()
+}
+
+fn parse_api_keys(value: &str) -> Vec<String> {
+    let mut keys: Vec<String> = Vec::new();
+    for key in value.split(',').map(str::trim).filter(|k| !k.is_empty()) {
+        if !keys.iter().any(|k| k == key) {
+            keys.push(key.to_string());
         }
     }
+    keys
+}
 
-    false
+/// API 키 존재 여부 확인
+pub fn has_api_key() -> bool {
+    find_api_key().is_some()
 }
 
 // ============================================================================
@@ -436,7 +757,7 @@ pub fn create_embedder_with_dimension(dimension: usize) -> Result<GeminiEmbeddin
     if !has_api_key() {
         anyhow::bail!(
             "GEMINI_API_KEY or GOOGLE_AI_API_KEY not set.\n\
-             Set: export GEMINI_API_KEY=your-api-key\n\
+             Set: export GEMINI_API_KEY=your-api-key (or `palank-rag auth set`)\n\
              Get your API key at: https://aistudio.google.com/app/apikey"
         );
     }
@@ -483,11 +804,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_api_keys() {
+        assert_eq!(
+            parse_api_keys(" key-a, key-b,,key-a "),
+            vec!["key-a".to_string(), "key-b".to_string()]
+        );
+        assert!(parse_api_keys(" , ").is_empty());
+    }
+
+    #[test]
+    fn test_key_rotation_round_robin() {
+        let embedder = GeminiEmbedding::with_keys(
+            vec!["key-aaaa".to_string(), "key-bbbb".to_string()],
+            DEFAULT_DIMENSION,
+        )
+        .unwrap();
+        assert_eq!(embedder.key_count(), 2);
+
+        let picked: Vec<&str> = (0..4).map(|_| embedder.next_slot().api_key.as_str()).collect();
+        assert_eq!(picked, vec!["key-aaaa", "key-bbbb", "key-aaaa", "key-bbbb"]);
+
+        let usage = embedder.key_usage();
+        assert_eq!(usage[1].key_hint, "****bbbb");
+        assert_eq!(usage[1].requests, 0);
+
+        assert!(GeminiEmbedding::with_keys(Vec::new(), DEFAULT_DIMENSION).is_err());
+    }
+
+    #[test]
+    fn test_quota_exhausted_keys_skipped() {
+        let embedder = GeminiEmbedding::with_keys(
+            vec!["key-aaaa".to_string(), "key-bbbb".to_string()],
+            DEFAULT_DIMENSION,
+        )
+        .unwrap();
+
+        embedder.keys[0].mark_quota_exhausted();
+        let picked: Vec<&str> = (0..3).map(|_| embedder.next_slot().api_key.as_str()).collect();
+        assert_eq!(picked, vec!["key-bbbb", "key-bbbb", "key-bbbb"]);
+        assert!(embedder.check_quota().is_ok());
+
+        embedder.keys[1].mark_quota_exhausted();
+        let err = embedder.check_quota().unwrap_err();
+        assert!(QuotaExhausted::is_cause_of(&err));
+    }
+
     #[tokio::test]
     async fn test_create_embedder_without_key_returns_error() {
         // 환경변수 제거 (테스트용)
+        std::env::remove_var("GEMINI_API_KEYS");
         std::env::remove_var("GEMINI_API_KEY");
         std::env::remove_var("GOOGLE_AI_API_KEY");
+        // 키가 저장된 기기에서도 키링을 조회하지 않도록
+        std::env::set_var(KEYRING_DISABLE_ENV_VAR, "1");
 
         let result = create_embedder();
         assert!(result.is_err());
@@ -0,0 +1,144 @@
+//! 429 응답 분석 - 재시도 대기 시간과 일일 할당량 소진 구분
+//!
+//! Gemini는 429 본문의 `details`에 `RetryInfo.retryDelay`(분당 제한)와
+//! `QuotaFailure.violations[].quotaId`(예: `...PerDay...`)를 담아 보냅니다.
+//! 분당 제한은 안내된 시간만큼 기다렸다 재시도하고, 일일 할당량 소진은 재시도해도
+//! 소용없으므로 바로 실패시킵니다.
+//!
+//! source: https://ai.google.dev/gemini-api/docs/rate-limits
+
+use std::time::Duration;
+
+use serde::Deserialize;
+use thiserror::Error;
+
+/// 일일 할당량 소진 (모든 API 키)
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Daily Gemini quota exhausted ({keys} key(s)): resume tomorrow or pass --resume to skip already ingested files")]
+pub struct QuotaExhausted {
+    /// 소진된 API 키 수
+    pub keys: usize,
+}
+
+impl QuotaExhausted {
+    /// 오류 체인에 할당량 소진이 있는지 (context로 감싼 경우 포함)
+    pub fn is_cause_of(error: &anyhow::Error) -> bool {
+        error.chain().any(|e| e.downcast_ref::<Self>().is_some())
+    }
+}
+
+/// 429 응답 분석 결과
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct RateLimitInfo {
+    /// 서버가 안내한 재시도 대기 시간 (`Retry-After` 헤더 또는 `RetryInfo`)
+    pub retry_after: Option<Duration>,
+    /// 일일 할당량 소진 여부
+    pub daily_quota: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorBody {
+    error: ErrorStatus,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorStatus {
+    #[serde(default)]
+    message: String,
+    #[serde(default)]
+    details: Vec<ErrorDetail>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ErrorDetail {
+    retry_delay: Option<String>,
+    violations: Vec<QuotaViolation>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct QuotaViolation {
+    quota_id: String,
+    quota_metric: String,
+}
+
+/// 429 응답 헤더/본문 분석
+pub(crate) fn parse_rate_limit(retry_after_header: Option<&str>, body: &str) -> RateLimitInfo {
+    let mut info = RateLimitInfo {
+        retry_after: retry_after_header
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .map(Duration::from_secs),
+        daily_quota: false,
+    };
+
+    let Ok(body) = serde_json::from_str::<ErrorBody>(body) else {
+        return info;
+    };
+
+    for detail in &body.error.details {
+        if let Some(delay) = detail.retry_delay.as_deref().and_then(parse_delay) {
+            info.retry_after.get_or_insert(delay);
+        }
+        info.daily_quota |= detail.violations.iter().any(|v| {
+            let id = v.quota_id.to_lowercase();
+            let metric = v.quota_metric.to_lowercase();
+            id.contains("perday") || metric.contains("per_day")
+        });
+    }
+    info.daily_quota |= body.error.message.to_lowercase().contains("per day");
+
+    info
+}
+
+/// protobuf Duration 문자열 (`"23s"`, `"1.5s"`)
+fn parse_delay(value: &str) -> Option<Duration> {
+    let seconds: f64 = value.trim().strip_suffix('s')?.parse().ok()?;
+    (seconds.is_finite() && seconds >= 0.0).then(|| Duration::from_secs_f64(seconds))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_per_minute_limit() {
+        let body = r#"{"error": {"code": 429, "status": "RESOURCE_EXHAUSTED",
+            "message": "Resource has been exhausted",
+            "details": [
+                {"@type": "type.googleapis.com/google.rpc.QuotaFailure",
+                 "violations": [{"quotaMetric": "generativelanguage.googleapis.com/embed_content_free_tier_requests",
+                                 "quotaId": "EmbedContentRequestsPerMinutePerProjectPerModel-FreeTier"}]},
+                {"@type": "type.googleapis.com/google.rpc.RetryInfo", "retryDelay": "23s"}
+            ]}}"#;
+
+        let info = parse_rate_limit(None, body);
+        assert_eq!(info.retry_after, Some(Duration::from_secs(23)));
+        assert!(!info.daily_quota);
+
+        // 헤더가 있으면 헤더 우선
+        let info = parse_rate_limit(Some("5"), body);
+        assert_eq!(info.retry_after, Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn test_parse_daily_quota() {
+        let body = r#"{"error": {"code": 429, "message": "Quota exceeded",
+            "details": [{"violations": [{"quotaId": "EmbedContentRequestsPerDayPerUserPerProjectPerModel-FreeTier"}]}]}}"#;
+        assert!(parse_rate_limit(None, body).daily_quota);
+
+        assert_eq!(parse_rate_limit(None, "not json"), RateLimitInfo::default());
+    }
+
+    #[test]
+    fn test_quota_exhausted_in_chain() {
+        let error =
+            anyhow::Error::new(QuotaExhausted { keys: 2 }).context("Failed to embed chunks");
+        assert!(QuotaExhausted::is_cause_of(&error));
+        assert!(!QuotaExhausted::is_cause_of(&anyhow::anyhow!("other")));
+    }
+}
@@ -0,0 +1,101 @@
+//! 최소 본문 길이 필터
+//!
+//! 빈 PDF, 몇 단어뿐인 페이지, 바이너리 잔해처럼 내용이 거의 없는 추출 결과는
+//! 검색 결과에 가끔 섞여 나올 뿐이므로 수집 단계에서 건너뜁니다.
+//! 길이는 글자/숫자만 세므로 공백, 구두점, 제어 문자, 깨진 문자(U+FFFD)는 포함되지 않습니다.
+//! CSV/TSV 구조화 모드의 행 묶음은 짧아도 표의 일부이므로 걸러내지 않습니다.
+//!
+//! ```toml
+//! [content_filter]
+//! min_chars = 50
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use super::ExtractedContent;
+
+/// 기본 최소 길이 (글자/숫자 수)
+pub const DEFAULT_MIN_CONTENT_CHARS: usize = 50;
+
+/// 최소 본문 길이 필터
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContentFilter {
+    /// 최소 글자/숫자 수 (0이면 필터 끔)
+    pub min_chars: usize,
+}
+
+impl Default for ContentFilter {
+    fn default() -> Self {
+        Self {
+            min_chars: DEFAULT_MIN_CONTENT_CHARS,
+        }
+    }
+}
+
+impl ContentFilter {
+    /// 최소 길이 지정
+    pub fn new(min_chars: usize) -> Self {
+        Self { min_chars }
+    }
+
+    /// 수집할 만한 길이인지
+    pub fn accepts(&self, text: &str) -> bool {
+        self.min_chars == 0 || content_length(text) >= self.min_chars
+    }
+
+    /// 추출 결과를 수집할지 (표의 행 묶음은 길이와 관계없이 통과)
+    pub fn accepts_content(&self, content: &ExtractedContent) -> bool {
+        content.metadata.row_range.is_some() || self.accepts(&content.text)
+    }
+}
+
+/// 본문 길이 (글자/숫자 수)
+pub fn content_length(text: &str) -> usize {
+    text.chars().filter(|c| c.is_alphanumeric()).count()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_length() {
+        assert_eq!(content_length(""), 0);
+        assert_eq!(content_length("  \n\t--- | ---\n"), 0);
+        assert_eq!(content_length("\u{FFFD}\u{0}\u{1}ab"), 2);
+        assert_eq!(content_length("한국어 문서 1"), 6);
+    }
+
+    #[test]
+    fn test_accepts() {
+        let filter = ContentFilter::new(5);
+        assert!(filter.accepts("hello"));
+        assert!(!filter.accepts("hi !!! \u{FFFD}\u{FFFD}\u{FFFD}"));
+
+        // 0이면 빈 본문도 통과
+        assert!(ContentFilter::new(0).accepts(""));
+        assert_eq!(ContentFilter::default().min_chars, DEFAULT_MIN_CONTENT_CHARS);
+    }
+
+    #[test]
+    fn test_accepts_content_keeps_table_rows() {
+        use crate::collector::FileType;
+        use crate::extractor::ContentMetadata;
+
+        let filter = ContentFilter::new(50);
+        let mut content = ExtractedContent {
+            text: "id: 1\nname: a".to_string(),
+            source_type: FileType::Text,
+            metadata: ContentMetadata::default(),
+        };
+        assert!(!filter.accepts_content(&content));
+
+        content.metadata.row_range = Some((1, 1));
+        assert!(filter.accepts_content(&content));
+    }
+}
@@ -8,8 +8,11 @@ use anyhow::{Context, Result};
 use base64::{engine::general_purpose::STANDARD, Engine};
 use serde::{Deserialize, Serialize};
 
+use super::UnsupportedFormat;
+use crate::embedding::EmbeddingApiError;
+
 /// Gemini Vision API 엔드포인트
-const GEMINI_VISION_URL: &str =
+pub(crate) const GEMINI_VISION_URL: &str =
     "https://generativelanguage.googleapis.com/v1beta/models/gemini-2.0-flash-exp:generateContent";
 
 /// 이미지에서 텍스트 추출
@@ -60,7 +63,12 @@ pub async fn extract_text_from_image(path: &Path, api_key: &str) -> Result<Strin
     let body = response.text().await?;
 
     if !status.is_success() {
-        anyhow::bail!("Vision API error ({}): {}", status, body);
+        // 상태 코드를 남겨 호출자가 영구 오류(4xx)를 구분할 수 있게 함
+        let error = EmbeddingApiError {
+            status: status.as_u16(),
+            message: body,
+        };
+        return Err(anyhow::Error::new(error).context("Vision API error"));
     }
 
     // 6. 응답 파싱
@@ -97,7 +105,7 @@ fn get_mime_type(path: &Path) -> Result<&'static str> {
         "webp" => Ok("image/webp"),
         "gif" => Ok("image/gif"),
         "bmp" => Ok("image/bmp"),
-        _ => anyhow::bail!("Unsupported image format: {}", ext),
+        _ => Err(UnsupportedFormat(format!("image extension {:?}", ext)).into()),
     }
 }
 
@@ -4,16 +4,39 @@
 //! - 텍스트 파일: 직접 읽기
 //! - 이미지 파일: Gemini Vision API로 텍스트 추출
 //! - PDF 파일: pdf-extract로 텍스트 추출
+//! - CSV/TSV 파일: 구조화 모드에서 행 묶음별 "열: 값" 문서로 변환
+//!
+//! 문서 제목은 `title::TitleRules`의 우선순위로 정합니다.
+//! 본문이 너무 짧은 추출 결과는 `filter::ContentFilter`로 걸러냅니다.
 
+pub mod filter;
 pub mod image;
 pub mod pdf;
+pub mod table;
+pub mod title;
+
+pub use filter::{content_length, ContentFilter, DEFAULT_MIN_CONTENT_CHARS};
+pub use title::{TitleRules, TitleSource};
 
 use std::path::Path;
 
 use anyhow::{Context, Result};
+use thiserror::Error;
 
 use crate::collector::FileType;
 
+/// 읽을 수 없는 형식 (UTF-8이 아닌 텍스트, 지원하지 않는 이미지 등)
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Unsupported format: {0}")]
+pub struct UnsupportedFormat(pub String);
+
+impl UnsupportedFormat {
+    /// 오류 체인에 형식 오류가 있는지 (context로 감싼 경우 포함)
+    pub fn is_cause_of(error: &anyhow::Error) -> bool {
+        error.chain().any(|e| e.downcast_ref::<Self>().is_some())
+    }
+}
+
 // ============================================================================
 // Extracted Content
 // ============================================================================
@@ -29,6 +52,18 @@ pub struct ExtractedContent {
     pub metadata: ContentMetadata,
 }
 
+impl ExtractedContent {
+    /// 추출 방식 이름 (수집 출처 기록용)
+    pub fn extractor_name(&self) -> &'static str {
+        match self.source_type {
+            FileType::Pdf => "pdf",
+            FileType::Image => "vision",
+            FileType::Text if self.metadata.row_range.is_some() => "table",
+            FileType::Text => "text",
+        }
+    }
+}
+
 /// 콘텐츠 메타데이터
 #[derive(Debug, Clone, Default)]
 pub struct ContentMetadata {
@@ -38,6 +73,8 @@ pub struct ContentMetadata {
     pub total_pages: Option<usize>,
     /// 이미지 설명 (Vision API에서 추출)
     pub image_description: Option<String>,
+    /// 표 행 범위 (CSV/TSV 구조화 모드, 1부터 시작)
+    pub row_range: Option<(usize, usize)>,
 }
 
 // ============================================================================
@@ -48,12 +85,23 @@ pub struct ContentMetadata {
 pub struct ContentExtractor {
     /// Gemini API 키
     api_key: Option<String>,
+    /// CSV/TSV 구조화 모드의 문서당 행 수 (None이면 원본 텍스트)
+    table_rows: Option<usize>,
 }
 
 impl ContentExtractor {
     /// API 키로 추출기 생성
     pub fn new(api_key: Option<String>) -> Self {
-        Self { api_key }
+        Self {
+            api_key,
+            table_rows: None,
+        }
+    }
+
+    /// CSV/TSV를 행 묶음 단위 문서로 추출 (None이면 원본 텍스트)
+    pub fn with_table_rows(mut self, rows: Option<usize>) -> Self {
+        self.table_rows = rows;
+        self
     }
 
     /// 환경변수에서 API 키 로드
@@ -65,7 +113,10 @@ impl ContentExtractor {
     /// 파일에서 콘텐츠 추출
     pub async fn extract(&self, path: &Path, file_type: FileType) -> Result<Vec<ExtractedContent>> {
         match file_type {
-            FileType::Text => self.extract_text(path).await,
+            FileType::Text => match (self.table_rows, table::delimiter_for(path)) {
+                (Some(rows), Some(delimiter)) => self.extract_table(path, delimiter, rows).await,
+                _ => self.extract_text(path).await,
+            },
             FileType::Image => self.extract_image(path).await,
             FileType::Pdf => self.extract_pdf(path).await,
         }
@@ -73,9 +124,15 @@ impl ContentExtractor {
 
     /// 텍스트 파일에서 추출
     async fn extract_text(&self, path: &Path) -> Result<Vec<ExtractedContent>> {
-        let text = tokio::fs::read_to_string(path)
-            .await
-            .with_context(|| format!("Failed to read text file: {:?}", path))?;
+        let text = match tokio::fs::read_to_string(path).await {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::InvalidData => {
+                return Err(UnsupportedFormat(format!("{:?} is not UTF-8 text", path)).into());
+            }
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to read text file: {:?}", path));
+            }
+        };
 
         Ok(vec![ExtractedContent {
             text,
@@ -84,6 +141,33 @@ impl ContentExtractor {
         }])
     }
 
+    /// CSV/TSV 파일에서 행 묶음별 추출
+    async fn extract_table(
+        &self,
+        path: &Path,
+        delimiter: u8,
+        rows_per_group: usize,
+    ) -> Result<Vec<ExtractedContent>> {
+        let text = tokio::fs::read_to_string(path)
+            .await
+            .with_context(|| format!("Failed to read table file: {:?}", path))?;
+
+        let groups = table::rows_to_groups(&text, delimiter, rows_per_group)
+            .with_context(|| format!("Failed to parse table file: {:?}", path))?;
+
+        Ok(groups
+            .into_iter()
+            .map(|group| ExtractedContent {
+                text: group.text,
+                source_type: FileType::Text,
+                metadata: ContentMetadata {
+                    row_range: Some((group.first_row, group.last_row)),
+                    ..Default::default()
+                },
+            })
+            .collect())
+    }
+
     /// 이미지 파일에서 추출 (Gemini Vision)
     async fn extract_image(&self, path: &Path) -> Result<Vec<ExtractedContent>> {
         let api_key = self
@@ -136,6 +220,19 @@ impl ContentExtractor {
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_non_utf8_text_is_unsupported() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("utf16.txt");
+        std::fs::write(&path, [0xFF, 0xFE, 0x41, 0x00]).unwrap();
+
+        let error = ContentExtractor::new(None)
+            .extract(&path, FileType::Text)
+            .await
+            .unwrap_err();
+        assert!(UnsupportedFormat::is_cause_of(&error));
+    }
+
     #[test]
     fn test_content_metadata_default() {
         let meta = ContentMetadata::default();
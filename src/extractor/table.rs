@@ -0,0 +1,124 @@
+//! 표 형식(CSV/TSV) 구조화 추출 모듈
+//!
+//! 원본 텍스트를 그대로 넣는 대신 각 행을 "열: 값" 줄로 변환하고,
+//! 행 묶음마다 하나의 문서로 만듭니다. 모든 줄에 열 이름이 붙으므로
+//! 청크로 나뉘어도 헤더 문맥이 유지됩니다.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+/// 변환된 행 묶음
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowGroup {
+    /// 첫 행 번호 (헤더 제외, 1부터 시작)
+    pub first_row: usize,
+    /// 마지막 행 번호
+    pub last_row: usize,
+    /// "열: 값" 형식 텍스트 (행 사이는 빈 줄)
+    pub text: String,
+}
+
+/// 확장자로 구분자 결정 (csv → `,`, tsv → 탭)
+pub fn delimiter_for(path: &Path) -> Option<u8> {
+    let ext = path.extension()?.to_str()?.to_lowercase();
+    match ext.as_str() {
+        "csv" => Some(b','),
+        "tsv" => Some(b'\t'),
+        _ => None,
+    }
+}
+
+/// 표 텍스트를 행 묶음으로 변환
+///
+/// 첫 줄은 헤더로 사용합니다. 빈 값은 생략하고, 값이 모두 빈 행은 건너뜁니다.
+/// 헤더보다 열이 많은 행의 초과 열은 `column N`으로 표시합니다.
+pub fn rows_to_groups(text: &str, delimiter: u8, rows_per_group: usize) -> Result<Vec<RowGroup>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(text.as_bytes());
+
+    let headers: Vec<String> = reader
+        .headers()
+        .context("Failed to read table header")?
+        .iter()
+        .map(str::to_string)
+        .collect();
+
+    let mut rows: Vec<(usize, String)> = Vec::new();
+    for (i, record) in reader.records().enumerate() {
+        let record = record.with_context(|| format!("Failed to parse table row {}", i + 1))?;
+
+        let lines: Vec<String> = record
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(col, value)| format!("{}: {}", column_name(&headers, col), value))
+            .collect();
+
+        if !lines.is_empty() {
+            rows.push((i + 1, lines.join("\n")));
+        }
+    }
+
+    Ok(rows
+        .chunks(rows_per_group.max(1))
+        .map(|group| RowGroup {
+            first_row: group[0].0,
+            last_row: group[group.len() - 1].0,
+            text: group
+                .iter()
+                .map(|(_, text)| text.as_str())
+                .collect::<Vec<_>>()
+                .join("\n\n"),
+        })
+        .collect())
+}
+
+/// 열 이름 (헤더가 비었거나 없으면 `column N`)
+fn column_name(headers: &[String], col: usize) -> String {
+    match headers.get(col) {
+        Some(name) if !name.is_empty() => name.clone(),
+        _ => format!("column {}", col + 1),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rows_to_groups() {
+        let csv = "name,plan,price\n\
+                   Alice,\"Pro, yearly\",120\n\
+                   ,,\n\
+                   Bob,Free,\n\
+                   Carol,Team,300,extra\n";
+
+        let groups = rows_to_groups(csv, b',', 1).unwrap();
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[0].text, "name: Alice\nplan: Pro, yearly\nprice: 120");
+        // 빈 행은 건너뛰지만 행 번호는 유지
+        assert_eq!(groups[1].first_row, 3);
+        assert_eq!(groups[1].text, "name: Bob\nplan: Free");
+        assert!(groups[2].text.ends_with("column 4: extra"));
+
+        let groups = rows_to_groups(csv, b',', 2).unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!((groups[0].first_row, groups[0].last_row), (1, 3));
+        assert!(groups[0].text.contains("price: 120\n\nname: Bob"));
+    }
+
+    #[test]
+    fn test_delimiter_for() {
+        assert_eq!(delimiter_for(Path::new("data/faq.CSV")), Some(b','));
+        assert_eq!(delimiter_for(Path::new("data/faq.tsv")), Some(b'\t'));
+        assert_eq!(delimiter_for(Path::new("data/faq.txt")), None);
+    }
+}
@@ -0,0 +1,199 @@
+//! 문서 제목 결정 규칙
+//!
+//! 제목 후보(front matter, 첫 H1, `og:title`, `<title>`, 파일명)를 설정된 우선순위대로
+//! 시도하여 처음으로 비어 있지 않은 값을 사용합니다. "Docs"처럼 사이트 전체에 공통인
+//! 제목은 `ignore`에 넣어 다음 후보로 넘어가게 합니다.
+//!
+//! ```toml
+//! [titles]
+//! order = ["front-matter", "h1", "og-title", "html-title", "filename"]
+//! ignore = ["Docs"]
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// 제목 후보
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TitleSource {
+    /// 파일 앞의 YAML(`---`)/TOML(`+++`) front matter `title`
+    FrontMatter,
+    /// 첫 번째 최상위 제목 (Markdown `# 제목`, HTML `<h1>`)
+    H1,
+    /// HTML `<meta property="og:title">`
+    OgTitle,
+    /// HTML `<title>`
+    HtmlTitle,
+    /// 파일 이름 (파일 수집에만 해당)
+    Filename,
+}
+
+/// 제목 결정 규칙
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TitleRules {
+    /// 시도 순서 (목록에 없는 후보는 사용하지 않음)
+    pub order: Vec<TitleSource>,
+    /// 무시할 제목 (대소문자 무시, 앞뒤 공백 제외 후 비교)
+    pub ignore: Vec<String>,
+}
+
+impl Default for TitleRules {
+    /// 웹은 기존처럼 `<title>` → `<h1>`, 파일은 front matter/H1이 없을 때 파일명
+    fn default() -> Self {
+        Self {
+            order: vec![
+                TitleSource::FrontMatter,
+                TitleSource::HtmlTitle,
+                TitleSource::H1,
+                TitleSource::Filename,
+            ],
+            ignore: Vec::new(),
+        }
+    }
+}
+
+impl TitleRules {
+    /// 우선순위대로 후보를 조회하여 처음으로 사용할 수 있는 제목
+    pub fn pick(&self, mut lookup: impl FnMut(TitleSource) -> Option<String>) -> Option<String> {
+        self.order.iter().find_map(|&source| {
+            let title = lookup(source)?;
+            let title = title.trim();
+            let ignored = self
+                .ignore
+                .iter()
+                .any(|i| i.trim().eq_ignore_ascii_case(title));
+            (!title.is_empty() && !ignored).then(|| title.to_string())
+        })
+    }
+
+    /// 텍스트 파일의 제목 (front matter, Markdown H1, 파일명)
+    pub fn for_text(&self, text: &str, file_name: Option<&str>) -> Option<String> {
+        self.pick(|source| match source {
+            TitleSource::FrontMatter => front_matter_title(text),
+            TitleSource::H1 => markdown_h1(text),
+            TitleSource::Filename => file_name.map(str::to_string),
+            TitleSource::OgTitle | TitleSource::HtmlTitle => None,
+        })
+    }
+}
+
+/// front matter 블록과 나머지 본문 분리 (front matter가 없으면 None)
+fn split_front_matter(text: &str) -> (Option<&str>, &str) {
+    let text = text.trim_start_matches('\u{feff}');
+    for fence in ["---", "+++"] {
+        let Some(rest) = text.strip_prefix(fence) else {
+            continue;
+        };
+        let Some(rest) = rest
+            .strip_prefix('\n')
+            .or_else(|| rest.strip_prefix("\r\n"))
+        else {
+            continue;
+        };
+
+        let mut offset = 0;
+        for line in rest.split_inclusive('\n') {
+            if line.trim_end() == fence {
+                return (Some(&rest[..offset]), &rest[offset + line.len()..]);
+            }
+            offset += line.len();
+        }
+    }
+    (None, text)
+}
+
+/// front matter의 `title: ...` (YAML) 또는 `title = "..."` (TOML)
+pub fn front_matter_title(text: &str) -> Option<String> {
+    let (front_matter, _) = split_front_matter(text);
+    front_matter?.lines().find_map(|line| {
+        let value = line
+            .strip_prefix("title")?
+            .trim_start()
+            .strip_prefix([':', '='])?
+            .trim();
+        let value = value
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+            .unwrap_or(value);
+        (!value.is_empty()).then(|| value.to_string())
+    })
+}
+
+/// 첫 번째 Markdown H1 (`# 제목`, front matter와 코드 블록 제외)
+pub fn markdown_h1(text: &str) -> Option<String> {
+    let (_, body) = split_front_matter(text);
+    let mut in_code_block = false;
+
+    for line in body.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+        if let Some(title) = line.strip_prefix("# ") {
+            let title = title.trim().trim_end_matches('#').trim();
+            if !title.is_empty() {
+                return Some(title.to_string());
+            }
+        }
+    }
+    None
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_front_matter_and_h1() {
+        let yaml = "---\nlayout: post\ntitle: \"Release Notes\"\n---\n# Changes\n\nbody";
+        assert_eq!(front_matter_title(yaml), Some("Release Notes".to_string()));
+        assert_eq!(markdown_h1(yaml), Some("Changes".to_string()));
+
+        let toml = "+++\ntitle = 'Guide'\n+++\nbody";
+        assert_eq!(front_matter_title(toml), Some("Guide".to_string()));
+
+        // 코드 블록 안의 주석은 제목이 아님
+        let code = "```sh\n# install\n```\n\n# Setup\n";
+        assert_eq!(markdown_h1(code), Some("Setup".to_string()));
+        assert_eq!(front_matter_title(code), None);
+    }
+
+    #[test]
+    fn test_pick_follows_order_and_ignore() {
+        let text = "# Docs\n\nbody";
+
+        let default = TitleRules::default();
+        assert_eq!(
+            default.for_text(text, Some("a.md")),
+            Some("Docs".to_string())
+        );
+
+        let rules = TitleRules {
+            order: vec![TitleSource::H1, TitleSource::Filename],
+            ignore: vec!["docs".to_string()],
+        };
+        assert_eq!(rules.for_text(text, Some("a.md")), Some("a.md".to_string()));
+
+        let filename_first = TitleRules {
+            order: vec![TitleSource::Filename, TitleSource::H1],
+            ignore: Vec::new(),
+        };
+        assert_eq!(
+            filename_first.for_text(text, Some("a.md")),
+            Some("a.md".to_string())
+        );
+        assert_eq!(
+            filename_first.for_text(text, None),
+            Some("Docs".to_string())
+        );
+    }
+}
@@ -0,0 +1,198 @@
+//! 답변 생성 모듈
+//!
+//! 검색 컨텍스트(`AskContext`)를 바탕으로 Gemini API로 질문에 대한 답변을 생성합니다.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::embedding::get_api_key;
+use crate::knowledge::AskContext;
+
+/// Gemini 답변 생성 API 엔드포인트
+pub(crate) const GEMINI_GENERATE_URL: &str =
+    "https://generativelanguage.googleapis.com/v1beta/models/gemini-2.0-flash:generateContent";
+
+/// 답변 생성 최대 토큰 수
+const MAX_OUTPUT_TOKENS: u32 = 2048;
+
+// ============================================================================
+// GeminiGenerator
+// ============================================================================
+
+/// Gemini 답변 생성기
+pub struct GeminiGenerator {
+    api_key: String,
+    client: reqwest::Client,
+}
+
+impl GeminiGenerator {
+    /// 새 생성기 생성
+    pub fn new(api_key: String) -> Self {
+        Self {
+            api_key,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// 환경변수/키링의 API 키로 생성
+    pub fn from_env() -> Result<Self> {
+        Ok(Self::new(get_api_key()?))
+    }
+
+    /// 컨텍스트를 바탕으로 질문에 답변
+    pub async fn answer(&self, question: &str, context: &AskContext) -> Result<String> {
+        self.generate(&build_prompt(question, context)).await
+    }
+
+    /// 프롬프트로 텍스트 생성
+    pub async fn generate(&self, prompt: &str) -> Result<String> {
+        let request = GenerateRequest {
+            contents: vec![GenerateContent {
+                parts: vec![TextPart {
+                    text: prompt.to_string(),
+                }],
+            }],
+            generation_config: GenerationConfig {
+                temperature: 0.2,
+                max_output_tokens: MAX_OUTPUT_TOKENS,
+            },
+        };
+
+        let response = self
+            .client
+            .post(GEMINI_GENERATE_URL)
+            .header("x-goog-api-key", &self.api_key)
+            .json(&request)
+            .send()
+            .await
+            .context("Failed to send generate API request")?;
+
+        let status = response.status();
+        let body = response.text().await?;
+
+        if !status.is_success() {
+            anyhow::bail!("Generate API error ({}): {}", status, body);
+        }
+
+        let generate_response: GenerateResponse =
+            serde_json::from_str(&body).context("Failed to parse generate API response")?;
+
+        let text = generate_response
+            .candidates
+            .into_iter()
+            .next()
+            .map(|c| {
+                c.content
+                    .parts
+                    .into_iter()
+                    .map(|p| p.text)
+                    .collect::<Vec<_>>()
+                    .join("")
+            })
+            .unwrap_or_default();
+
+        if text.trim().is_empty() {
+            anyhow::bail!("Generate API returned an empty answer");
+        }
+
+        Ok(text)
+    }
+}
+
+/// 답변 생성 프롬프트 구성
+pub fn build_prompt(question: &str, context: &AskContext) -> String {
+    format!(
+        "{}\n\n## 참고 문서\n\n{}## 질문\n\n{}\n\n## 답변\n",
+        ANSWER_INSTRUCTIONS,
+        context.render(),
+        question.trim()
+    )
+}
+
+/// 답변 생성 지시사항
+const ANSWER_INSTRUCTIONS: &str = r#"아래 참고 문서만을 근거로 질문에 답변해주세요.
+
+지시사항:
+1. 참고 문서에 없는 내용은 추측하지 말고 "문서에서 찾을 수 없습니다"라고 답합니다
+2. 코드 예시는 참고 문서의 코드를 우선 사용합니다
+3. 답변에 사용한 문서는 문장 끝에 참고 문서 제목 앞의 번호로 [1], [2]처럼 인용합니다 (URL은 따로 적지 않습니다)
+4. 질문과 같은 언어로 답변합니다"#;
+
+// ============================================================================
+// API Types
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+struct GenerateRequest {
+    contents: Vec<GenerateContent>,
+    #[serde(rename = "generationConfig")]
+    generation_config: GenerationConfig,
+}
+
+#[derive(Debug, Serialize)]
+struct GenerateContent {
+    parts: Vec<TextPart>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TextPart {
+    text: String,
+}
+
+#[derive(Debug, Serialize)]
+struct GenerationConfig {
+    temperature: f32,
+    #[serde(rename = "maxOutputTokens")]
+    max_output_tokens: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct GenerateResponse {
+    #[serde(default)]
+    candidates: Vec<Candidate>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Candidate {
+    content: CandidateContent,
+}
+
+#[derive(Debug, Deserialize)]
+struct CandidateContent {
+    #[serde(default)]
+    parts: Vec<TextPart>,
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::ContextChunk;
+
+    #[test]
+    fn test_build_prompt() {
+        let context = AskContext {
+            query: "how to pin".to_string(),
+            chunks: vec![ContextChunk {
+                doc_id: 1,
+                chunk_index: Some(0),
+                url: "https://wiki.example.com/guide".to_string(),
+                title: Some("Internal Guide".to_string()),
+                text: "Use `pin --id`.".to_string(),
+                score: 0.9,
+                pinned: true,
+                citation: None,
+            }],
+        };
+
+        let prompt = build_prompt("how to pin?", &context);
+
+        assert!(prompt.starts_with(ANSWER_INSTRUCTIONS));
+        assert!(prompt.contains("### [1] Internal Guide (고정)"));
+        assert!(prompt.contains("출처: https://wiki.example.com/guide"));
+        assert!(prompt.trim_end().ends_with("## 답변"));
+    }
+}
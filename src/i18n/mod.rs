@@ -0,0 +1,921 @@
+//! 출력 언어 - 한국어/영어 메시지 카탈로그
+//!
+//! CLI 출력 문구를 `Msg` 키로 찾아 현재 언어의 문구를 돌려줍니다.
+//! 언어는 `PALANK_RAG_LANG` 환경 변수가 설정 파일의 `lang`보다 우선하며,
+//! 둘 다 없으면 한국어입니다.
+//!
+//! ```toml
+//! lang = "en"
+//! ```
+//!
+//! 카탈로그 대상은 팀원이 매일 쓰는 명령의 출력입니다:
+//! `query`(`--collections`, `--explain`, `--bundle` 포함), `list`, `show`, `delete`,
+//! `pin`/`unpin`, `pins`, `open`, `status`, `ingest`의 진행/요약 메시지, `reembed`와
+//! `repair`의 진행/요약 메시지, 그리고 API 키 안내.
+//! 그 밖의 명령(`watch`, `daemon`, `compact`, `bundle` 등)의 메시지와 로그는
+//! 아직 카탈로그 밖이라 한국어로 출력됩니다.
+//! 수집한 문서 본문에 들어가는 문구(대화 참여자 표기 등)는 출력이 아니므로 언어 설정과
+//! 관계없이 같습니다. 언어를 바꿨다고 같은 원본의 본문과 임베딩이 달라지면 안 되기 때문입니다.
+//!
+//! 새 문구를 옮길 때는 `Msg`에 변형을 추가하고 `catalog`에 두 언어 문구를 함께 적습니다.
+//!
+//! ## 사용법
+//! ```rust,ignore
+//! use palank_rag::i18n::{self, Lang, Msg};
+//!
+//! i18n::set_lang(Lang::En);
+//! println!("{}", Msg::NoResults.text());
+//! println!("{}", Msg::ResultsHeader.format(&[&3]));
+//! ```
+
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+/// 언어 지정 환경 변수
+pub const LANG_ENV: &str = "PALANK_RAG_LANG";
+
+static CURRENT: AtomicU8 = AtomicU8::new(Lang::Ko as u8);
+
+// ============================================================================
+// Lang
+// ============================================================================
+
+/// 출력 언어
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Lang {
+    #[default]
+    Ko,
+    En,
+}
+
+impl Lang {
+    /// 언어 이름 파싱 (`ko`, `en`, `en_US.UTF-8`, `korean` 등, 대소문자 무시)
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim().to_ascii_lowercase();
+        let code = value.split(['_', '-', '.']).next().unwrap_or_default();
+        match code {
+            "ko" | "kr" | "korean" => Some(Self::Ko),
+            "en" | "english" => Some(Self::En),
+            _ => None,
+        }
+    }
+
+    /// 환경 변수, 설정 순서로 언어 결정 (알 수 없는 값은 무시)
+    pub fn resolve(configured: Option<Lang>) -> Self {
+        std::env::var(LANG_ENV)
+            .ok()
+            .and_then(|value| Self::parse(&value))
+            .or(configured)
+            .unwrap_or_default()
+    }
+}
+
+/// 현재 출력 언어
+pub fn current() -> Lang {
+    match CURRENT.load(Ordering::Relaxed) {
+        x if x == Lang::En as u8 => Lang::En,
+        _ => Lang::Ko,
+    }
+}
+
+/// 출력 언어 변경
+pub fn set_lang(lang: Lang) {
+    CURRENT.store(lang as u8, Ordering::Relaxed);
+}
+
+// ============================================================================
+// Msg
+// ============================================================================
+
+/// 메시지 키
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Msg {
+    ApiKeyMissing,
+    ApiKeyHelp,
+    ApiKeySetupHint,
+    Searching,
+    ReportSaved,
+    PartialResults,
+    NoResults,
+    ResultsHeader,
+    LabelTitle,
+    LabelLocation,
+    LabelCitation,
+    LabelContent,
+    LabelSnippet,
+    QueryId,
+    FeedbackHint,
+    OpenHint,
+    NoDocuments,
+    DocumentsHeader,
+    Published,
+    Expired,
+    ExpiresAt,
+    DocNotFound,
+    UrlNotFound,
+    FileNotFound,
+    DocDeleted,
+    VectorCleanupNote,
+    NothingToDelete,
+    DocPinned,
+    DocUnpinned,
+    StatusDataDir,
+    StatusApiKeySet,
+    StatusApiKeyMissing,
+    StatusDocuments,
+    StatusContentBytes,
+    StatusStatsFailed,
+    StatusStoreOpenFailed,
+    StatusVectorIndex,
+    StatusVectorTable,
+    StatusVectorNoIndex,
+    StatusVectorIndexDetail,
+    StatusVectorFragmented,
+    StatusVectorStaleIndex,
+    StatusVectorUnindexed,
+    StatusVectorHealthy,
+    StatusDescribeFailed,
+    SearchingCollections,
+    CollectionNotFound,
+    NoCollections,
+    SearchingBundle,
+    ResultLine,
+    ExplainHeader,
+    ExplainFts,
+    ExplainFtsMissing,
+    ExplainVector,
+    ExplainVectorMissing,
+    ShowHeader,
+    ShowTitle,
+    ShowFramework,
+    ShowIngested,
+    ShowLength,
+    ShowPinned,
+    ShowExpired,
+    ShowExpiresAt,
+    ShowCitation,
+    ShowProvenance,
+    ProvenanceSource,
+    ProvenanceExtractor,
+    ProvenanceCommand,
+    ProvenanceHost,
+    ProvenanceVersion,
+    ProvenanceMissing,
+    ShowContent,
+    IngestScraping,
+    IngestUrlTooShort,
+    IngestSaving,
+    IngestDocAdded,
+    IngestUrl,
+    IngestChangesAdded,
+    IngestNoChanges,
+    IngestNoFailureList,
+    IngestRetryingFailures,
+    IngestGitChanged,
+    IngestTooLarge,
+    IngestUnsupported,
+    IngestNoFiles,
+    IngestTargets,
+    IngestFileTypes,
+    IngestTotalSize,
+    IngestVisionWarning,
+    IngestItemFailed,
+    IngestItemTooShort,
+    IngestExtracted,
+    IngestExtractedSkipped,
+    IngestKeyUsage,
+    IngestKeyUsageLine,
+    IngestNoBibEntries,
+    IngestBibEntries,
+    IngestCitationUpdated,
+    IngestBibSkipped,
+    IngestItemAdded,
+    IngestBibDone,
+    IngestRunningSql,
+    IngestNoRows,
+    IngestRows,
+    IngestDone,
+    IngestNoBookmarks,
+    IngestBookmarks,
+    IngestBookmarkTooShort,
+    IngestBookmarkScraped,
+    IngestBookmarkScrapeFailed,
+    IngestBookmarksDone,
+    IngestNoConversations,
+    IngestConversations,
+    IngestSavingDocs,
+    IngestSavingFiles,
+    IngestStoreFailed,
+    IngestFileStoreFailed,
+    IngestEmbedRetry,
+    IngestBatchRetry,
+    IngestQuotaStop,
+    IngestQuotaResume,
+    IngestCircuitStop,
+    IngestCircuitResume,
+    IngestLimitStop,
+    IngestLimitHint,
+    IngestSkippedResume,
+    IngestSkippedUnsupported,
+    IngestSkippedTooShort,
+    IngestFailureKinds,
+    IngestFailureList,
+    IngestRetryHint,
+    FailureUnsupportedFormat,
+    FailureTooLarge,
+    FailureExtraction,
+    FailureEmbedding,
+    FailureStore,
+    OpenNoLastResults,
+    OpenIndexOutOfRange,
+    OpenTargetMissing,
+    Opening,
+    NoPins,
+    PinHint,
+    PinsHeader,
+    RepairChecking,
+    RepairSqliteCorrupt,
+    RepairFtsMismatch,
+    RepairVectorsCorrupt,
+    RepairHealthy,
+    RepairNoBackup,
+    RepairRestoring,
+    RepairMovedAside,
+    RepairVectorsMovedAside,
+    RepairFtsRebuilt,
+    RepairNoApiKey,
+    RepairSyncing,
+    RepairDone,
+    RepairReembedded,
+    RepairOrphans,
+    RepairDocEmbeddings,
+    RepairFingerprinted,
+    RepairChunkRows,
+    RepairNormalized,
+    RepairNormalizedHint,
+    ReembedNothing,
+    ReembedModelCounts,
+    ReembedModelLine,
+    ReembedCurrentModel,
+    ReembedTargets,
+    ReembedQuotaStop,
+    ReembedQuotaResume,
+    ReembedDone,
+    ReembedRemaining,
+}
+
+impl Msg {
+    /// 모든 메시지 키 (카탈로그 검사용)
+    pub const ALL: &'static [Msg] = &[
+        Msg::ApiKeyMissing,
+        Msg::ApiKeyHelp,
+        Msg::ApiKeySetupHint,
+        Msg::Searching,
+        Msg::ReportSaved,
+        Msg::PartialResults,
+        Msg::NoResults,
+        Msg::ResultsHeader,
+        Msg::LabelTitle,
+        Msg::LabelLocation,
+        Msg::LabelCitation,
+        Msg::LabelContent,
+        Msg::LabelSnippet,
+        Msg::QueryId,
+        Msg::FeedbackHint,
+        Msg::OpenHint,
+        Msg::NoDocuments,
+        Msg::DocumentsHeader,
+        Msg::Published,
+        Msg::Expired,
+        Msg::ExpiresAt,
+        Msg::DocNotFound,
+        Msg::UrlNotFound,
+        Msg::FileNotFound,
+        Msg::DocDeleted,
+        Msg::VectorCleanupNote,
+        Msg::NothingToDelete,
+        Msg::DocPinned,
+        Msg::DocUnpinned,
+        Msg::StatusDataDir,
+        Msg::StatusApiKeySet,
+        Msg::StatusApiKeyMissing,
+        Msg::StatusDocuments,
+        Msg::StatusContentBytes,
+        Msg::StatusStatsFailed,
+        Msg::StatusStoreOpenFailed,
+        Msg::StatusVectorIndex,
+        Msg::StatusVectorTable,
+        Msg::StatusVectorNoIndex,
+        Msg::StatusVectorIndexDetail,
+        Msg::StatusVectorFragmented,
+        Msg::StatusVectorStaleIndex,
+        Msg::StatusVectorUnindexed,
+        Msg::StatusVectorHealthy,
+        Msg::StatusDescribeFailed,
+        Msg::SearchingCollections,
+        Msg::CollectionNotFound,
+        Msg::NoCollections,
+        Msg::SearchingBundle,
+        Msg::ResultLine,
+        Msg::ExplainHeader,
+        Msg::ExplainFts,
+        Msg::ExplainFtsMissing,
+        Msg::ExplainVector,
+        Msg::ExplainVectorMissing,
+        Msg::ShowHeader,
+        Msg::ShowTitle,
+        Msg::ShowFramework,
+        Msg::ShowIngested,
+        Msg::ShowLength,
+        Msg::ShowPinned,
+        Msg::ShowExpired,
+        Msg::ShowExpiresAt,
+        Msg::ShowCitation,
+        Msg::ShowProvenance,
+        Msg::ProvenanceSource,
+        Msg::ProvenanceExtractor,
+        Msg::ProvenanceCommand,
+        Msg::ProvenanceHost,
+        Msg::ProvenanceVersion,
+        Msg::ProvenanceMissing,
+        Msg::ShowContent,
+        Msg::IngestScraping,
+        Msg::IngestUrlTooShort,
+        Msg::IngestSaving,
+        Msg::IngestDocAdded,
+        Msg::IngestUrl,
+        Msg::IngestChangesAdded,
+        Msg::IngestNoChanges,
+        Msg::IngestNoFailureList,
+        Msg::IngestRetryingFailures,
+        Msg::IngestGitChanged,
+        Msg::IngestTooLarge,
+        Msg::IngestUnsupported,
+        Msg::IngestNoFiles,
+        Msg::IngestTargets,
+        Msg::IngestFileTypes,
+        Msg::IngestTotalSize,
+        Msg::IngestVisionWarning,
+        Msg::IngestItemFailed,
+        Msg::IngestItemTooShort,
+        Msg::IngestExtracted,
+        Msg::IngestExtractedSkipped,
+        Msg::IngestKeyUsage,
+        Msg::IngestKeyUsageLine,
+        Msg::IngestNoBibEntries,
+        Msg::IngestBibEntries,
+        Msg::IngestCitationUpdated,
+        Msg::IngestBibSkipped,
+        Msg::IngestItemAdded,
+        Msg::IngestBibDone,
+        Msg::IngestRunningSql,
+        Msg::IngestNoRows,
+        Msg::IngestRows,
+        Msg::IngestDone,
+        Msg::IngestNoBookmarks,
+        Msg::IngestBookmarks,
+        Msg::IngestBookmarkTooShort,
+        Msg::IngestBookmarkScraped,
+        Msg::IngestBookmarkScrapeFailed,
+        Msg::IngestBookmarksDone,
+        Msg::IngestNoConversations,
+        Msg::IngestConversations,
+        Msg::IngestSavingDocs,
+        Msg::IngestSavingFiles,
+        Msg::IngestStoreFailed,
+        Msg::IngestFileStoreFailed,
+        Msg::IngestEmbedRetry,
+        Msg::IngestBatchRetry,
+        Msg::IngestQuotaStop,
+        Msg::IngestQuotaResume,
+        Msg::IngestCircuitStop,
+        Msg::IngestCircuitResume,
+        Msg::IngestLimitStop,
+        Msg::IngestLimitHint,
+        Msg::IngestSkippedResume,
+        Msg::IngestSkippedUnsupported,
+        Msg::IngestSkippedTooShort,
+        Msg::IngestFailureKinds,
+        Msg::IngestFailureList,
+        Msg::IngestRetryHint,
+        Msg::FailureUnsupportedFormat,
+        Msg::FailureTooLarge,
+        Msg::FailureExtraction,
+        Msg::FailureEmbedding,
+        Msg::FailureStore,
+        Msg::OpenNoLastResults,
+        Msg::OpenIndexOutOfRange,
+        Msg::OpenTargetMissing,
+        Msg::Opening,
+        Msg::NoPins,
+        Msg::PinHint,
+        Msg::PinsHeader,
+        Msg::RepairChecking,
+        Msg::RepairSqliteCorrupt,
+        Msg::RepairFtsMismatch,
+        Msg::RepairVectorsCorrupt,
+        Msg::RepairHealthy,
+        Msg::RepairNoBackup,
+        Msg::RepairRestoring,
+        Msg::RepairMovedAside,
+        Msg::RepairVectorsMovedAside,
+        Msg::RepairFtsRebuilt,
+        Msg::RepairNoApiKey,
+        Msg::RepairSyncing,
+        Msg::RepairDone,
+        Msg::RepairReembedded,
+        Msg::RepairOrphans,
+        Msg::RepairDocEmbeddings,
+        Msg::RepairFingerprinted,
+        Msg::RepairChunkRows,
+        Msg::RepairNormalized,
+        Msg::RepairNormalizedHint,
+        Msg::ReembedNothing,
+        Msg::ReembedModelCounts,
+        Msg::ReembedModelLine,
+        Msg::ReembedCurrentModel,
+        Msg::ReembedTargets,
+        Msg::ReembedQuotaStop,
+        Msg::ReembedQuotaResume,
+        Msg::ReembedDone,
+        Msg::ReembedRemaining,
+    ];
+
+    /// 현재 언어 문구
+    pub fn text(self) -> &'static str {
+        self.text_in(current())
+    }
+
+    /// 지정 언어 문구
+    pub fn text_in(self, lang: Lang) -> &'static str {
+        let (ko, en) = catalog(self);
+        match lang {
+            Lang::Ko => ko,
+            Lang::En => en,
+        }
+    }
+
+    /// 현재 언어 문구의 `{}`를 순서대로 채움
+    pub fn format(self, args: &[&dyn fmt::Display]) -> String {
+        fill(self.text(), args)
+    }
+}
+
+/// 메시지 카탈로그 (한국어, 영어)
+fn catalog(msg: Msg) -> (&'static str, &'static str) {
+    match msg {
+        Msg::ApiKeyMissing => (
+            "API 키가 설정되지 않았습니다.\n\
+             설정: export GEMINI_API_KEY=your-key 또는 palank-rag auth set",
+            "API key is not set.\n\
+             Set it with: export GEMINI_API_KEY=your-key or palank-rag auth set",
+        ),
+        Msg::ApiKeyHelp => (
+            "API 키가 설정되지 않았습니다.\n\n\
+             설정 방법:\n  \
+             export GEMINI_API_KEY=your-api-key\n  \
+             또는\n  \
+             export GOOGLE_AI_API_KEY=your-api-key\n  \
+             또는 (OS 키링에 저장)\n  \
+             palank-rag auth set\n\n\
+             API 키 발급: https://aistudio.google.com/app/apikey",
+            "API key is not set.\n\n\
+             To set it:\n  \
+             export GEMINI_API_KEY=your-api-key\n  \
+             or\n  \
+             export GOOGLE_AI_API_KEY=your-api-key\n  \
+             or (store in the OS keyring)\n  \
+             palank-rag auth set\n\n\
+             Get an API key: https://aistudio.google.com/app/apikey",
+        ),
+        Msg::ApiKeySetupHint => (
+            "    설정: export GEMINI_API_KEY=your-key 또는 palank-rag auth set",
+            "    Set it with: export GEMINI_API_KEY=your-key or palank-rag auth set",
+        ),
+        Msg::Searching => ("[*] 검색 중: \"{}\"", "[*] Searching: \"{}\""),
+        Msg::ReportSaved => ("[OK] 보고서 저장: {}", "[OK] Report saved: {}"),
+        Msg::PartialResults => (
+            "[!] 벡터 검색이 시간 예산({}ms)을 넘어 키워드 검색 결과만 표시합니다.",
+            "[!] Vector search exceeded the time budget ({}ms); showing keyword results only.",
+        ),
+        Msg::NoResults => ("\n[!] 검색 결과가 없습니다.", "\n[!] No results found."),
+        Msg::ResultsHeader => ("\n[OK] 검색 결과 ({} 건):\n", "\n[OK] Results ({}):\n"),
+        Msg::LabelTitle => ("   제목: {}", "   Title: {}"),
+        Msg::LabelLocation => ("   위치: {}", "   Location: {}"),
+        Msg::LabelCitation => ("   인용: {}", "   Citation: {}"),
+        Msg::LabelContent => ("   내용: {}", "   Content: {}"),
+        Msg::LabelSnippet => ("   스니펫: {}", "   Snippet: {}"),
+        Msg::QueryId => ("[*] 쿼리 ID: {}", "[*] Query ID: {}"),
+        Msg::FeedbackHint => (
+            "    판정: palank-rag feedback {} <문서 ID> --relevant|--irrelevant",
+            "    Judge: palank-rag feedback {} <doc ID> --relevant|--irrelevant",
+        ),
+        Msg::OpenHint => (
+            "    열기: palank-rag open <번호>",
+            "    Open: palank-rag open <number>",
+        ),
+        Msg::NoDocuments => ("[!] 저장된 문서가 없습니다.", "[!] No documents stored."),
+        Msg::DocumentsHeader => (
+            "[OK] 저장된 문서 ({} 건):\n",
+            "[OK] Stored documents ({}):\n",
+        ),
+        Msg::Published => ("        게시: {}", "        Published: {}"),
+        Msg::Expired => ("        만료됨: {}", "        Expired: {}"),
+        Msg::ExpiresAt => ("        만료 예정: {}", "        Expires: {}"),
+        Msg::DocNotFound => ("ID {}인 문서를 찾을 수 없습니다", "No document with ID {}"),
+        Msg::UrlNotFound => (
+            "URL '{}'인 문서를 찾을 수 없습니다",
+            "No document with URL '{}'",
+        ),
+        Msg::FileNotFound => ("파일을 찾을 수 없습니다: {}", "File not found: {}"),
+        Msg::DocDeleted => ("[OK] 문서 #{} 삭제됨", "[OK] Document #{} deleted"),
+        Msg::VectorCleanupNote => (
+            "     (주의: 벡터 인덱스는 별도로 정리가 필요할 수 있습니다)",
+            "     (Note: the vector index may need to be cleaned up separately)",
+        ),
+        Msg::NothingToDelete => (
+            "[!] 삭제할 문서를 찾을 수 없습니다",
+            "[!] No document to delete",
+        ),
+        Msg::DocPinned => (
+            "[OK] 문서 #{} 고정됨 (ask 컨텍스트에 항상 포함)",
+            "[OK] Document #{} pinned (always included in ask context)",
+        ),
+        Msg::DocUnpinned => ("[OK] 문서 #{} 고정 해제됨", "[OK] Document #{} unpinned"),
+        Msg::StatusDataDir => ("[*] 데이터 디렉토리: {}", "[*] Data directory: {}"),
+        Msg::StatusApiKeySet => ("[OK] API 키: 설정됨 ({})", "[OK] API key: set ({})"),
+        Msg::StatusApiKeyMissing => ("[!] API 키: 미설정", "[!] API key: not set"),
+        Msg::StatusDocuments => ("[OK] 저장된 문서: {} 건", "[OK] Stored documents: {}"),
+        Msg::StatusContentBytes => ("     총 콘텐츠: {} bytes", "     Total content: {} bytes"),
+        Msg::StatusStatsFailed => ("[!] 통계 조회 실패: {}", "[!] Failed to read stats: {}"),
+        Msg::StatusStoreOpenFailed => (
+            "[!] KnowledgeStore 열기 실패: {}",
+            "[!] Failed to open KnowledgeStore: {}",
+        ),
+        Msg::StatusVectorIndex => ("[OK] 벡터 인덱스: {} 청크", "[OK] Vector index: {} chunks"),
+        Msg::StatusVectorTable => (
+            "[*] 벡터 테이블 {}: {} 행, 버전 {}, 조각 {}개, 디스크 {}",
+            "[*] Vector table {}: {} rows, version {}, {} fragments, {} on disk",
+        ),
+        Msg::StatusVectorNoIndex => (
+            "     인덱스: 없음 (전체 스캔)",
+            "     Index: none (brute-force scan)",
+        ),
+        Msg::StatusVectorIndexDetail => (
+            "     인덱스 {} ({}): 색인 {} 행, 미색인 {} 행",
+            "     Index {} ({}): {} rows indexed, {} unindexed",
+        ),
+        Msg::StatusVectorFragmented => (
+            "[!] {}: 조각 {}개 - `palank-rag compact`로 병합하세요",
+            "[!] {}: {} fragments - run `palank-rag compact` to merge them",
+        ),
+        Msg::StatusVectorStaleIndex => (
+            "[!] {}: 인덱스에 없는 행 {}개 - `palank-rag compact`로 인덱스를 갱신하세요",
+            "[!] {}: {} rows missing from the index - run `palank-rag compact` to update it",
+        ),
+        Msg::StatusVectorUnindexed => (
+            "[*] {}: 벡터 인덱스 없이 {} 행을 전체 스캔 중 (검색이 느려질 수 있음)",
+            "[*] {}: scanning {} rows without a vector index (searches may slow down)",
+        ),
+        Msg::StatusVectorHealthy => (
+            "[OK] 벡터 인덱스 상태: 정리/재생성 필요 없음",
+            "[OK] Vector index health: no compaction or rebuild needed",
+        ),
+        Msg::StatusDescribeFailed => (
+            "[!] 벡터 저장소 상세 조회 실패: {}",
+            "[!] Failed to describe vector store: {}",
+        ),
+        Msg::SearchingCollections => (
+            "[*] 컬렉션 {}개 검색 중 ({}): \"{}\"",
+            "[*] Searching {} collections ({}): \"{}\"",
+        ),
+        Msg::CollectionNotFound => (
+            "컬렉션이 없습니다: {} ({})",
+            "Collection not found: {} ({})",
+        ),
+        Msg::NoCollections => ("검색할 컬렉션이 없습니다", "No collections to search"),
+        Msg::SearchingBundle => (
+            "[*] 번들 검색: {} (문서 {} 건, {} 생성)",
+            "[*] Searching bundle: {} ({} documents, created {})",
+        ),
+        Msg::ResultLine => (
+            "{}. [{}] [점수: {}] Doc #{}",
+            "{}. [{}] [Score: {}] Doc #{}",
+        ),
+        Msg::ExplainHeader => ("   근거:", "   Why:"),
+        Msg::ExplainFts => (
+            "     FTS  #{} bm25={}  {}  일치: {}",
+            "     FTS  #{} bm25={}  {}  matched: {}",
+        ),
+        Msg::ExplainFtsMissing => ("     FTS  (후보 아님)", "     FTS  (not a candidate)"),
+        Msg::ExplainVector => (
+            "     VEC  #{} 유사도={} (청크 #{})  {}",
+            "     VEC  #{} similarity={} (chunk #{})  {}",
+        ),
+        Msg::ExplainVectorMissing => ("     VEC  (후보 아님)", "     VEC  (not a candidate)"),
+        Msg::ShowHeader => ("[OK] 문서 #{}\n", "[OK] Document #{}\n"),
+        Msg::ShowTitle => ("  제목: {}", "  Title: {}"),
+        Msg::ShowFramework => ("  프레임워크: {}", "  Framework: {}"),
+        Msg::ShowIngested => ("  수집: {}", "  Ingested: {}"),
+        Msg::ShowLength => ("  길이: {} 자", "  Length: {} chars"),
+        Msg::ShowPinned => ("  고정: 예", "  Pinned: yes"),
+        Msg::ShowExpired => ("  만료됨: {}", "  Expired: {}"),
+        Msg::ShowExpiresAt => ("  만료: {}", "  Expires: {}"),
+        Msg::ShowCitation => ("  인용: {}", "  Citation: {}"),
+        Msg::ShowProvenance => ("\n  수집 출처:", "\n  Provenance:"),
+        Msg::ProvenanceSource => ("    경로: {}", "    Source: {}"),
+        Msg::ProvenanceExtractor => ("    추출: {}", "    Extractor: {}"),
+        Msg::ProvenanceCommand => ("    명령: {}", "    Command: {}"),
+        Msg::ProvenanceHost => ("    호스트: {}", "    Host: {}"),
+        Msg::ProvenanceVersion => ("    버전: palank-rag {}", "    Version: palank-rag {}"),
+        Msg::ProvenanceMissing => (
+            "    (기록 없음 - 출처 기록 이전에 수집된 문서)",
+            "    (none - ingested before provenance was recorded)",
+        ),
+        Msg::ShowContent => ("\n  내용: {}", "\n  Content: {}"),
+        Msg::IngestScraping => ("[*] URL 스크래핑 중: {}", "[*] Scraping URL: {}"),
+        Msg::IngestUrlTooShort => (
+            "[!] 본문이 너무 짧아 건너뜁니다 ({} < {} 자): {}",
+            "[!] Skipping, content too short ({} < {} chars): {}",
+        ),
+        Msg::IngestSaving => (
+            "[*] 문서 저장 및 임베딩 생성 중...",
+            "[*] Saving the document and generating embeddings...",
+        ),
+        Msg::IngestDocAdded => ("[OK] 문서가 추가되었습니다 (ID: {})", "[OK] Document added (ID: {})"),
+        Msg::IngestUrl => ("     URL: {}", "     URL: {}"),
+        Msg::IngestChangesAdded => (
+            "[OK] 변경 이력 문서 추가 (ID: {})",
+            "[OK] Changelog document added (ID: {})",
+        ),
+        Msg::IngestNoChanges => (
+            "[*] 이전 버전이 없거나 변경 사항이 없습니다.",
+            "[*] No previous version or no changes.",
+        ),
+        Msg::IngestNoFailureList => (
+            "[*] 직전 수집의 실패 목록이 없습니다.",
+            "[*] No failure list from the previous ingest.",
+        ),
+        Msg::IngestRetryingFailures => (
+            "[*] 직전 수집에서 실패한 {} 파일 재시도",
+            "[*] Retrying {} files that failed in the previous ingest",
+        ),
+        Msg::IngestGitChanged => ("[*] {} 이후 변경된 파일 {} 개", "[*] Files changed since {}: {}"),
+        Msg::IngestTooLarge => ("[!] 파일 크기 초과: {} ({} > {})", "[!] File too large: {} ({} > {})"),
+        Msg::IngestUnsupported => ("[!] 지원하지 않는 파일 형식: {}", "[!] Unsupported file format: {}"),
+        Msg::IngestNoFiles => ("[!] 수집할 파일이 없습니다.", "[!] No files to ingest."),
+        Msg::IngestTargets => ("[*] 수집 대상: {} 파일", "[*] Files to ingest: {}"),
+        Msg::IngestFileTypes => (
+            "    텍스트: {}, 이미지: {}, PDF: {}",
+            "    Text: {}, images: {}, PDF: {}",
+        ),
+        Msg::IngestTotalSize => ("    총 크기: {}", "    Total size: {}"),
+        Msg::IngestVisionWarning => (
+            "[!] 이미지 {} 개를 Gemini Vision으로 처리합니다. API 호출이 발생합니다.",
+            "[!] Processing {} images with Gemini Vision. This makes API calls.",
+        ),
+        Msg::IngestItemFailed => ("실패: {}", "failed: {}"),
+        Msg::IngestItemTooShort => ("본문이 너무 짧아 건너뜀", "skipped, content too short"),
+        Msg::IngestExtracted => ("추출 완료", "extracted"),
+        Msg::IngestExtractedSkipped => (
+            "추출 완료 (짧은 본문 {} 건너뜀)",
+            "extracted ({} short sections skipped)",
+        ),
+        Msg::IngestKeyUsage => ("[*] API 키별 사용량:", "[*] Usage per API key:"),
+        Msg::IngestKeyUsageLine => (
+            "    {}: 요청 {}, 성공 {}, 429 {}",
+            "    {}: requests {}, succeeded {}, 429 {}",
+        ),
+        Msg::IngestNoBibEntries => ("[!] 서지 항목이 없습니다.", "[!] No bibliography entries."),
+        Msg::IngestBibEntries => ("[*] 서지 항목: {} 개", "[*] Bibliography entries: {}"),
+        Msg::IngestCitationUpdated => ("인용 정보 갱신 (Doc #{})", "citation updated (Doc #{})"),
+        Msg::IngestBibSkipped => ("건너뜀 (연결 파일/초록 없음)", "skipped (no linked file or abstract)"),
+        Msg::IngestItemAdded => ("추가 (Doc #{})", "added (Doc #{})"),
+        Msg::IngestBibDone => (
+            "[OK] 완료: 추가 {}, 인용 갱신 {}, 건너뜀 {}, 실패 {}",
+            "[OK] Done: added {}, citations updated {}, skipped {}, failed {}",
+        ),
+        Msg::IngestRunningSql => ("[*] SQL 쿼리 실행 중...", "[*] Running SQL query..."),
+        Msg::IngestNoRows => ("[!] 쿼리 결과가 없습니다.", "[!] The query returned no rows."),
+        Msg::IngestRows => ("[*] 결과 행: {} 개", "[*] Rows: {}"),
+        Msg::IngestDone => ("[OK] 완료: 성공 {}, 실패 {}", "[OK] Done: succeeded {}, failed {}"),
+        Msg::IngestNoBookmarks => ("[!] 수집할 북마크가 없습니다.", "[!] No bookmarks to ingest."),
+        Msg::IngestBookmarks => ("[*] 북마크: {} 개", "[*] Bookmarks: {}"),
+        Msg::IngestBookmarkTooShort => (
+            "[{}/{}] {} ... 본문이 너무 짧음, 북마크 정보만 저장",
+            "[{}/{}] {} ... content too short, saving the bookmark only",
+        ),
+        Msg::IngestBookmarkScraped => ("[{}/{}] {} ... 스크랩 완료", "[{}/{}] {} ... scraped"),
+        Msg::IngestBookmarkScrapeFailed => (
+            "[{}/{}] {} ... 스크랩 실패 ({}), 북마크 정보만 저장",
+            "[{}/{}] {} ... scrape failed ({}), saving the bookmark only",
+        ),
+        Msg::IngestBookmarksDone => (
+            "[OK] 완료: 성공 {}, 실패 {} (스크랩 실패 {}, 짧은 본문 {})",
+            "[OK] Done: succeeded {}, failed {} (scrape failed {}, short content {})",
+        ),
+        Msg::IngestNoConversations => ("[!] 수집할 대화가 없습니다.", "[!] No conversations to ingest."),
+        Msg::IngestConversations => ("[*] 대화: {} 개 (발화 {} 개)", "[*] Conversations: {} ({} turns)"),
+        Msg::IngestSavingDocs => ("[*] {} 문서 저장 중...", "[*] Saving {} documents..."),
+        Msg::IngestSavingFiles => (
+            "[*] {} 파일 ({} 문서) 저장 중...",
+            "[*] Saving {} files ({} documents)...",
+        ),
+        Msg::IngestStoreFailed => ("[!] 저장 실패: {}", "[!] Save failed: {}"),
+        Msg::IngestFileStoreFailed => ("[!] 저장 실패: {}: {}", "[!] Save failed: {}: {}"),
+        Msg::IngestEmbedRetry => (
+            "[!] 임베딩 실패, 남은 파일을 다시 저장합니다: {}",
+            "[!] Embedding failed, saving the remaining files again: {}",
+        ),
+        Msg::IngestBatchRetry => (
+            "[!] 배치 저장 실패, 파일별로 다시 저장합니다: {}",
+            "[!] Batch save failed, saving file by file: {}",
+        ),
+        Msg::IngestQuotaStop => ("[!] 저장 실패: 일일 할당량 소진", "[!] Save failed: daily quota exhausted"),
+        Msg::IngestQuotaResume => (
+            "    완료 {} 파일, 내일 같은 명령에 --resume 을 붙여 이어서 수집하세요",
+            "    {} files done. Run the same command with --resume tomorrow to continue",
+        ),
+        Msg::IngestCircuitStop => (
+            "[!] 저장 실패: 임베딩 API 연속 실패로 수집을 중단합니다",
+            "[!] Save failed: stopping after repeated embedding API failures",
+        ),
+        Msg::IngestCircuitResume => (
+            "    완료 {} 파일, API 복구 후 같은 명령에 --resume 을 붙여 이어서 수집하세요",
+            "    {} files done. Once the API recovers, run the same command with --resume to continue",
+        ),
+        Msg::IngestLimitStop => (
+            "[!] 실행당 수집 한도를 넘어 수집을 중단합니다",
+            "[!] Stopping: the per-run ingest limit was exceeded",
+        ),
+        Msg::IngestLimitHint => (
+            "    완료 {} 건, 범위를 좁히거나 설정 파일의 [limits] 값을 늘려 다시 실행하세요",
+            "    {} done. Narrow the scope or raise [limits] in the config file and run again",
+        ),
+        Msg::IngestSkippedResume => (
+            "    이미 수집됨 (--resume): {}",
+            "    Already ingested (--resume): {}",
+        ),
+        Msg::IngestSkippedUnsupported => (
+            "    지원하지 않는 확장자 (건너뜀): {}",
+            "    Unsupported extension (skipped): {}",
+        ),
+        Msg::IngestSkippedTooShort => (
+            "    본문이 너무 짧음 (건너뜀): {}",
+            "    Content too short (skipped): {}",
+        ),
+        Msg::IngestFailureKinds => ("[!] 실패 유형:", "[!] Failures by type:"),
+        Msg::IngestFailureList => ("    실패 목록: {}", "    Failure list: {}"),
+        Msg::IngestRetryHint => (
+            "    실패한 파일만 재시도: palank-rag ingest --errors-only",
+            "    Retry only the failed files: palank-rag ingest --errors-only",
+        ),
+        Msg::FailureUnsupportedFormat => ("지원하지 않는 형식", "Unsupported format"),
+        Msg::FailureTooLarge => ("파일 크기 초과", "File too large"),
+        Msg::FailureExtraction => ("추출 실패", "Extraction failed"),
+        Msg::FailureEmbedding => ("임베딩 할당량/API", "Embedding quota/API"),
+        Msg::FailureStore => ("저장 실패", "Save failed"),
+        Msg::OpenNoLastResults => (
+            "저장된 검색 결과가 없습니다. 먼저 palank-rag query를 실행하세요",
+            "No saved search results. Run palank-rag query first",
+        ),
+        Msg::OpenIndexOutOfRange => (
+            "결과 번호는 1~{} 사이여야 합니다",
+            "The result number must be between 1 and {}",
+        ),
+        Msg::OpenTargetMissing => ("결과 번호 또는 --id가 필요합니다", "A result number or --id is required"),
+        Msg::Opening => ("[*] 문서 #{} 열기: {}", "[*] Opening document #{}: {}"),
+        Msg::NoPins => ("[!] 고정된 문서가 없습니다.", "[!] No pinned documents."),
+        Msg::PinHint => (
+            "    고정: palank-rag pin --id <ID> 또는 --url <URL>",
+            "    To pin one: palank-rag pin --id <ID> or --url <URL>",
+        ),
+        Msg::PinsHeader => ("[OK] 고정된 문서 ({} 건):\n", "[OK] Pinned documents ({}):\n"),
+        Msg::RepairChecking => ("[*] 저장소 검사 중: {}", "[*] Checking store: {}"),
+        Msg::RepairSqliteCorrupt => ("[!] SQLite 손상: {}", "[!] SQLite corruption: {}"),
+        Msg::RepairFtsMismatch => ("[!] FTS5 인덱스 불일치: {}", "[!] FTS5 index mismatch: {}"),
+        Msg::RepairVectorsCorrupt => ("[!] 벡터 인덱스 손상: {}", "[!] Vector index corruption: {}"),
+        Msg::RepairHealthy => ("[OK] 손상 없음", "[OK] No corruption found"),
+        Msg::RepairNoBackup => (
+            "복원할 백업이 없습니다 (--backup DIR로 지정)",
+            "No backup to restore (specify one with --backup DIR)",
+        ),
+        Msg::RepairRestoring => ("[*] 백업에서 복원: {}", "[*] Restoring from backup: {}"),
+        Msg::RepairMovedAside => ("    손상 파일 보관: {}", "    Damaged file kept at: {}"),
+        Msg::RepairVectorsMovedAside => (
+            "[*] 손상된 벡터 인덱스 보관: {}",
+            "[*] Damaged vector index kept at: {}",
+        ),
+        Msg::RepairFtsRebuilt => (
+            "[OK] FTS5 인덱스 재생성: {} 건",
+            "[OK] FTS5 index rebuilt: {} documents",
+        ),
+        Msg::RepairNoApiKey => (
+            "[!] API 키가 없어 재임베딩을 건너뜁니다 (설정 후 repair 재실행)",
+            "[!] Skipping re-embedding without an API key (set one and run repair again)",
+        ),
+        Msg::RepairSyncing => ("[*] 벡터 인덱스 동기화 중...", "[*] Syncing the vector index..."),
+        Msg::RepairDone => ("[OK] 복구 완료", "[OK] Repair complete"),
+        Msg::RepairReembedded => ("     재임베딩: {} 건", "     Re-embedded: {}"),
+        Msg::RepairOrphans => ("     고아 벡터 삭제: {} 건", "     Orphan vectors removed: {}"),
+        Msg::RepairDocEmbeddings => ("     문서 임베딩 저장: {} 건", "     Document embeddings stored: {}"),
+        Msg::RepairFingerprinted => ("     청크 지문 기록: {} 건", "     Chunk fingerprints recorded: {}"),
+        Msg::RepairChunkRows => ("     고아 청크 기록 삭제: {} 건", "     Orphan chunk records removed: {}"),
+        Msg::RepairNormalized => (
+            "[OK] 제목/본문 정규화: {} 건",
+            "[OK] Normalized stored titles/content: {} documents",
+        ),
+        Msg::RepairNormalizedHint => (
+            "     바뀐 문서의 벡터는 palank-rag reembed --changed-chunker로 다시 만드세요",
+            "     Rebuild vectors for these documents with palank-rag reembed --changed-chunker",
+        ),
+        Msg::ReembedNothing => (
+            "[OK] 다시 임베딩할 문서가 없습니다 (청커: {}, 모델: {})",
+            "[OK] No documents to re-embed (chunker: {}, model: {})",
+        ),
+        Msg::ReembedModelCounts => ("[*] 모델별 청크 수:", "[*] Chunks per model:"),
+        Msg::ReembedModelLine => ("    {}: {} 청크{}", "    {}: {} chunks{}"),
+        Msg::ReembedCurrentModel => (" (현재)", " (current)"),
+        Msg::ReembedTargets => (
+            "[*] 재임베딩 대상: {} 문서 (전체 {}, 청커: {}, 모델: {})",
+            "[*] Re-embedding {} documents (total {}, chunker: {}, model: {})",
+        ),
+        Msg::ReembedQuotaStop => (
+            "[!] 재임베딩 중단: 임베딩 할당량/한도 소진",
+            "[!] Re-embedding stopped: embedding quota/limit exhausted",
+        ),
+        Msg::ReembedQuotaResume => (
+            "    완료된 문서 묶음은 저장되었습니다. 내일 같은 명령을 다시 실행하면 남은 문서부터 이어서 진행합니다",
+            "    Completed batches were saved. Run the same command again tomorrow to continue with the remaining documents",
+        ),
+        Msg::ReembedDone => ("[OK] 재임베딩 완료: {} 건", "[OK] Re-embedding complete: {} documents"),
+        Msg::ReembedRemaining => (
+            "    남은 문서 {} 건은 같은 명령을 다시 실행하여 이어서 처리하세요",
+            "    Run the same command again to process the remaining {} documents",
+        ),
+    }
+}
+
+/// 문구의 `{}`를 인자로 차례대로 채움 (남는 `{}`는 그대로 둠)
+fn fill(template: &str, args: &[&dyn fmt::Display]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut args = args.iter();
+    let mut rest = template;
+    while let Some(pos) = rest.find("{}") {
+        out.push_str(&rest[..pos]);
+        match args.next() {
+            Some(arg) => out.push_str(&arg.to_string()),
+            None => out.push_str("{}"),
+        }
+        rest = &rest[pos + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lang() {
+        assert_eq!(Lang::parse("en"), Some(Lang::En));
+        assert_eq!(Lang::parse("EN_us.UTF-8"), Some(Lang::En));
+        assert_eq!(Lang::parse("ko-KR"), Some(Lang::Ko));
+        assert_eq!(Lang::parse("Korean"), Some(Lang::Ko));
+        assert_eq!(Lang::parse("fr"), None);
+        assert_eq!(Lang::parse(""), None);
+    }
+
+    #[test]
+    fn test_catalog_placeholders_match() {
+        for msg in Msg::ALL {
+            let (ko, en) = catalog(*msg);
+            assert_eq!(
+                ko.matches("{}").count(),
+                en.matches("{}").count(),
+                "{:?}",
+                msg
+            );
+            assert!(!en.chars().any(|c| ('가'..='힣').contains(&c)), "{:?}", msg);
+        }
+    }
+
+    #[test]
+    fn test_fill() {
+        assert_eq!(fill("#{} 삭제됨", &[&3]), "#3 삭제됨");
+        assert_eq!(fill("{}:{}", &[&"a"]), "a:{}");
+        assert_eq!(fill("없음", &[&1]), "없음");
+        assert_eq!(
+            Msg::DocDeleted.text_in(Lang::En),
+            "[OK] Document #{} deleted"
+        );
+    }
+}
@@ -0,0 +1,182 @@
+//! 상용구 청크 억제 - 여러 문서에 거의 그대로 반복되는 청크 (쿠키 배너, 라이선스 문구)
+//!
+//! 수집 시 청크마다 정규화한 텍스트의 지문을 `chunk_fingerprints` 테이블에 기록합니다.
+//! 검색 시 벡터 결과 청크의 지문이 `min_docs`개 이상의 문서에 나오면 상용구로 보고
+//! 벡터 결과에서 뒤로 내리거나(demote) 빼냅니다(exclude).
+//! 일반적인 질의에서 상용구 청크가 벡터 결과 상위를 차지하지 않도록 합니다.
+//!
+//! ```toml
+//! [boilerplate]
+//! mode = "demote"   # off | demote | exclude
+//! min_docs = 5
+//! ```
+//!
+//! 지문은 대소문자, 숫자(연도, 버전), 문장부호, 공백 차이를 무시하므로
+//! "© 2023 Acme. All rights reserved."와 "© 2024 Acme - all rights reserved"는 같은 지문입니다.
+//! 지문 기록이 없는 청크(기록 이전에 수집)도 검색 시 청크 텍스트로 지문을 계산해 판별합니다.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::vector::SearchResult;
+use crate::normalize::{normalize, NormalizeOptions};
+
+/// 기본 상용구 판정 문서 수
+pub const DEFAULT_BOILERPLATE_MIN_DOCS: usize = 5;
+
+// ============================================================================
+// Config
+// ============================================================================
+
+/// 상용구 청크 처리 방식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BoilerplateMode {
+    /// 억제하지 않음
+    Off,
+    /// 벡터 결과에서 상용구가 아닌 청크 뒤로 내림
+    #[default]
+    Demote,
+    /// 벡터 결과에서 제외
+    Exclude,
+}
+
+/// 상용구 청크 억제 설정
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BoilerplateConfig {
+    /// 처리 방식
+    pub mode: BoilerplateMode,
+    /// 같은 지문이 이 수 이상의 문서에 나오면 상용구 (2 미만이면 억제하지 않음)
+    pub min_docs: usize,
+}
+
+impl Default for BoilerplateConfig {
+    fn default() -> Self {
+        Self {
+            mode: BoilerplateMode::default(),
+            min_docs: DEFAULT_BOILERPLATE_MIN_DOCS,
+        }
+    }
+}
+
+impl BoilerplateConfig {
+    /// 억제 설정이 켜져 있는지
+    pub fn is_enabled(&self) -> bool {
+        self.mode != BoilerplateMode::Off && self.min_docs >= 2
+    }
+}
+
+// ============================================================================
+// Functions
+// ============================================================================
+
+/// 청크 지문 (정규화한 텍스트의 SHA-256 앞 16자, 단어가 없으면 None)
+///
+/// 텍스트를 정규화(`NormalizeOptions::KEY`)한 뒤 숫자로만 된 단어와 문장부호는 버리고
+/// 나머지 단어를 이어 붙여 해시합니다.
+pub fn chunk_fingerprint(text: &str) -> Option<String> {
+    let normalized = normalize(text, NormalizeOptions::KEY)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty() && !word.chars().all(|c| c.is_numeric()))
+        .collect::<Vec<_>>()
+        .join(" ");
+    if normalized.is_empty() {
+        return None;
+    }
+
+    Some(
+        Sha256::digest(normalized.as_bytes())
+            .iter()
+            .take(8)
+            .map(|b| format!("{:02x}", b))
+            .collect(),
+    )
+}
+
+/// 벡터 결과에서 상용구 청크 내리기/제외
+///
+/// `doc_counts`는 지문별 문서 수입니다. demote는 상용구 청크를 상용구가 아닌 청크 뒤로
+/// 옮기며, 각 무리 안에서는 원래 순위를 유지합니다.
+pub fn suppress_boilerplate(
+    results: Vec<SearchResult>,
+    doc_counts: &HashMap<String, usize>,
+    config: &BoilerplateConfig,
+) -> Vec<SearchResult> {
+    if !config.is_enabled() {
+        return results;
+    }
+
+    let (boilerplate, content): (Vec<_>, Vec<_>) = results.into_iter().partition(|r| {
+        chunk_fingerprint(&r.chunk_text)
+            .and_then(|fp| doc_counts.get(&fp))
+            .is_some_and(|&count| count >= config.min_docs)
+    });
+    if !boilerplate.is_empty() {
+        tracing::debug!("Suppressed {} boilerplate chunks", boilerplate.len());
+    }
+
+    match config.mode {
+        BoilerplateMode::Exclude => content,
+        _ => content.into_iter().chain(boilerplate).collect(),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hit(doc_id: i64, text: &str) -> SearchResult {
+        SearchResult {
+            doc_id,
+            chunk_index: 0,
+            chunk_text: text.to_string(),
+            similarity: 0.9,
+        }
+    }
+
+    #[test]
+    fn test_chunk_fingerprint() {
+        assert_eq!(
+            chunk_fingerprint("© 2023 Acme. All rights reserved."),
+            chunk_fingerprint("© 2024 Acme - all   rights\nreserved")
+        );
+        assert_ne!(
+            chunk_fingerprint("We use cookies."),
+            chunk_fingerprint("We use cookies to improve your experience.")
+        );
+        assert_eq!(chunk_fingerprint("2024 --- 1.0"), None);
+        assert_eq!(chunk_fingerprint("cookies").unwrap().len(), 16);
+    }
+
+    #[test]
+    fn test_suppress_boilerplate() {
+        let banner = "We use cookies to improve your experience.";
+        let results = vec![hit(1, banner), hit(2, "Ownership rules"), hit(3, banner)];
+        let doc_counts = HashMap::from([(chunk_fingerprint(banner).unwrap(), 12)]);
+
+        let demoted =
+            suppress_boilerplate(results.clone(), &doc_counts, &BoilerplateConfig::default());
+        let order: Vec<i64> = demoted.iter().map(|r| r.doc_id).collect();
+        assert_eq!(order, vec![2, 1, 3]);
+
+        let exclude = BoilerplateConfig {
+            mode: BoilerplateMode::Exclude,
+            ..Default::default()
+        };
+        let excluded = suppress_boilerplate(results.clone(), &doc_counts, &exclude);
+        assert_eq!(excluded.len(), 1);
+
+        let strict = BoilerplateConfig {
+            mode: BoilerplateMode::Exclude,
+            min_docs: 20,
+        };
+        assert_eq!(suppress_boilerplate(results, &doc_counts, &strict).len(), 3);
+    }
+}
@@ -0,0 +1,369 @@
+//! 번들 - 읽기 전용 지식베이스 스냅샷 (`bundle create`, `query --bundle`)
+//!
+//! 프로젝트 지식베이스를 파일 하나로 묶어 동료에게 넘기면, 받은 쪽은 자기 지식베이스에
+//! 가져오지 않고 바로 검색할 수 있습니다. 번들은 SQLite 파일 하나입니다.
+//!
+//! - 원본 `knowledge.db`의 모든 테이블 (`VACUUM INTO` 스냅샷)
+//! - `bundle_vectors`: 청크 벡터 (임베딩은 리틀 엔디언 f32 BLOB)
+//! - `bundle_doc_vectors`: 문서 임베딩 (2단계 검색용)
+//! - `bundle_manifest`: 형식 버전, 문서/청크 수, 임베딩 차원 (JSON)
+//!
+//! 검색할 때는 번들을 읽기 전용으로 열어 `<데이터 디렉토리>/bundles/<번들 ID>/`에 저장소
+//! 형태로 한 번 풀어 두고 이후에는 다시 씁니다. 번들 파일 자체는 바꾸지 않습니다.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+
+use super::integrity::{SQLITE_FILE, VECTORS_DIR};
+use super::lance::LanceVectorStore;
+use super::vector::{VectorEntry, VectorStore, EMBEDDING_DIMENSION};
+
+/// 번들 형식 버전 (읽을 수 있는 최신 버전)
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// 푼 번들 저장소 디렉토리 (데이터 디렉토리 아래)
+pub const BUNDLES_DIR: &str = "bundles";
+
+/// 다 푼 번들 디렉토리에 마지막으로 쓰는 매니페스트 (없으면 다시 풂)
+const EXTRACTED_MANIFEST: &str = "bundle.json";
+
+/// 풀 때 한 번에 저장할 청크 벡터 수
+const EXTRACT_BATCH_SIZE: usize = 1024;
+
+// ============================================================================
+// Manifest
+// ============================================================================
+
+/// 번들 매니페스트
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub format_version: u32,
+    /// 번들 ID (푼 저장소 디렉토리 이름)
+    pub id: String,
+    pub created_at: DateTime<Utc>,
+    /// 만든 palank-rag 버전
+    pub app_version: String,
+    pub documents: usize,
+    pub chunks: usize,
+    /// 임베딩 차원 (검색하는 쪽 임베딩 모델과 같아야 함)
+    pub dimension: usize,
+}
+
+/// 번들 매니페스트 읽기 (형식 버전과 임베딩 차원 확인)
+pub fn read_manifest(bundle: &Path) -> Result<BundleManifest> {
+    let conn = open_read_only(bundle)?;
+    let json: String = conn
+        .query_row(
+            "SELECT value FROM bundle_manifest WHERE key = 'manifest'",
+            [],
+            |row| row.get(0),
+        )
+        .with_context(|| format!("Not a palank-rag bundle: {:?}", bundle))?;
+    let manifest: BundleManifest =
+        serde_json::from_str(&json).context("Failed to parse bundle manifest")?;
+    // ID는 디렉토리 이름으로 쓰므로 UUID만 허용
+    uuid::Uuid::parse_str(&manifest.id)
+        .with_context(|| format!("Invalid bundle ID: {:?}", manifest.id))?;
+
+    if manifest.format_version > BUNDLE_FORMAT_VERSION {
+        anyhow::bail!(
+            "Bundle format {} is newer than supported ({}); upgrade palank-rag",
+            manifest.format_version,
+            BUNDLE_FORMAT_VERSION
+        );
+    }
+    if manifest.dimension != EMBEDDING_DIMENSION as usize {
+        anyhow::bail!(
+            "Bundle embedding dimension {} does not match {}",
+            manifest.dimension,
+            EMBEDDING_DIMENSION
+        );
+    }
+
+    Ok(manifest)
+}
+
+fn open_read_only(path: &Path) -> Result<Connection> {
+    Connection::open_with_flags(
+        path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .with_context(|| format!("Failed to open {:?}", path))
+}
+
+// ============================================================================
+// Create
+// ============================================================================
+
+/// 데이터 디렉토리의 저장소를 번들 파일로 묶기
+///
+/// 이미 있는 파일은 덮어쓰지 않으며, 실패하면 만들던 파일을 지웁니다.
+pub async fn create_bundle(data_dir: &Path, out: &Path) -> Result<BundleManifest> {
+    if out.exists() {
+        anyhow::bail!("Bundle file already exists: {:?}", out);
+    }
+    let db_path = data_dir.join(SQLITE_FILE);
+    if !db_path.is_file() {
+        anyhow::bail!("Knowledge store not found: {:?}", db_path);
+    }
+
+    let result = write_bundle(data_dir, &db_path, out).await;
+    if result.is_err() {
+        let _ = std::fs::remove_file(out);
+    }
+    result
+}
+
+async fn write_bundle(data_dir: &Path, db_path: &Path, out: &Path) -> Result<BundleManifest> {
+    // 열린 저장소와 어긋나지 않는 일관된 스냅샷
+    let source = open_read_only(db_path)?;
+    source
+        .execute("VACUUM INTO ?1", params![out.to_string_lossy()])
+        .context("Failed to snapshot knowledge store")?;
+    drop(source);
+
+    let vectors = LanceVectorStore::open(&data_dir.join(VECTORS_DIR)).await?;
+    let entries = vectors.entries().await?;
+    let doc_embeddings = vectors.doc_embeddings().await?;
+
+    let mut conn = Connection::open(out).context("Failed to open bundle file")?;
+    let tx = conn.transaction()?;
+    tx.execute_batch(
+        "CREATE TABLE bundle_manifest (key TEXT PRIMARY KEY, value TEXT NOT NULL);
+         CREATE TABLE bundle_vectors (
+             doc_id INTEGER NOT NULL,
+             chunk_index INTEGER NOT NULL,
+             chunk_text TEXT NOT NULL,
+             embedding BLOB NOT NULL
+         );
+         CREATE TABLE bundle_doc_vectors (
+             doc_id INTEGER PRIMARY KEY,
+             embedding BLOB NOT NULL
+         );",
+    )?;
+    {
+        let mut insert = tx.prepare(
+            "INSERT INTO bundle_vectors (doc_id, chunk_index, chunk_text, embedding)
+             VALUES (?1, ?2, ?3, ?4)",
+        )?;
+        for entry in &entries {
+            insert.execute(params![
+                entry.doc_id,
+                entry.chunk_index,
+                entry.chunk_text,
+                encode_embedding(&entry.embedding)
+            ])?;
+        }
+
+        let mut insert =
+            tx.prepare("INSERT INTO bundle_doc_vectors (doc_id, embedding) VALUES (?1, ?2)")?;
+        for (doc_id, embedding) in &doc_embeddings {
+            insert.execute(params![doc_id, encode_embedding(embedding)])?;
+        }
+    }
+
+    let documents: i64 = tx.query_row("SELECT COUNT(*) FROM documents", [], |row| row.get(0))?;
+    let manifest = BundleManifest {
+        format_version: BUNDLE_FORMAT_VERSION,
+        id: uuid::Uuid::new_v4().to_string(),
+        created_at: Utc::now(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        documents: documents as usize,
+        chunks: entries.len(),
+        dimension: EMBEDDING_DIMENSION as usize,
+    };
+    tx.execute(
+        "INSERT INTO bundle_manifest (key, value) VALUES ('manifest', ?1)",
+        params![serde_json::to_string(&manifest)?],
+    )?;
+    tx.commit().context("Failed to write bundle")?;
+
+    Ok(manifest)
+}
+
+// ============================================================================
+// Open
+// ============================================================================
+
+/// 번들을 검색할 수 있는 저장소 디렉토리로 풀기 (이미 풀었으면 그대로 반환)
+///
+/// 반환한 디렉토리는 `HybridRetriever::with_data_dir`로 엽니다.
+pub async fn open_bundle(bundle: &Path, data_dir: &Path) -> Result<PathBuf> {
+    let manifest = read_manifest(bundle)?;
+    let dir = data_dir.join(BUNDLES_DIR).join(&manifest.id);
+    if dir.join(EXTRACTED_MANIFEST).is_file() {
+        return Ok(dir);
+    }
+
+    // 중간에 실패한 흔적은 지우고 다시 풂
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir)
+            .with_context(|| format!("Failed to clear partial bundle {:?}", dir))?;
+    }
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create bundle directory {:?}", dir))?;
+
+    if let Err(e) = extract_bundle(bundle, &dir).await {
+        let _ = std::fs::remove_dir_all(&dir);
+        return Err(e.context(format!("Failed to extract bundle {:?}", bundle)));
+    }
+    std::fs::write(
+        dir.join(EXTRACTED_MANIFEST),
+        serde_json::to_string_pretty(&manifest)?,
+    )?;
+
+    Ok(dir)
+}
+
+async fn extract_bundle(bundle: &Path, dir: &Path) -> Result<()> {
+    let db_path = dir.join(SQLITE_FILE);
+    std::fs::copy(bundle, &db_path).context("Failed to copy bundle database")?;
+
+    let conn = Connection::open(&db_path)?;
+    let entries = {
+        let mut stmt = conn.prepare(
+            "SELECT doc_id, chunk_index, chunk_text, embedding FROM bundle_vectors
+             ORDER BY doc_id, chunk_index",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, i32>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Vec<u8>>(3)?,
+            ))
+        })?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            let (doc_id, chunk_index, chunk_text, blob) = row?;
+            entries.push(VectorEntry {
+                doc_id,
+                chunk_index,
+                chunk_text,
+                embedding: decode_embedding(&blob)?,
+            });
+        }
+        entries
+    };
+    let doc_embeddings = {
+        let mut stmt = conn.prepare("SELECT doc_id, embedding FROM bundle_doc_vectors")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, Vec<u8>>(1)?))
+        })?;
+
+        let mut docs = Vec::new();
+        for row in rows {
+            let (doc_id, blob) = row?;
+            docs.push((doc_id, decode_embedding(&blob)?));
+        }
+        docs
+    };
+
+    // 벡터는 LanceDB로 옮기므로 SQLite 사본에서는 지움
+    conn.execute_batch(
+        "DROP TABLE bundle_vectors;
+         DROP TABLE bundle_doc_vectors;
+         DROP TABLE bundle_manifest;
+         VACUUM;",
+    )?;
+    drop(conn);
+
+    let vectors = LanceVectorStore::open(&dir.join(VECTORS_DIR)).await?;
+    for batch in entries.chunks(EXTRACT_BATCH_SIZE) {
+        vectors.insert_batch(batch).await?;
+    }
+    vectors.upsert_doc_embeddings(&doc_embeddings).await?;
+
+    Ok(())
+}
+
+// ============================================================================
+// Embedding encoding
+// ============================================================================
+
+fn encode_embedding(embedding: &[f32]) -> Vec<u8> {
+    embedding.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+fn decode_embedding(blob: &[u8]) -> Result<Vec<f32>> {
+    let expected = EMBEDDING_DIMENSION as usize * 4;
+    if blob.len() != expected {
+        anyhow::bail!(
+            "Invalid embedding size in bundle: {} bytes, expected {}",
+            blob.len(),
+            expected
+        );
+    }
+    Ok(blob
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::HybridRetriever;
+    use crate::test_support::{sample_documents, EphemeralRetriever, MockEmbedding};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_bundle_roundtrip() {
+        let source = EphemeralRetriever::with_fixtures(sample_documents())
+            .await
+            .unwrap();
+        let out_dir = TempDir::new().unwrap();
+        let bundle = out_dir.path().join("project.palank");
+
+        let manifest = create_bundle(source.data_dir(), &bundle).await.unwrap();
+        assert_eq!(manifest.documents, 3);
+        assert!(manifest.chunks >= 3);
+        assert_eq!(read_manifest(&bundle).unwrap(), manifest);
+        assert!(create_bundle(source.data_dir(), &bundle).await.is_err());
+
+        let receiver = TempDir::new().unwrap();
+        let dir = open_bundle(&bundle, receiver.path()).await.unwrap();
+        assert_eq!(dir, receiver.path().join(BUNDLES_DIR).join(&manifest.id));
+        // 두 번째는 이미 푼 저장소를 그대로 씀
+        assert_eq!(open_bundle(&bundle, receiver.path()).await.unwrap(), dir);
+
+        let retriever = HybridRetriever::with_embedder(&dir, Box::new(MockEmbedding::new()))
+            .await
+            .unwrap();
+        let results = retriever.search("Rust ownership", 3).await.unwrap();
+        assert_eq!(results[0].url, "fixture://rust-ownership");
+
+        let vectors = LanceVectorStore::open(&dir.join(VECTORS_DIR))
+            .await
+            .unwrap();
+        assert_eq!(vectors.count().await.unwrap(), manifest.chunks);
+    }
+
+    #[test]
+    fn test_embedding_encoding() {
+        let embedding: Vec<f32> = (0..EMBEDDING_DIMENSION).map(|i| i as f32 * 0.5).collect();
+        let blob = encode_embedding(&embedding);
+        assert_eq!(decode_embedding(&blob).unwrap(), embedding);
+        assert!(decode_embedding(&blob[..8]).is_err());
+    }
+
+    #[test]
+    fn test_rejects_non_bundle() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("plain.db");
+        Connection::open(&path)
+            .unwrap()
+            .execute_batch("CREATE TABLE t (x INTEGER);")
+            .unwrap();
+        assert!(read_manifest(&path).is_err());
+    }
+}
@@ -0,0 +1,265 @@
+//! 변경 이력 문서 - `ingest --url --changelog`
+//!
+//! URL을 다시 수집할 때 이전 본문과 줄 단위로 비교하여 변경 사항을
+//! 날짜가 붙은 별도 문서로 만듭니다. "v2 마이그레이션 가이드에서 무엇이 바뀌었나"
+//! 같은 질문을 변경 이력 문서로 검색할 수 있습니다.
+
+use chrono::{DateTime, Utc};
+
+use super::store::{Document, NewDocument};
+use crate::normalize::normalize_document;
+
+/// LCS 비교를 수행할 최대 셀 수 (공통 앞/뒤를 제외한 줄 수의 곱)
+///
+/// 넘으면 가운데 구간 전체를 삭제+추가로 처리합니다.
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+// ============================================================================
+// Diff
+// ============================================================================
+
+/// 연속된 변경 구간
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DiffHunk {
+    /// 삭제된 줄
+    pub removed: Vec<String>,
+    /// 추가된 줄
+    pub added: Vec<String>,
+}
+
+/// 두 본문의 줄 단위 차이
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContentDiff {
+    pub hunks: Vec<DiffHunk>,
+}
+
+impl ContentDiff {
+    /// 변경 없음 여부
+    pub fn is_empty(&self) -> bool {
+        self.hunks.is_empty()
+    }
+
+    /// 추가된 줄 수
+    pub fn added_count(&self) -> usize {
+        self.hunks.iter().map(|h| h.added.len()).sum()
+    }
+
+    /// 삭제된 줄 수
+    pub fn removed_count(&self) -> usize {
+        self.hunks.iter().map(|h| h.removed.len()).sum()
+    }
+
+    /// 구간별 `- 삭제` / `+ 추가` 텍스트
+    pub fn render(&self) -> String {
+        self.hunks
+            .iter()
+            .map(|hunk| {
+                hunk.removed
+                    .iter()
+                    .map(|line| format!("- {}", line))
+                    .chain(hunk.added.iter().map(|line| format!("+ {}", line)))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+/// 줄 단위 비교 (공백만 있는 줄과 줄 끝 공백은 무시)
+pub fn diff_lines(old: &str, new: &str) -> ContentDiff {
+    let old: Vec<&str> = significant_lines(old);
+    let new: Vec<&str> = significant_lines(new);
+
+    // 공통 앞/뒤 제거
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old = &old[prefix..old.len() - suffix];
+    let new = &new[prefix..new.len() - suffix];
+
+    let mut hunks = Vec::new();
+    let mut current = DiffHunk::default();
+    let mut flush = |current: &mut DiffHunk| {
+        if !current.removed.is_empty() || !current.added.is_empty() {
+            hunks.push(std::mem::take(current));
+        }
+    };
+
+    if old.len().saturating_mul(new.len()) > MAX_DIFF_CELLS {
+        current.removed = old.iter().map(|l| l.to_string()).collect();
+        current.added = new.iter().map(|l| l.to_string()).collect();
+        flush(&mut current);
+        return ContentDiff { hunks };
+    }
+
+    // lcs[i][j] = old[i..], new[j..]의 LCS 길이
+    let (n, m) = (old.len(), new.len());
+    let mut lcs = vec![0u32; (n + 1) * (m + 1)];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i * (m + 1) + j] = if old[i] == new[j] {
+                lcs[(i + 1) * (m + 1) + j + 1] + 1
+            } else {
+                lcs[(i + 1) * (m + 1) + j].max(lcs[i * (m + 1) + j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && old[i] == new[j] {
+            flush(&mut current);
+            i += 1;
+            j += 1;
+        } else if j < m && (i == n || lcs[i * (m + 1) + j + 1] >= lcs[(i + 1) * (m + 1) + j]) {
+            current.added.push(new[j].to_string());
+            j += 1;
+        } else {
+            current.removed.push(old[i].to_string());
+            i += 1;
+        }
+    }
+    flush(&mut current);
+
+    ContentDiff { hunks }
+}
+
+fn significant_lines(text: &str) -> Vec<&str> {
+    text.lines()
+        .map(str::trim_end)
+        .filter(|l| !l.is_empty())
+        .collect()
+}
+
+// ============================================================================
+// Changelog Document
+// ============================================================================
+
+/// 이전 문서와 새 문서를 비교하여 변경 이력 문서 생성 (변경이 없으면 None)
+///
+/// URL은 `<원본 URL>#changes-<시각>` 형식이라 수집할 때마다 새 문서로 쌓입니다.
+pub fn changelog_document(
+    previous: &Document,
+    current: &NewDocument,
+    at: DateTime<Utc>,
+) -> Option<NewDocument> {
+    // 저장된 본문은 정규화되어 있으므로 새 본문도 같은 규칙으로 정규화하여 비교
+    // (CRLF, NBSP, 줄 끝 공백만 다른 페이지가 변경으로 잡히지 않도록)
+    let diff = diff_lines(
+        &normalize_document(&previous.content),
+        &normalize_document(&current.content),
+    );
+    if diff.is_empty() {
+        return None;
+    }
+
+    let name = current
+        .title
+        .as_deref()
+        .or(previous.title.as_deref())
+        .unwrap_or(&current.url);
+    let title = format!("{} 변경 사항 ({})", name, at.format("%Y-%m-%d"));
+
+    let content = format!(
+        "# {}\n\n출처: {}\n이전 수집: {}\n변경: +{}줄 / -{}줄\n\n{}\n",
+        title,
+        current.url,
+        previous.created_at.format("%Y-%m-%d"),
+        diff.added_count(),
+        diff.removed_count(),
+        diff.render()
+    );
+
+    Some(NewDocument {
+        url: format!("{}#changes-{}", current.url, at.format("%Y%m%dT%H%M%SZ")),
+        title: Some(title),
+        content,
+        framework: current.framework.clone(),
+        expires_at: current.expires_at,
+        citation: None,
+        metadata: None,
+        provenance: current.provenance.clone(),
+    })
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_lines() {
+        let old = "# Guide\n\nStep 1: install\nStep 2: configure\nStep 3: run\n";
+        let new = "# Guide\n\nStep 1: install v2\nStep 2: configure\nStep 3: run\nStep 4: verify\n";
+
+        let diff = diff_lines(old, new);
+        assert_eq!(diff.hunks.len(), 2);
+        assert_eq!(diff.hunks[0].removed, vec!["Step 1: install"]);
+        assert_eq!(diff.hunks[0].added, vec!["Step 1: install v2"]);
+        assert_eq!(diff.hunks[1].added, vec!["Step 4: verify"]);
+        assert_eq!((diff.added_count(), diff.removed_count()), (2, 1));
+        assert_eq!(
+            diff.render(),
+            "- Step 1: install\n+ Step 1: install v2\n\n+ Step 4: verify"
+        );
+
+        // 빈 줄/줄 끝 공백 차이는 무시
+        assert!(diff_lines(
+            old,
+            "# Guide  \nStep 1: install\n\n\nStep 2: configure\nStep 3: run"
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_changelog_document() {
+        let previous = Document {
+            id: 1,
+            url: "https://docs.example.com/migrate".to_string(),
+            title: Some("Migration Guide".to_string()),
+            content: "Use createClient()".to_string(),
+            framework: Some("example".to_string()),
+            created_at: Utc::now(),
+            expires_at: None,
+            pinned: false,
+            citation: None,
+            metadata: None,
+            provenance: None,
+        };
+        let mut current = NewDocument {
+            url: previous.url.clone(),
+            title: previous.title.clone(),
+            content: previous.content.clone(),
+            framework: previous.framework.clone(),
+            ..Default::default()
+        };
+        let at = "2026-03-01T09:30:00Z".parse().unwrap();
+
+        assert!(changelog_document(&previous, &current, at).is_none());
+
+        // 정규화로 사라지는 차이(CRLF, NBSP, 줄 끝 공백)는 변경이 아님
+        current.content = "Use\u{00A0}createClient()  \r\n".to_string();
+        assert!(changelog_document(&previous, &current, at).is_none());
+
+        current.content = "Use createClient({ v2: true })".to_string();
+        let doc = changelog_document(&previous, &current, at).unwrap();
+        assert_eq!(
+            doc.url,
+            "https://docs.example.com/migrate#changes-20260301T093000Z"
+        );
+        assert_eq!(
+            doc.title.as_deref(),
+            Some("Migration Guide 변경 사항 (2026-03-01)")
+        );
+        assert_eq!(doc.framework.as_deref(), Some("example"));
+        assert!(doc.content.contains("+ Use createClient({ v2: true })"));
+    }
+}
@@ -0,0 +1,397 @@
+//! 청크 종류 판별 - 설명문(prose)과 코드, 코드의 언어
+//!
+//! 튜토리얼이나 API 문서처럼 설명과 예제 코드가 섞인 문서에서 청크마다 코드인지
+//! 설명문인지(코드라면 어떤 언어인지) 판별하여 `chunk_kinds` 테이블에 저장합니다.
+//! `query --only-code`/`--only-prose` 필터와, 코딩 질문일 때 코드 청크를 먼저 넣는
+//! 컨텍스트 구성에 씁니다.
+//!
+//! 판별 순서:
+//! 1. 소스 코드 파일(`file://…/*.rs`)에서 수집한 문서는 확장자의 언어로 모두 코드
+//! 2. 코드 펜스(```` ``` ````) 안의 줄이 절반 이상이면 코드 (언어는 펜스 태그)
+//! 3. 코드처럼 보이는 줄(`;`/`{`로 끝남, `fn `/`def ` 등으로 시작)이 대부분이면 코드
+//!
+//! 언어는 펜스 태그가 없으면 키워드 빈도로 추정하며, 추정하지 못하면 비워 둡니다.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::location::source_path;
+
+/// 코드로 판단할 코드 같은 줄 비율
+const CODE_LINE_RATIO: f32 = 0.6;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// 청크 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChunkKind {
+    /// 설명문
+    Prose,
+    /// 코드
+    Code,
+}
+
+impl ChunkKind {
+    /// 저장용 이름
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Prose => "prose",
+            Self::Code => "code",
+        }
+    }
+
+    /// 저장된 이름 파싱
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "prose" => Some(Self::Prose),
+            "code" => Some(Self::Code),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ChunkKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 청크 판별 결과
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChunkClass {
+    pub kind: ChunkKind,
+    /// 코드 언어 (`rust`, `python` 등, 알 수 없으면 None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+impl ChunkClass {
+    /// 설명문
+    pub fn prose() -> Self {
+        Self {
+            kind: ChunkKind::Prose,
+            language: None,
+        }
+    }
+
+    /// 코드 (언어 지정)
+    pub fn code(language: Option<&str>) -> Self {
+        Self {
+            kind: ChunkKind::Code,
+            language: language.map(str::to_string),
+        }
+    }
+
+    /// 코드 청크인지
+    pub fn is_code(&self) -> bool {
+        self.kind == ChunkKind::Code
+    }
+}
+
+// ============================================================================
+// Detection
+// ============================================================================
+
+/// 문서 URL과 청크 텍스트로 청크 판별
+///
+/// 소스 코드 파일이면 확장자의 언어로 코드, 그 밖에는 `classify`로 판별합니다.
+pub fn classify_chunk(url: &str, text: &str) -> ChunkClass {
+    let extension = source_path(url).and_then(|path| {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_lowercase)
+    });
+    match extension {
+        Some(ext) => ChunkClass::code(normalize_language(&ext)),
+        None => classify(text),
+    }
+}
+
+/// 청크 텍스트만으로 설명문/코드 판별
+pub fn classify(text: &str) -> ChunkClass {
+    let mut fence: Option<String> = None;
+    let mut fence_tags: Vec<String> = Vec::new();
+    let mut fenced = 0usize;
+    let mut code_like = 0usize;
+    let mut total = 0usize;
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if let Some(tag) = trimmed.strip_prefix("```") {
+            fence = match fence {
+                Some(_) => None,
+                None => {
+                    let tag = tag.trim().to_lowercase();
+                    if !tag.is_empty() {
+                        fence_tags.push(tag.clone());
+                    }
+                    Some(tag)
+                }
+            };
+            continue;
+        }
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        total += 1;
+        if fence.is_some() {
+            fenced += 1;
+        } else if looks_like_code(line) {
+            code_like += 1;
+        }
+    }
+
+    if total == 0 {
+        return ChunkClass::prose();
+    }
+
+    let is_code = (fenced > 0 && fenced * 2 >= total)
+        || (total >= 2 && (fenced + code_like) as f32 / total as f32 >= CODE_LINE_RATIO);
+    if !is_code {
+        return ChunkClass::prose();
+    }
+
+    let language = fence_tags
+        .iter()
+        .find_map(|tag| normalize_language(tag))
+        .or_else(|| guess_language(text));
+    ChunkClass::code(language)
+}
+
+/// 코드처럼 보이는 줄인지
+fn looks_like_code(line: &str) -> bool {
+    // 들여쓴 줄 (Markdown 코드 블록, 함수 본문)
+    if line.starts_with("    ") || line.starts_with('\t') {
+        return true;
+    }
+
+    let trimmed = line.trim();
+    // 마침표로 끝나는 긴 줄은 문장
+    if trimmed.ends_with('.') && trimmed.split_whitespace().count() >= 6 {
+        return false;
+    }
+
+    const PREFIXES: &[&str] = &[
+        "fn ",
+        "pub ",
+        "let ",
+        "use ",
+        "impl ",
+        "struct ",
+        "enum ",
+        "def ",
+        "class ",
+        "import ",
+        "from ",
+        "return ",
+        "function ",
+        "const ",
+        "var ",
+        "export ",
+        "func ",
+        "package ",
+        "#include",
+        "#!",
+        "//",
+        "/*",
+        "@",
+        "if (",
+        "for (",
+        "while (",
+        "} ",
+        "SELECT ",
+        "INSERT ",
+        "CREATE ",
+        "$ ",
+    ];
+    const SUFFIXES: &[char] = &[';', '{', '}', '(', '[', ','];
+
+    PREFIXES.iter().any(|p| trimmed.starts_with(p))
+        || trimmed.ends_with(SUFFIXES)
+        || trimmed == ")"
+        || trimmed.contains(" => ")
+        || trimmed.contains(" := ")
+}
+
+/// 펜스 태그/확장자를 언어 이름으로 정규화 (코드 언어가 아니면 None)
+fn normalize_language(tag: &str) -> Option<&'static str> {
+    let tag = tag.split([',', ' ', '{']).next().unwrap_or(tag);
+    let language = match tag {
+        "rust" | "rs" => "rust",
+        "python" | "py" | "python3" => "python",
+        "javascript" | "js" | "jsx" | "mjs" => "javascript",
+        "typescript" | "ts" | "tsx" => "typescript",
+        "go" | "golang" => "go",
+        "java" => "java",
+        "c" | "h" => "c",
+        "cpp" | "c++" | "hpp" | "cc" => "cpp",
+        "sh" | "bash" | "zsh" | "shell" | "console" => "shell",
+        "sql" => "sql",
+        "html" => "html",
+        "css" | "scss" => "css",
+        "json" => "json",
+        "toml" => "toml",
+        "yaml" | "yml" => "yaml",
+        _ => return None,
+    };
+    Some(language)
+}
+
+/// 언어별 특징 키워드
+const LANGUAGE_MARKERS: &[(&str, &[&str])] = &[
+    (
+        "rust",
+        &[
+            "fn ", "let mut ", "impl ", "pub fn", "&self", "println!", "::new(",
+        ],
+    ),
+    (
+        "python",
+        &[
+            "def ", "elif ", "self.", "print(", "__init__", "None:", "import ",
+        ],
+    ),
+    (
+        "javascript",
+        &["function ", "=> {", "console.log", "require(", "const "],
+    ),
+    (
+        "typescript",
+        &["interface ", ": string", ": number", "export type "],
+    ),
+    ("go", &["func ", "package ", ":= ", "fmt."]),
+    (
+        "java",
+        &["public class", "System.out", "public static void"],
+    ),
+    ("cpp", &["#include", "std::", "printf("]),
+    ("shell", &["#!/bin", "$ ", "echo ", "sudo ", "export "]),
+    (
+        "sql",
+        &[
+            "SELECT ",
+            " FROM ",
+            " WHERE ",
+            "INSERT INTO",
+            "CREATE TABLE",
+        ],
+    ),
+];
+
+/// 키워드 빈도로 언어 추정 (가장 많이 나온 언어, 없으면 None)
+fn guess_language(text: &str) -> Option<&'static str> {
+    let mut best: Option<(&'static str, usize)> = None;
+    for (language, markers) in LANGUAGE_MARKERS {
+        let score: usize = markers.iter().map(|m| text.matches(m).count()).sum();
+        // 동점이면 표의 앞쪽 언어
+        if score > best.map_or(0, |(_, top)| top) {
+            best = Some((*language, score));
+        }
+    }
+    best.map(|(language, _)| language)
+}
+
+/// 코딩 질문인지 (코드 청크를 먼저 넣을지 판단)
+///
+/// 코드 식별자 모양(`foo()`, `a::b`, 백틱)이나 코드/예제를 묻는 표현이 있으면 코딩 질문으로 봅니다.
+pub fn is_coding_question(query: &str) -> bool {
+    const PHRASES: &[&str] = &[
+        "코드",
+        "예제",
+        "구현",
+        "함수",
+        "메서드",
+        "에러",
+        "컴파일",
+        "문법",
+        "code",
+        "example",
+        "snippet",
+        "implement",
+        "function",
+        "method",
+        "syntax",
+        "compile",
+        "error",
+    ];
+
+    let lower = query.to_lowercase();
+    query.contains('`')
+        || query.contains("()")
+        || query.contains("::")
+        || PHRASES.iter().any(|p| lower.contains(p))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_prose_and_code() {
+        let prose = "React는 사용자 인터페이스를 만들기 위한 라이브러리입니다.\n\
+                     컴포넌트를 조합하여 복잡한 화면을 구성할 수 있습니다.";
+        assert_eq!(classify(prose), ChunkClass::prose());
+
+        let fenced = "예제:\n```rust\nfn main() {\n    println!(\"hi\");\n}\n```";
+        assert_eq!(classify(fenced), ChunkClass::code(Some("rust")));
+
+        let bare = "def add(a, b):\n    return a + b\n\nprint(add(1, 2))";
+        assert_eq!(classify(bare), ChunkClass::code(Some("python")));
+
+        let mixed =
+            "상태를 관리하는 방법은 여러 가지가 있으며 이 문서에서는 그중 하나를 설명합니다.\n\
+                     먼저 스토어를 만든 뒤 컴포넌트에서 구독하는 순서로 진행합니다.\n\
+                     마지막으로 변경 사항을 화면에 반영하는 방법을 살펴봅니다.\n\
+                     `useState` 훅을 쓰면 됩니다.";
+        assert!(!classify(mixed).is_code());
+        assert_eq!(classify("").kind, ChunkKind::Prose);
+    }
+
+    #[test]
+    fn test_classify_chunk_uses_extension() {
+        let class = classify_chunk("file:///src/lib.rs", "//! 모듈 설명");
+        assert_eq!(class, ChunkClass::code(Some("rust")));
+
+        let class = classify_chunk("file:///notes/readme.md", "그냥 메모입니다.");
+        assert_eq!(class.kind, ChunkKind::Prose);
+    }
+
+    #[test]
+    fn test_guess_language() {
+        assert_eq!(
+            guess_language("package main\nfunc main() {\n  x := 1\n}"),
+            Some("go")
+        );
+        assert_eq!(
+            guess_language("SELECT id FROM documents WHERE id = 1;"),
+            Some("sql")
+        );
+        assert_eq!(guess_language("a + b"), None);
+        assert_eq!(normalize_language("rust,ignore"), Some("rust"));
+        assert_eq!(normalize_language("text"), None);
+    }
+
+    #[test]
+    fn test_is_coding_question() {
+        assert!(is_coding_question("useEffect 정리 함수 예제"));
+        assert!(is_coding_question("How do I call `Vec::with_capacity`?"));
+        assert!(!is_coding_question("React의 설계 철학은?"));
+    }
+
+    #[test]
+    fn test_kind_roundtrip() {
+        for kind in [ChunkKind::Prose, ChunkKind::Code] {
+            assert_eq!(ChunkKind::parse(kind.as_str()), Some(kind));
+        }
+        assert_eq!(ChunkKind::parse("other"), None);
+    }
+}
@@ -5,7 +5,13 @@
 //! Markdown 인식 텍스트 분할을 제공합니다.
 //! 문서 구조를 존중하면서 적절한 크기의 청크로 나눕니다.
 
+use std::sync::Arc;
+
 use regex::Regex;
+use sha2::{Digest, Sha256};
+
+use crate::embedding::OversizeStrategy;
+use crate::tokens::{default_tokenizer, GeminiEstimator, Tokenizer};
 
 // ============================================================================
 // Chunk Configuration
@@ -20,6 +26,10 @@ pub struct ChunkConfig {
     pub max_characters: usize,
     /// 오버랩 크기 (문자 수)
     pub overlap_characters: usize,
+    /// 임베딩 입력 한도를 넘는 청크 처리 방식
+    pub oversize: OversizeStrategy,
+    /// 임베딩 입력 한도 검사용 토큰 계산기
+    pub tokenizer: Arc<dyn Tokenizer>,
 }
 
 impl Default for ChunkConfig {
@@ -28,6 +38,8 @@ impl Default for ChunkConfig {
             min_characters: 200,
             max_characters: 1200,
             overlap_characters: 100,
+            oversize: OversizeStrategy::Average,
+            tokenizer: default_tokenizer(),
         }
     }
 }
@@ -39,6 +51,8 @@ impl ChunkConfig {
             min_characters: 300,
             max_characters: 1500,
             overlap_characters: 150,
+            oversize: OversizeStrategy::Average,
+            tokenizer: default_tokenizer(),
         }
     }
 
@@ -48,8 +62,27 @@ impl ChunkConfig {
             min_characters: 500,
             max_characters: 1000,
             overlap_characters: 0,
+            oversize: OversizeStrategy::Average,
+            tokenizer: default_tokenizer(),
         }
     }
+
+    /// 설정 해시 (sha256 앞 8자) - 설정이 하나라도 바뀌면 달라짐
+    pub fn fingerprint(&self) -> String {
+        let mut canonical = format!(
+            "min={};max={};overlap={};oversize={:?}",
+            self.min_characters, self.max_characters, self.overlap_characters, self.oversize
+        );
+        // 기본 토크나이저는 생략 (도입 전에 수집한 문서의 지문 유지)
+        if self.tokenizer.name() != GeminiEstimator.name() {
+            canonical.push_str(&format!(";tokenizer={}", self.tokenizer.name()));
+        }
+        Sha256::digest(canonical.as_bytes())
+            .iter()
+            .take(4)
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
 }
 
 // ============================================================================
@@ -63,6 +96,13 @@ pub trait Chunker: Send + Sync {
 
     /// 청커 이름
     fn name(&self) -> &'static str;
+
+    /// 청커 지문 (이름 + 설정 해시)
+    ///
+    /// 문서마다 저장해 두고 현재 값과 비교하여 예전 설정으로 청킹된 문서를 찾습니다.
+    fn fingerprint(&self) -> String {
+        self.name().to_string()
+    }
 }
 
 // ============================================================================
@@ -131,49 +171,26 @@ impl MarkdownChunker {
         let mut chunks = Vec::new();
         let mut current = String::new();
 
-        // 이중 줄바꿈(문단 경계)으로 분할
-        for para in section.split("\n\n") {
+        // 빈 줄(문단 경계)로 분할 - 코드 블록 내부는 분할하지 않음
+        for para in split_paragraphs(section) {
             let para = para.trim();
             if para.is_empty() {
                 continue;
             }
 
-            // 현재 청크에 추가하면 최대 크기 초과?
+            // 현재 청크에 추가하면 최대 크기 초과? (작은 청크는 이후 병합)
             if !current.is_empty()
                 && current.len() + para.len() + 2 > self.config.max_characters
             {
-                // 현재 청크 저장
-                if current.len() >= self.config.min_characters {
-                    chunks.push(current.clone());
-                    current = String::new();
-                }
+                chunks.push(std::mem::take(&mut current));
             }
 
             // 문단 자체가 최대 크기 초과?
             if para.len() > self.config.max_characters {
-                // 현재까지 저장
-                if !current.is_empty() && current.len() >= self.config.min_characters {
-                    chunks.push(current.clone());
-                    current = String::new();
-                }
-
-                // 긴 문단을 줄 단위로 분할
-                let mut line_chunk = String::new();
-                for line in para.lines() {
-                    if !line_chunk.is_empty()
-                        && line_chunk.len() + line.len() + 1 > self.config.max_characters
-                    {
-                        chunks.push(line_chunk.clone());
-                        line_chunk = String::new();
-                    }
-                    if !line_chunk.is_empty() {
-                        line_chunk.push('\n');
-                    }
-                    line_chunk.push_str(line);
-                }
-                if !line_chunk.is_empty() {
-                    current = line_chunk;
-                }
+                // 긴 문단을 줄 단위로 분할, 마지막 조각은 다음 문단과 이어붙임
+                let mut pieces = self.split_long_paragraph(para);
+                current = pieces.pop().unwrap_or_default();
+                chunks.extend(pieces);
             } else {
                 // 문단 추가
                 if !current.is_empty() {
@@ -192,6 +209,40 @@ impl MarkdownChunker {
         self.merge_small_chunks(chunks)
     }
 
+    /// 긴 문단을 줄 단위로 분할
+    ///
+    /// 최대 크기 이하의 코드 블록은 하나의 단위로 유지하고,
+    /// 최대 크기를 넘는 단일 줄은 문자 경계에서 강제로 자릅니다.
+    fn split_long_paragraph(&self, para: &str) -> Vec<String> {
+        let max = self.config.max_characters;
+        let mut chunks = Vec::new();
+        let mut current = String::new();
+
+        for unit in paragraph_units(para, max) {
+            let pieces = if unit.len() > max {
+                hard_split(&unit, max)
+            } else {
+                vec![unit]
+            };
+
+            for piece in pieces {
+                if !current.is_empty() && current.len() + piece.len() + 1 > max {
+                    chunks.push(std::mem::take(&mut current));
+                }
+                if !current.is_empty() {
+                    current.push('\n');
+                }
+                current.push_str(&piece);
+            }
+        }
+
+        if !current.is_empty() {
+            chunks.push(current);
+        }
+
+        chunks
+    }
+
     /// 작은 청크 병합
     fn merge_small_chunks(&self, chunks: Vec<String>) -> Vec<String> {
         if self.config.min_characters == 0 {
@@ -233,8 +284,8 @@ impl MarkdownChunker {
                 let prev = &chunks[i - 1];
                 let overlap_start = prev.len().saturating_sub(self.config.overlap_characters);
 
-                // UTF-8 경계 조정
-                let overlap_start = floor_char_boundary(prev, overlap_start);
+                // UTF-8 경계 조정 (오버랩이 설정 크기를 넘지 않도록 올림)
+                let overlap_start = ceil_char_boundary(prev, overlap_start);
 
                 // 단어 경계에서 시작
                 let overlap_text = &prev[overlap_start..];
@@ -258,31 +309,57 @@ impl MarkdownChunker {
     }
 }
 
-impl Chunker for MarkdownChunker {
-    fn chunk(&self, text: &str) -> Vec<String> {
+impl MarkdownChunker {
+    /// 청크와 각 청크의 제목 경로 (예: `["설치", "Linux"]`)
+    ///
+    /// `chunk`와 같은 청크를 돌려주며, 청크 미리보기(`chunk-preview`)에서 사용합니다.
+    pub fn chunk_with_headings(&self, text: &str) -> Vec<(Vec<String>, String)> {
         if text.trim().is_empty() {
             return vec![];
         }
 
-        // 1. 섹션으로 분할
-        let sections = self.split_sections(text);
+        // (레벨, 제목) 스택 - 같거나 높은 레벨의 제목을 만나면 교체
+        let mut headings: Vec<(usize, String)> = Vec::new();
+        let mut paths = Vec::new();
+        let mut chunks = Vec::new();
 
-        // 2. 긴 섹션 분할
-        let mut chunks: Vec<String> = sections
-            .into_iter()
-            .flat_map(|s| self.split_long_section(&s))
-            .collect();
+        // 1. 섹션으로 분할 (섹션은 제목 줄로 시작)
+        for section in self.split_sections(text) {
+            if let Some((level, title)) = section.lines().next().and_then(parse_heading) {
+                headings.retain(|(l, _)| *l < level);
+                headings.push((level, title));
+            }
+            let path: Vec<String> = headings.iter().map(|(_, t)| t.clone()).collect();
 
-        // 3. 빈 청크 제거
-        chunks.retain(|c| !c.trim().is_empty());
+            // 2. 긴 섹션 분할, 3. 빈 청크 제거
+            for chunk in self.split_long_section(&section) {
+                if !chunk.trim().is_empty() {
+                    paths.push(path.clone());
+                    chunks.push(chunk);
+                }
+            }
+        }
 
         // 4. 오버랩 적용
-        self.apply_overlap(chunks)
+        paths.into_iter().zip(self.apply_overlap(chunks)).collect()
+    }
+}
+
+impl Chunker for MarkdownChunker {
+    fn chunk(&self, text: &str) -> Vec<String> {
+        self.chunk_with_headings(text)
+            .into_iter()
+            .map(|(_, chunk)| chunk)
+            .collect()
     }
 
     fn name(&self) -> &'static str {
         "MarkdownChunker"
     }
+
+    fn fingerprint(&self) -> String {
+        format!("{}:{}", self.name(), self.config.fingerprint())
+    }
 }
 
 // ============================================================================
@@ -303,6 +380,253 @@ fn floor_char_boundary(s: &str, index: usize) -> usize {
     }
 }
 
+/// UTF-8 경계 조정 (인덱스 이상으로)
+#[inline]
+fn ceil_char_boundary(s: &str, index: usize) -> usize {
+    let mut i = index.min(s.len());
+    while i < s.len() && !s.is_char_boundary(i) {
+        i += 1;
+    }
+    i
+}
+
+/// Markdown 제목 줄 파싱 (`## 제목` → (2, "제목"))
+fn parse_heading(line: &str) -> Option<(usize, String)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    let title = line[level..].strip_prefix(|c: char| c.is_whitespace())?;
+    (1..=6)
+        .contains(&level)
+        .then(|| (level, title.trim().trim_end_matches('#').trim().to_string()))
+}
+
+/// 코드 펜스 줄 여부
+#[inline]
+fn is_fence_line(line: &str) -> bool {
+    line.trim_start().starts_with("```")
+}
+
+/// 빈 줄 기준 문단 분할 (코드 블록 내부의 빈 줄은 무시)
+fn split_paragraphs(text: &str) -> Vec<String> {
+    let mut paragraphs = Vec::new();
+    let mut current = String::new();
+    let mut in_code_block = false;
+
+    for line in text.lines() {
+        if is_fence_line(line) {
+            in_code_block = !in_code_block;
+        }
+
+        if !in_code_block && line.trim().is_empty() {
+            if !current.is_empty() {
+                paragraphs.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(line);
+    }
+
+    if !current.is_empty() {
+        paragraphs.push(current);
+    }
+
+    paragraphs
+}
+
+/// 문단을 분할 단위로 나눔
+///
+/// 일반 줄은 한 줄이 한 단위이고, `max` 이하의 코드 블록은 통째로 한 단위입니다.
+fn paragraph_units(para: &str, max: usize) -> Vec<String> {
+    let mut units = Vec::new();
+    let mut block: Option<Vec<&str>> = None;
+
+    for line in para.lines() {
+        match block.as_mut() {
+            Some(lines) => {
+                lines.push(line);
+                if is_fence_line(line) {
+                    units.extend(finish_block(block.take().unwrap_or_default(), max));
+                }
+            }
+            None if is_fence_line(line) => block = Some(vec![line]),
+            None => units.push(line.to_string()),
+        }
+    }
+
+    // 닫히지 않은 코드 블록
+    if let Some(lines) = block {
+        units.extend(finish_block(lines, max));
+    }
+
+    units
+}
+
+/// 코드 블록 줄들을 단위로 변환 (크면 줄 단위로 풀어냄)
+fn finish_block(lines: Vec<&str>, max: usize) -> Vec<String> {
+    let joined = lines.join("\n");
+    if joined.len() <= max {
+        vec![joined]
+    } else {
+        lines.into_iter().map(|l| l.to_string()).collect()
+    }
+}
+
+/// 텍스트를 `max` 바이트 이하 조각으로 강제 분할 (UTF-8 안전)
+///
+/// 가능하면 공백 위치에서 자릅니다.
+fn hard_split(text: &str, max: usize) -> Vec<String> {
+    let mut pieces = Vec::new();
+    let mut rest = text.trim();
+
+    while rest.len() > max {
+        let mut cut = floor_char_boundary(rest, max);
+        if cut == 0 {
+            // max가 문자 하나보다 작은 경우 최소 한 문자는 진행
+            cut = rest.chars().next().map(char::len_utf8).unwrap_or(rest.len());
+        } else if let Some(ws) = rest[..cut].rfind(char::is_whitespace) {
+            if ws > 0 {
+                cut = ws;
+            }
+        }
+
+        let piece = rest[..cut].trim_end();
+        if !piece.is_empty() {
+            pieces.push(piece.to_string());
+        }
+        rest = rest[cut..].trim_start();
+    }
+
+    if !rest.is_empty() {
+        pieces.push(rest.to_string());
+    }
+
+    pieces
+}
+
+// ============================================================================
+// Invariant Validation
+// ============================================================================
+
+/// 오버랩 표시자 길이 ("...\n" + "\n---\n")
+const OVERLAP_MARKER_LEN: usize = 9;
+
+/// 청크 불변식 위반
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ChunkViolation {
+    /// 빈 청크 (공백만 있는 경우 포함)
+    #[error("chunk #{index} is empty")]
+    EmptyChunk { index: usize },
+
+    /// 최대 크기 초과 (오버랩 허용치 포함)
+    #[error("chunk #{index} is {len} bytes, exceeds limit of {limit}")]
+    TooLarge { index: usize, len: usize, limit: usize },
+
+    /// 원문의 내용이 청크에서 누락됨 (공백 제외 문자 기준)
+    #[error("source content missing from chunks at char offset {offset}")]
+    MissingContent { offset: usize },
+
+    /// 최대 크기 이하의 코드 블록이 여러 청크로 분리됨
+    #[error("code block starting at line {line} was split across chunks")]
+    SplitCodeFence { line: usize },
+
+    /// 원문에 없던 U+FFFD 문자 (잘못된 UTF-8 경계 분할의 흔적)
+    #[error("chunk #{index} contains a replacement character not present in source")]
+    ReplacementCharacter { index: usize },
+}
+
+/// 청크 결과의 불변식 검증
+///
+/// 커스텀 `Chunker` 구현의 정확성을 확인하는 용도로도 사용할 수 있습니다.
+///
+/// 검증 항목:
+/// - 빈 청크 없음
+/// - 크기 상한: `max_characters` (+ 오버랩 사용 시 `overlap_characters` + 표시자)
+/// - 커버리지: 원문의 공백 제외 문자가 순서대로 모두 청크에 포함됨
+/// - UTF-8: 원문에 없던 U+FFFD 문자가 생기지 않음
+/// - 코드 펜스: `max_characters` 이하의 코드 블록은 한 청크 안에 온전히 포함됨
+pub fn validate_chunks(
+    text: &str,
+    chunks: &[String],
+    config: &ChunkConfig,
+) -> Result<(), ChunkViolation> {
+    let limit = if config.overlap_characters > 0 {
+        config.max_characters + config.overlap_characters + OVERLAP_MARKER_LEN
+    } else {
+        config.max_characters
+    };
+    let source_has_replacement = text.contains('\u{FFFD}');
+
+    for (index, chunk) in chunks.iter().enumerate() {
+        if chunk.trim().is_empty() {
+            return Err(ChunkViolation::EmptyChunk { index });
+        }
+        if chunk.len() > limit {
+            return Err(ChunkViolation::TooLarge {
+                index,
+                len: chunk.len(),
+                limit,
+            });
+        }
+        if !source_has_replacement && chunk.contains('\u{FFFD}') {
+            return Err(ChunkViolation::ReplacementCharacter { index });
+        }
+    }
+
+    // 커버리지: 공백 제외 문자 시퀀스가 청크 연결본의 부분 수열인지 확인
+    let mut chunk_chars = chunks
+        .iter()
+        .flat_map(|c| c.chars())
+        .filter(|c| !c.is_whitespace());
+    for (offset, expected) in text.chars().enumerate() {
+        if expected.is_whitespace() {
+            continue;
+        }
+        if !chunk_chars.any(|c| c == expected) {
+            return Err(ChunkViolation::MissingContent { offset });
+        }
+    }
+
+    // 코드 펜스 무결성
+    for (line, block) in fenced_blocks(text) {
+        let block = block.trim();
+        if block.len() <= config.max_characters && !chunks.iter().any(|c| c.contains(block)) {
+            return Err(ChunkViolation::SplitCodeFence { line });
+        }
+    }
+
+    Ok(())
+}
+
+/// 원문의 코드 블록 목록 (시작 줄 번호(1부터), 블록 텍스트)
+fn fenced_blocks(text: &str) -> Vec<(usize, String)> {
+    let mut blocks = Vec::new();
+    let mut current: Option<(usize, Vec<&str>)> = None;
+
+    for (i, line) in text.lines().enumerate() {
+        match current.as_mut() {
+            Some((_, lines)) => {
+                lines.push(line);
+                if is_fence_line(line) {
+                    if let Some((start, lines)) = current.take() {
+                        blocks.push((start, lines.join("\n")));
+                    }
+                }
+            }
+            None if is_fence_line(line) => current = Some((i + 1, vec![line])),
+            None => {}
+        }
+    }
+
+    if let Some((start, lines)) = current {
+        blocks.push((start, lines.join("\n")));
+    }
+
+    blocks
+}
+
 // ============================================================================
 // Factory Functions
 // ============================================================================
@@ -347,6 +671,7 @@ mod tests {
             min_characters: 50,
             max_characters: 200,
             overlap_characters: 0,
+            ..Default::default()
         };
         let chunker = MarkdownChunker::new(config);
 
@@ -378,6 +703,7 @@ More text after code."#;
             min_characters: 10,
             max_characters: 200,
             overlap_characters: 0,
+            ..Default::default()
         };
         let chunker = MarkdownChunker::new(config);
 
@@ -393,6 +719,34 @@ Content for section 2."#;
         assert!(!chunks.is_empty());
     }
 
+    #[test]
+    fn test_chunk_with_headings() {
+        let chunker = MarkdownChunker::new(ChunkConfig {
+            min_characters: 10,
+            max_characters: 200,
+            overlap_characters: 20,
+            ..Default::default()
+        });
+
+        let text = "Intro paragraph.\n\n# Guide\n\nOverview.\n\n## Install ##\n\nRun it.\n\n### Linux\n\napt.\n\n## Usage\n\nCall it.";
+        let chunks = chunker.chunk_with_headings(text);
+        let paths: Vec<String> = chunks.iter().map(|(p, _)| p.join(" > ")).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "",
+                "Guide",
+                "Guide > Install",
+                "Guide > Install > Linux",
+                "Guide > Usage"
+            ]
+        );
+
+        // 제목 경로를 뺀 청크는 chunk()와 동일
+        let plain: Vec<String> = chunks.into_iter().map(|(_, c)| c).collect();
+        assert_eq!(plain, chunker.chunk(text));
+    }
+
     #[test]
     fn test_config_presets() {
         let default = ChunkConfig::default();
@@ -406,6 +760,29 @@ Content for section 2."#;
         assert_eq!(fast.overlap_characters, 0);
     }
 
+    #[test]
+    fn test_fingerprint_tracks_config() {
+        let default = MarkdownChunker::with_defaults().fingerprint();
+        assert!(default.starts_with("MarkdownChunker:"));
+        assert_eq!(default, MarkdownChunker::with_defaults().fingerprint());
+
+        let changed = MarkdownChunker::new(ChunkConfig {
+            overlap_characters: 0,
+            ..Default::default()
+        });
+        assert_ne!(changed.fingerprint(), default);
+        let split = MarkdownChunker::new(ChunkConfig {
+            oversize: OversizeStrategy::Split,
+            ..Default::default()
+        });
+        assert_ne!(split.fingerprint(), default);
+        let tokenizer = MarkdownChunker::new(ChunkConfig {
+            tokenizer: Arc::new(crate::tokens::CharRatio::new("approx", 4.0)),
+            ..Default::default()
+        });
+        assert_ne!(tokenizer.fingerprint(), default);
+    }
+
     #[test]
     fn test_floor_char_boundary() {
         let s = "Hello, 세계!"; // UTF-8 다중 바이트 문자
@@ -418,6 +795,140 @@ Content for section 2."#;
 
         // 빈 문자열
         assert_eq!(floor_char_boundary("", 0), 0);
+
+        // 다중 바이트 문자 중간은 올림
+        assert_eq!(ceil_char_boundary(s, 8), 10);
+        assert_eq!(ceil_char_boundary(s, 100), s.len());
+    }
+
+    #[test]
+    fn test_long_paragraph_keeps_leading_small_paragraph() {
+        let config = ChunkConfig {
+            min_characters: 50,
+            max_characters: 80,
+            overlap_characters: 0,
+            ..Default::default()
+        };
+        let chunker = MarkdownChunker::new(config.clone());
+
+        let long_para = (0..10)
+            .map(|i| format!("line number {}", i))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let text = format!("intro\n\n{}", long_para);
+
+        let chunks = chunker.chunk(&text);
+        assert!(chunks.iter().any(|c| c.contains("intro")));
+        assert_eq!(validate_chunks(&text, &chunks, &config), Ok(()));
+    }
+
+    #[test]
+    fn test_code_block_with_blank_lines_not_split() {
+        let config = ChunkConfig {
+            min_characters: 0,
+            max_characters: 120,
+            overlap_characters: 0,
+            ..Default::default()
+        };
+        let chunker = MarkdownChunker::new(config.clone());
+
+        let text = format!(
+            "{}\n\n```rust\nfn a() {{}}\n\nfn b() {{}}\n```\n\n{}",
+            "prose ".repeat(15),
+            "tail ".repeat(15)
+        );
+
+        let chunks = chunker.chunk(&text);
+        assert_eq!(validate_chunks(&text, &chunks, &config), Ok(()));
+    }
+
+    #[test]
+    fn test_hard_split_long_line() {
+        let config = ChunkConfig {
+            min_characters: 0,
+            max_characters: 30,
+            overlap_characters: 0,
+            ..Default::default()
+        };
+        let chunker = MarkdownChunker::new(config.clone());
+
+        let text = "가나다라마바사아자차카타파하".repeat(5);
+        let chunks = chunker.chunk(&text);
+        assert!(chunks.len() > 1);
+        assert_eq!(validate_chunks(&text, &chunks, &config), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_chunks_detects_violations() {
+        let config = ChunkConfig {
+            min_characters: 0,
+            max_characters: 10,
+            overlap_characters: 0,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            validate_chunks("abc def", &["abc".to_string()], &config),
+            Err(ChunkViolation::MissingContent { offset: 4 })
+        );
+        assert_eq!(
+            validate_chunks("abc", &["abc".to_string(), " ".to_string()], &config),
+            Err(ChunkViolation::EmptyChunk { index: 1 })
+        );
+        assert!(matches!(
+            validate_chunks("x", &["x".repeat(11)], &config),
+            Err(ChunkViolation::TooLarge { index: 0, .. })
+        ));
+
+        let text = "```\na\n```";
+        let split = vec!["```\na".to_string(), "```".to_string()];
+        assert_eq!(
+            validate_chunks(text, &split, &config),
+            Err(ChunkViolation::SplitCodeFence { line: 1 })
+        );
+    }
+
+    mod proptests {
+        use super::*;
+        use proptest::prelude::*;
+
+        /// Markdown 유사 블록 생성
+        fn block() -> impl Strategy<Value = String> {
+            let word = prop_oneof!["[a-z]{1,12}", "[가-힣]{1,6}", "[0-9]{1,4}"];
+            let words = prop::collection::vec(word, 1..40).prop_map(|w| w.join(" "));
+            prop_oneof![
+                (1usize..4, words.clone())
+                    .prop_map(|(level, w)| format!("{} {}", "#".repeat(level), w)),
+                prop::collection::vec(words.clone(), 1..4).prop_map(|lines| lines.join("\n")),
+                prop::collection::vec(prop_oneof![words.clone(), Just(String::new())], 1..8)
+                    .prop_map(|lines| format!("```\n{}\n```", lines.join("\n"))),
+                "[a-z가-힣]{100,400}",
+            ]
+        }
+
+        fn document() -> impl Strategy<Value = String> {
+            prop::collection::vec(block(), 1..12).prop_map(|blocks| blocks.join("\n\n"))
+        }
+
+        fn config() -> impl Strategy<Value = ChunkConfig> {
+            (40usize..400, 0usize..100, 0usize..80).prop_map(|(max, min_pct, overlap)| {
+                ChunkConfig {
+                    min_characters: max * min_pct / 100,
+                    max_characters: max,
+                    overlap_characters: overlap,
+                    ..Default::default()
+                }
+            })
+        }
+
+        proptest! {
+            #[test]
+            fn prop_markdown_chunker_invariants(text in document(), config in config()) {
+                let chunker = MarkdownChunker::new(config.clone());
+                let chunks = chunker.chunk(&text);
+                prop_assert_eq!(validate_chunks(&text, &chunks, &config), Ok(()));
+            }
+        }
     }
 
     #[test]
@@ -426,6 +937,7 @@ Content for section 2."#;
             min_characters: 100,
             max_characters: 500,
             overlap_characters: 0,
+            ..Default::default()
         };
         let chunker = MarkdownChunker::new(config);
 
@@ -0,0 +1,289 @@
+//! 컬렉션 - 여러 지식베이스를 한 번에 검색 (`query --collections a,b,c`)
+//!
+//! 업무 문서, 개인 메모, 오픈소스 문서처럼 성격이 다른 지식베이스를 따로 두고
+//! 필요할 때 함께 검색합니다. 컬렉션마다 SQLite/LanceDB 저장소가 따로 있습니다.
+//! 모든 명령에 전역 옵션 `--collection NAME`을 붙이면 그 컬렉션을 대상으로 하며,
+//! 처음 수집할 때 저장소가 만들어집니다 (`palank-rag ingest --collection work --dir ~/work`).
+//!
+//! ```text
+//! ~/.palank-rag/                  # default 컬렉션
+//! ├── knowledge.db
+//! ├── vectors.lance/
+//! └── collections/
+//!     ├── work/                   # work 컬렉션
+//!     │   ├── knowledge.db
+//!     │   └── vectors.lance/
+//!     └── oss/
+//! ```
+//!
+//! 각 컬렉션을 동시에 검색한 뒤 컬렉션 안의 순위로 RRF를 다시 계산하여 합칩니다.
+//! 컬렉션마다 문서 수가 달라 점수 크기를 그대로 비교할 수 없기 때문입니다.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use futures::future::try_join_all;
+use serde::Serialize;
+
+use super::hybrid::{HybridRetriever, HybridSearchResult, SearchOptions, RRF_K};
+use super::integrity::SQLITE_FILE;
+
+/// 컬렉션 디렉토리 (데이터 디렉토리 아래)
+pub const COLLECTIONS_DIR: &str = "collections";
+
+/// 기본 컬렉션 이름 (데이터 디렉토리 자체)
+pub const DEFAULT_COLLECTION: &str = "default";
+
+/// 모든 컬렉션을 뜻하는 이름
+const ALL_COLLECTIONS: &str = "all";
+
+// ============================================================================
+// Layout
+// ============================================================================
+
+/// 컬렉션 저장소 디렉토리
+///
+/// 이름은 영문자, 숫자, `-`, `_`만 쓸 수 있습니다.
+pub fn collection_dir(data_dir: &Path, name: &str) -> Result<PathBuf> {
+    if name == DEFAULT_COLLECTION {
+        return Ok(data_dir.to_path_buf());
+    }
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid || name == ALL_COLLECTIONS {
+        anyhow::bail!("Invalid collection name: {:?}", name);
+    }
+    Ok(data_dir.join(COLLECTIONS_DIR).join(name))
+}
+
+/// 저장소가 있는 컬렉션 목록 (default 먼저, 나머지는 이름순)
+pub fn list_collections(data_dir: &Path) -> Vec<String> {
+    let mut names = Vec::new();
+    if data_dir.join(SQLITE_FILE).is_file() {
+        names.push(DEFAULT_COLLECTION.to_string());
+    }
+
+    let Ok(entries) = std::fs::read_dir(data_dir.join(COLLECTIONS_DIR)) else {
+        return names;
+    };
+    let mut others: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.path().join(SQLITE_FILE).is_file())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| collection_dir(data_dir, name).is_ok())
+        .collect();
+    others.sort();
+    names.extend(others);
+
+    names
+}
+
+/// `--collections` 값을 컬렉션 이름 목록으로 (`all`은 저장소가 있는 모든 컬렉션, 중복 제거)
+pub fn resolve_collections(data_dir: &Path, names: &[String]) -> Result<Vec<String>> {
+    let mut resolved: Vec<String> = Vec::new();
+    for name in names.iter().map(|n| n.trim()).filter(|n| !n.is_empty()) {
+        let expanded = if name == ALL_COLLECTIONS {
+            list_collections(data_dir)
+        } else {
+            collection_dir(data_dir, name)?;
+            vec![name.to_string()]
+        };
+        for name in expanded {
+            if !resolved.contains(&name) {
+                resolved.push(name);
+            }
+        }
+    }
+    Ok(resolved)
+}
+
+// ============================================================================
+// Search
+// ============================================================================
+
+/// 컬렉션 표시가 붙은 검색 결과
+#[derive(Debug, Clone, Serialize)]
+pub struct CollectionHit {
+    /// 결과가 나온 컬렉션
+    pub collection: String,
+    /// 컬렉션 간 RRF 스코어 (높을수록 좋음)
+    pub score: f32,
+    #[serde(flatten)]
+    pub result: HybridSearchResult,
+}
+
+/// 여러 컬렉션을 동시에 검색하여 통합
+///
+/// 컬렉션은 같은 임베딩 모델을 쓰므로 쿼리 임베딩은 첫 컬렉션에서 한 번만 계산합니다.
+/// 한 컬렉션이라도 실패하면 어느 컬렉션인지 밝혀 실패합니다.
+pub async fn search_collections(
+    collections: &[(String, &HybridRetriever)],
+    query: &str,
+    options: &SearchOptions,
+) -> Result<Vec<CollectionHit>> {
+    let Some((_, first)) = collections.first() else {
+        return Ok(Vec::new());
+    };
+    let embedding = &first.embed_query(query).await?;
+
+    let searches = collections.iter().map(|(name, retriever)| async move {
+        let results = retriever
+            .search_with_embedding(query, embedding, options)
+            .await
+            .with_context(|| format!("Search failed in collection {:?}", name))?;
+        Ok::<_, anyhow::Error>((name.clone(), results))
+    });
+    let per_collection = try_join_all(searches).await?;
+
+    Ok(fuse_collections(per_collection, options.limit))
+}
+
+/// 컬렉션별 결과를 순위 기반 RRF로 통합
+///
+/// 같은 순위끼리는 컬렉션 안의 RRF 스코어가 높은 쪽, 그다음 앞에 지정한 컬렉션이 먼저입니다.
+pub fn fuse_collections(
+    per_collection: Vec<(String, Vec<HybridSearchResult>)>,
+    limit: usize,
+) -> Vec<CollectionHit> {
+    let mut hits: Vec<(usize, CollectionHit)> = Vec::new();
+    for (order, (collection, results)) in per_collection.into_iter().enumerate() {
+        for (rank, result) in results.into_iter().enumerate() {
+            let score = 1.0 / (RRF_K + rank as f32 + 1.0);
+            let hit = CollectionHit {
+                collection: collection.clone(),
+                score,
+                result,
+            };
+            hits.push((order, hit));
+        }
+    }
+
+    hits.sort_by(|(order_a, a), (order_b, b)| {
+        b.score
+            .total_cmp(&a.score)
+            .then(b.result.rrf_score.total_cmp(&a.result.rrf_score))
+            .then(order_a.cmp(order_b))
+    });
+    hits.truncate(limit);
+
+    hits.into_iter().map(|(_, hit)| hit).collect()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::SearchMethod;
+    use crate::test_support::{sample_documents, EphemeralRetriever};
+    use tempfile::TempDir;
+
+    fn result(doc_id: i64, rrf_score: f32) -> HybridSearchResult {
+        HybridSearchResult {
+            doc_id,
+            url: format!("fixture://{}", doc_id),
+            title: None,
+            chunk_text: None,
+            chunk_index: None,
+            snippet: None,
+            rrf_score,
+            method: SearchMethod::Hybrid,
+            citation: None,
+            location: None,
+            partial: false,
+        }
+    }
+
+    #[test]
+    fn test_fuse_collections() {
+        let fused = fuse_collections(
+            vec![
+                ("work".into(), vec![result(1, 0.02), result(2, 0.01)]),
+                ("notes".into(), vec![result(1, 0.03), result(5, 0.02)]),
+            ],
+            3,
+        );
+
+        let labels: Vec<_> = fused
+            .iter()
+            .map(|hit| (hit.collection.as_str(), hit.result.doc_id))
+            .collect();
+        // 1위끼리는 컬렉션 안 스코어가 높은 notes가 먼저, 같은 doc_id도 컬렉션이 다르면 따로
+        assert_eq!(labels, vec![("notes", 1), ("work", 1), ("notes", 5)]);
+        assert!(fused[0].score > fused[2].score);
+    }
+
+    #[test]
+    fn test_collection_dir() {
+        let data_dir = Path::new("/data");
+        assert_eq!(collection_dir(data_dir, "default").unwrap(), data_dir);
+        assert_eq!(
+            collection_dir(data_dir, "work_2").unwrap(),
+            data_dir.join("collections/work_2")
+        );
+        assert!(collection_dir(data_dir, "../etc").is_err());
+        assert!(collection_dir(data_dir, "").is_err());
+        assert!(collection_dir(data_dir, "all").is_err());
+    }
+
+    #[test]
+    fn test_resolve_collections() {
+        let dir = TempDir::new().unwrap();
+        let data_dir = dir.path();
+        std::fs::write(data_dir.join(SQLITE_FILE), "").unwrap();
+        for name in ["oss", "work", "empty"] {
+            std::fs::create_dir_all(data_dir.join(COLLECTIONS_DIR).join(name)).unwrap();
+        }
+        for name in ["oss", "work"] {
+            let path = data_dir.join(COLLECTIONS_DIR).join(name).join(SQLITE_FILE);
+            std::fs::write(path, "").unwrap();
+        }
+
+        assert_eq!(list_collections(data_dir), vec!["default", "oss", "work"]);
+
+        let names = vec!["work".to_string(), "all".to_string()];
+        let resolved = resolve_collections(data_dir, &names).unwrap();
+        assert_eq!(resolved, vec!["work", "default", "oss"]);
+        assert!(resolve_collections(data_dir, &["a/b".to_string()]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search_collections() {
+        let work = EphemeralRetriever::with_fixtures(sample_documents())
+            .await
+            .unwrap();
+        let notes = EphemeralRetriever::with_fixtures(sample_documents()[..1].to_vec())
+            .await
+            .unwrap();
+
+        let collections = vec![
+            ("work".to_string(), work.retriever()),
+            ("notes".to_string(), notes.retriever()),
+        ];
+        let options = SearchOptions {
+            limit: 10,
+            ..Default::default()
+        };
+        let calls = work.embedder().call_count() + notes.embedder().call_count();
+        let hits = search_collections(&collections, "Rust ownership", &options)
+            .await
+            .unwrap();
+        // 쿼리 임베딩은 첫 컬렉션에서 한 번만
+        assert_eq!(
+            work.embedder().call_count() + notes.embedder().call_count(),
+            calls + 1
+        );
+
+        assert!(hits.iter().any(|hit| hit.collection == "work"));
+        assert!(hits.iter().any(|hit| hit.collection == "notes"));
+        assert!(hits.windows(2).all(|w| w[0].score >= w[1].score));
+        assert!(hits
+            .iter()
+            .take(2)
+            .all(|hit| hit.result.url == "fixture://rust-ownership"));
+    }
+}
@@ -0,0 +1,145 @@
+//! 검색 설정 A/B 비교 - `compare`
+//!
+//! 같은 쿼리를 두 검색 설정으로 실행한 순위 목록을 비교하여
+//! 문서별 순위 변화와 두 목록의 겹침 정도를 계산합니다.
+//! 평가 데이터셋 없이 가중치를 조정할 때 사용합니다.
+
+use serde::Serialize;
+
+use super::hybrid::HybridSearchResult;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// 한 문서의 순위 변화
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RankChange {
+    pub doc_id: i64,
+    pub url: String,
+    pub title: Option<String>,
+    /// 설정 A에서의 순위 (1부터, 없으면 결과에 없음)
+    pub rank_a: Option<usize>,
+    /// 설정 B에서의 순위 (1부터, 없으면 결과에 없음)
+    pub rank_b: Option<usize>,
+}
+
+impl RankChange {
+    /// 순위 변화량 (양수면 B에서 올라감)
+    pub fn delta(&self) -> Option<i64> {
+        match (self.rank_a, self.rank_b) {
+            (Some(a), Some(b)) => Some(a as i64 - b as i64),
+            _ => None,
+        }
+    }
+}
+
+/// 두 순위 목록의 비교 결과
+#[derive(Debug, Clone, Serialize)]
+pub struct RankingDiff {
+    /// 문서별 순위 변화 (B 순위 순, B에 없는 문서는 뒤에 A 순위 순)
+    pub changes: Vec<RankChange>,
+    /// 양쪽 결과에 모두 있는 문서 수
+    pub overlap: usize,
+    /// 양쪽 상위 결과가 같은지
+    pub same_top: bool,
+}
+
+// ============================================================================
+// Comparison
+// ============================================================================
+
+/// 두 검색 결과 목록의 순위 비교
+pub fn compare_rankings(a: &[HybridSearchResult], b: &[HybridSearchResult]) -> RankingDiff {
+    let rank_in = |results: &[HybridSearchResult], doc_id: i64| {
+        results.iter().position(|r| r.doc_id == doc_id).map(|i| i + 1)
+    };
+
+    let mut changes: Vec<RankChange> = b
+        .iter()
+        .enumerate()
+        .map(|(i, result)| RankChange {
+            doc_id: result.doc_id,
+            url: result.url.clone(),
+            title: result.title.clone(),
+            rank_a: rank_in(a, result.doc_id),
+            rank_b: Some(i + 1),
+        })
+        .collect();
+
+    // A에만 있는 문서 (B에서 빠짐)
+    changes.extend(
+        a.iter()
+            .enumerate()
+            .filter(|(_, result)| rank_in(b, result.doc_id).is_none())
+            .map(|(i, result)| RankChange {
+                doc_id: result.doc_id,
+                url: result.url.clone(),
+                title: result.title.clone(),
+                rank_a: Some(i + 1),
+                rank_b: None,
+            }),
+    );
+
+    let overlap = changes
+        .iter()
+        .filter(|c| c.rank_a.is_some() && c.rank_b.is_some())
+        .count();
+    let same_top = match (a.first(), b.first()) {
+        (Some(x), Some(y)) => x.doc_id == y.doc_id,
+        (None, None) => true,
+        _ => false,
+    };
+
+    RankingDiff {
+        changes,
+        overlap,
+        same_top,
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::SearchMethod;
+
+    fn result(doc_id: i64) -> HybridSearchResult {
+        HybridSearchResult {
+            doc_id,
+            url: format!("https://example.com/{}", doc_id),
+            title: None,
+            chunk_text: None,
+            chunk_index: None,
+            snippet: None,
+            rrf_score: 0.0,
+            method: SearchMethod::Hybrid,
+            citation: None,
+            location: None,
+            partial: false,
+        }
+    }
+
+    #[test]
+    fn test_compare_rankings() {
+        let a = vec![result(1), result(2), result(3)];
+        let b = vec![result(3), result(1), result(4)];
+
+        let diff = compare_rankings(&a, &b);
+
+        assert_eq!(diff.overlap, 2);
+        assert!(!diff.same_top);
+        assert_eq!(diff.changes.len(), 4);
+
+        // B 순위 순, 이어서 A에만 있는 문서
+        let ids: Vec<i64> = diff.changes.iter().map(|c| c.doc_id).collect();
+        assert_eq!(ids, vec![3, 1, 4, 2]);
+        assert_eq!(diff.changes[0].delta(), Some(2));
+        assert_eq!(diff.changes[1].delta(), Some(-1));
+        assert_eq!(diff.changes[2].rank_a, None);
+        assert_eq!(diff.changes[3].rank_b, None);
+    }
+}
@@ -0,0 +1,264 @@
+//! Ask 컨텍스트 - 답변 생성용 검색 컨텍스트
+//!
+//! 하이브리드 검색 결과와 고정(pinned) 문서의 관련 청크를 모아
+//! LLM 프롬프트에 넣을 컨텍스트를 구성합니다.
+//!
+//! 청크마다 읽는 순서대로 `[1]`, `[2]` 인용 번호를 붙여 렌더링하므로, 생성된 답변의 번호를
+//! `AskContext::citations()`의 URL/제목과 맞춰 링크로 보여줄 수 있습니다.
+
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::citation::Citation;
+use crate::tokens::{default_tokenizer, Tokenizer};
+
+use super::hybrid::SearchOptions;
+
+/// 기본 컨텍스트 최대 길이 (문자 수)
+pub const DEFAULT_CONTEXT_CHARS: usize = 12_000;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// 컨텍스트 구성 옵션
+#[derive(Debug, Clone)]
+pub struct ContextOptions {
+    /// 검색 옵션 (결과 수, 프레임워크 필터)
+    pub search: SearchOptions,
+    /// 고정 문서의 가장 관련 있는 청크를 항상 포함
+    pub include_pinned: bool,
+    /// 컨텍스트 최대 길이 (문자 수)
+    pub max_chars: usize,
+    /// 컨텍스트 최대 토큰 수 (추정치, None이면 문자 수만 제한)
+    pub max_tokens: Option<usize>,
+    /// `max_tokens` 검사용 토큰 계산기 (답변 모델에 맞춤)
+    pub tokenizer: Arc<dyn Tokenizer>,
+    /// 코딩 질문이면 검색 결과 중 코드 청크를 먼저 채움 (고정 문서 다음)
+    pub code_first: bool,
+}
+
+impl Default for ContextOptions {
+    fn default() -> Self {
+        Self {
+            search: SearchOptions::default(),
+            include_pinned: true,
+            max_chars: DEFAULT_CONTEXT_CHARS,
+            max_tokens: None,
+            tokenizer: default_tokenizer(),
+            code_first: true,
+        }
+    }
+}
+
+/// 컨텍스트에 포함된 청크
+#[derive(Debug, Clone, Serialize)]
+pub struct ContextChunk {
+    pub doc_id: i64,
+    /// 벡터 청크 번호 (FTS 스니펫이면 None)
+    pub chunk_index: Option<i32>,
+    pub url: String,
+    pub title: Option<String>,
+    /// 청크 텍스트 (벡터 청크 또는 FTS 스니펫)
+    pub text: String,
+    /// 검색 점수 (RRF 또는 고정 문서의 벡터 유사도)
+    pub score: f32,
+    /// 고정 문서에서 가져온 청크인지
+    pub pinned: bool,
+    /// 인용 정보 (BibTeX/Zotero에서 가져온 문서)
+    pub citation: Option<Citation>,
+}
+
+/// 답변의 인용 번호가 가리키는 출처
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SourceCitation {
+    /// 인용 번호 (`[1]`이면 1, 컨텍스트 청크 순서)
+    pub marker: usize,
+    pub doc_id: i64,
+    /// 벡터 청크 번호 (FTS 스니펫이면 None)
+    pub chunk_index: Option<i32>,
+    pub url: String,
+    pub title: Option<String>,
+    /// 고정 문서에서 가져온 청크인지
+    pub pinned: bool,
+    /// 인용 정보 (BibTeX/Zotero에서 가져온 문서)
+    pub citation: Option<Citation>,
+}
+
+/// 답변 생성용 컨텍스트
+#[derive(Debug, Clone, Serialize)]
+pub struct AskContext {
+    /// 원본 질문
+    pub query: String,
+    /// 포함된 청크 (고정 문서 먼저, 그다음 검색 순위 순)
+    pub chunks: Vec<ContextChunk>,
+}
+
+impl AskContext {
+    /// 청크가 없는지
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    /// 청크별 인용 번호와 출처 (렌더링된 `[n]` 표시와 같은 순서)
+    pub fn citations(&self) -> Vec<SourceCitation> {
+        self.chunks
+            .iter()
+            .enumerate()
+            .map(|(i, chunk)| SourceCitation {
+                marker: i + 1,
+                doc_id: chunk.doc_id,
+                chunk_index: chunk.chunk_index,
+                url: chunk.url.clone(),
+                title: chunk.title.clone(),
+                pinned: chunk.pinned,
+                citation: chunk.citation.clone(),
+            })
+            .collect()
+    }
+
+    /// 전체 텍스트 길이 (문자 수)
+    pub fn char_count(&self) -> usize {
+        self.chunks.iter().map(|c| c.text.chars().count()).sum()
+    }
+
+    /// 전체 텍스트 토큰 수 (주어진 토크나이저 기준)
+    pub fn token_count(&self, tokenizer: &dyn Tokenizer) -> usize {
+        self.chunks.iter().map(|c| tokenizer.count(&c.text)).sum()
+    }
+
+    /// 프롬프트용 텍스트로 렌더링 (청크마다 `[n]` 인용 번호 표시)
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+
+        for (i, chunk) in self.chunks.iter().enumerate() {
+            let title = chunk.title.as_deref().unwrap_or("-");
+            let pin = if chunk.pinned { " (고정)" } else { "" };
+            out.push_str(&format!(
+                "### [{}] {}{}\n출처: {}\n",
+                i + 1,
+                title,
+                pin,
+                chunk.url
+            ));
+            if let Some(ref citation) = chunk.citation {
+                out.push_str(&format!(
+                    "인용: ({}) {}\n",
+                    citation.short(),
+                    citation.format()
+                ));
+            }
+            out.push('\n');
+            out.push_str(chunk.text.trim());
+            out.push_str("\n\n");
+        }
+
+        out
+    }
+
+    /// 길이 제한(문자 수, 토큰 수)을 지키며 청크 추가
+    ///
+    /// 같은 문서의 같은 텍스트는 한 번만 포함합니다.
+    ///
+    /// # Returns
+    /// 추가했으면 true (중복이거나 길이 초과면 false)
+    pub(crate) fn push_within(
+        &mut self,
+        chunk: ContextChunk,
+        max_chars: usize,
+        max_tokens: Option<usize>,
+        tokenizer: &dyn Tokenizer,
+    ) -> bool {
+        if self
+            .chunks
+            .iter()
+            .any(|c| c.doc_id == chunk.doc_id && c.text == chunk.text)
+        {
+            return false;
+        }
+
+        // 첫 청크는 길이와 관계없이 포함 (빈 컨텍스트 방지)
+        if !self.chunks.is_empty() {
+            if self.char_count() + chunk.text.chars().count() > max_chars {
+                return false;
+            }
+            if let Some(max) = max_tokens {
+                if self.token_count(tokenizer) + tokenizer.count(&chunk.text) > max {
+                    return false;
+                }
+            }
+        }
+
+        self.chunks.push(chunk);
+        true
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokens::{CharRatio, GeminiEstimator};
+
+    fn chunk(doc_id: i64, text: &str) -> ContextChunk {
+        ContextChunk {
+            doc_id,
+            chunk_index: Some(0),
+            url: format!("https://example.com/{}", doc_id),
+            title: None,
+            text: text.to_string(),
+            score: 1.0,
+            pinned: false,
+            citation: None,
+        }
+    }
+
+    #[test]
+    fn test_push_within_dedup_and_budget() {
+        let mut context = AskContext {
+            query: "q".to_string(),
+            chunks: Vec::new(),
+        };
+
+        let gemini = GeminiEstimator;
+        assert!(context.push_within(chunk(1, "aaaaaaaaaa"), 5, None, &gemini));
+        assert!(!context.push_within(chunk(1, "aaaaaaaaaa"), 100, None, &gemini));
+        assert!(!context.push_within(chunk(2, "bbbbb"), 12, None, &gemini));
+        assert!(context.push_within(chunk(2, "bb"), 12, None, &gemini));
+        assert_eq!(context.chunks.len(), 2);
+
+        // 토큰 예산: "aaaaaaaaaa"(4) + "bb"(1) = 5
+        assert_eq!(context.token_count(&gemini), 5);
+        assert!(!context.push_within(chunk(3, "cccc"), 100, Some(6), &gemini));
+        // 토크나이저마다 예산이 다름: "aaaaaaaaaa"(3) + "bb"(1) + "cccc"(1) = 5
+        let ratio = CharRatio::new("approx", 4.0);
+        assert_eq!(context.token_count(&ratio), 4);
+        assert!(context.push_within(chunk(3, "cccc"), 100, Some(5), &ratio));
+
+        let rendered = context.render();
+        assert!(rendered.contains("### [2] -\n출처: https://example.com/2"));
+    }
+
+    #[test]
+    fn test_citations_follow_reading_order() {
+        let mut context = AskContext {
+            query: "q".to_string(),
+            chunks: Vec::new(),
+        };
+        context.push_within(chunk(7, "first"), 100, None, &GeminiEstimator);
+        context.push_within(chunk(3, "second"), 100, None, &GeminiEstimator);
+
+        let citations = context.citations();
+        assert_eq!(citations.len(), 2);
+        assert_eq!((citations[0].marker, citations[0].doc_id), (1, 7));
+        assert_eq!((citations[1].marker, citations[1].doc_id), (2, 3));
+        assert_eq!(citations[1].url, "https://example.com/3");
+        assert_eq!(citations[1].chunk_index, Some(0));
+        let rendered = context.render();
+        assert!(rendered.contains("### [1] -\n출처: https://example.com/7"));
+    }
+}
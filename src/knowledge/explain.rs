@@ -0,0 +1,231 @@
+//! 검색 결과 설명 - `query --explain`
+//!
+//! 각 결과가 어느 검색 경로(FTS5/벡터)에서 몇 위로 찾아졌는지,
+//! RRF 점수가 어떻게 합산되었는지, 어떤 키워드가 일치했는지를 보여줍니다.
+
+use serde::Serialize;
+
+use super::hybrid::{FusedCandidate, FusionConfig, HybridSearchResult, ScoreNormalization};
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// 설명이 붙은 검색 결과
+#[derive(Debug, Clone)]
+pub struct ExplainedResult {
+    /// 검색 결과
+    pub result: HybridSearchResult,
+    /// 순위 근거
+    pub explanation: ResultExplanation,
+}
+
+/// 한 결과의 순위 근거
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ResultExplanation {
+    /// FTS5 경로 기여 (키워드 검색에서 찾은 경우)
+    pub fts: Option<FtsLeg>,
+    /// 벡터 경로 기여 (벡터 검색에서 찾은 경우)
+    pub vector: Option<VectorLeg>,
+    /// RRF 통합 점수 (두 경로 기여의 합)
+    pub fused_score: f32,
+    /// 적용된 RRF 상수 k
+    pub rrf_k: f32,
+    /// 적용된 점수 정규화 방식
+    pub normalization: ScoreNormalization,
+    /// 검색 피드백 보정 (`feedback` 판정이 없으면 0)
+    pub feedback_boost: f32,
+    /// 선호 버전 보정 (`--prefer-version`이 없거나 해당 없으면 0)
+    pub version_boost: f32,
+}
+
+/// FTS5 경로 기여
+#[derive(Debug, Clone, Serialize)]
+pub struct FtsLeg {
+    /// FTS5 결과 내 순위 (1부터)
+    pub rank: usize,
+    /// BM25 점수 (낮을수록 관련성 높음)
+    pub bm25_score: f64,
+    /// 경로 가중치
+    pub weight: f32,
+    /// 통합 점수 기여 (순위 기반이면 weight / (k + rank))
+    pub contribution: f32,
+    /// 스니펫에서 일치한 키워드 (소문자, 중복 제거)
+    pub matched_terms: Vec<String>,
+}
+
+/// 벡터 경로 기여
+#[derive(Debug, Clone, Serialize)]
+pub struct VectorLeg {
+    /// 벡터 결과 내 순위 (1부터)
+    pub rank: usize,
+    /// 가장 가까운 청크 인덱스
+    pub chunk_index: i32,
+    /// 가장 가까운 청크 유사도
+    pub similarity: f32,
+    /// 경로 가중치
+    pub weight: f32,
+    /// 통합 점수 기여 (순위 기반이면 weight / (k + rank))
+    pub contribution: f32,
+}
+
+// ============================================================================
+// ResultExplanation
+// ============================================================================
+
+impl ResultExplanation {
+    /// RRF 통합 후보로부터 설명 생성
+    pub fn from_candidate(candidate: &FusedCandidate<'_>, fusion: &FusionConfig) -> Self {
+        let fts = candidate.fts.zip(candidate.fts_rank).map(|(fts, rank)| FtsLeg {
+            rank,
+            bm25_score: fts.bm25_score,
+            weight: fusion.fts_weight,
+            contribution: candidate.fts_contribution,
+            matched_terms: highlighted_terms(&fts.content_snippet),
+        });
+
+        let vector = candidate
+            .vector
+            .zip(candidate.vector_rank)
+            .map(|(vector, rank)| VectorLeg {
+                rank,
+                chunk_index: vector.chunk_index,
+                similarity: vector.similarity,
+                weight: fusion.vector_weight,
+                contribution: candidate.vector_contribution,
+            });
+
+        Self {
+            fts,
+            vector,
+            fused_score: candidate.score,
+            rrf_k: fusion.rrf_k,
+            normalization: fusion.normalization,
+            feedback_boost: candidate.feedback_boost,
+            version_boost: candidate.version_boost,
+        }
+    }
+
+    /// 점수 계산식 (예: `1/(60+1) + 1/(60+3) = 0.0323`)
+    ///
+    /// 점수 정규화를 쓰면 경로별 기여의 합으로 표시합니다 (예: `min_max: 1.0000 + 0.4000 = 1.4000`).
+    /// 피드백/선호 버전 보정이 있으면 마지막 항으로 붙입니다.
+    pub fn arithmetic(&self) -> String {
+        let mut terms: Vec<String> = if self.normalization == ScoreNormalization::Rank {
+            [
+                self.fts.as_ref().map(|f| (f.weight, f.rank)),
+                self.vector.as_ref().map(|v| (v.weight, v.rank)),
+            ]
+            .into_iter()
+            .flatten()
+            .map(|(weight, rank)| format!("{}/({}+{})", weight, self.rrf_k, rank))
+            .collect()
+        } else {
+            [
+                self.fts.as_ref().map(|f| f.contribution),
+                self.vector.as_ref().map(|v| v.contribution),
+            ]
+            .into_iter()
+            .flatten()
+            .map(|contribution| format!("{:.4}", contribution))
+            .collect()
+        };
+
+        if self.feedback_boost != 0.0 {
+            terms.push(format!("피드백({:+.4})", self.feedback_boost));
+        }
+        if self.version_boost != 0.0 {
+            terms.push(format!("버전({:+.4})", self.version_boost));
+        }
+
+        let sum = format!("{} = {:.4}", terms.join(" + "), self.fused_score);
+        match self.normalization {
+            ScoreNormalization::Rank => sum,
+            other => format!("{}: {}", other.label(), sum),
+        }
+    }
+}
+
+/// FTS5 스니펫의 `<b>...</b>` 강조 구간에서 일치한 키워드 추출
+///
+/// 토크나이저가 실제로 일치시킨 단어이므로 쿼리 원문과 다를 수 있습니다.
+fn highlighted_terms(snippet: &str) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+
+    for part in snippet.split("<b>").skip(1) {
+        let Some((term, _)) = part.split_once("</b>") else {
+            continue;
+        };
+        let term = term.trim().to_lowercase();
+        if !term.is_empty() && !terms.contains(&term) {
+            terms.push(term);
+        }
+    }
+
+    terms
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::{rrf_fuse, rrf_fuse_with, FtsSearchResult, SearchResult};
+
+    #[test]
+    fn test_highlighted_terms() {
+        let snippet = "...use <b>Hooks</b> for <b>state</b>; <b>hooks</b> run...";
+        assert_eq!(highlighted_terms(snippet), vec!["hooks", "state"]);
+        assert!(highlighted_terms("no highlights").is_empty());
+    }
+
+    #[test]
+    fn test_explanation_from_candidate() {
+        let fts = vec![FtsSearchResult {
+            doc_id: 7,
+            title: None,
+            content_snippet: "<b>rust</b> async".to_string(),
+            bm25_score: -3.5,
+        }];
+        let vector = vec![
+            SearchResult {
+                doc_id: 1,
+                chunk_index: 0,
+                chunk_text: String::new(),
+                similarity: 0.9,
+            },
+            SearchResult {
+                doc_id: 7,
+                chunk_index: 2,
+                chunk_text: String::new(),
+                similarity: 0.8,
+            },
+        ];
+
+        let fused = rrf_fuse(&fts, &vector, 10);
+        let explanation = ResultExplanation::from_candidate(&fused[0], &FusionConfig::default());
+
+        let fts_leg = explanation.fts.as_ref().unwrap();
+        let vector_leg = explanation.vector.as_ref().unwrap();
+        assert_eq!(fts_leg.rank, 1);
+        assert_eq!(fts_leg.matched_terms, vec!["rust"]);
+        assert_eq!(vector_leg.rank, 2);
+        assert_eq!(vector_leg.chunk_index, 2);
+        assert!(
+            (fts_leg.contribution + vector_leg.contribution - explanation.fused_score).abs()
+                < 1e-6
+        );
+        assert!(explanation.arithmetic().starts_with("1/(60+1) + 1/(60+2) = "));
+
+        let min_max = FusionConfig {
+            normalization: ScoreNormalization::MinMax,
+            ..Default::default()
+        };
+        let fused = rrf_fuse_with(&fts, &vector, 10, &min_max);
+        let doc7 = fused.iter().find(|c| c.doc_id == 7).unwrap();
+        let explanation = ResultExplanation::from_candidate(doc7, &min_max);
+        assert_eq!(explanation.arithmetic(), "min_max: 1.0000 + 0.0000 = 1.0000");
+    }
+}
@@ -0,0 +1,129 @@
+//! 검색 피드백 - `feedback <query-id> <doc_id> --relevant/--irrelevant`
+//!
+//! 검색 결과에 대한 관련/무관 판정을 저장하고, 다음 검색에서 문서별 보정 점수로 씁니다.
+//! 같은 쿼리에 대한 판정이 가장 크게 반영되고, 다른 쿼리에서 받은 판정은 약하게 반영됩니다.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use super::hybrid::FusedCandidate;
+use crate::normalize::{normalize, NormalizeOptions};
+
+/// 다른 쿼리에서 받은 판정의 반영 비율
+const OTHER_QUERY_FACTOR: f32 = 0.25;
+
+/// 문서당 반영하는 순 판정 수 상한 (같은 판정을 반복해도 무한히 커지지 않도록)
+const MAX_NET_VOTES: i64 = 3;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// 한 문서가 받은 판정 (관련 +1, 무관 -1의 합)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct FeedbackVotes {
+    /// 같은 쿼리에서 받은 순 판정
+    pub query: i64,
+    /// 다른 쿼리에서 받은 순 판정
+    pub other: i64,
+}
+
+impl FeedbackVotes {
+    /// 보정 점수 = weight * (같은 쿼리 + 다른 쿼리 * 0.25), 각 판정은 ±3에서 자름
+    pub fn boost(&self, weight: f32) -> f32 {
+        let query = self.query.clamp(-MAX_NET_VOTES, MAX_NET_VOTES) as f32;
+        let other = self.other.clamp(-MAX_NET_VOTES, MAX_NET_VOTES) as f32;
+        weight * (query + other * OTHER_QUERY_FACTOR)
+    }
+}
+
+// ============================================================================
+// Functions
+// ============================================================================
+
+/// 쿼리 ID (정규화한 쿼리 텍스트의 SHA-256 앞 8자)
+///
+/// 쿼리 기록을 따로 저장하지 않아도 같은 쿼리는 항상 같은 ID가 됩니다.
+/// 대소문자와 공백, 유니코드 표기 차이는 무시합니다 (`NormalizeOptions::KEY`).
+pub fn query_id(query: &str) -> String {
+    let normalized = normalize(query, NormalizeOptions::KEY);
+
+    Sha256::digest(normalized.as_bytes())
+        .iter()
+        .take(4)
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// 통합 후보에 피드백 보정을 더하고 다시 정렬
+///
+/// 동점은 `rrf_fuse_with`와 같이 doc_id 순으로 정렬합니다.
+pub fn apply_feedback(
+    candidates: &mut [FusedCandidate<'_>],
+    votes: &HashMap<i64, FeedbackVotes>,
+    weight: f32,
+) {
+    if votes.is_empty() || weight == 0.0 {
+        return;
+    }
+
+    for candidate in candidates.iter_mut() {
+        if let Some(v) = votes.get(&candidate.doc_id) {
+            candidate.feedback_boost = v.boost(weight);
+            candidate.score += candidate.feedback_boost;
+        }
+    }
+
+    candidates.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.doc_id.cmp(&b.doc_id))
+    });
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::{rrf_fuse, SearchResult};
+
+    #[test]
+    fn test_query_id() {
+        assert_eq!(query_id("React  Hooks"), query_id("react hooks"));
+        assert_ne!(query_id("react hooks"), query_id("react state"));
+        assert_eq!(query_id("react hooks").len(), 8);
+    }
+
+    #[test]
+    fn test_apply_feedback() {
+        let vector: Vec<SearchResult> = (1..=3)
+            .map(|doc_id| SearchResult {
+                doc_id,
+                chunk_index: 0,
+                chunk_text: String::new(),
+                similarity: 0.5,
+            })
+            .collect();
+        let mut fused = rrf_fuse(&[], &vector, 10);
+
+        let votes = HashMap::from([
+            (3, FeedbackVotes { query: 1, other: 0 }),
+            (1, FeedbackVotes { query: -1, other: 0 }),
+        ]);
+        apply_feedback(&mut fused, &votes, 0.01);
+
+        let order: Vec<i64> = fused.iter().map(|c| c.doc_id).collect();
+        assert_eq!(order, vec![3, 2, 1]);
+        assert!((fused[0].feedback_boost - 0.01).abs() < 1e-6);
+
+        // 상한과 다른 쿼리 비율
+        let votes = FeedbackVotes { query: 10, other: -4 };
+        assert!((votes.boost(1.0) - (3.0 - 0.75)).abs() < 1e-6);
+    }
+}
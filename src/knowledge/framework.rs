@@ -0,0 +1,251 @@
+//! 프레임워크 버전 태그 - `react@18`, `python@3.12`
+//!
+//! 프레임워크 태그에 `@버전`을 붙여 같은 프레임워크의 버전별 문서를 구분합니다.
+//! 태그는 기존 `framework` 컬럼에 그대로 저장하며, 필터는 다음과 같이 동작합니다.
+//! - `react`: 모든 버전의 react 문서 (버전 없는 문서 포함)
+//! - `react@18`: react 18 문서만 (`react@18.2`도 포함)
+//!
+//! 수집 시 버전 없이 `--framework react`만 지정하면 URL 경로의 버전(`/v18/`, `/3.12/`)을
+//! 찾아 자동으로 붙입니다. 검색 시 `prefer_version`으로 선호 버전을 올리고
+//! 같은 프레임워크의 다른 버전을 내릴 수 있습니다.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use super::hybrid::FusedCandidate;
+
+/// 선호 버전 보정 기본값 (RRF 한 경로 1위 점수의 절반 남짓)
+pub const VERSION_WEIGHT: f32 = 0.01;
+
+// ============================================================================
+// FrameworkTag
+// ============================================================================
+
+/// 프레임워크 태그 (이름 + 선택적 버전)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrameworkTag {
+    pub name: String,
+    pub version: Option<String>,
+}
+
+impl FrameworkTag {
+    /// `이름[@버전]` 파싱 (버전의 `v` 접두사 제거, `@`로 시작하는 이름은 버전 없는 이름으로 취급)
+    pub fn parse(tag: &str) -> Self {
+        let tag = tag.trim();
+        let split = tag
+            .split_once('@')
+            .map(|(name, version)| (name, version.trim_start_matches(['v', 'V'])));
+        match split {
+            Some((name, version)) if !name.is_empty() && !version.is_empty() => Self {
+                name: name.to_string(),
+                version: Some(version.to_string()),
+            },
+            _ => Self {
+                name: tag.to_string(),
+                version: None,
+            },
+        }
+    }
+
+    /// 저장된 태그가 이 필터에 해당하는지
+    ///
+    /// 버전이 없으면 이름만 비교하고, 있으면 같은 버전이거나 하위 버전(`18` → `18.2`)이어야 합니다.
+    pub fn matches(&self, stored: &str) -> bool {
+        let stored = Self::parse(stored);
+        if stored.name != self.name {
+            return false;
+        }
+        match (&self.version, &stored.version) {
+            (None, _) => true,
+            (Some(want), Some(have)) => version_matches(want, have),
+            (Some(_), None) => false,
+        }
+    }
+
+    /// 선호 버전 보정 (같은 버전 +weight, 같은 프레임워크의 다른 버전 -weight, 나머지 0)
+    pub fn boost(&self, stored: Option<&str>, weight: f32) -> f32 {
+        let (Some(want), Some(stored)) = (&self.version, stored.map(Self::parse)) else {
+            return 0.0;
+        };
+        if stored.name != self.name {
+            return 0.0;
+        }
+        match stored.version {
+            Some(ref have) if version_matches(want, have) => weight,
+            Some(_) => -weight,
+            None => 0.0,
+        }
+    }
+}
+
+impl fmt::Display for FrameworkTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.version {
+            Some(ref version) => write!(f, "{}@{}", self.name, version),
+            None => f.write_str(&self.name),
+        }
+    }
+}
+
+/// `want`가 `have`와 같거나 그 상위 버전 접두사인지 (`18` ↔ `18.2.0`)
+fn version_matches(want: &str, have: &str) -> bool {
+    have == want
+        || have
+            .strip_prefix(want)
+            .is_some_and(|rest| rest.starts_with('.'))
+}
+
+// ============================================================================
+// Functions
+// ============================================================================
+
+/// URL에서 문서 버전 찾기
+///
+/// 경로 조각(또는 첫 번째 호스트 이름 조각) 중 `v18`, `v1.2`처럼 `v`로 시작하거나
+/// `3.12`, `1.38.0`, `18.x`처럼 점으로 나뉜 숫자를 버전으로 봅니다.
+/// 날짜나 글 번호와 헷갈리지 않도록 점 없는 숫자(`/2024/`)는 버전으로 보지 않습니다.
+pub fn detect_version(url: &str) -> Option<String> {
+    let rest = url.split_once("://").map(|(_, r)| r).unwrap_or(url);
+    let rest = rest.split(['?', '#']).next().unwrap_or(rest);
+    let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+
+    let host_label = host.split('.').next().unwrap_or_default();
+    std::iter::once(host_label)
+        .chain(path.split('/'))
+        .find(|segment| is_version(segment))
+        .map(|segment| segment.trim_start_matches(['v', 'V']).to_string())
+}
+
+fn is_version(segment: &str) -> bool {
+    let (prefixed, body) = match segment.strip_prefix(['v', 'V']) {
+        Some(body) => (true, body),
+        None => (false, segment),
+    };
+    let parts: Vec<&str> = body.split('.').collect();
+    let valid = !body.is_empty()
+        && parts[0].chars().all(|c| c.is_ascii_digit())
+        && !parts[0].is_empty()
+        && parts[1..]
+            .iter()
+            .all(|p| *p == "x" || (!p.is_empty() && p.chars().all(|c| c.is_ascii_digit())));
+
+    valid && (prefixed || parts.len() > 1)
+}
+
+/// 수집 시 프레임워크 태그에 버전 붙이기
+///
+/// 태그에 이미 버전이 있거나 URL에서 버전을 찾지 못하면 태그만 정규화합니다.
+pub fn pair_framework(framework: Option<&str>, url: &str) -> Option<String> {
+    let mut tag = FrameworkTag::parse(framework?);
+    if tag.version.is_none() {
+        tag.version = detect_version(url);
+    }
+    Some(tag.to_string())
+}
+
+/// 통합 후보에 선호 버전 보정을 더하고 다시 정렬
+///
+/// `frameworks`는 doc_id별 저장된 프레임워크 태그입니다.
+pub fn apply_version_preference(
+    candidates: &mut [FusedCandidate<'_>],
+    frameworks: &HashMap<i64, Option<String>>,
+    prefer: &FrameworkTag,
+    weight: f32,
+) {
+    if prefer.version.is_none() || weight == 0.0 {
+        return;
+    }
+
+    for candidate in candidates.iter_mut() {
+        let stored = frameworks.get(&candidate.doc_id).and_then(|f| f.as_deref());
+        candidate.version_boost = prefer.boost(stored, weight);
+        candidate.score += candidate.version_boost;
+    }
+
+    candidates.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.doc_id.cmp(&b.doc_id))
+    });
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_match() {
+        let tag = FrameworkTag::parse("react@18");
+        assert_eq!(tag.name, "react");
+        assert_eq!(tag.version.as_deref(), Some("18"));
+        assert_eq!(tag.to_string(), "react@18");
+        assert_eq!(FrameworkTag::parse("@scope").version, None);
+        assert_eq!(FrameworkTag::parse("react@v18"), tag);
+
+        assert!(tag.matches("react@18"));
+        assert!(tag.matches("react@18.2.0"));
+        assert!(!tag.matches("react@180"));
+        assert!(!tag.matches("react@19"));
+        assert!(!tag.matches("react"));
+
+        let any = FrameworkTag::parse("react");
+        assert!(any.matches("react"));
+        assert!(any.matches("react@19"));
+        assert!(!any.matches("react-native"));
+    }
+
+    #[test]
+    fn test_detect_version() {
+        assert_eq!(
+            detect_version("https://docs.python.org/3.12/library/os.html").as_deref(),
+            Some("3.12")
+        );
+        assert_eq!(
+            detect_version("https://docs.rs/tokio/1.38.0/tokio/").as_deref(),
+            Some("1.38.0")
+        );
+        assert_eq!(
+            detect_version("https://v17.angular.io/guide").as_deref(),
+            Some("17")
+        );
+        assert_eq!(
+            detect_version("https://example.com/docs/v2/intro").as_deref(),
+            Some("2")
+        );
+        assert!(detect_version("https://blog.example.com/2024/05/post").is_none());
+        assert!(detect_version("https://react.dev/reference/react").is_none());
+    }
+
+    #[test]
+    fn test_pair_framework() {
+        let url = "https://docs.python.org/3.12/library/os.html";
+        assert_eq!(
+            pair_framework(Some("python"), url).as_deref(),
+            Some("python@3.12")
+        );
+        assert_eq!(
+            pair_framework(Some("python@v3.11"), url).as_deref(),
+            Some("python@3.11")
+        );
+        assert_eq!(pair_framework(None, url), None);
+        assert_eq!(
+            pair_framework(Some("react"), "https://react.dev/learn").as_deref(),
+            Some("react")
+        );
+    }
+
+    #[test]
+    fn test_version_boost() {
+        let prefer = FrameworkTag::parse("react@19");
+        assert_eq!(prefer.boost(Some("react@19.1"), 0.01), 0.01);
+        assert_eq!(prefer.boost(Some("react@18"), 0.01), -0.01);
+        assert_eq!(prefer.boost(Some("react"), 0.01), 0.0);
+        assert_eq!(prefer.boost(Some("vue@3"), 0.01), 0.0);
+        assert_eq!(prefer.boost(None, 0.01), 0.0);
+    }
+}
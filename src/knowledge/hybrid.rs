@@ -7,24 +7,48 @@
 //!
 //! ref: https://www.elastic.co/blog/hybrid-search-rrf
 
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use anyhow::{Context, Result};
+use chrono::Utc;
+use futures::{Stream, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
 
-use crate::embedding::{EmbeddingProvider, GeminiEmbedding};
+use crate::citation::Citation;
+use crate::embedding::{
+    split_for_embedding, truncate_head, truncate_tail, CircuitBreaker, EmbeddingProvider,
+    GeminiEmbedding, OversizeStrategy, MAX_INPUT_TOKENS,
+};
+use crate::generation::GeminiGenerator;
+use crate::normalize::{normalize_document, normalize_query};
 
-use super::chunker::{default_chunker, Chunker};
+use super::boilerplate::{
+    chunk_fingerprint, suppress_boilerplate, BoilerplateConfig, BoilerplateMode,
+};
+use super::chunk_kind::{classify_chunk, is_coding_question, ChunkClass, ChunkKind};
+use super::chunker::{default_chunker, markdown_chunker, ChunkConfig, Chunker};
+use super::context::{AskContext, ContextChunk, ContextOptions};
+use super::explain::{ExplainedResult, ResultExplanation};
+use super::feedback::{apply_feedback, query_id};
+use super::framework::{apply_version_preference, FrameworkTag, VERSION_WEIGHT};
+use super::integrity::{StoreCorrupted, SQLITE_FILE, VECTORS_DIR};
+use super::location::{chunk_line_ranges, source_path, SourceLocation};
 use super::lance::LanceVectorStore;
-use super::store::{get_data_dir, FtsSearchResult, KnowledgeStore, NewDocument};
-use super::vector::{SearchResult, VectorEntry, VectorStore};
+use super::store::{get_data_dir, Document, FtsSearchResult, KnowledgeStore, NewDocument};
+use super::trace::{EmbeddingFingerprint, RetrievalTrace};
+use super::vector::{
+    mean_embedding, SearchResult, VectorEntry, VectorStore, EMBEDDING_DIMENSION,
+};
 
 // ============================================================================
 // Types
 // ============================================================================
 
 /// 하이브리드 검색 결과
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct HybridSearchResult {
     /// 문서 ID
     pub doc_id: i64,
@@ -34,16 +58,28 @@ pub struct HybridSearchResult {
     pub title: Option<String>,
     /// 관련 청크 텍스트 (벡터 검색 결과)
     pub chunk_text: Option<String>,
+    /// 관련 청크 번호 (벡터 검색 결과)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunk_index: Option<i32>,
     /// 콘텐츠 스니펫 (FTS5 결과)
     pub snippet: Option<String>,
     /// RRF 통합 스코어 (높을수록 좋음)
     pub rrf_score: f32,
     /// 검색 방법 (vector, fts, hybrid)
     pub method: SearchMethod,
+    /// 인용 정보 (BibTeX/Zotero에서 가져온 문서)
+    pub citation: Option<Citation>,
+    /// 원본 파일 위치 (소스 코드 파일에서 수집한 문서)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<SourceLocation>,
+    /// 벡터 검색이 시간 예산을 넘어 FTS5 결과만으로 만든 결과 (`SearchOptions::time_budget_ms`)
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub partial: bool,
 }
 
 /// 검색 방법
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum SearchMethod {
     /// 벡터 검색만 사용
     Vector,
@@ -53,18 +89,337 @@ pub enum SearchMethod {
     Hybrid,
 }
 
+/// RRF 통합 후보 (문서 조회 전)
+#[derive(Debug, Clone)]
+pub struct FusedCandidate<'a> {
+    /// 문서 ID
+    pub doc_id: i64,
+    /// RRF 통합 스코어
+    pub score: f32,
+    /// FTS5 결과 (키워드 검색에서 찾은 경우)
+    pub fts: Option<&'a FtsSearchResult>,
+    /// 벡터 결과 (벡터 검색에서 찾은 경우)
+    pub vector: Option<&'a SearchResult>,
+    /// FTS5 결과 내 순위 (1부터)
+    pub fts_rank: Option<usize>,
+    /// 벡터 결과 내 순위 (1부터)
+    pub vector_rank: Option<usize>,
+    /// FTS5 경로 기여 점수 (찾지 못했으면 0)
+    pub fts_contribution: f32,
+    /// 벡터 경로 기여 점수 (찾지 못했으면 0)
+    pub vector_contribution: f32,
+    /// 검색 피드백 보정 점수 (판정이 없으면 0)
+    pub feedback_boost: f32,
+    /// 선호 버전 보정 점수 (`SearchOptions::prefer_version`, 없으면 0)
+    pub version_boost: f32,
+}
+
+impl FusedCandidate<'_> {
+    /// 어느 검색 경로에서 찾았는지
+    pub fn method(&self) -> SearchMethod {
+        match (self.fts.is_some(), self.vector.is_some()) {
+            (true, false) => SearchMethod::Fts,
+            (false, true) => SearchMethod::Vector,
+            _ => SearchMethod::Hybrid,
+        }
+    }
+}
+
+/// RRF 상수 k (기본값, 높은 순위에 더 많은 가중치)
+pub const RRF_K: f32 = 60.0;
+
+/// 피드백 보정 기본값 (순위 기반 RRF에서 1위와 10위 차이 정도)
+pub const FEEDBACK_WEIGHT: f32 = 0.002;
+
+/// 경로별 점수 정규화 방식
+///
+/// `Rank`는 순위만 보는 기본 RRF입니다. 나머지는 각 경로의 원 점수(BM25, 유사도)를
+/// 경로 안에서 정규화한 뒤 가중 합산하므로, 1위가 2위보다 얼마나 앞서는지가 반영됩니다.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoreNormalization {
+    /// 순위 기반 RRF: weight / (k + rank)
+    #[default]
+    Rank,
+    /// 최소-최대 정규화: weight * (x - min) / (max - min), 0.0 ~ weight
+    MinMax,
+    /// 표준 점수를 경로 최하위가 0이 되도록 이동: weight * (x - min) / std
+    ///
+    /// 평균 아래 결과가 음수가 되면 한 경로에만 있는 문서보다 뒤로 밀리므로 이동합니다.
+    ZScore,
+}
+
+impl ScoreNormalization {
+    /// 설정 파일에 쓰는 이름
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Rank => "rank",
+            Self::MinMax => "min_max",
+            Self::ZScore => "z_score",
+        }
+    }
+
+    /// 이름으로 찾기 (`label()`, 하이픈 표기도 허용)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().replace('-', "_").as_str() {
+            "rank" => Some(Self::Rank),
+            "min_max" => Some(Self::MinMax),
+            "z_score" => Some(Self::ZScore),
+            _ => None,
+        }
+    }
+}
+
+/// RRF 통합 설정
+///
+/// 검색 경로별 가중치와 k 상수, 점수 정규화 방식을 조정합니다.
+/// 기본값은 가중치 1.0, k = 60, 순위 기반 RRF 입니다.
+/// 설정 파일의 `[search]` 섹션(query, ask)과 `compare` 명령어의 설정(TOML)으로 사용됩니다.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FusionConfig {
+    /// RRF 상수 k (작을수록 상위 순위의 영향이 큼)
+    pub rrf_k: f32,
+    /// FTS5 경로 가중치
+    pub fts_weight: f32,
+    /// 벡터 경로 가중치
+    pub vector_weight: f32,
+    /// 점수 정규화 방식 (`rank`, `min_max`, `z_score`)
+    pub normalization: ScoreNormalization,
+    /// 검색 피드백 1건당 보정 점수 (0이면 피드백 무시)
+    pub feedback_weight: f32,
+}
+
+impl Default for FusionConfig {
+    fn default() -> Self {
+        Self {
+            rrf_k: RRF_K,
+            fts_weight: 1.0,
+            vector_weight: 1.0,
+            normalization: ScoreNormalization::Rank,
+            feedback_weight: FEEDBACK_WEIGHT,
+        }
+    }
+}
+
+impl FusionConfig {
+    /// 설정 검사 (`rrf_k`가 0 이하이면 상위 순위의 점수가 무한대나 음수가 됨)
+    pub fn validate(&self) -> Result<()> {
+        if !self.rrf_k.is_finite() || self.rrf_k <= 0.0 {
+            anyhow::bail!("rrf_k must be greater than 0 (got {})", self.rrf_k);
+        }
+        Ok(())
+    }
+
+    /// 한 경로 순위의 RRF 기여 점수 = weight / (k + rank), rank는 1부터
+    pub fn contribution(&self, weight: f32, rank: usize) -> f32 {
+        weight / (self.rrf_k + rank as f32)
+    }
+
+    /// 한 경로 결과들의 기여 점수 (결과 순서가 곧 순위)
+    ///
+    /// `raw`는 높을수록 관련성이 높은 원 점수이며, `Rank` 방식에서는 순위만 사용합니다.
+    /// 기여 점수는 음수가 되지 않으며, 점수가 모두 같으면 min-max는 weight, z-score는 0을 줍니다.
+    pub fn leg_contributions(&self, weight: f32, raw: &[f32]) -> Vec<f32> {
+        match self.normalization {
+            ScoreNormalization::Rank => (1..=raw.len())
+                .map(|rank| self.contribution(weight, rank))
+                .collect(),
+            ScoreNormalization::MinMax => {
+                let min = raw.iter().copied().fold(f32::INFINITY, f32::min);
+                let max = raw.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+                let range = max - min;
+                raw.iter()
+                    .map(|x| if range > 0.0 { weight * (x - min) / range } else { weight })
+                    .collect()
+            }
+            ScoreNormalization::ZScore => {
+                let n = raw.len() as f32;
+                let mean = raw.iter().sum::<f32>() / n;
+                let std = (raw.iter().map(|x| (x - mean).powi(2)).sum::<f32>() / n).sqrt();
+                // (x - mean) / std 에서 최하위 (min - mean) / std 를 빼서 0 이상으로
+                let min = raw.iter().copied().fold(f32::INFINITY, f32::min);
+                raw.iter()
+                    .map(|x| if std > 0.0 { weight * (x - min) / std } else { 0.0 })
+                    .collect()
+            }
+        }
+    }
+}
+
+// ============================================================================
+// RRF Fusion
+// ============================================================================
+
+/// RRF (Reciprocal Rank Fusion) 알고리즘
+///
+/// 두 검색 결과를 순위 기반으로 통합합니다.
+/// 문서 조회 없이 순수 계산만 수행하므로 벤치마크/테스트에서 직접 사용할 수 있습니다.
+/// ref: https://www.elastic.co/blog/hybrid-search-rrf
+///
+/// RRF Score = sum(1 / (k + rank))
+pub fn rrf_fuse<'a>(
+    fts_results: &'a [FtsSearchResult],
+    vector_results: &'a [SearchResult],
+    limit: usize,
+) -> Vec<FusedCandidate<'a>> {
+    rrf_fuse_with(fts_results, vector_results, limit, &FusionConfig::default())
+}
+
+/// 가중치를 적용한 RRF 통합
+///
+/// RRF Score = sum(weight / (k + rank))
+///
+/// `fusion.normalization`이 `Rank`가 아니면 순위 대신 경로별로 정규화한 원 점수를 합산합니다.
+/// FTS5의 BM25는 낮을수록 좋으므로 부호를 바꿔 사용합니다.
+pub fn rrf_fuse_with<'a>(
+    fts_results: &'a [FtsSearchResult],
+    vector_results: &'a [SearchResult],
+    limit: usize,
+    fusion: &FusionConfig,
+) -> Vec<FusedCandidate<'a>> {
+    // doc_id -> 후보
+    let mut scores: HashMap<i64, FusedCandidate<'a>> = HashMap::new();
+    let empty = |doc_id| FusedCandidate {
+        doc_id,
+        score: 0.0,
+        fts: None,
+        vector: None,
+        fts_rank: None,
+        vector_rank: None,
+        fts_contribution: 0.0,
+        vector_contribution: 0.0,
+        feedback_boost: 0.0,
+        version_boost: 0.0,
+    };
+
+    // FTS5 결과 추가
+    let fts_raw: Vec<f32> = fts_results.iter().map(|r| -r.bm25_score as f32).collect();
+    let fts_contributions = fusion.leg_contributions(fusion.fts_weight, &fts_raw);
+    for (rank, (result, contribution)) in
+        fts_results.iter().zip(fts_contributions).enumerate()
+    {
+        let entry = scores.entry(result.doc_id).or_insert_with(|| empty(result.doc_id));
+        entry.score += contribution;
+        entry.fts = Some(result);
+        entry.fts_rank = Some(rank + 1);
+        entry.fts_contribution = contribution;
+    }
+
+    // 벡터 결과 추가
+    let vector_raw: Vec<f32> = vector_results.iter().map(|r| r.similarity).collect();
+    let vector_contributions = fusion.leg_contributions(fusion.vector_weight, &vector_raw);
+    for (rank, (result, contribution)) in
+        vector_results.iter().zip(vector_contributions).enumerate()
+    {
+        let entry = scores.entry(result.doc_id).or_insert_with(|| empty(result.doc_id));
+        entry.score += contribution;
+        entry.vector = Some(result);
+        entry.vector_rank = Some(rank + 1);
+        entry.vector_contribution = contribution;
+    }
+
+    // 정렬 및 자르기 (동점은 doc_id 순으로 결정적 정렬)
+    let mut results: Vec<FusedCandidate<'a>> = scores.into_values().collect();
+    results.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.doc_id.cmp(&b.doc_id))
+    });
+    results.truncate(limit);
+
+    results
+}
+
+/// 검색 옵션
+///
+/// 하이브리드 검색의 결과 수와 범위(프레임워크 등)를 지정합니다.
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchOptions {
+    /// 최대 결과 수
+    pub limit: usize,
+    /// 프레임워크 필터 (FTS5/벡터 양쪽에 동일하게 적용, `react@18`처럼 버전 지정 가능)
+    pub framework: Option<String>,
+    /// 선호 버전 (`react@19`) - 같은 버전 문서를 올리고 같은 프레임워크의 다른 버전을 내림
+    pub prefer_version: Option<String>,
+    /// RRF 통합 설정 (가중치, k)
+    pub fusion: FusionConfig,
+    /// 2단계 검색: 문서 임베딩으로 상위 N개 문서를 먼저 고른 뒤 그 안에서만 청크 검색
+    ///
+    /// 문서 임베딩이 아직 없으면(`repair` 전) 전체 청크 검색으로 대체합니다.
+    pub two_stage: Option<usize>,
+    /// 벡터 경로(쿼리 임베딩 + 벡터 검색) 시간 예산 (밀리초)
+    ///
+    /// 넘으면 기다리지 않고 FTS5 결과만으로 결과를 만들며 `HybridSearchResult::partial`로 표시합니다.
+    pub time_budget_ms: Option<u64>,
+    /// 청크 종류 필터 (`query --only-code`/`--only-prose`)
+    ///
+    /// 벡터 결과는 해당 종류의 청크만, FTS5 결과는 해당 종류의 청크가 있는 문서만 남깁니다.
+    pub chunk_kind: Option<ChunkKind>,
+    /// 상용구 청크(여러 문서에 반복되는 쿠키 배너, 라이선스 문구) 처리 (`[boilerplate]` 설정)
+    pub boilerplate: BoilerplateConfig,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            limit: 5,
+            framework: None,
+            prefer_version: None,
+            fusion: FusionConfig::default(),
+            two_stage: None,
+            time_budget_ms: None,
+            chunk_kind: None,
+            boilerplate: BoilerplateConfig::default(),
+        }
+    }
+}
+
+impl SearchOptions {
+    /// 결과 수만 지정한 옵션
+    pub fn with_limit(limit: usize) -> Self {
+        Self {
+            limit,
+            ..Default::default()
+        }
+    }
+}
+
 // ============================================================================
 // HybridRetriever
 // ============================================================================
 
+/// 일괄 추가 시 한 번에 임베딩할 청크 수
+const EMBED_BATCH_SIZE: usize = 64;
+
+/// 재임베딩(repair) 시 한 번에 저장할 문서 수
+const REINDEX_BATCH_DOCS: usize = 32;
+
+/// 입력 한도 초과 청크 요약 지시문 (`OversizeStrategy::Summarize`)
+const SUMMARIZE_INSTRUCTIONS: &str = "다음 글을 검색 색인용으로 요약하세요. \
+     핵심 용어, 이름, 코드 식별자는 그대로 유지하고 요약문만 출력하세요.";
+
+/// 프레임워크 범위 벡터 검색 시 후보 배수 (필터링으로 줄어드는 만큼 더 가져옴)
+const SCOPED_OVERSAMPLE: usize = 4;
+
+/// `search_stream`이 문서 정보를 한 번에 조회하는 결과 수
+const STREAM_PAGE_SIZE: usize = 10;
+
 /// 하이브리드 검색기
 ///
 /// SQLite FTS5 (키워드) + LanceDB (벡터)를 RRF로 통합합니다.
 pub struct HybridRetriever {
     store: KnowledgeStore,
     vector: LanceVectorStore,
-    embedder: GeminiEmbedding,
+    embedder: Box<dyn EmbeddingProvider>,
     chunker: Box<dyn Chunker>,
+    /// 청커를 만든 청킹 설정 (임베딩 입력 한도를 넘는 청크 처리 방식 포함)
+    chunk_config: ChunkConfig,
+    /// `OversizeStrategy::Summarize`용 요약 생성기
+    summarizer: Option<GeminiGenerator>,
+    /// 검색 트레이스 저장 디렉토리 (None이면 저장 안 함)
+    trace_dir: Option<PathBuf>,
 }
 
 impl HybridRetriever {
@@ -81,25 +436,43 @@ impl HybridRetriever {
     /// # Arguments
     /// * `data_dir` - 데이터 저장 디렉토리
     pub async fn with_data_dir(data_dir: &Path) -> Result<Self> {
+        // Gemini 임베딩 (API 장애 시 요청마다 재시도하지 않도록 서킷 브레이커로 감쌈)
+        let embedder = GeminiEmbedding::from_env()
+            .context("Failed to create embedder")?;
+
+        Self::with_embedder(data_dir, Box::new(CircuitBreaker::new(embedder))).await
+    }
+
+    /// 임베딩 프로바이더를 지정하여 생성
+    ///
+    /// 테스트나 자체 임베딩 모델을 사용하는 경우에 씁니다.
+    ///
+    /// # Arguments
+    /// * `data_dir` - 데이터 저장 디렉토리
+    /// * `embedder` - 임베딩 프로바이더
+    pub async fn with_embedder(
+        data_dir: &Path,
+        embedder: Box<dyn EmbeddingProvider>,
+    ) -> Result<Self> {
         // 디렉토리 생성
         if !data_dir.exists() {
             std::fs::create_dir_all(data_dir)
                 .context("Failed to create data directory")?;
         }
 
-        // SQLite 저장소
-        let db_path = data_dir.join("knowledge.db");
+        // SQLite 저장소 (열 때 quick_check)
+        let db_path = data_dir.join(SQLITE_FILE);
         let store = KnowledgeStore::open(&db_path)
             .context("Failed to open knowledge store")?;
 
-        // LanceDB 벡터 저장소
-        let lance_path = data_dir.join("vectors.lance");
+        // LanceDB 벡터 저장소 (매니페스트 검증)
+        let lance_path = data_dir.join(VECTORS_DIR);
         let vector = LanceVectorStore::open(&lance_path).await
             .context("Failed to open vector store")?;
-
-        // Gemini 임베딩
-        let embedder = GeminiEmbedding::from_env()
-            .context("Failed to create embedder")?;
+        vector.validate().await.map_err(|e| StoreCorrupted {
+            path: lance_path.clone(),
+            detail: format!("{:#}", e),
+        })?;
 
         // 청커
         let chunker = default_chunker();
@@ -109,12 +482,47 @@ impl HybridRetriever {
             vector,
             embedder,
             chunker,
+            chunk_config: ChunkConfig::default(),
+            summarizer: None,
+            trace_dir: None,
         })
     }
 
+    /// 청킹 설정 지정 (입력 한도 초과 청크 처리 방식 포함)
+    pub fn with_chunk_config(mut self, config: ChunkConfig) -> Self {
+        self.chunker = markdown_chunker(config.clone());
+        self.chunk_config = config;
+        self
+    }
+
+    /// 입력 한도 초과 청크 처리 방식만 지정 (나머지 청킹 설정은 유지)
+    pub fn with_oversize(self, oversize: OversizeStrategy) -> Self {
+        let config = ChunkConfig {
+            oversize,
+            ..self.chunk_config.clone()
+        };
+        self.with_chunk_config(config)
+    }
+
+    /// `OversizeStrategy::Summarize`에 쓸 요약 생성기 지정
+    pub fn with_summarizer(mut self, generator: GeminiGenerator) -> Self {
+        self.summarizer = Some(generator);
+        self
+    }
+
+    /// 검색할 때마다 트레이스(JSON)를 지정한 디렉토리에 저장
+    ///
+    /// 기본 위치는 `~/.palank-rag/traces` (`get_data_dir().join(TRACES_DIR)`)입니다.
+    pub fn with_trace_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.trace_dir = Some(dir.into());
+        self
+    }
+
     /// 문서 추가 (자동 임베딩)
     ///
-    /// 문서를 SQLite에 저장하고, 청킹 후 LanceDB에 임베딩을 저장합니다.
+    /// 청킹과 임베딩을 마친 뒤 문서를 SQLite에 저장하고, LanceDB에 임베딩을 저장합니다.
+    /// 임베딩이나 벡터 저장에 실패하면 벡터 없는 문서가 남지 않도록 문서를 저장하지 않습니다
+    /// (같은 URL을 다시 수집한 경우 ID는 그대로이며 이전 문서와 벡터가 남습니다).
     ///
     /// # Arguments
     /// * `doc` - 새 문서
@@ -122,34 +530,46 @@ impl HybridRetriever {
     /// # Returns
     /// 문서 ID
     pub async fn add_document(&self, doc: NewDocument) -> Result<i64> {
-        // 1. SQLite에 문서 저장
-        let doc_id = self.store.add_document(doc.clone())
-            .context("Failed to add document to store")?;
-
-        // 2. 텍스트 청킹
-        let chunks = self.chunker.chunk(&doc.content);
+        // 1. 텍스트 청킹 (저장할 본문과 같은 정규화 적용)
+        let content = normalize_document(&doc.content);
+        let chunks = self.chunk(&content);
         if chunks.is_empty() {
             tracing::warn!("No chunks generated for document: {}", doc.url);
-            return Ok(doc_id);
         }
 
-        // 3. 임베딩 생성 및 저장
+        // 2. 임베딩 생성 (문서 ID는 저장 후 채움)
         let mut entries = Vec::with_capacity(chunks.len());
-
         for (i, chunk) in chunks.iter().enumerate() {
-            let embedding = self.embedder.embed(chunk).await
+            let input = self.embedding_input(chunk).await?;
+            let embedding = self.embedder.embed(&input).await
                 .context("Failed to embed chunk")?;
 
             entries.push(VectorEntry {
-                doc_id,
+                doc_id: 0,
                 chunk_index: i as i32,
                 chunk_text: chunk.clone(),
                 embedding,
             });
         }
 
-        self.vector.insert_batch(&entries).await
-            .context("Failed to insert vectors")?;
+        // 3. SQLite에 문서 저장 (같은 URL이면 같은 ID로 갱신, 롤백용으로 이전 행 보관)
+        let previous = self.store.get_by_url(&doc.url)?;
+        let doc_id = self.store.add_document(doc.clone())
+            .context("Failed to add document to store")?;
+        for entry in &mut entries {
+            entry.doc_id = doc_id;
+        }
+
+        // 4. 청크 기록 및 벡터 저장
+        let chunked = [(content, chunks)];
+        self.index_added(
+            std::slice::from_ref(&doc),
+            &[doc_id],
+            &[previous],
+            &chunked,
+            &entries,
+        )
+        .await?;
 
         tracing::info!(
             "Added document: {} (id={}, chunks={})",
@@ -159,198 +579,1484 @@ impl HybridRetriever {
         Ok(doc_id)
     }
 
-    /// 문서 삭제
+    /// 여러 문서 일괄 추가 (자동 임베딩)
     ///
-    /// SQLite와 LanceDB에서 모두 삭제합니다.
-    pub async fn delete_document(&self, doc_id: i64) -> Result<bool> {
-        // 벡터 먼저 삭제
-        self.vector.delete_by_doc_id(doc_id).await?;
-
-        // SQLite에서 삭제
-        self.store.delete_document(doc_id)
+    /// 모든 문서의 청크를 모아 `EMBED_BATCH_SIZE` 단위로 임베딩한 뒤,
+    /// SQLite에는 한 트랜잭션으로 저장하고 LanceDB에 한 번에 삽입합니다.
+    /// 폴더 수집처럼 문서가 많을 때 `add_document` 반복보다 훨씬 빠릅니다.
+    /// 임베딩이나 벡터 저장에 실패하면 배치의 새 문서는 남기지 않고, 다시 수집한 문서는
+    /// 이전 상태로 되돌립니다.
+    ///
+    /// # Returns
+    /// 입력 순서와 같은 순서의 문서 ID 목록
+    pub async fn add_documents(&self, docs: &[NewDocument]) -> Result<Vec<i64>> {
+        let mut prepared = self.prepare_documents(docs).await;
+        if let Some(e) = prepared.take_error() {
+            return Err(e);
+        }
+        self.store_prepared(&prepared, 0..prepared.len()).await
     }
 
-    /// 하이브리드 검색 (RRF 통합)
+    /// 문서 청킹과 임베딩 (저장은 `store_prepared`)
     ///
-    /// FTS5와 벡터 검색을 RRF 알고리즘으로 통합합니다.
+    /// 임베딩이 중간에 실패하면 그때까지 임베딩을 마친 앞쪽 문서의 벡터는 남겨 두므로,
+    /// 호출자는 그 문서들을 다시 임베딩하지 않고 저장할 수 있습니다.
+    pub async fn prepare_documents(&self, docs: &[NewDocument]) -> PreparedDocuments {
+        // 1. 모든 문서 청킹 (저장할 본문과 같은 정규화 적용)
+        let mut chunked = Vec::with_capacity(docs.len());
+        let mut pending: Vec<(i64, i32, String)> = Vec::new();
+        for (position, doc) in docs.iter().enumerate() {
+            let content = normalize_document(&doc.content);
+            let chunks = self.chunk(&content);
+            if chunks.is_empty() {
+                tracing::warn!("No chunks generated for document: {}", doc.url);
+            }
+            pending.extend(
+                chunks
+                    .iter()
+                    .enumerate()
+                    .map(|(i, chunk)| (position as i64, i as i32, chunk.clone())),
+            );
+            chunked.push((content, chunks));
+        }
+
+        // 2. 배치 단위 임베딩 (문서 ID 자리에는 입력 순번)
+        let mut entries = Vec::with_capacity(pending.len());
+        let error = self.embed_pending_into(&pending, &mut entries).await.err();
+
+        // 모든 청크가 임베딩된 앞쪽 문서 수
+        let mut chunk_end = 0;
+        let embedded = chunked
+            .iter()
+            .take_while(|(_, chunks)| {
+                chunk_end += chunks.len();
+                chunk_end <= entries.len()
+            })
+            .count();
+
+        PreparedDocuments {
+            docs: docs.to_vec(),
+            chunked,
+            entries,
+            embedded,
+            error,
+        }
+    }
+
+    /// 준비한 문서 중 `range` 범위를 저장 (SQLite 한 트랜잭션 + 벡터 교체)
     ///
-    /// # Arguments
-    /// * `query` - 검색 쿼리
-    /// * `limit` - 최대 결과 수
+    /// 저장이 실패해도 `prepared`의 임베딩은 그대로이므로 범위를 나눠 다시 저장할 수 있습니다.
     ///
     /// # Returns
-    /// RRF 스코어 기준 정렬된 검색 결과
-    pub async fn search(&self, query: &str, limit: usize) -> Result<Vec<HybridSearchResult>> {
-        // 1. FTS5 키워드 검색
-        let fts_results = self.store.search_fts(query, limit * 2)?;
+    /// 범위 안 문서의 ID 목록 (입력 순서)
+    pub async fn store_prepared(
+        &self,
+        prepared: &PreparedDocuments,
+        range: Range<usize>,
+    ) -> Result<Vec<i64>> {
+        if range.end > prepared.embedded {
+            anyhow::bail!(
+                "Documents {}..{} are not embedded (embedded: {})",
+                range.start,
+                range.end,
+                prepared.embedded
+            );
+        }
+        let docs = &prepared.docs[range.clone()];
+
+        // 3. SQLite에 일괄 저장 (단일 트랜잭션, 롤백용으로 이전 행 보관)
+        let previous = docs
+            .iter()
+            .map(|doc| self.store.get_by_url(&doc.url))
+            .collect::<Result<Vec<_>>>()?;
+        let doc_ids = self.store.add_documents(docs)
+            .context("Failed to add documents to store")?;
+        let entries: Vec<VectorEntry> = prepared
+            .entries
+            .iter()
+            .filter(|entry| range.contains(&(entry.doc_id as usize)))
+            .map(|entry| VectorEntry {
+                doc_id: doc_ids[entry.doc_id as usize - range.start],
+                ..entry.clone()
+            })
+            .collect();
 
-        // 2. 벡터 검색
-        let query_embedding = self.embedder.embed(query).await?;
-        let vector_results = self.vector.search(&query_embedding, limit * 2).await?;
+        // 4. 청크 기록 및 LanceDB 일괄 삽입 (청크 + 문서 임베딩)
+        let chunked = &prepared.chunked[range];
+        self.index_added(docs, &doc_ids, &previous, chunked, &entries)
+            .await?;
 
-        // 3. RRF 통합
-        let merged = self.rrf_merge(&fts_results, &vector_results, limit);
+        tracing::info!(
+            "Added {} documents (chunks={})",
+            doc_ids.len(), entries.len()
+        );
 
-        Ok(merged)
+        Ok(doc_ids)
     }
 
-    /// 벡터 검색만 수행
-    pub async fn search_vector(&self, query: &str, limit: usize) -> Result<Vec<HybridSearchResult>> {
-        let query_embedding = self.embedder.embed(query).await?;
-        let results = self.vector.search(&query_embedding, limit).await?;
+    /// 방금 저장한 문서의 벡터 교체와 청크 기록 저장
+    ///
+    /// 청크 벡터는 한 번의 커밋으로 교체합니다. 이 단계가 실패하면 새로 추가한 문서는 지우고,
+    /// 다시 수집한 문서는 이전 행(`previous`)으로 되돌려 기존 벡터와 짝이 맞게 합니다.
+    /// 벡터 교체 이후(문서 임베딩, 청크 기록) 실패는 문서와 벡터가 이미 일치하므로 되돌리지
+    /// 않으며, `repair`가 빠진 기록을 채웁니다.
+    async fn index_added(
+        &self,
+        docs: &[NewDocument],
+        doc_ids: &[i64],
+        previous: &[Option<Document>],
+        chunked: &[(String, Vec<String>)],
+        entries: &[VectorEntry],
+    ) -> Result<()> {
+        if let Err(e) = self.vector.replace_doc_vectors(doc_ids, entries).await {
+            for (&doc_id, previous) in doc_ids.iter().zip(previous) {
+                let discarded = match previous {
+                    Some(previous) => self.store.restore_document(previous).map(|_| ()),
+                    None => self.delete_document(doc_id).await.map(|_| ()),
+                };
+                if let Err(e) = discarded {
+                    tracing::warn!("Failed to roll back document {}: {:#}", doc_id, e);
+                }
+            }
+            return Err(e.context("Failed to insert vectors"));
+        }
 
-        let mut hybrid_results = Vec::with_capacity(results.len());
+        // 청크가 없어진 문서는 이전 문서 임베딩도 삭제
+        let with_chunks: HashSet<i64> = entries.iter().map(|e| e.doc_id).collect();
+        let without_chunks: Vec<i64> = doc_ids
+            .iter()
+            .filter(|id| !with_chunks.contains(id))
+            .copied()
+            .collect();
+        self.vector.delete_by_doc_ids(&without_chunks).await?;
+        self.vector
+            .upsert_doc_embeddings(&doc_embeddings(entries))
+            .await
+            .context("Failed to insert document embeddings")?;
+        for ((doc, &doc_id), (content, chunks)) in docs.iter().zip(doc_ids).zip(chunked) {
+            self.record_chunk_lines(doc_id, &doc.url, content, chunks)?;
+            self.record_chunk_kinds(doc_id, &doc.url, chunks)?;
+            self.record_chunk_fingerprints(doc_id, chunks)?;
+        }
+        self.store
+            .set_chunker(doc_ids, &self.chunker.fingerprint())?;
+        self.record_chunk_models(doc_ids, entries)
+    }
 
-        for result in results {
-            let doc = self.store.get_document(result.doc_id)?;
-            let (url, title) = doc.map(|d| (d.url, d.title)).unwrap_or_default();
+    /// 텍스트 청킹 (`Split`이면 입력 한도를 넘는 청크를 하위 청크로 나눔)
+    fn chunk(&self, content: &str) -> Vec<String> {
+        let chunks = self.chunker.chunk(content);
+        if self.chunk_config.oversize != OversizeStrategy::Split {
+            return chunks;
+        }
 
-            hybrid_results.push(HybridSearchResult {
-                doc_id: result.doc_id,
-                url,
-                title,
-                chunk_text: Some(result.chunk_text),
-                snippet: None,
-                rrf_score: result.similarity,
-                method: SearchMethod::Vector,
-            });
+        chunks
+            .into_iter()
+            .flat_map(|chunk| {
+                split_for_embedding(&chunk, MAX_INPUT_TOKENS, &*self.chunk_config.tokenizer)
+            })
+            .collect()
+    }
+
+    /// 청크의 임베딩 입력 (입력 한도를 넘으면 설정한 방식으로 줄임)
+    ///
+    /// `Average`는 임베더가 나누어 평균하므로 그대로 넘깁니다.
+    /// 저장되는 청크 본문은 항상 원문입니다.
+    async fn embedding_input(&self, chunk: &str) -> Result<String> {
+        let tokenizer = &*self.chunk_config.tokenizer;
+        if tokenizer.fits(chunk, MAX_INPUT_TOKENS) {
+            return Ok(chunk.to_string());
         }
 
-        Ok(hybrid_results)
+        match self.chunk_config.oversize {
+            OversizeStrategy::Average | OversizeStrategy::Split => Ok(chunk.to_string()),
+            OversizeStrategy::TruncateHead => {
+                Ok(truncate_head(chunk, MAX_INPUT_TOKENS, tokenizer))
+            }
+            OversizeStrategy::TruncateTail => {
+                Ok(truncate_tail(chunk, MAX_INPUT_TOKENS, tokenizer))
+            }
+            OversizeStrategy::Summarize => {
+                let Some(ref generator) = self.summarizer else {
+                    anyhow::bail!("Oversize strategy 'summarize' requires a summarizer");
+                };
+                let summary = generator
+                    .generate(&format!("{}\n\n{}", SUMMARIZE_INSTRUCTIONS, chunk))
+                    .await
+                    .context("Failed to summarize oversized chunk")?;
+                Ok(truncate_tail(&summary, MAX_INPUT_TOKENS, tokenizer))
+            }
+        }
     }
 
-    /// FTS5 키워드 검색만 수행
-    pub fn search_fts(&self, query: &str, limit: usize) -> Result<Vec<HybridSearchResult>> {
-        let results = self.store.search_fts(query, limit)?;
+    /// 소스 코드 문서면 청크별 원본 줄 범위 저장 (quickfix 출력용)
+    fn record_chunk_lines(
+        &self,
+        doc_id: i64,
+        url: &str,
+        content: &str,
+        chunks: &[String],
+    ) -> Result<()> {
+        if source_path(url).is_none() {
+            return Ok(());
+        }
 
-        let mut hybrid_results = Vec::with_capacity(results.len());
+        self.store
+            .set_chunk_lines(doc_id, &chunk_line_ranges(content, chunks))
+            .context("Failed to store chunk line ranges")
+    }
 
-        for result in results {
-            let doc = self.store.get_document(result.doc_id)?;
-            let (url, title) = doc.map(|d| (d.url, d.title)).unwrap_or_default();
+    /// 청크별 지문 저장 (상용구 청크 판별용 빈도 색인)
+    fn record_chunk_fingerprints(&self, doc_id: i64, chunks: &[String]) -> Result<()> {
+        let fingerprints: Vec<Option<String>> =
+            chunks.iter().map(|c| chunk_fingerprint(c)).collect();
+        self.store
+            .set_chunk_fingerprints(doc_id, &fingerprints)
+            .context("Failed to store chunk fingerprints")
+    }
 
-            // BM25 스코어 정규화 (음수 -> 양수)
-            let normalized_score = 1.0 / (1.0 + result.bm25_score.abs()) as f32;
+    /// 청크 벡터를 만든 임베딩 모델 저장 (`reembed --outdated-model` 대상 판별)
+    fn record_chunk_models(&self, doc_ids: &[i64], entries: &[VectorEntry]) -> Result<()> {
+        let chunks: Vec<(i64, i32)> = entries.iter().map(|e| (e.doc_id, e.chunk_index)).collect();
+        self.store
+            .set_chunk_models(doc_ids, &chunks, &self.embedder.model_version())
+            .context("Failed to store chunk models")
+    }
 
-            hybrid_results.push(HybridSearchResult {
-                doc_id: result.doc_id,
-                url,
-                title,
-                chunk_text: None,
-                snippet: Some(result.content_snippet),
-                rrf_score: normalized_score,
-                method: SearchMethod::Fts,
+    /// 청크별 종류(설명문/코드, 언어) 저장
+    fn record_chunk_kinds(&self, doc_id: i64, url: &str, chunks: &[String]) -> Result<()> {
+        let classes: Vec<ChunkClass> = chunks.iter().map(|c| classify_chunk(url, c)).collect();
+        self.store
+            .set_chunk_kinds(doc_id, &classes)
+            .context("Failed to store chunk kinds")
+    }
+
+    /// 결과에 원본 파일 위치 붙이기
+    ///
+    /// 벡터 경로에서 찾은 결과는 해당 청크의 줄 범위를, 나머지는 파일 첫 줄을 가리킵니다.
+    fn attach_locations<'r>(
+        &self,
+        hits: impl IntoIterator<Item = (&'r mut HybridSearchResult, Option<i32>)>,
+    ) -> Result<()> {
+        let hits: Vec<_> = hits
+            .into_iter()
+            .filter_map(|(result, chunk_index)| {
+                let path = source_path(&result.url)?;
+                Some((result, chunk_index, path))
+            })
+            .collect();
+        let keys: Vec<(i64, i32)> = hits
+            .iter()
+            .filter_map(|(result, chunk_index, _)| Some((result.doc_id, (*chunk_index)?)))
+            .collect();
+        let ranges = self.store.chunk_lines_batch(&keys)?;
+
+        for (result, chunk_index, path) in hits {
+            let lines = chunk_index.and_then(|index| ranges.get(&(result.doc_id, index)).copied());
+            let (start_line, end_line) = lines.unwrap_or((1, 1));
+            result.location = Some(SourceLocation {
+                path,
+                start_line,
+                end_line,
             });
         }
 
-        Ok(hybrid_results)
+        Ok(())
     }
 
-    /// RRF (Reciprocal Rank Fusion) 알고리즘
-    ///
-    /// 두 검색 결과를 순위 기반으로 통합합니다.
-    /// ref: https://www.elastic.co/blog/hybrid-search-rrf
-    ///
-    /// RRF Score = sum(1 / (k + rank))
-    /// k = 60 (기본값, 높은 순위에 더 많은 가중치)
-    fn rrf_merge(
-        &self,
-        fts_results: &[FtsSearchResult],
-        vector_results: &[SearchResult],
-        limit: usize,
-    ) -> Vec<HybridSearchResult> {
-        const K: f32 = 60.0;
+    /// (문서 ID, 청크 순번, 청크) 목록을 `EMBED_BATCH_SIZE` 단위로 임베딩
+    async fn embed_pending(&self, pending: &[(i64, i32, String)]) -> Result<Vec<VectorEntry>> {
+        let mut entries = Vec::with_capacity(pending.len());
+        self.embed_pending_into(pending, &mut entries).await?;
+        Ok(entries)
+    }
 
-        // doc_id -> (rrf_score, fts_result, vector_result)
-        let mut scores: HashMap<i64, (f32, Option<&FtsSearchResult>, Option<&SearchResult>)> =
-            HashMap::new();
+    /// `embed_pending`과 같되, 실패하면 그 전까지 임베딩한 배치를 `entries`에 남김
+    async fn embed_pending_into(
+        &self,
+        pending: &[(i64, i32, String)],
+        entries: &mut Vec<VectorEntry>,
+    ) -> Result<()> {
+        for batch in pending.chunks(EMBED_BATCH_SIZE) {
+            let mut texts = Vec::with_capacity(batch.len());
+            for (_, _, text) in batch {
+                texts.push(self.embedding_input(text).await?);
+            }
+            let embeddings = self.embedder.embed_batch(&texts).await
+                .context("Failed to embed chunks")?;
 
-        // FTS5 결과 추가
-        for (rank, result) in fts_results.iter().enumerate() {
-            let rrf_score = 1.0 / (K + rank as f32 + 1.0);
-            let entry = scores.entry(result.doc_id).or_insert((0.0, None, None));
-            entry.0 += rrf_score;
-            entry.1 = Some(result);
+            entries.extend(batch.iter().zip(embeddings).map(
+                |((doc_id, chunk_index, text), embedding)| VectorEntry {
+                    doc_id: *doc_id,
+                    chunk_index: *chunk_index,
+                    chunk_text: text.clone(),
+                    embedding,
+                },
+            ));
         }
 
-        // 벡터 결과 추가
-        for (rank, result) in vector_results.iter().enumerate() {
-            let rrf_score = 1.0 / (K + rank as f32 + 1.0);
-            let entry = scores.entry(result.doc_id).or_insert((0.0, None, None));
-            entry.0 += rrf_score;
-            entry.2 = Some(result);
-        }
+        Ok(())
+    }
 
-        // 결과 생성 및 정렬
-        let mut results: Vec<(i64, f32, Option<&FtsSearchResult>, Option<&SearchResult>)> = scores
-            .into_iter()
-            .map(|(doc_id, (score, fts, vec))| (doc_id, score, fts, vec))
+    /// SQLite와 벡터 인덱스 동기화 (repair)
+    ///
+    /// 벡터가 없는 문서는 SQLite 본문으로 다시 임베딩하고, 문서가 없는 벡터와 청크 기록은 삭제합니다.
+    /// 청크 벡터는 있지만 문서 임베딩이 없는 문서(기존 인덱스)는 청크 평균으로 채우고,
+    /// 청크 지문 기록이 없는 문서는 현재 청커로 다시 청킹하여 지문만 기록합니다.
+    /// `REINDEX_BATCH_DOCS` 문서마다 저장하므로 중간에 실패해도 다시 실행하면 이어서 진행합니다.
+    pub async fn reindex_missing(&self) -> Result<ReindexReport> {
+        let doc_ids: HashSet<i64> = self.store.document_ids()?.into_iter().collect();
+        let vector_ids = self.vector.doc_ids().await?;
+        let doc_embedding_ids = self.vector.doc_embedding_ids().await?;
+
+        let orphans: Vec<i64> = vector_ids
+            .union(&doc_embedding_ids)
+            .filter(|id| !doc_ids.contains(id))
+            .copied()
             .collect();
+        self.vector.delete_by_doc_ids(&orphans).await?;
 
-        results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-        results.truncate(limit);
+        // 문서 임베딩 채우기 (임베딩 API 호출 없음)
+        let mut without_doc_embedding: Vec<i64> = vector_ids
+            .iter()
+            .filter(|id| doc_ids.contains(id) && !doc_embedding_ids.contains(id))
+            .copied()
+            .collect();
+        without_doc_embedding.sort_unstable();
+        let mut doc_embeddings_added = 0;
+        for group in without_doc_embedding.chunks(REINDEX_BATCH_DOCS) {
+            let means = self.vector.chunk_embedding_means(group).await?;
+            doc_embeddings_added += self.vector.upsert_doc_embeddings(&means).await?;
+        }
 
-        // HybridSearchResult로 변환
-        results
-            .into_iter()
-            .map(|(doc_id, rrf_score, fts_opt, vec_opt)| {
-                let doc = self.store.get_document(doc_id).ok().flatten();
-                let (url, title) = doc.map(|d| (d.url, d.title)).unwrap_or_default();
-
-                let method = match (fts_opt.is_some(), vec_opt.is_some()) {
-                    (true, true) => SearchMethod::Hybrid,
-                    (true, false) => SearchMethod::Fts,
-                    (false, true) => SearchMethod::Vector,
-                    (false, false) => SearchMethod::Hybrid, // shouldn't happen
-                };
+        let mut missing: Vec<i64> = doc_ids.difference(&vector_ids).copied().collect();
+        missing.sort_unstable();
 
-                HybridSearchResult {
-                    doc_id,
-                    url,
-                    title,
-                    chunk_text: vec_opt.map(|v| v.chunk_text.clone()),
-                    snippet: fts_opt.map(|f| f.content_snippet.clone()),
-                    rrf_score,
-                    method,
-                }
-            })
-            .collect()
-    }
+        let mut report = ReindexReport {
+            orphans_removed: orphans.len(),
+            doc_embeddings: doc_embeddings_added,
+            chunk_rows_removed: self.store.delete_orphan_chunk_rows()?,
+            ..Default::default()
+        };
+        for group in missing.chunks(REINDEX_BATCH_DOCS) {
+            report.doc_embeddings += self.reembed_group(group).await?;
 
-    /// 저장소 통계
-    pub async fn stats(&self) -> Result<HybridStats> {
-        let store_stats = self.store.stats()?;
-        let vector_count = self.vector.count().await?;
+            report.reembedded += group.len();
+            tracing::info!(
+                "Re-embedded {}/{} documents",
+                report.reembedded,
+                missing.len()
+            );
+        }
 
-        Ok(HybridStats {
-            document_count: store_stats.document_count,
-            vector_count,
-            total_content_bytes: store_stats.total_content_bytes,
-        })
+        // 청크 지문 채우기 (기록 이전에 수집한 문서, 임베딩 API 호출 없음)
+        for doc_id in self.store.ids_without_fingerprints()? {
+            let Some(doc) = self.store.get_document(doc_id)? else {
+                continue;
+            };
+            let chunks = self.chunk(&normalize_document(&doc.content));
+            if chunks.is_empty() {
+                continue;
+            }
+            self.record_chunk_fingerprints(doc_id, &chunks)?;
+            report.fingerprinted += 1;
+        }
+
+        Ok(report)
     }
 
-    /// 내부 스토어 접근
-    pub fn store(&self) -> &KnowledgeStore {
-        &self.store
+    /// 현재 청커 지문 (`Chunker::fingerprint`)
+    pub fn chunker_fingerprint(&self) -> String {
+        self.chunker.fingerprint()
     }
 
-    /// 내부 벡터 스토어 접근
-    pub fn vector_store(&self) -> &LanceVectorStore {
-        &self.vector
+    /// 현재와 다른 청커/설정으로 청킹된 문서를 다시 청킹하고 임베딩 (`reembed --changed-chunker`)
+    ///
+    /// 청커 기록이 없는 문서(기록 이전에 수집)도 포함합니다.
+    /// `REINDEX_BATCH_DOCS` 문서마다 저장하므로 중간에 실패해도 다시 실행하면 남은 문서만 처리합니다.
+    ///
+    /// # Returns
+    /// 다시 임베딩한 문서 수
+    pub async fn reembed_changed_chunker(&self) -> Result<usize> {
+        let ids = self.store.ids_with_other_chunker(&self.chunker.fingerprint())?;
+        self.reembed_documents(&ids).await
     }
-}
 
-/// 하이브리드 저장소 통계
-#[derive(Debug, Clone)]
-pub struct HybridStats {
-    pub document_count: usize,
-    pub vector_count: usize,
-    pub total_content_bytes: usize,
-}
+    /// 현재 임베딩 모델 버전 (`EmbeddingProvider::model_version`)
+    pub fn embedding_model(&self) -> String {
+        self.embedder.model_version()
+    }
 
-// ============================================================================
-// Tests
-// ============================================================================
+    /// 현재와 다른 임베딩 모델로 만든 벡터가 있는 문서를 다시 임베딩 (`reembed --outdated-model`)
+    ///
+    /// 모델 기록이 없는 문서(기록 이전에 수집)도 포함합니다.
+    /// `limit`을 주면 문서 ID 순으로 그 수만 처리하므로, 모델을 바꾼 뒤 큰 말뭉치를
+    /// 일일 할당량 안에서 며칠에 나누어 옮길 수 있습니다.
+    ///
+    /// # Returns
+    /// 다시 임베딩한 문서 수
+    pub async fn reembed_outdated_model(&self, limit: Option<usize>) -> Result<usize> {
+        let model = self.embedder.model_version();
+        let mut ids = self.store.ids_with_other_model(&model)?;
+        if let Some(limit) = limit {
+            ids.truncate(limit);
+        }
+        self.reembed_documents(&ids).await
+    }
+
+    /// 지정한 문서를 현재 청커로 다시 청킹하고 임베딩
+    ///
+    /// 정규화 이전에 수집한 문서는 저장된 제목/본문(과 FTS5 인덱스)도 정규화한 텍스트로 바꿉니다.
+    /// 새 임베딩을 모두 만든 뒤 기존 벡터를 교체하므로 임베딩 도중 실패해도 기존 벡터는 남습니다.
+    /// 교체(삭제 후 추가)는 원자적이지 않아 추가가 실패하면 그 묶음의 문서는 벡터가 빠진 채로
+    /// 남으며, `repair`(`reindex_missing`)가 벡터 없는 문서로 찾아 다시 임베딩합니다.
+    pub async fn reembed_documents(&self, doc_ids: &[i64]) -> Result<usize> {
+        let mut reembedded = 0;
+        for group in doc_ids.chunks(REINDEX_BATCH_DOCS) {
+            self.reembed_group(group).await?;
+
+            reembedded += group.len();
+            tracing::info!("Re-embedded {}/{} documents", reembedded, doc_ids.len());
+        }
+
+        Ok(reembedded)
+    }
+
+    /// 문서 묶음을 청킹/임베딩하여 벡터 교체 (저장한 문서 임베딩 수 반환)
+    async fn reembed_group(&self, group: &[i64]) -> Result<usize> {
+        // 정규화 이전에 수집한 문서도 새로 수집한 문서와 같은 본문/청크가 되도록 먼저 다시 씀
+        self.store
+            .normalize_stored_text(Some(group))
+            .context("Failed to normalize stored text")?;
+
+        let mut docs = Vec::new();
+        let mut pending = Vec::new();
+        for &doc_id in group {
+            let Some(doc) = self.store.get_document(doc_id)? else {
+                continue;
+            };
+            let chunks = self.chunk(&doc.content);
+            pending.extend(
+                chunks
+                    .iter()
+                    .enumerate()
+                    .map(|(i, chunk)| (doc_id, i as i32, chunk.clone())),
+            );
+            docs.push((doc, chunks));
+        }
+
+        let entries = self.embed_pending(&pending).await?;
+        // 삭제 후 추가 사이에 실패하면 벡터가 빠지므로 repair로 복구하도록 안내
+        self.vector.delete_by_doc_ids(group).await?;
+        self.vector.insert_batch(&entries).await.context(
+            "Failed to insert vectors (run `repair` to re-embed the affected documents)",
+        )?;
+        let doc_embeddings = self
+            .vector
+            .upsert_doc_embeddings(&doc_embeddings(&entries))
+            .await
+            .context("Failed to insert document embeddings (run `repair` to restore them)")?;
+
+        for (doc, chunks) in &docs {
+            self.record_chunk_lines(doc.id, &doc.url, &doc.content, chunks)?;
+            self.record_chunk_kinds(doc.id, &doc.url, chunks)?;
+            self.record_chunk_fingerprints(doc.id, chunks)?;
+        }
+        self.store.set_chunker(group, &self.chunker.fingerprint())?;
+        self.record_chunk_models(group, &entries)?;
+
+        Ok(doc_embeddings)
+    }
+
+    /// 문서 삭제
+    ///
+    /// SQLite와 LanceDB에서 모두 삭제합니다.
+    pub async fn delete_document(&self, doc_id: i64) -> Result<bool> {
+        // 벡터 먼저 삭제
+        self.vector.delete_by_doc_id(doc_id).await?;
+
+        // SQLite에서 삭제
+        self.store.delete_document(doc_id)
+    }
+
+    /// 하이브리드 검색 (RRF 통합)
+    ///
+    /// FTS5와 벡터 검색을 RRF 알고리즘으로 통합합니다.
+    ///
+    /// # Arguments
+    /// * `query` - 검색 쿼리
+    /// * `limit` - 최대 결과 수
+    ///
+    /// # Returns
+    /// RRF 스코어 기준 정렬된 검색 결과
+    pub async fn search(&self, query: &str, limit: usize) -> Result<Vec<HybridSearchResult>> {
+        self.search_with(query, &SearchOptions::with_limit(limit)).await
+    }
+
+    /// 옵션을 지정한 하이브리드 검색
+    ///
+    /// 프레임워크 필터는 FTS5와 벡터 검색 양쪽에 적용되어
+    /// 범위 밖 문서가 한쪽 결과를 통해 통합 순위에 섞이지 않습니다.
+    pub async fn search_with(
+        &self,
+        query: &str,
+        options: &SearchOptions,
+    ) -> Result<Vec<HybridSearchResult>> {
+        self.hybrid_search(query, None, options).await
+    }
+
+    /// 미리 계산한 쿼리 임베딩으로 하이브리드 검색 (쿼리 임베딩 API 호출 없음)
+    ///
+    /// 같은 쿼리로 여러 컬렉션을 검색할 때 임베딩을 한 번만 계산하도록 씁니다.
+    /// `embedding`은 `embed_query`로 만든 것이어야 하며, 순위는 `search_with`와 같습니다.
+    pub async fn search_with_embedding(
+        &self,
+        query: &str,
+        embedding: &[f32],
+        options: &SearchOptions,
+    ) -> Result<Vec<HybridSearchResult>> {
+        if embedding.len() != EMBEDDING_DIMENSION as usize {
+            anyhow::bail!(
+                "Embedding dimension mismatch: expected {}, got {}",
+                EMBEDDING_DIMENSION,
+                embedding.len()
+            );
+        }
+        self.hybrid_search(query, Some(embedding), options).await
+    }
+
+    /// 검색 쿼리 임베딩 (`search_with`와 같은 정규화 적용)
+    pub async fn embed_query(&self, query: &str) -> Result<Vec<f32>> {
+        self.embedder
+            .embed(&normalize_query(query))
+            .await
+            .context("Failed to embed query")
+    }
+
+    /// FTS5 + 벡터 검색 후 RRF 통합 (`embedding`이 없으면 쿼리를 임베딩)
+    async fn hybrid_search(
+        &self,
+        query: &str,
+        embedding: Option<&[f32]>,
+        options: &SearchOptions,
+    ) -> Result<Vec<HybridSearchResult>> {
+        // 1. FTS5 + 벡터 검색 (동시 실행)
+        let (fts_results, vector_results, query_embedding) = self
+            .retrieve_candidates(query, embedding, options)
+            .await?;
+
+        // 2. RRF 통합 (문서 정보는 일괄 조회)
+        let mut results = self.rrf_merge(query, &fts_results, &vector_results, options)?;
+        if query_embedding.is_none() {
+            results.iter_mut().for_each(|r| r.partial = true);
+        }
+
+        self.record_trace(
+            query,
+            options,
+            query_embedding.as_deref().unwrap_or_default(),
+            fts_results,
+            vector_results,
+            &results,
+        );
+
+        Ok(results)
+    }
+
+    /// 순위 근거를 포함한 하이브리드 검색 (`query --explain`)
+    ///
+    /// `search_with`와 같은 순위를 내며, 결과마다 FTS5/벡터 순위와
+    /// RRF 점수 계산, 일치한 키워드, 가장 가까운 청크 유사도를 붙입니다.
+    pub async fn search_explained(
+        &self,
+        query: &str,
+        options: &SearchOptions,
+    ) -> Result<Vec<ExplainedResult>> {
+        let (fts_results, vector_results, query_embedding) = self
+            .retrieve_candidates(query, None, options)
+            .await?;
+
+        let candidates = self.fuse(query, &fts_results, &vector_results, options)?;
+        let doc_ids: Vec<i64> = candidates.iter().map(|c| c.doc_id).collect();
+        let summaries = self.store.get_summaries(&doc_ids)?;
+
+        let mut results: Vec<ExplainedResult> = candidates
+            .iter()
+            .map(|candidate| {
+                let (url, title, citation) = summaries
+                    .get(&candidate.doc_id)
+                    .map(|d| (d.url.clone(), d.title.clone(), d.citation.clone()))
+                    .unwrap_or_default();
+                let mut result = build_result(candidate, url, title, citation);
+                result.partial = query_embedding.is_none();
+                ExplainedResult {
+                    result,
+                    explanation: ResultExplanation::from_candidate(candidate, &options.fusion),
+                }
+            })
+            .collect();
+        self.attach_locations(
+            results
+                .iter_mut()
+                .zip(&candidates)
+                .map(|(e, c)| (&mut e.result, c.vector.map(|v| v.chunk_index))),
+        )?;
+
+        let fused: Vec<HybridSearchResult> = results.iter().map(|e| e.result.clone()).collect();
+        self.record_trace(
+            query,
+            options,
+            query_embedding.as_deref().unwrap_or_default(),
+            fts_results,
+            vector_results,
+            &fused,
+        );
+
+        Ok(results)
+    }
+
+    /// 답변 생성용 컨텍스트 구성
+    ///
+    /// 고정 문서가 있으면 각 문서에서 질문과 가장 가까운 청크를 먼저 넣고,
+    /// 이어서 하이브리드 검색 결과를 `max_chars`(와 `max_tokens`) 한도까지 채웁니다.
+    /// 고정 문서에도 검색과 같은 프레임워크 범위와 청크 종류 필터를 적용합니다.
+    pub async fn build_context(&self, query: &str, options: &ContextOptions) -> Result<AskContext> {
+        let search = &options.search;
+        let (fts_results, vector_results, query_embedding) = self
+            .retrieve_candidates(query, None, search)
+            .await?;
+
+        let mut context = AskContext {
+            query: query.to_string(),
+            chunks: Vec::new(),
+        };
+
+        // 1. 고정 문서의 가장 관련 있는 청크 (검색 순위와 무관하게 포함, 시간 예산 초과 시 생략)
+        let pinned_embedding = query_embedding.as_deref().filter(|_| options.include_pinned);
+        if let Some(query_embedding) = pinned_embedding {
+            let framework = search.framework.as_deref().map(FrameworkTag::parse);
+            let pins = self.store.list_pinned()?.into_iter().filter(|pin| {
+                framework.as_ref().is_none_or(|f| {
+                    pin.framework
+                        .as_deref()
+                        .is_some_and(|stored| f.matches(stored))
+                })
+            });
+
+            let mut pinned_chunks = Vec::new();
+            for pin in pins {
+                let best = self
+                    .vector
+                    .search_in_docs(query_embedding, &[pin.id], 1)
+                    .await?
+                    .into_iter()
+                    .next();
+
+                let best = best.filter(|hit| {
+                    search
+                        .chunk_kind
+                        .is_none_or(|kind| classify_chunk(&pin.url, &hit.chunk_text).kind == kind)
+                });
+                if let Some(hit) = best {
+                    pinned_chunks.push(ContextChunk {
+                        doc_id: pin.id,
+                        chunk_index: Some(hit.chunk_index),
+                        url: pin.url,
+                        title: pin.title,
+                        text: hit.chunk_text,
+                        score: hit.similarity,
+                        pinned: true,
+                        citation: pin.citation,
+                    });
+                }
+            }
+
+            pinned_chunks.sort_by(|a, b| b.score.total_cmp(&a.score));
+            for chunk in pinned_chunks {
+                context.push_within(
+                    chunk,
+                    options.max_chars,
+                    options.max_tokens,
+                    &*options.tokenizer,
+                );
+            }
+        }
+
+        // 2. 하이브리드 검색 결과
+        let mut results = self.rrf_merge(query, &fts_results, &vector_results, search)?;
+        self.record_trace(
+            query,
+            search,
+            query_embedding.as_deref().unwrap_or_default(),
+            fts_results,
+            vector_results,
+            &results,
+        );
+
+        // 코딩 질문이면 코드 청크를 먼저 채움 (종류 안에서는 검색 순위 유지)
+        if options.code_first && is_coding_question(query) {
+            let doc_ids: Vec<i64> = results.iter().map(|r| r.doc_id).collect();
+            let kinds = self.store.chunk_kinds(&doc_ids)?;
+            results.sort_by_key(|r| !result_class(&kinds, r).is_code());
+        }
+
+        for result in results {
+            let Some(text) = result.chunk_text.or(result.snippet) else {
+                continue;
+            };
+
+            context.push_within(
+                ContextChunk {
+                    doc_id: result.doc_id,
+                    chunk_index: result.chunk_index,
+                    url: result.url,
+                    title: result.title,
+                    text,
+                    score: result.rrf_score,
+                    pinned: false,
+                    citation: result.citation,
+                },
+                options.max_chars,
+                options.max_tokens,
+                &*options.tokenizer,
+            );
+        }
+
+        Ok(context)
+    }
+
+    /// FTS5 검색과 벡터 검색(임베딩 포함)을 동시에 실행
+    ///
+    /// FTS5는 동기 SQLite 호출이므로 blocking 스레드에서 실행하고,
+    /// 그동안 쿼리 임베딩 + LanceDB 검색을 진행합니다.
+    /// 각 경로에서 결과 수의 2배를 후보로 가져옵니다.
+    ///
+    /// 벡터 경로가 `time_budget_ms`를 넘으면 벡터 결과 없이 반환하며,
+    /// 이때 쿼리 임베딩은 `None`입니다. `embedding`을 넘기면 쿼리를 다시 임베딩하지 않습니다.
+    async fn retrieve_candidates(
+        &self,
+        query: &str,
+        embedding: Option<&[f32]>,
+        options: &SearchOptions,
+    ) -> Result<(Vec<FtsSearchResult>, Vec<SearchResult>, Option<Vec<f32>>)> {
+        // FTS5와 임베딩 모두 수집한 본문과 같은 규칙으로 정규화한 쿼리 사용
+        let query = &normalize_query(query);
+        let candidate_limit = options.limit * 2;
+        let framework = options
+            .framework
+            .as_deref()
+            .map(|f| FrameworkTag::parse(f).to_string());
+        let framework = framework.as_deref();
+        let store = self.store.clone();
+        let fts_query = query.to_string();
+        let fts_framework = framework.map(|f| f.to_string());
+        let fts_task = tokio::task::spawn_blocking(move || {
+            store.search_fts(&fts_query, candidate_limit, fts_framework.as_deref())
+        });
+
+        let vector_task = async {
+            let query_embedding = match embedding {
+                Some(embedding) => embedding.to_vec(),
+                None => self.embedder.embed(query).await?,
+            };
+            let results = self
+                .scoped_vector_search(
+                    &query_embedding,
+                    candidate_limit,
+                    framework,
+                    options.two_stage,
+                    options.chunk_kind,
+                    &options.boilerplate,
+                )
+                .await?;
+            Ok::<_, anyhow::Error>((results, query_embedding))
+        };
+        let budgeted_vector_task = async {
+            let Some(ms) = options.time_budget_ms else {
+                return vector_task.await.map(Some);
+            };
+            match tokio::time::timeout(Duration::from_millis(ms), vector_task).await {
+                Ok(result) => result.map(Some),
+                Err(_) => {
+                    tracing::warn!("Vector search exceeded time budget ({}ms), using FTS only", ms);
+                    Ok(None)
+                }
+            }
+        };
+
+        let (fts_results, vector_results) = tokio::join!(fts_task, budgeted_vector_task);
+        let fts_results = fts_results.context("FTS search task failed")??;
+        let fts_results = match options.chunk_kind {
+            Some(kind) => self.filter_fts_by_kind(fts_results, kind)?,
+            None => fts_results,
+        };
+        let (vector_results, query_embedding) = match vector_results? {
+            Some((results, embedding)) => (results, Some(embedding)),
+            None => (Vec::new(), None),
+        };
+
+        Ok((fts_results, vector_results, query_embedding))
+    }
+
+    /// 벡터 검색 후 만료/범위 밖 문서와 다른 종류의 청크 제외, 상용구 청크 억제
+    ///
+    /// `two_stage`가 있으면 문서 임베딩으로 가까운 문서를 먼저 고르고
+    /// 그 문서들의 청크만 검색합니다. 문서 후보도 만료/범위 밖 문서를 빼고 고르므로
+    /// 전체 상위 문서가 모두 다른 프레임워크여도 범위 안 문서의 청크를 검색합니다.
+    async fn scoped_vector_search(
+        &self,
+        embedding: &[f32],
+        limit: usize,
+        framework: Option<&str>,
+        two_stage: Option<usize>,
+        chunk_kind: Option<ChunkKind>,
+        boilerplate: &BoilerplateConfig,
+    ) -> Result<Vec<SearchResult>> {
+        // 프레임워크/청크 종류 필터, 상용구 제외로 줄어드는 만큼 더 가져옴
+        let excludes_boilerplate =
+            boilerplate.is_enabled() && boilerplate.mode == BoilerplateMode::Exclude;
+        let fetch_limit = if framework.is_some() || chunk_kind.is_some() || excludes_boilerplate {
+            limit * SCOPED_OVERSAMPLE
+        } else {
+            limit
+        };
+
+        let shortlist: Vec<i64> = match two_stage {
+            Some(docs) => {
+                let fetch_docs = if framework.is_some() {
+                    docs * SCOPED_OVERSAMPLE
+                } else {
+                    docs
+                };
+                let candidates: Vec<i64> = self
+                    .vector
+                    .search_doc_embeddings(embedding, fetch_docs)
+                    .await?
+                    .into_iter()
+                    .map(|(doc_id, _)| doc_id)
+                    .collect();
+                let in_scope = self.in_scope_doc_ids(&candidates, framework)?;
+                candidates
+                    .into_iter()
+                    .filter(|id| in_scope.contains(id))
+                    .take(docs)
+                    .collect()
+            }
+            None => Vec::new(),
+        };
+        let results = if shortlist.is_empty() {
+            self.vector.search(embedding, fetch_limit).await?
+        } else {
+            self.vector.search_in_docs(embedding, &shortlist, fetch_limit).await?
+        };
+        let results = match chunk_kind {
+            Some(kind) => self.filter_by_chunk_kind(results, kind)?,
+            None => results,
+        };
+        let results = self.suppress_boilerplate(results, boilerplate)?;
+        self.filter_vector_results(results, limit, framework)
+    }
+
+    /// 여러 문서에 반복되는 상용구 청크를 뒤로 내리거나 제외
+    fn suppress_boilerplate(
+        &self,
+        results: Vec<SearchResult>,
+        config: &BoilerplateConfig,
+    ) -> Result<Vec<SearchResult>> {
+        if !config.is_enabled() || results.is_empty() {
+            return Ok(results);
+        }
+
+        let fingerprints: Vec<String> = results
+            .iter()
+            .filter_map(|r| chunk_fingerprint(&r.chunk_text))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let doc_counts = self.store.fingerprint_doc_counts(&fingerprints)?;
+
+        Ok(suppress_boilerplate(results, &doc_counts, config))
+    }
+
+    /// 지정한 종류의 청크만 남김
+    ///
+    /// 종류 기록이 없는 청크(기록 이전에 수집)는 청크 텍스트로 바로 판별합니다.
+    fn filter_by_chunk_kind(
+        &self,
+        results: Vec<SearchResult>,
+        kind: ChunkKind,
+    ) -> Result<Vec<SearchResult>> {
+        let doc_ids: Vec<i64> = results.iter().map(|r| r.doc_id).collect();
+        let kinds = self.store.chunk_kinds(&doc_ids)?;
+        let summaries = self.store.get_summaries(&doc_ids)?;
+
+        Ok(results
+            .into_iter()
+            .filter(|r| {
+                let found = match kinds.get(&(r.doc_id, r.chunk_index)) {
+                    Some(class) => class.kind,
+                    None => {
+                        let url = summaries.get(&r.doc_id).map_or("", |d| d.url.as_str());
+                        classify_chunk(url, &r.chunk_text).kind
+                    }
+                };
+                found == kind
+            })
+            .collect())
+    }
+
+    /// 지정한 종류의 청크가 있는 문서의 FTS5 결과만 남김 (종류 기록이 없는 문서는 유지)
+    fn filter_fts_by_kind(
+        &self,
+        results: Vec<FtsSearchResult>,
+        kind: ChunkKind,
+    ) -> Result<Vec<FtsSearchResult>> {
+        let doc_ids: Vec<i64> = results.iter().map(|r| r.doc_id).collect();
+        let kinds = self.store.chunk_kinds(&doc_ids)?;
+        let recorded: HashSet<i64> = kinds.keys().map(|(doc_id, _)| *doc_id).collect();
+        let matching: HashSet<i64> = kinds
+            .iter()
+            .filter(|(_, class)| class.kind == kind)
+            .map(|((doc_id, _), _)| *doc_id)
+            .collect();
+
+        Ok(results
+            .into_iter()
+            .filter(|r| !recorded.contains(&r.doc_id) || matching.contains(&r.doc_id))
+            .collect())
+    }
+
+    /// 벡터 검색 결과를 문서 상태로 필터링
+    ///
+    /// LanceDB에는 문서 메타데이터가 없으므로 SQLite 문서 정보로
+    /// 만료된 문서와 범위 밖 프레임워크를 걸러냅니다.
+    fn filter_vector_results(
+        &self,
+        results: Vec<SearchResult>,
+        limit: usize,
+        framework: Option<&str>,
+    ) -> Result<Vec<SearchResult>> {
+        let doc_ids: Vec<i64> = results.iter().map(|r| r.doc_id).collect();
+        let in_scope = self.in_scope_doc_ids(&doc_ids, framework)?;
+
+        Ok(results
+            .into_iter()
+            .filter(|r| in_scope.contains(&r.doc_id))
+            .take(limit)
+            .collect())
+    }
+
+    /// 만료되지 않았고 프레임워크 범위 안인 문서 ID
+    ///
+    /// SQLite에 없는 문서(벡터만 남음)는 프레임워크 필터가 없을 때만 포함합니다.
+    fn in_scope_doc_ids(&self, doc_ids: &[i64], framework: Option<&str>) -> Result<HashSet<i64>> {
+        let summaries = self.store.get_summaries(doc_ids)?;
+        let framework = framework.map(FrameworkTag::parse);
+
+        Ok(doc_ids
+            .iter()
+            .copied()
+            .filter(|id| match summaries.get(id) {
+                Some(doc) => {
+                    !doc.is_expired()
+                        && framework.as_ref().is_none_or(|f| {
+                            doc.framework.as_deref().is_some_and(|stored| f.matches(stored))
+                        })
+                }
+                None => framework.is_none(),
+            })
+            .collect())
+    }
+
+    /// 스트리밍 하이브리드 검색
+    ///
+    /// FTS5와 벡터 검색이 모두 끝나 RRF 통합 순위가 정해진 뒤, 문서 정보와 파일 위치를
+    /// `STREAM_PAGE_SIZE`개씩 일괄 조회하며 결과를 순서대로 내보냅니다. 첫 결과는 검색 자체를
+    /// 앞당기지 않으며, 호출자(서버/TUI)는 뒤쪽 페이지를 조회하는 동안 앞쪽 결과를 먼저
+    /// 렌더링할 수 있을 뿐입니다. 순위와 필터는 같은 옵션의 `search_with`와 같습니다.
+    ///
+    /// # Arguments
+    /// * `query` - 검색 쿼리
+    /// * `options` - 결과 수, 프레임워크 필터, RRF 설정 등
+    pub fn search_stream<'a>(
+        &'a self,
+        query: &'a str,
+        options: &'a SearchOptions,
+    ) -> impl Stream<Item = Result<HybridSearchResult>> + 'a {
+        futures::stream::once(async move {
+            let (fts_results, vector_results, query_embedding) =
+                self.retrieve_candidates(query, None, options).await?;
+            let partial = query_embedding.is_none();
+
+            // 참조를 소유 값으로 변환 (스트림이 결과를 보유해야 함)
+            let candidates: Vec<_> = self
+                .fuse(query, &fts_results, &vector_results, options)?
+                .into_iter()
+                .map(|c| {
+                    let legs = (
+                        c.fts_rank,
+                        c.vector_rank,
+                        c.fts_contribution,
+                        c.vector_contribution,
+                        c.feedback_boost,
+                        c.version_boost,
+                    );
+                    (c.doc_id, c.score, c.fts.cloned(), c.vector.cloned(), legs)
+                })
+                .collect();
+
+            Ok::<_, anyhow::Error>((candidates, partial))
+        })
+        .map_ok(move |(candidates, partial)| {
+            let mut pages = Vec::new();
+            let mut candidates = candidates.into_iter().peekable();
+            while candidates.peek().is_some() {
+                let page: Vec<_> = candidates.by_ref().take(STREAM_PAGE_SIZE).collect();
+                pages.push(page);
+            }
+
+            futures::stream::iter(pages)
+                .map(move |page| {
+                    let page: Vec<FusedCandidate<'_>> = page
+                        .iter()
+                        .map(|(doc_id, score, fts, vector, legs)| {
+                            let (
+                                fts_rank,
+                                vector_rank,
+                                fts_contribution,
+                                vector_contribution,
+                                feedback_boost,
+                                version_boost,
+                            ) = *legs;
+                            FusedCandidate {
+                                doc_id: *doc_id,
+                                score: *score,
+                                fts: fts.as_ref(),
+                                vector: vector.as_ref(),
+                                fts_rank,
+                                vector_rank,
+                                fts_contribution,
+                                vector_contribution,
+                                feedback_boost,
+                                version_boost,
+                            }
+                        })
+                        .collect();
+                    let mut results = self.resolve_candidates(&page)?;
+                    for result in &mut results {
+                        result.partial = partial;
+                    }
+                    let results = results.into_iter().map(Ok::<_, anyhow::Error>);
+                    Ok::<_, anyhow::Error>(futures::stream::iter(results))
+                })
+                .try_flatten()
+        })
+        .try_flatten()
+        .boxed()
+    }
+
+    /// 벡터 검색만 수행
+    pub async fn search_vector(&self, query: &str, limit: usize) -> Result<Vec<HybridSearchResult>> {
+        let query_embedding = self.embedder.embed(&normalize_query(query)).await?;
+        let results = self.vector.search(&query_embedding, limit).await?;
+        let results = self.filter_vector_results(results, limit, None)?;
+
+        self.vector_only_results(results)
+    }
+
+    /// 이미 계산된 임베딩으로 벡터 검색 (임베딩 API 호출 없음)
+    ///
+    /// 자체 모델이나 캐시에서 임베딩을 가져온 호출자, 임베딩 키 없는 읽기 전용 배포용입니다.
+    /// 쿼리 텍스트가 없으므로 FTS5 없이 벡터 검색만 하며, 프레임워크 필터와
+    /// 만료 문서 제외는 `search_with`와 같습니다 (`fusion`은 사용하지 않음).
+    ///
+    /// # Arguments
+    /// * `embedding` - 쿼리 임베딩 (저장된 벡터와 같은 모델/차원)
+    /// * `options` - 결과 수와 프레임워크 필터
+    pub async fn search_by_embedding(
+        &self,
+        embedding: &[f32],
+        options: &SearchOptions,
+    ) -> Result<Vec<HybridSearchResult>> {
+        if embedding.len() != EMBEDDING_DIMENSION as usize {
+            anyhow::bail!(
+                "Embedding dimension mismatch: expected {}, got {}",
+                EMBEDDING_DIMENSION,
+                embedding.len()
+            );
+        }
+
+        let results = self
+            .scoped_vector_search(
+                embedding,
+                options.limit,
+                options.framework.as_deref(),
+                options.two_stage,
+                options.chunk_kind,
+                &options.boilerplate,
+            )
+            .await?;
+
+        self.vector_only_results(results)
+    }
+
+    /// 벡터 검색 결과에 문서 정보를 붙여 변환 (점수는 코사인 유사도)
+    fn vector_only_results(&self, results: Vec<SearchResult>) -> Result<Vec<HybridSearchResult>> {
+        let doc_ids: Vec<i64> = results.iter().map(|r| r.doc_id).collect();
+        let summaries = self.store.get_summaries(&doc_ids)?;
+        let mut hybrid_results = Vec::with_capacity(results.len());
+        let chunk_indices: Vec<Option<i32>> = results.iter().map(|r| Some(r.chunk_index)).collect();
+
+        for result in results {
+            let (url, title, citation) = summaries
+                .get(&result.doc_id)
+                .map(|d| (d.url.clone(), d.title.clone(), d.citation.clone()))
+                .unwrap_or_default();
+
+            hybrid_results.push(HybridSearchResult {
+                doc_id: result.doc_id,
+                url,
+                title,
+                chunk_index: Some(result.chunk_index),
+                chunk_text: Some(result.chunk_text),
+                snippet: None,
+                rrf_score: result.similarity,
+                method: SearchMethod::Vector,
+                citation,
+                location: None,
+                partial: false,
+            });
+        }
+
+        self.attach_locations(hybrid_results.iter_mut().zip(chunk_indices))?;
+        Ok(hybrid_results)
+    }
+
+    /// FTS5 키워드 검색만 수행
+    pub fn search_fts(&self, query: &str, limit: usize) -> Result<Vec<HybridSearchResult>> {
+        let results = self.store.search_fts(query, limit, None)?;
+
+        let doc_ids: Vec<i64> = results.iter().map(|r| r.doc_id).collect();
+        let summaries = self.store.get_summaries(&doc_ids)?;
+        let mut hybrid_results = Vec::with_capacity(results.len());
+
+        for result in results {
+            let (url, title, citation) = summaries
+                .get(&result.doc_id)
+                .map(|d| (d.url.clone(), d.title.clone(), d.citation.clone()))
+                .unwrap_or_default();
+
+            // BM25 스코어 정규화 (음수 -> 양수)
+            let normalized_score = 1.0 / (1.0 + result.bm25_score.abs()) as f32;
+
+            hybrid_results.push(HybridSearchResult {
+                doc_id: result.doc_id,
+                url,
+                title,
+                chunk_text: None,
+                chunk_index: None,
+                snippet: Some(result.content_snippet),
+                rrf_score: normalized_score,
+                method: SearchMethod::Fts,
+                citation,
+                location: None,
+                partial: false,
+            });
+        }
+
+        self.attach_locations(hybrid_results.iter_mut().map(|r| (r, None)))?;
+        Ok(hybrid_results)
+    }
+
+    /// RRF 통합 + 검색 피드백/선호 버전 보정
+    ///
+    /// 보정으로 순위가 바뀔 수 있으므로 후보 전체를 통합해 보정한 뒤 결과 수만큼 자릅니다.
+    fn fuse<'a>(
+        &self,
+        query: &str,
+        fts_results: &'a [FtsSearchResult],
+        vector_results: &'a [SearchResult],
+        options: &SearchOptions,
+    ) -> Result<Vec<FusedCandidate<'a>>> {
+        let fusion = &options.fusion;
+        let mut candidates = rrf_fuse_with(fts_results, vector_results, usize::MAX, fusion);
+
+        if fusion.feedback_weight != 0.0 {
+            let votes = self.store.feedback_votes(&query_id(query))?;
+            apply_feedback(&mut candidates, &votes, fusion.feedback_weight);
+        }
+
+        if let Some(ref prefer) = options.prefer_version {
+            let doc_ids: Vec<i64> = candidates.iter().map(|c| c.doc_id).collect();
+            let frameworks = self
+                .store
+                .get_summaries(&doc_ids)?
+                .into_iter()
+                .map(|(id, doc)| (id, doc.framework))
+                .collect();
+            apply_version_preference(
+                &mut candidates,
+                &frameworks,
+                &FrameworkTag::parse(prefer),
+                VERSION_WEIGHT,
+            );
+        }
+
+        candidates.truncate(options.limit);
+        Ok(candidates)
+    }
+
+    /// RRF 통합 후 문서 정보(URL, 제목)를 붙여 결과 생성
+    ///
+    /// 문서 정보는 `WHERE id IN (...)` 한 번으로 일괄 조회합니다.
+    fn rrf_merge(
+        &self,
+        query: &str,
+        fts_results: &[FtsSearchResult],
+        vector_results: &[SearchResult],
+        options: &SearchOptions,
+    ) -> Result<Vec<HybridSearchResult>> {
+        let candidates = self.fuse(query, fts_results, vector_results, options)?;
+        self.resolve_candidates(&candidates)
+    }
+
+    /// 통합 후보에 문서 정보(URL, 제목)와 파일 위치를 붙여 결과로 변환
+    ///
+    /// 문서 정보와 청크 줄 범위는 각각 한 번의 쿼리로 일괄 조회합니다.
+    fn resolve_candidates(
+        &self,
+        candidates: &[FusedCandidate<'_>],
+    ) -> Result<Vec<HybridSearchResult>> {
+        let doc_ids: Vec<i64> = candidates.iter().map(|c| c.doc_id).collect();
+        let summaries = self.store.get_summaries(&doc_ids)?;
+
+        let mut results: Vec<HybridSearchResult> = candidates
+            .iter()
+            .map(|candidate| {
+                let (url, title, citation) = summaries
+                    .get(&candidate.doc_id)
+                    .map(|d| (d.url.clone(), d.title.clone(), d.citation.clone()))
+                    .unwrap_or_default();
+                build_result(candidate, url, title, citation)
+            })
+            .collect();
+        self.attach_locations(
+            results
+                .iter_mut()
+                .zip(candidates)
+                .map(|(r, c)| (r, c.vector.map(|v| v.chunk_index))),
+        )?;
+
+        Ok(results)
+    }
+
+    /// 트레이스 디렉토리가 설정되어 있으면 검색 트레이스 저장
+    ///
+    /// 트레이스는 디버깅용이므로 저장 실패는 경고만 남기고 검색은 계속합니다.
+    fn record_trace(
+        &self,
+        query: &str,
+        options: &SearchOptions,
+        query_embedding: &[f32],
+        fts_candidates: Vec<FtsSearchResult>,
+        vector_candidates: Vec<SearchResult>,
+        fused: &[HybridSearchResult],
+    ) {
+        let Some(ref dir) = self.trace_dir else {
+            return;
+        };
+
+        let trace = RetrievalTrace {
+            timestamp: Utc::now(),
+            query: query.to_string(),
+            options: options.clone(),
+            embedding: EmbeddingFingerprint::of(query_embedding),
+            fts_candidates,
+            vector_candidates,
+            fused: fused.to_vec(),
+        };
+
+        match trace.write_to(dir) {
+            Ok(path) => tracing::debug!("Retrieval trace written: {:?}", path),
+            Err(e) => tracing::warn!("Failed to write retrieval trace: {}", e),
+        }
+    }
+
+    /// 원본 URL의 문서가 임베딩까지 모두 저장되었는지
+    ///
+    /// 임베딩 도중 실패하면 SQLite에만 남은 문서가 생기므로 벡터까지 확인합니다.
+    pub async fn is_ingested(&self, url: &str) -> Result<bool> {
+        let ids = self.store.ids_for_source(url)?;
+        if ids.is_empty() {
+            return Ok(false);
+        }
+
+        for id in ids {
+            if !self.vector.has_embeddings(id).await? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// 저장소 정리 (compact)
+    ///
+    /// 만료된 문서와 벡터를 삭제하고 SQLite/LanceDB를 최적화합니다.
+    pub async fn compact(&self) -> Result<CompactReport> {
+        let expired = self.store.delete_expired()?;
+        self.vector.delete_by_doc_ids(&expired).await?;
+
+        self.vector.optimize().await?;
+        self.store.optimize()?;
+
+        Ok(CompactReport {
+            expired_documents: expired.len(),
+        })
+    }
+
+    /// 저장소 통계
+    pub async fn stats(&self) -> Result<HybridStats> {
+        let store_stats = self.store.stats()?;
+        let vector_count = self.vector.count().await?;
+
+        Ok(HybridStats {
+            document_count: store_stats.document_count,
+            vector_count,
+            total_content_bytes: store_stats.total_content_bytes,
+        })
+    }
+
+    /// 내부 스토어 접근
+    pub fn store(&self) -> &KnowledgeStore {
+        &self.store
+    }
+
+    /// 내부 벡터 스토어 접근
+    pub fn vector_store(&self) -> &LanceVectorStore {
+        &self.vector
+    }
+}
+
+/// 청크 임베딩을 문서별로 평균한 문서 임베딩
+fn doc_embeddings(entries: &[VectorEntry]) -> Vec<(i64, Vec<f32>)> {
+    let mut by_doc: BTreeMap<i64, Vec<&[f32]>> = BTreeMap::new();
+    for entry in entries {
+        by_doc.entry(entry.doc_id).or_default().push(&entry.embedding);
+    }
+
+    by_doc
+        .into_iter()
+        .filter_map(|(doc_id, vectors)| mean_embedding(vectors).map(|mean| (doc_id, mean)))
+        .collect()
+}
+
+/// 검색 결과 청크의 종류 (기록이 없거나 FTS5 스니펫이면 텍스트로 판별)
+fn result_class(
+    kinds: &HashMap<(i64, i32), ChunkClass>,
+    result: &HybridSearchResult,
+) -> ChunkClass {
+    let recorded = result
+        .chunk_index
+        .and_then(|i| kinds.get(&(result.doc_id, i)));
+    if let Some(class) = recorded {
+        return class.clone();
+    }
+    let text = result.chunk_text.as_deref().or(result.snippet.as_deref());
+    classify_chunk(&result.url, text.unwrap_or_default())
+}
+
+/// 통합 후보 + 문서 정보로 검색 결과 생성
+fn build_result(
+    candidate: &FusedCandidate<'_>,
+    url: String,
+    title: Option<String>,
+    citation: Option<Citation>,
+) -> HybridSearchResult {
+    HybridSearchResult {
+        doc_id: candidate.doc_id,
+        url,
+        title,
+        chunk_text: candidate.vector.map(|v| v.chunk_text.clone()),
+        chunk_index: candidate.vector.map(|v| v.chunk_index),
+        snippet: candidate.fts.map(|f| f.content_snippet.clone()),
+        rrf_score: candidate.score,
+        method: candidate.method(),
+        citation,
+        location: None,
+        partial: false,
+    }
+}
+
+/// 청킹과 임베딩을 마친 저장 전 문서 묶음 (`HybridRetriever::prepare_documents`)
+///
+/// 저장이 실패해도 임베딩을 다시 하지 않고 일부 문서만 골라 다시 저장할 수 있습니다.
+pub struct PreparedDocuments {
+    docs: Vec<NewDocument>,
+    /// 문서별 (정규화한 본문, 청크)
+    chunked: Vec<(String, Vec<String>)>,
+    /// 청크 벡터 (문서 ID 자리에는 입력 순번)
+    entries: Vec<VectorEntry>,
+    /// 모든 청크가 임베딩된 앞쪽 문서 수
+    embedded: usize,
+    /// 임베딩 실패 (있으면 `embedded` 이후 문서는 벡터가 없음)
+    error: Option<anyhow::Error>,
+}
+
+impl PreparedDocuments {
+    /// 문서 수
+    pub fn len(&self) -> usize {
+        self.docs.len()
+    }
+
+    /// 문서가 없는지
+    pub fn is_empty(&self) -> bool {
+        self.docs.is_empty()
+    }
+
+    /// 임베딩을 마쳐 저장할 수 있는 앞쪽 문서 수
+    pub fn embedded(&self) -> usize {
+        self.embedded
+    }
+
+    /// 임베딩 실패 꺼내기
+    pub fn take_error(&mut self) -> Option<anyhow::Error> {
+        self.error.take()
+    }
+}
+
+/// 하이브리드 저장소 통계
+#[derive(Debug, Clone)]
+pub struct HybridStats {
+    pub document_count: usize,
+    pub vector_count: usize,
+    pub total_content_bytes: usize,
+}
+
+/// 정리(compact) 결과
+#[derive(Debug, Clone)]
+pub struct CompactReport {
+    /// 삭제된 만료 문서 수
+    pub expired_documents: usize,
+}
+
+/// 벡터 인덱스 동기화(repair) 결과
+#[derive(Debug, Clone, Default)]
+pub struct ReindexReport {
+    /// 다시 임베딩한 문서 수
+    pub reembedded: usize,
+    /// 삭제한 고아 벡터의 문서 수
+    pub orphans_removed: usize,
+    /// 새로 저장한 문서 임베딩 수 (재임베딩 + 기존 청크 평균으로 채운 문서)
+    pub doc_embeddings: usize,
+    /// 청크 지문(상용구 판별용)을 새로 기록한 문서 수
+    pub fingerprinted: usize,
+    /// 없는 문서 ID에 남아 있던 청크 부가 기록(줄 범위, 종류, 지문) 삭제 수
+    pub chunk_rows_removed: usize,
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
 
 #[cfg(test)]
 mod tests {
@@ -379,4 +2085,787 @@ mod tests {
         // 순위가 높을수록 스코어가 높음
         assert!(score_rank_1 > score_rank_5);
     }
+
+    #[tokio::test]
+    async fn test_search_stream_matches_search() {
+        use crate::test_support::{sample_documents, EphemeralRetriever};
+
+        let rag = EphemeralRetriever::with_fixtures(sample_documents())
+            .await
+            .unwrap();
+
+        let options = SearchOptions::with_limit(3);
+        let streamed: Vec<HybridSearchResult> = rag
+            .search_stream("react hooks state", &options)
+            .try_collect()
+            .await
+            .unwrap();
+        let collected = rag.search("react hooks state", 3).await.unwrap();
+
+        assert_eq!(streamed.len(), collected.len());
+        assert_eq!(streamed[0].url, "fixture://react-hooks");
+        for (a, b) in streamed.iter().zip(collected.iter()) {
+            assert_eq!(a.doc_id, b.doc_id);
+        }
+
+        // 프레임워크 필터도 search_with와 같이 적용
+        let options = SearchOptions {
+            framework: Some("sqlite".to_string()),
+            ..SearchOptions::with_limit(3)
+        };
+        let streamed: Vec<HybridSearchResult> = rag
+            .search_stream("react hooks state", &options)
+            .try_collect()
+            .await
+            .unwrap();
+        let collected = rag
+            .search_with("react hooks state", &options)
+            .await
+            .unwrap();
+        assert!(streamed.iter().all(|r| r.url == "fixture://sqlite-fts5"));
+        assert_eq!(
+            streamed.iter().map(|r| r.doc_id).collect::<Vec<_>>(),
+            collected.iter().map(|r| r.doc_id).collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_stream_pages_in_order() {
+        use crate::test_support::EphemeralRetriever;
+
+        // 여러 페이지에 걸친 결과도 search_with와 같은 순서로 나옴
+        let docs = (0..STREAM_PAGE_SIZE * 2 + 5)
+            .map(|i| NewDocument {
+                url: format!("fixture://paging-{}", i),
+                title: Some(format!("Paging {}", i)),
+                content: format!("# Paging {}\n\nstream paging fixture {}", i, "x ".repeat(i)),
+                ..Default::default()
+            })
+            .collect();
+        let rag = EphemeralRetriever::with_fixtures(docs).await.unwrap();
+
+        let options = SearchOptions::with_limit(STREAM_PAGE_SIZE * 2 + 5);
+        let streamed: Vec<HybridSearchResult> = rag
+            .search_stream("stream paging fixture", &options)
+            .try_collect()
+            .await
+            .unwrap();
+        let collected = rag
+            .search_with("stream paging fixture", &options)
+            .await
+            .unwrap();
+
+        assert!(streamed.len() > STREAM_PAGE_SIZE);
+        assert_eq!(
+            streamed.iter().map(|r| r.doc_id).collect::<Vec<_>>(),
+            collected.iter().map(|r| r.doc_id).collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_add_documents_bulk() {
+        use crate::test_support::{sample_documents, EphemeralRetriever};
+
+        let rag = EphemeralRetriever::new().await.unwrap();
+        let ids = rag.add_documents(&sample_documents()).await.unwrap();
+        assert_eq!(ids.len(), 3);
+
+        let stats = rag.stats().await.unwrap();
+        assert_eq!(stats.document_count, 3);
+        assert!(stats.vector_count >= 3);
+
+        let results = rag.search("sqlite full-text bm25", 1).await.unwrap();
+        assert_eq!(results[0].url, "fixture://sqlite-fts5");
+    }
+
+    #[tokio::test]
+    async fn test_reingest_replaces_vectors_in_place() {
+        use crate::test_support::{sample_documents, EphemeralRetriever};
+
+        let rag = EphemeralRetriever::new().await.unwrap();
+        let docs = sample_documents();
+        let ids = rag.add_documents(&docs).await.unwrap();
+        let vectors = rag.stats().await.unwrap().vector_count;
+
+        // 같은 URL을 다시 수집하면 ID가 유지되고 이전 벡터는 남지 않음
+        let again = rag.add_documents(&docs).await.unwrap();
+        assert_eq!(again, ids);
+        let stats = rag.stats().await.unwrap();
+        assert_eq!(stats.document_count, docs.len());
+        assert_eq!(stats.vector_count, vectors);
+
+        // 준비(임베딩)한 문서는 범위를 나눠 저장할 수 있음
+        let mut prepared = rag.prepare_documents(&docs).await;
+        assert!(prepared.take_error().is_none());
+        assert_eq!(prepared.embedded(), docs.len());
+        let first = rag.store_prepared(&prepared, 0..1).await.unwrap();
+        assert_eq!(first, vec![ids[0]]);
+        let rest = rag.store_prepared(&prepared, 1..docs.len()).await.unwrap();
+        assert_eq!(rest, ids[1..].to_vec());
+        assert_eq!(rag.stats().await.unwrap().vector_count, vectors);
+    }
+
+    #[tokio::test]
+    async fn test_is_ingested() {
+        use crate::test_support::{sample_documents, EphemeralRetriever};
+
+        let rag = EphemeralRetriever::with_fixtures(sample_documents())
+            .await
+            .unwrap();
+        assert!(rag.is_ingested("fixture://react-hooks").await.unwrap());
+        assert!(!rag.is_ingested("fixture://missing").await.unwrap());
+
+        // 임베딩 없이 SQLite에만 있는 문서는 미완료
+        rag.store()
+            .add_document(NewDocument {
+                url: "fixture://partial".to_string(),
+                content: "partial".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(!rag.is_ingested("fixture://partial").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_reindex_missing() {
+        use crate::test_support::{sample_documents, EphemeralRetriever};
+
+        let rag = EphemeralRetriever::with_fixtures(sample_documents())
+            .await
+            .unwrap();
+        let ids = rag.store().document_ids().unwrap();
+        rag.vector_store().delete_by_doc_ids(&ids[..1]).await.unwrap();
+        rag.vector_store()
+            .insert_batch(&[VectorEntry {
+                doc_id: 999,
+                chunk_index: 0,
+                chunk_text: "orphan".to_string(),
+                embedding: rag.embedder().embed_sync("orphan"),
+            }])
+            .await
+            .unwrap();
+
+        let report = rag.reindex_missing().await.unwrap();
+        assert_eq!(report.reembedded, 1);
+        assert_eq!(report.orphans_removed, 1);
+        assert_eq!(report.doc_embeddings, 1);
+
+        let vector_ids = rag.vector_store().doc_ids().await.unwrap();
+        assert_eq!(vector_ids, ids.into_iter().collect());
+
+        // 이미 동기화된 상태면 할 일 없음
+        let report = rag.reindex_missing().await.unwrap();
+        assert_eq!(report.reembedded + report.orphans_removed, 0);
+    }
+
+    #[tokio::test]
+    async fn test_reembed_changed_chunker() {
+        use crate::test_support::{sample_documents, EphemeralRetriever};
+
+        let rag = EphemeralRetriever::with_fixtures(sample_documents())
+            .await
+            .unwrap();
+        let ids = rag.store().document_ids().unwrap();
+        assert!(rag
+            .store()
+            .ids_with_other_chunker(&rag.chunker_fingerprint())
+            .unwrap()
+            .is_empty());
+
+        // 예전 설정으로 청킹된 문서
+        rag.store().set_chunker(&ids[..1], "MarkdownChunker:00000000").unwrap();
+        assert_eq!(rag.reembed_changed_chunker().await.unwrap(), 1);
+        assert_eq!(rag.reembed_changed_chunker().await.unwrap(), 0);
+
+        let vector_ids = rag.vector_store().doc_ids().await.unwrap();
+        assert_eq!(vector_ids, ids.into_iter().collect());
+    }
+
+    #[tokio::test]
+    async fn test_reembed_outdated_model() {
+        use crate::test_support::{sample_documents, EphemeralRetriever};
+
+        let rag = EphemeralRetriever::with_fixtures(sample_documents())
+            .await
+            .unwrap();
+        let ids = rag.store().document_ids().unwrap();
+        let model = rag.embedding_model();
+        assert!(rag.store().ids_with_other_model(&model).unwrap().is_empty());
+
+        // 예전 모델로 임베딩된 문서 - 한도만큼씩 나누어 처리
+        rag.store()
+            .set_chunk_models(&ids, &[(ids[0], 0), (ids[1], 0)], "old-embedding/768")
+            .unwrap();
+        assert_eq!(rag.reembed_outdated_model(Some(1)).await.unwrap(), 1);
+        assert_eq!(rag.store().ids_with_other_model(&model).unwrap().len(), 2);
+        assert_eq!(rag.reembed_outdated_model(None).await.unwrap(), 2);
+        assert_eq!(rag.reembed_outdated_model(None).await.unwrap(), 0);
+
+        let counts = rag.store().chunk_model_counts().unwrap();
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts[0].0, model);
+    }
+
+    #[tokio::test]
+    async fn test_time_budget_returns_partial_fts_results() {
+        use crate::test_support::{sample_documents, EphemeralRetriever};
+
+        let rag = EphemeralRetriever::with_fixtures(sample_documents())
+            .await
+            .unwrap();
+        let options = SearchOptions {
+            limit: 3,
+            time_budget_ms: Some(1000),
+            ..Default::default()
+        };
+
+        let results = rag.search_with("hooks", &options).await.unwrap();
+        assert!(results.iter().all(|r| !r.partial));
+
+        // 임베딩 API 지연 -> 기다리지 않고 FTS5 결과만
+        rag.embedder().set_latency(Duration::from_secs(30));
+        let results = rag.search_with("hooks", &options).await.unwrap();
+        assert!(!results.is_empty());
+        assert!(results
+            .iter()
+            .all(|r| r.partial && r.method == SearchMethod::Fts));
+    }
+
+    #[tokio::test]
+    async fn test_search_with_framework() {
+        use crate::test_support::{sample_documents, EphemeralRetriever};
+
+        let rag = EphemeralRetriever::with_fixtures(sample_documents())
+            .await
+            .unwrap();
+
+        let options = SearchOptions {
+            limit: 5,
+            framework: Some("react".to_string()),
+            ..Default::default()
+        };
+        let results = rag.search_with("state ownership search", &options).await.unwrap();
+
+        assert!(!results.is_empty());
+        assert!(results.iter().all(|r| r.url == "fixture://react-hooks"));
+    }
+
+    #[tokio::test]
+    async fn test_search_with_chunk_kind() {
+        use crate::test_support::{sample_documents, EphemeralRetriever};
+
+        let mut docs = sample_documents();
+        docs.push(NewDocument {
+            url: "fixture://react-hooks-example".to_string(),
+            title: Some("useState example".to_string()),
+            content: [
+                "```jsx",
+                "const [count, setCount] = useState(0);",
+                "useEffect(() => {",
+                "  document.title = `clicked ${count} times`;",
+                "}, [count]);",
+                "```",
+            ]
+            .join("\n"),
+            framework: Some("react".to_string()),
+            ..Default::default()
+        });
+        let rag = EphemeralRetriever::with_fixtures(docs).await.unwrap();
+
+        let example = rag
+            .store()
+            .get_by_url("fixture://react-hooks-example")
+            .unwrap()
+            .unwrap();
+        let kinds = rag.store().chunk_kinds(&[example.id]).unwrap();
+        assert_eq!(
+            kinds[&(example.id, 0)],
+            ChunkClass::code(Some("javascript"))
+        );
+
+        let code_only = SearchOptions {
+            chunk_kind: Some(ChunkKind::Code),
+            ..SearchOptions::with_limit(5)
+        };
+        let results = rag.search_with("useState state", &code_only).await.unwrap();
+        assert!(!results.is_empty());
+        assert!(results.iter().all(|r| r.doc_id == example.id));
+
+        let prose_only = SearchOptions {
+            chunk_kind: Some(ChunkKind::Prose),
+            ..SearchOptions::with_limit(5)
+        };
+        let results = rag
+            .search_with("useState state", &prose_only)
+            .await
+            .unwrap();
+        assert!(results.iter().all(|r| r.doc_id != example.id));
+    }
+
+    #[tokio::test]
+    async fn test_doc_embeddings_on_ingest() {
+        use crate::test_support::{sample_documents, EphemeralRetriever};
+
+        let rag = EphemeralRetriever::with_fixtures(sample_documents())
+            .await
+            .unwrap();
+        let ids = rag.store().document_ids().unwrap();
+        assert_eq!(
+            rag.vector_store().doc_embedding_ids().await.unwrap(),
+            ids.iter().copied().collect()
+        );
+
+        let embedding = rag.embedder().embed_sync("state hooks component");
+        let hits = rag.vector_store().search_doc_embeddings(&embedding, 1).await.unwrap();
+        assert_eq!(hits.len(), 1);
+
+        // 이미 문서 임베딩이 있으면 repair에서 추가하지 않음
+        let report = rag.reindex_missing().await.unwrap();
+        assert_eq!(report.doc_embeddings, 0);
+    }
+
+    #[tokio::test]
+    async fn test_oversize_strategy() {
+        use crate::test_support::MockEmbedding;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let config = |oversize| ChunkConfig {
+            min_characters: 0,
+            max_characters: 20_000,
+            overlap_characters: 0,
+            oversize,
+            ..Default::default()
+        };
+        // 한 청크가 입력 한도(2048 토큰)를 넘는 문서
+        let doc = NewDocument {
+            url: "fixture://long".to_string(),
+            content: "word ".repeat(2000),
+            ..Default::default()
+        };
+
+        let rag = HybridRetriever::with_embedder(dir.path(), Box::new(MockEmbedding::new()))
+            .await
+            .unwrap()
+            .with_chunk_config(config(OversizeStrategy::Split));
+        rag.add_document(doc.clone()).await.unwrap();
+        assert!(rag.vector_store().count().await.unwrap() >= 2);
+
+        // 요약 생성기 없이 summarize는 실패
+        let dir = tempfile::TempDir::new().unwrap();
+        let rag = HybridRetriever::with_embedder(dir.path(), Box::new(MockEmbedding::new()))
+            .await
+            .unwrap()
+            .with_chunk_config(config(OversizeStrategy::Summarize));
+        assert!(rag.add_document(doc.clone()).await.is_err());
+        assert!(rag.add_documents(&[doc]).await.is_err());
+        // 임베딩에 실패한 문서는 저장되지 않음 (벡터 없는 문서가 검색에 섞이지 않음)
+        assert_eq!(rag.stats().await.unwrap().document_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_with_oversize_keeps_chunk_config() {
+        use crate::test_support::MockEmbedding;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let config = ChunkConfig {
+            max_characters: 500,
+            ..Default::default()
+        };
+        let rag = HybridRetriever::with_embedder(dir.path(), Box::new(MockEmbedding::new()))
+            .await
+            .unwrap()
+            .with_chunk_config(config.clone())
+            .with_oversize(OversizeStrategy::Split);
+
+        // 처리 방식만 바뀌고 청크 크기 설정은 유지
+        let expected = markdown_chunker(ChunkConfig {
+            oversize: OversizeStrategy::Split,
+            ..config
+        });
+        assert_eq!(rag.chunker_fingerprint(), expected.fingerprint());
+    }
+
+    #[tokio::test]
+    async fn test_two_stage_search() {
+        use crate::test_support::{sample_documents, EphemeralRetriever};
+
+        let rag = EphemeralRetriever::with_fixtures(sample_documents())
+            .await
+            .unwrap();
+
+        let options = SearchOptions {
+            limit: 3,
+            two_stage: Some(1),
+            ..Default::default()
+        };
+        let embedding = rag.embedder().embed_sync("state hooks component");
+        let results = rag.search_by_embedding(&embedding, &options).await.unwrap();
+        assert!(!results.is_empty());
+        assert!(results.iter().all(|r| r.url == "fixture://react-hooks"));
+
+        // 전체 상위 문서가 범위 밖이어도 범위 안 문서에서 후보를 고름
+        let scoped = SearchOptions {
+            framework: Some("sqlite".to_string()),
+            ..options
+        };
+        let results = rag.search_by_embedding(&embedding, &scoped).await.unwrap();
+        assert!(!results.is_empty());
+        assert!(results.iter().all(|r| r.url == "fixture://sqlite-fts5"));
+    }
+
+    #[tokio::test]
+    async fn test_search_by_embedding() {
+        use crate::test_support::{sample_documents, EphemeralRetriever};
+
+        let rag = EphemeralRetriever::with_fixtures(sample_documents())
+            .await
+            .unwrap();
+        let embedding = rag.embedder().embed_sync("state hooks component");
+        let calls = rag.embedder().call_count();
+
+        let results = rag
+            .search_by_embedding(&embedding, &SearchOptions::with_limit(3))
+            .await
+            .unwrap();
+        assert_eq!(results[0].url, "fixture://react-hooks");
+        assert!(results.iter().all(|r| r.method == SearchMethod::Vector));
+        // 임베딩 API를 호출하지 않음
+        assert_eq!(rag.embedder().call_count(), calls);
+
+        let scoped = SearchOptions {
+            limit: 3,
+            framework: Some("sqlite".to_string()),
+            ..Default::default()
+        };
+        let results = rag.search_by_embedding(&embedding, &scoped).await.unwrap();
+        assert!(results.iter().all(|r| r.url == "fixture://sqlite-fts5"));
+
+        assert!(rag
+            .search_by_embedding(&[0.1; 3], &SearchOptions::default())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_boilerplate_suppression() {
+        use crate::test_support::{sample_documents, EphemeralRetriever};
+
+        let banner = "We use cookies to improve your experience on this site.";
+        let mut docs = sample_documents();
+        for i in 0..5 {
+            docs.push(NewDocument {
+                url: format!("fixture://page-{}", i),
+                content: banner.replace("this", &format!("this {}", 2020 + i)),
+                ..Default::default()
+            });
+        }
+        let rag = EphemeralRetriever::with_fixtures(docs).await.unwrap();
+        assert!(rag.store().ids_without_fingerprints().unwrap().is_empty());
+
+        let embedding = rag
+            .embedder()
+            .embed_sync("cookies experience site ownership");
+        let is_banner = |r: &HybridSearchResult| r.url.starts_with("fixture://page-");
+
+        let off = SearchOptions {
+            limit: 8,
+            boilerplate: BoilerplateConfig {
+                mode: BoilerplateMode::Off,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let results = rag.search_by_embedding(&embedding, &off).await.unwrap();
+        assert!(is_banner(&results[0]));
+
+        // 기본값(demote): 상용구 청크는 나머지 청크 뒤로
+        let demote = SearchOptions::with_limit(8);
+        let results = rag.search_by_embedding(&embedding, &demote).await.unwrap();
+        assert_eq!(results[0].url, "fixture://rust-ownership");
+        let first_banner = results.iter().position(is_banner).unwrap();
+        assert!(results[first_banner..].iter().all(is_banner));
+
+        let exclude = SearchOptions {
+            limit: 8,
+            boilerplate: BoilerplateConfig {
+                mode: BoilerplateMode::Exclude,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let results = rag.search_by_embedding(&embedding, &exclude).await.unwrap();
+        assert!(!results.is_empty());
+        assert!(!results.iter().any(is_banner));
+    }
+
+    #[tokio::test]
+    async fn test_search_explained_matches_search() {
+        use crate::test_support::{sample_documents, EphemeralRetriever};
+
+        let rag = EphemeralRetriever::with_fixtures(sample_documents())
+            .await
+            .unwrap();
+
+        let options = SearchOptions::with_limit(3);
+        let plain = rag.search_with("react hooks state", &options).await.unwrap();
+        let explained = rag.search_explained("react hooks state", &options).await.unwrap();
+
+        assert_eq!(plain.len(), explained.len());
+        for (p, e) in plain.iter().zip(&explained) {
+            assert_eq!(p.doc_id, e.result.doc_id);
+            assert_eq!(p.method, e.result.method);
+            assert!((e.explanation.fused_score - p.rrf_score).abs() < 1e-6);
+        }
+
+        let top = &explained[0].explanation;
+        assert_eq!(top.fts.as_ref().unwrap().rank, 1);
+        assert!(top.fts.as_ref().unwrap().matched_terms.contains(&"hooks".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_search_writes_trace() {
+        use crate::test_support::{sample_documents, MockEmbedding};
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let trace_dir = dir.path().join("traces");
+        let rag = HybridRetriever::with_embedder(dir.path(), Box::new(MockEmbedding::new()))
+            .await
+            .unwrap()
+            .with_trace_dir(&trace_dir);
+        rag.add_documents(&sample_documents()).await.unwrap();
+
+        let results = rag.search("react hooks state", 3).await.unwrap();
+
+        let files: Vec<_> = std::fs::read_dir(&trace_dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .collect();
+        assert_eq!(files.len(), 1);
+
+        let trace: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&files[0]).unwrap()).unwrap();
+        assert_eq!(trace["query"], "react hooks state");
+        assert_eq!(trace["embedding"]["sha256"].as_str().unwrap().len(), 64);
+        assert!(!trace["fts_candidates"].as_array().unwrap().is_empty());
+        assert!(!trace["vector_candidates"].as_array().unwrap().is_empty());
+        assert_eq!(trace["fused"].as_array().unwrap().len(), results.len());
+        assert_eq!(trace["fused"][0]["doc_id"], results[0].doc_id);
+    }
+
+    #[tokio::test]
+    async fn test_expired_documents_excluded_and_compacted() {
+        use crate::test_support::EphemeralRetriever;
+
+        let rag = EphemeralRetriever::new().await.unwrap();
+        let expired_id = rag
+            .add_document(NewDocument {
+                url: "note://expired".to_string(),
+                content: "temporary deployment ticket notes".to_string(),
+                expires_at: Some(chrono::Utc::now() - chrono::Duration::hours(1)),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        rag.add_document(NewDocument {
+            url: "note://current".to_string(),
+            content: "current deployment ticket notes".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+        let results = rag.search("deployment ticket notes", 5).await.unwrap();
+        assert!(!results.is_empty());
+        assert!(results.iter().all(|r| r.doc_id != expired_id));
+
+        let report = rag.compact().await.unwrap();
+        assert_eq!(report.expired_documents, 1);
+        assert!(!rag.vector_store().has_embeddings(expired_id).await.unwrap());
+        assert_eq!(rag.stats().await.unwrap().document_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_build_context_includes_pinned() {
+        use crate::test_support::{sample_documents, EphemeralRetriever};
+
+        let rag = EphemeralRetriever::with_fixtures(sample_documents())
+            .await
+            .unwrap();
+        let guide = rag
+            .store()
+            .get_by_url("fixture://sqlite-fts5")
+            .unwrap()
+            .unwrap();
+        rag.store().set_pinned(guide.id, true).unwrap();
+
+        let options = ContextOptions {
+            search: SearchOptions::with_limit(1),
+            ..Default::default()
+        };
+        let context = rag
+            .build_context("react hooks state", &options)
+            .await
+            .unwrap();
+
+        assert_eq!(context.chunks.len(), 2);
+        assert!(context.chunks[0].pinned);
+        assert_eq!(context.chunks[0].doc_id, guide.id);
+        assert_eq!(context.chunks[1].url, "fixture://react-hooks");
+
+        // 프레임워크 범위 밖의 고정 문서는 넣지 않음
+        let react_only = ContextOptions {
+            search: SearchOptions {
+                framework: Some("react".to_string()),
+                ..SearchOptions::with_limit(1)
+            },
+            ..Default::default()
+        };
+        let context = rag
+            .build_context("react hooks state", &react_only)
+            .await
+            .unwrap();
+        assert!(context.chunks.iter().all(|c| !c.pinned));
+        assert_eq!(context.chunks[0].url, "fixture://react-hooks");
+
+        let without_pins = ContextOptions {
+            include_pinned: false,
+            ..options
+        };
+        let context = rag
+            .build_context("react hooks state", &without_pins)
+            .await
+            .unwrap();
+        assert!(context.chunks.iter().all(|c| !c.pinned));
+    }
+
+    #[test]
+    fn test_rrf_fuse() {
+        let fts = vec![
+            FtsSearchResult {
+                doc_id: 1,
+                title: None,
+                content_snippet: "one".to_string(),
+                bm25_score: -2.0,
+            },
+            FtsSearchResult {
+                doc_id: 2,
+                title: None,
+                content_snippet: "two".to_string(),
+                bm25_score: -1.0,
+            },
+        ];
+        let vector = vec![SearchResult {
+            doc_id: 2,
+            chunk_index: 0,
+            chunk_text: "two".to_string(),
+            similarity: 0.9,
+        }];
+
+        let fused = rrf_fuse(&fts, &vector, 10);
+        assert_eq!(fused.len(), 2);
+
+        // 양쪽에서 찾은 문서가 1위
+        assert_eq!(fused[0].doc_id, 2);
+        assert_eq!(fused[0].method(), SearchMethod::Hybrid);
+        assert!((fused[0].score - (1.0 / 62.0 + 1.0 / 61.0)).abs() < 1e-6);
+        assert_eq!(fused[1].method(), SearchMethod::Fts);
+
+        assert_eq!(rrf_fuse(&fts, &vector, 1).len(), 1);
+    }
+
+    #[test]
+    fn test_rrf_fuse_weighted() {
+        let fts = vec![FtsSearchResult {
+            doc_id: 1,
+            title: None,
+            content_snippet: "one".to_string(),
+            bm25_score: -2.0,
+        }];
+        let vector = vec![SearchResult {
+            doc_id: 2,
+            chunk_index: 0,
+            chunk_text: "two".to_string(),
+            similarity: 0.9,
+        }];
+
+        // 동점이면 doc_id 순
+        assert_eq!(rrf_fuse(&fts, &vector, 10)[0].doc_id, 1);
+
+        let fusion = FusionConfig {
+            rrf_k: 10.0,
+            vector_weight: 2.0,
+            ..Default::default()
+        };
+        let fused = rrf_fuse_with(&fts, &vector, 10, &fusion);
+        assert_eq!(fused[0].doc_id, 2);
+        assert!((fused[0].score - 2.0 / 11.0).abs() < 1e-6);
+
+        let parsed: FusionConfig = toml::from_str("vector_weight = 2.0\nrrf_k = 10").unwrap();
+        assert_eq!(parsed, fusion);
+        assert!(toml::from_str::<FusionConfig>("rerank = true").is_err());
+    }
+
+    #[test]
+    fn test_score_normalization() {
+        let fts = vec![
+            FtsSearchResult {
+                doc_id: 2,
+                title: None,
+                content_snippet: "two".to_string(),
+                bm25_score: -9.0,
+            },
+            FtsSearchResult {
+                doc_id: 1,
+                title: None,
+                content_snippet: "one".to_string(),
+                bm25_score: -1.0,
+            },
+        ];
+        let vector: Vec<SearchResult> = [(1, 0.81), (2, 0.80), (3, 0.10)]
+            .into_iter()
+            .map(|(doc_id, similarity)| SearchResult {
+                doc_id,
+                chunk_index: 0,
+                chunk_text: String::new(),
+                similarity,
+            })
+            .collect();
+
+        // 순위만 보면 1, 2가 동점 -> doc_id 순
+        assert_eq!(rrf_fuse(&fts, &vector, 10)[0].doc_id, 1);
+
+        // 벡터 점수 차이는 미미하지만 FTS 1위가 압도적
+        let min_max = FusionConfig {
+            normalization: ScoreNormalization::MinMax,
+            ..Default::default()
+        };
+        let fused = rrf_fuse_with(&fts, &vector, 10, &min_max);
+        assert_eq!(fused[0].doc_id, 2);
+        assert!((fused[0].fts_contribution - 1.0).abs() < 1e-6);
+        assert!((fused[1].score - 1.0).abs() < 1e-6);
+        assert!(fused[2].score.abs() < 1e-6);
+
+        let z_score = FusionConfig {
+            normalization: ScoreNormalization::ZScore,
+            ..Default::default()
+        };
+        let fused = rrf_fuse_with(&fts, &vector, 10, &z_score);
+        assert_eq!(fused[0].doc_id, 2);
+        // FTS 표준 점수 (1, -1)을 최하위가 0이 되도록 이동 -> (2, 0)
+        assert!((fused[0].fts_contribution - 2.0).abs() < 1e-4);
+        assert!(fused[1].fts_contribution.abs() < 1e-4);
+        // 평균 아래 결과도 음수가 아님 (경로 최하위는 결과에 없는 것과 같은 0)
+        assert!(fused
+            .iter()
+            .all(|c| c.fts_contribution >= 0.0 && c.vector_contribution >= 0.0));
+        assert_eq!(fused.last().unwrap().doc_id, 3);
+
+        let parsed: FusionConfig = toml::from_str("normalization = \"z_score\"").unwrap();
+        assert_eq!(parsed, z_score);
+        assert_eq!(
+            ScoreNormalization::parse("min-max"),
+            Some(ScoreNormalization::MinMax)
+        );
+        assert_eq!(ScoreNormalization::parse("cosine"), None);
+    }
 }
@@ -0,0 +1,284 @@
+//! 저장소 무결성 검사와 복구
+//!
+//! 저장소를 열 때 SQLite `PRAGMA quick_check`와 LanceDB 매니페스트(테이블 열기, 스키마,
+//! 행 수)를 확인하여, 손상된 저장소가 검색 도중 알 수 없는 오류로 터지는 대신
+//! `StoreCorrupted`로 바로 실패하게 합니다. 복구(`palank-rag repair`) 순서:
+//!
+//! 1. SQLite가 손상되었으면 손상 파일을 옆으로 옮기고 최신 백업으로 복원
+//! 2. FTS5 인덱스가 본문과 어긋났으면 다시 생성
+//! 3. 벡터 테이블이 손상되었으면 옆으로 옮기고, 벡터가 없는 문서를 SQLite 본문으로 다시 임베딩
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{Local, NaiveDateTime};
+use rusqlite::Connection;
+use thiserror::Error;
+
+use super::lance::LanceVectorStore;
+
+/// SQLite 파일 이름 (데이터 디렉토리 기준)
+pub const SQLITE_FILE: &str = "knowledge.db";
+
+/// LanceDB 디렉토리 이름 (데이터 디렉토리 기준)
+pub const VECTORS_DIR: &str = "vectors.lance";
+
+/// 백업 디렉토리 이름 형식 (`<백업 위치>/20260101-030000/`)
+pub const BACKUP_STAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+/// 저장소 손상
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Store at {path:?} is corrupted ({detail}): run `palank-rag repair`")]
+pub struct StoreCorrupted {
+    /// 손상된 파일/디렉토리
+    pub path: PathBuf,
+    /// 검사 결과
+    pub detail: String,
+}
+
+impl StoreCorrupted {
+    /// 오류 체인에 저장소 손상이 있는지 (context로 감싼 경우 포함)
+    pub fn is_cause_of(error: &anyhow::Error) -> bool {
+        error.chain().any(|e| e.downcast_ref::<Self>().is_some())
+    }
+}
+
+// ============================================================================
+// Checks
+// ============================================================================
+
+/// `PRAGMA quick_check` 결과 중 문제 항목 (정상이면 빈 목록)
+///
+/// SQLite 파일이 아닌 경우처럼 검사 자체가 실패해도 문제로 보고합니다.
+pub fn sqlite_problems(conn: &Connection) -> Vec<String> {
+    let rows = conn.prepare("PRAGMA quick_check").and_then(|mut stmt| {
+        stmt.query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()
+    });
+
+    match rows {
+        Ok(rows) => rows.into_iter().filter(|row| row != "ok").collect(),
+        Err(e) => vec![e.to_string()],
+    }
+}
+
+/// FTS5 인덱스와 본문 테이블의 일치 여부 (어긋나면 설명)
+///
+/// FTS5가 없는 환경에서는 검사하지 않습니다.
+fn fts_problem(conn: &Connection) -> Option<String> {
+    let exists = conn
+        .prepare("SELECT 1 FROM sqlite_master WHERE name = 'documents_fts'")
+        .and_then(|mut stmt| stmt.exists([]))
+        .unwrap_or(false);
+    if !exists {
+        return None;
+    }
+
+    // source: https://www.sqlite.org/fts5.html#the_integrity_check_command
+    conn.execute(
+        "INSERT INTO documents_fts(documents_fts, rank) VALUES('integrity-check', 1)",
+        [],
+    )
+    .err()
+    .map(|e| e.to_string())
+}
+
+/// 무결성 검사 결과
+#[derive(Debug, Clone, Default)]
+pub struct IntegrityReport {
+    /// SQLite 손상 항목
+    pub sqlite: Vec<String>,
+    /// FTS5 인덱스 불일치
+    pub fts: Option<String>,
+    /// 벡터 테이블 손상
+    pub vectors: Option<String>,
+}
+
+impl IntegrityReport {
+    pub fn is_healthy(&self) -> bool {
+        self.sqlite.is_empty() && self.fts.is_none() && self.vectors.is_none()
+    }
+}
+
+/// 데이터 디렉토리의 SQLite/FTS5/LanceDB 검사 (저장소를 열지 않고 파일만 확인)
+pub async fn check_integrity(data_dir: &Path) -> IntegrityReport {
+    let mut report = IntegrityReport::default();
+
+    let db_path = data_dir.join(SQLITE_FILE);
+    if db_path.exists() {
+        match Connection::open(&db_path) {
+            Ok(conn) => {
+                report.sqlite = sqlite_problems(&conn);
+                if report.sqlite.is_empty() {
+                    report.fts = fts_problem(&conn);
+                }
+            }
+            Err(e) => report.sqlite.push(e.to_string()),
+        }
+    }
+
+    let lance_path = data_dir.join(VECTORS_DIR);
+    if lance_path.exists() {
+        let result = async {
+            let vector = LanceVectorStore::open(&lance_path).await?;
+            vector.validate().await?;
+            // 데이터 파일 누락은 전체를 읽어야 드러남
+            vector.doc_ids().await?;
+            anyhow::Ok(())
+        }
+        .await;
+        report.vectors = result.err().map(|e| format!("{:#}", e));
+    }
+
+    report
+}
+
+// ============================================================================
+// Recovery
+// ============================================================================
+
+/// 가장 최근 백업 디렉토리 (`knowledge.db`가 있는 것 중 이름순 마지막 = 최신)
+pub fn latest_backup(root: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(root)
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| is_backup_dir(p) && p.join(SQLITE_FILE).is_file())
+        .max()
+}
+
+/// 이름이 백업 시각(`BACKUP_STAMP_FORMAT`)인 디렉토리인지
+///
+/// 백업 위치에 사용자가 둔 다른 디렉토리를 백업으로 보고 복원/정리하지 않도록 합니다.
+pub fn is_backup_dir(path: &Path) -> bool {
+    path.is_dir()
+        && path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| NaiveDateTime::parse_from_str(name, BACKUP_STAMP_FORMAT).is_ok())
+}
+
+/// 손상된 파일/디렉토리를 `<이름>.corrupt-<시각>`으로 옮김 (삭제하지 않음)
+pub fn set_aside(path: &Path) -> Result<PathBuf> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| anyhow::anyhow!("Invalid store path: {:?}", path))?
+        .to_string_lossy();
+    let target = path.with_file_name(format!(
+        "{}.corrupt-{}",
+        name,
+        Local::now().format(BACKUP_STAMP_FORMAT)
+    ));
+
+    std::fs::rename(path, &target).with_context(|| format!("Failed to move {:?} aside", path))?;
+    tracing::warn!("Moved corrupted store aside: {:?}", target);
+    Ok(target)
+}
+
+/// 백업에서 SQLite와 벡터를 함께 복원
+///
+/// 문서 ID가 어긋나지 않도록 현재 벡터도 백업 시점의 것으로 되돌립니다.
+/// 기존 파일은 `set_aside`로 옮겨 둡니다.
+pub fn restore_backup(backup: &Path, data_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut moved = Vec::new();
+
+    let db_path = data_dir.join(SQLITE_FILE);
+    for suffix in ["", "-wal", "-shm"] {
+        let path = PathBuf::from(format!("{}{}", db_path.display(), suffix));
+        if path.exists() {
+            moved.push(set_aside(&path)?);
+        }
+    }
+    std::fs::copy(backup.join(SQLITE_FILE), &db_path)
+        .with_context(|| format!("Failed to restore database from {:?}", backup))?;
+
+    let lance_path = data_dir.join(VECTORS_DIR);
+    if lance_path.exists() {
+        moved.push(set_aside(&lance_path)?);
+    }
+    let backup_vectors = backup.join(VECTORS_DIR);
+    if backup_vectors.is_dir() {
+        copy_dir(&backup_vectors, &lance_path)?;
+    }
+
+    tracing::info!("Restored store from backup {:?}", backup);
+    Ok(moved)
+}
+
+/// 디렉토리 재귀 복사 (백업/복원용)
+pub(crate) fn copy_dir(src: &Path, dest: &Path) -> Result<()> {
+    std::fs::create_dir_all(dest)?;
+    for entry in std::fs::read_dir(src)? {
+        let entry = entry?;
+        let target = dest.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            std::fs::copy(entry.path(), &target)
+                .with_context(|| format!("Failed to copy {:?}", entry.path()))?;
+        }
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::{KnowledgeStore, NewDocument};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_detects_corrupted_sqlite() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join(SQLITE_FILE);
+        std::fs::write(&db_path, vec![0x42u8; 8192]).unwrap();
+
+        let error = KnowledgeStore::open(&db_path).err().unwrap();
+        assert!(StoreCorrupted::is_cause_of(&error));
+
+        let report = check_integrity(dir.path()).await;
+        assert!(!report.sqlite.is_empty());
+        assert!(!report.is_healthy());
+    }
+
+    #[tokio::test]
+    async fn test_restore_latest_backup() {
+        let dir = TempDir::new().unwrap();
+        let backups = dir.path().join("backups");
+
+        for name in ["20260101-000000", "20260102-000000"] {
+            let backup = backups.join(name);
+            let store = KnowledgeStore::open(&backup.join(SQLITE_FILE)).unwrap();
+            store
+                .add_document(NewDocument {
+                    url: format!("https://example.com/{}", name),
+                    content: "backup".to_string(),
+                    ..Default::default()
+                })
+                .unwrap();
+        }
+        // 비어 있는 디렉토리나 이름이 백업 시각이 아닌 디렉토리는 백업으로 보지 않음
+        std::fs::create_dir_all(backups.join("20260103-000000")).unwrap();
+        let other = backups.join("zz-manual");
+        KnowledgeStore::open(&other.join(SQLITE_FILE)).unwrap();
+
+        let latest = latest_backup(&backups).unwrap();
+        assert!(latest.ends_with("20260102-000000"));
+
+        std::fs::write(dir.path().join(SQLITE_FILE), b"garbage").unwrap();
+        let moved = restore_backup(&latest, dir.path()).unwrap();
+        assert_eq!(moved.len(), 1);
+        assert!(moved[0].exists());
+
+        let store = KnowledgeStore::open(&dir.path().join(SQLITE_FILE)).unwrap();
+        assert!(store
+            .get_by_url("https://example.com/20260102-000000")
+            .unwrap()
+            .is_some());
+        assert!(check_integrity(dir.path()).await.is_healthy());
+    }
+}
@@ -5,8 +5,11 @@
 //! ANN (Approximate Nearest Neighbor) 검색으로 대용량 벡터에서도 빠른 검색을 지원합니다.
 //! ref: https://lancedb.github.io/lancedb/
 
-use std::path::Path;
+use std::collections::{BTreeMap, HashSet};
+use std::future::Future;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{Context, Result};
 use arrow_array::{
@@ -16,13 +19,32 @@ use arrow_array::{
 use arrow_schema::{DataType, Field, Schema};
 use async_trait::async_trait;
 use lancedb::connection::Connection;
-use lancedb::query::{ExecutableQuery, QueryBase};
+use lancedb::query::{ExecutableQuery, QueryBase, Select};
+use lancedb::table::OptimizeAction;
+use serde::Serialize;
 
-use super::vector::{SearchResult, VectorEntry, VectorStore, EMBEDDING_DIMENSION};
+use crate::limits::dir_size;
+
+use super::vector::{mean_embedding, SearchResult, VectorEntry, VectorStore, EMBEDDING_DIMENSION};
 
 /// 벡터 테이블 이름
 const TABLE_NAME: &str = "vectors";
 
+/// 문서 단위 임베딩 테이블 이름 (청크 임베딩 평균, 거친 검색용)
+const DOC_TABLE_NAME: &str = "doc_vectors";
+
+/// 이 수를 넘는 데이터 조각이 있으면 `compact` 권장
+pub const FRAGMENT_COMPACT_THRESHOLD: usize = 32;
+
+/// 벡터 인덱스 없이 전체 스캔하면 검색이 느려지기 시작하는 행 수
+pub const INDEX_RECOMMENDED_ROWS: usize = 100_000;
+
+/// 쓰기 충돌 시 최대 재시도 횟수
+pub const WRITE_RETRIES: u32 = 5;
+
+/// 쓰기 재시도 첫 대기 시간 (회마다 두 배)
+const WRITE_RETRY_BACKOFF_MS: u64 = 50;
+
 // ============================================================================
 // LanceVectorStore
 // ============================================================================
@@ -33,6 +55,7 @@ const TABLE_NAME: &str = "vectors";
 /// Apache Arrow 기반으로 빠른 읽기/쓰기를 제공합니다.
 pub struct LanceVectorStore {
     db: Connection,
+    path: PathBuf,
 }
 
 impl LanceVectorStore {
@@ -59,7 +82,10 @@ impl LanceVectorStore {
             .await
             .context("Failed to connect to LanceDB")?;
 
-        Ok(Self { db })
+        Ok(Self {
+            db,
+            path: path.to_path_buf(),
+        })
     }
 
     /// 벡터 테이블 스키마 생성
@@ -68,14 +94,15 @@ impl LanceVectorStore {
             Field::new("doc_id", DataType::Int64, false),
             Field::new("chunk_index", DataType::Int32, false),
             Field::new("chunk_text", DataType::Utf8, false),
-            Field::new(
-                "embedding",
-                DataType::FixedSizeList(
-                    Arc::new(Field::new("item", DataType::Float32, true)),
-                    EMBEDDING_DIMENSION,
-                ),
-                false,
-            ),
+            Field::new("embedding", embedding_type(), false),
+        ])
+    }
+
+    /// 문서 임베딩 테이블 스키마
+    fn doc_schema() -> Schema {
+        Schema::new(vec![
+            Field::new("doc_id", DataType::Int64, false),
+            Field::new("embedding", embedding_type(), false),
         ])
     }
 
@@ -88,22 +115,7 @@ impl LanceVectorStore {
         let doc_ids: Vec<i64> = entries.iter().map(|e| e.doc_id).collect();
         let chunk_indices: Vec<i32> = entries.iter().map(|e| e.chunk_index).collect();
         let chunk_texts: Vec<&str> = entries.iter().map(|e| e.chunk_text.as_str()).collect();
-
-        // 임베딩을 FixedSizeList로 변환
-        let embeddings_flat: Vec<f32> = entries
-            .iter()
-            .flat_map(|e| e.embedding.iter().copied())
-            .collect();
-
-        let values = Float32Array::from(embeddings_flat);
-        let field = Arc::new(Field::new("item", DataType::Float32, true));
-        let embeddings_list = FixedSizeListArray::try_new(
-            field,
-            EMBEDDING_DIMENSION,
-            Arc::new(values) as Arc<dyn Array>,
-            None,
-        )
-        .context("Failed to create embedding array")?;
+        let embeddings_list = embedding_array(entries.iter().map(|e| e.embedding.as_slice()))?;
 
         let batch = RecordBatch::try_new(
             Arc::new(Self::create_schema()),
@@ -119,164 +131,881 @@ impl LanceVectorStore {
         Ok(batch)
     }
 
-    /// 테이블 존재 여부 확인
-    async fn table_exists(&self) -> bool {
-        self.db
-            .table_names()
+    /// 필터를 적용한 벡터 검색
+    ///
+    /// # Arguments
+    /// * `query_embedding` - 쿼리 임베딩
+    /// * `limit` - 최대 결과 수
+    /// * `filter` - SQL 필터 (예: `doc_id IN (1, 2)`)
+    async fn search_filtered(
+        &self,
+        query_embedding: &[f32],
+        limit: usize,
+        filter: Option<String>,
+    ) -> Result<Vec<SearchResult>> {
+        if !self.table_exists(TABLE_NAME).await {
+            return Ok(vec![]);
+        }
+
+        let table = self
+            .db
+            .open_table(TABLE_NAME)
             .execute()
             .await
-            .map(|names| names.contains(&TABLE_NAME.to_string()))
-            .unwrap_or(false)
+            .context("Failed to open table for search")?;
+
+        // 벡터 검색 (필터는 검색 전에 적용)
+        let mut query = table
+            .vector_search(query_embedding.to_vec())
+            .context("Failed to create vector search")?
+            .limit(limit);
+        if let Some(filter) = filter {
+            query = query.only_if(filter);
+        }
+
+        let results = query
+            .execute()
+            .await
+            .context("Failed to execute vector search")?;
+
+        let mut search_results = Vec::new();
+
+        // RecordBatch 스트림에서 결과 추출
+        use futures::TryStreamExt;
+        let batches: Vec<RecordBatch> = results.try_collect().await?;
+
+        for batch in batches {
+            let doc_ids = batch
+                .column_by_name("doc_id")
+                .and_then(|c| c.as_any().downcast_ref::<Int64Array>())
+                .ok_or_else(|| anyhow::anyhow!("Missing doc_id column"))?;
+
+            let chunk_indices = batch
+                .column_by_name("chunk_index")
+                .and_then(|c| c.as_any().downcast_ref::<Int32Array>())
+                .ok_or_else(|| anyhow::anyhow!("Missing chunk_index column"))?;
+
+            let chunk_texts = batch
+                .column_by_name("chunk_text")
+                .and_then(|c| c.as_any().downcast_ref::<StringArray>())
+                .ok_or_else(|| anyhow::anyhow!("Missing chunk_text column"))?;
+
+            // _distance 컬럼 (LanceDB가 자동 추가)
+            let distances = batch
+                .column_by_name("_distance")
+                .and_then(|c| c.as_any().downcast_ref::<Float32Array>())
+                .ok_or_else(|| anyhow::anyhow!("Missing _distance column"))?;
+
+            for i in 0..batch.num_rows() {
+                let distance = distances.value(i);
+                // 거리를 유사도로 변환 (L2 거리 -> 코사인 유사도 근사)
+                let similarity = 1.0 / (1.0 + distance);
+
+                search_results.push(SearchResult {
+                    doc_id: doc_ids.value(i),
+                    chunk_index: chunk_indices.value(i),
+                    chunk_text: chunk_texts.value(i).to_string(),
+                    similarity,
+                });
+            }
+        }
+
+        Ok(search_results)
     }
 
-    /// 테이블 생성 또는 열기
-    async fn get_or_create_table(&self, batch: RecordBatch) -> Result<lancedb::table::Table> {
-        let schema = batch.schema();
-        if self.table_exists().await {
-            self.db
-                .open_table(TABLE_NAME)
-                .execute()
-                .await
-                .context("Failed to open existing table")
-        } else {
-            // RecordBatchIterator로 감싸서 전달
-            let batches = RecordBatchIterator::new(vec![Ok(batch)], schema);
-            self.db
-                .create_table(TABLE_NAME, batches)
-                .execute()
-                .await
-                .context("Failed to create table")
+    /// 지정한 문서들 안에서만 벡터 검색
+    ///
+    /// 고정 문서의 가장 관련 있는 청크를 찾을 때 사용합니다.
+    pub async fn search_in_docs(
+        &self,
+        query_embedding: &[f32],
+        doc_ids: &[i64],
+        limit: usize,
+    ) -> Result<Vec<SearchResult>> {
+        if doc_ids.is_empty() {
+            return Ok(Vec::new());
         }
+
+        // doc_id는 i64 타입으로 검증됨 - SQL 인젝션 방지
+        let ids: Vec<String> = doc_ids.iter().map(|id| id.to_string()).collect();
+        let filter = format!("doc_id IN ({})", ids.join(", "));
+        self.search_filtered(query_embedding, limit, Some(filter))
+            .await
     }
-}
 
-#[async_trait]
-impl VectorStore for LanceVectorStore {
-    async fn insert_batch(&self, entries: &[VectorEntry]) -> Result<usize> {
+    /// 여러 문서의 벡터를 한 번에 삭제 (문서 임베딩 포함)
+    pub async fn delete_by_doc_ids(&self, doc_ids: &[i64]) -> Result<()> {
+        self.delete_rows(TABLE_NAME, doc_ids).await?;
+        self.delete_rows(DOC_TABLE_NAME, doc_ids).await
+    }
+
+    /// 문서들의 청크 벡터를 한 번의 커밋으로 교체
+    ///
+    /// (문서 ID, 청크 순번)이 같은 행은 갱신하고, 새 청크는 추가하며, 지정한 문서의 나머지
+    /// 행은 삭제합니다. 하나의 merge insert로 커밋하므로 실패하면 기존 벡터가 그대로 남습니다.
+    pub async fn replace_doc_vectors(
+        &self,
+        doc_ids: &[i64],
+        entries: &[VectorEntry],
+    ) -> Result<()> {
         if entries.is_empty() {
-            return Ok(0);
+            return self.delete_rows(TABLE_NAME, doc_ids).await;
+        }
+        if !self.table_exists(TABLE_NAME).await {
+            self.insert_batch(entries).await?;
+            return Ok(());
         }
 
-        let batch = Self::entries_to_batch(entries)?;
-        let schema = batch.schema();
+        let batch = &Self::entries_to_batch(entries)?;
+        // doc_id는 i64 타입으로 검증됨 - SQL 인젝션 방지
+        let ids: Vec<String> = doc_ids.iter().map(|id| id.to_string()).collect();
+        let filter = &format!("doc_id IN ({})", ids.join(", "));
 
-        if self.table_exists().await {
-            // 기존 테이블에 추가
+        self.retry_write("replace", move || async move {
             let table = self
                 .db
                 .open_table(TABLE_NAME)
                 .execute()
                 .await
-                .context("Failed to open table")?;
+                .context("Failed to open table for replace")?;
+            let batches = RecordBatchIterator::new(vec![Ok(batch.clone())], batch.schema());
+
+            let mut merge = table.merge_insert(&["doc_id", "chunk_index"]);
+            merge
+                .when_matched_update_all(None)
+                .when_not_matched_insert_all()
+                .when_not_matched_by_source_delete(Some(filter.clone()));
+            merge
+                .execute(Box::new(batches))
+                .await
+                .context("Failed to replace vectors")
+        })
+        .await
+    }
 
-            let batches = RecordBatchIterator::new(vec![Ok(batch)], schema);
-            table
-                .add(batches)
+    /// 테이블에서 지정한 문서의 행 삭제
+    async fn delete_rows(&self, name: &str, doc_ids: &[i64]) -> Result<()> {
+        if doc_ids.is_empty() || !self.table_exists(name).await {
+            return Ok(());
+        }
+
+        // doc_id는 i64 타입으로 검증됨 - SQL 인젝션 방지
+        let ids: Vec<String> = doc_ids.iter().map(|id| id.to_string()).collect();
+        let filter = &format!("doc_id IN ({})", ids.join(", "));
+
+        self.retry_write("delete", move || async move {
+            let table = self
+                .db
+                .open_table(name)
                 .execute()
                 .await
-                .context("Failed to add vectors to table")?;
-        } else {
-            // 새 테이블 생성
-            self.get_or_create_table(batch).await?;
+                .context("Failed to open table for delete")?;
+            table
+                .delete(filter)
+                .await
+                .context("Failed to delete vectors")
+        })
+        .await
+    }
+
+    /// 테이블 최적화 (작은 파일 병합, 오래된 버전 정리, 인덱스 갱신)
+    pub async fn optimize(&self) -> Result<()> {
+        for name in [TABLE_NAME, DOC_TABLE_NAME] {
+            if !self.table_exists(name).await {
+                continue;
+            }
+
+            self.retry_write("optimize", move || async move {
+                let table = self
+                    .db
+                    .open_table(name)
+                    .execute()
+                    .await
+                    .context("Failed to open table for optimize")?;
+                table
+                    .optimize(OptimizeAction::All)
+                    .await
+                    .with_context(|| format!("Failed to optimize {} table", name))
+            })
+            .await?;
         }
 
-        Ok(entries.len())
+        Ok(())
     }
 
-    async fn search(&self, query_embedding: &[f32], limit: usize) -> Result<Vec<SearchResult>> {
-        if !self.table_exists().await {
+    /// 테이블 매니페스트 검증 (열기, 필수 컬럼 스키마, 행 수)
+    ///
+    /// 데이터 파일 전체를 읽지는 않으므로 열 때마다 호출해도 가볍습니다.
+    pub async fn validate(&self) -> Result<()> {
+        self.validate_table(TABLE_NAME, Self::create_schema()).await?;
+        self.validate_table(DOC_TABLE_NAME, Self::doc_schema())
+            .await
+            .context("Document embedding table")
+    }
+
+    /// 테이블 하나의 매니페스트 검증 (테이블이 없으면 정상)
+    async fn validate_table(&self, name: &str, expected_schema: Schema) -> Result<()> {
+        if !self.table_exists(name).await {
+            return Ok(());
+        }
+
+        let table = self
+            .db
+            .open_table(name)
+            .execute()
+            .await
+            .context("Failed to open vector table manifest")?;
+
+        let schema = table
+            .schema()
+            .await
+            .context("Failed to read vector table schema")?;
+        for expected in expected_schema.fields() {
+            match schema.field_with_name(expected.name()) {
+                Ok(field) if field.data_type() == expected.data_type() => {}
+                Ok(field) => anyhow::bail!(
+                    "Column {} has type {:?}, expected {:?}",
+                    expected.name(),
+                    field.data_type(),
+                    expected.data_type()
+                ),
+                Err(_) => anyhow::bail!("Missing column {}", expected.name()),
+            }
+        }
+
+        table
+            .count_rows(None)
+            .await
+            .context("Failed to read vector table fragments")?;
+
+        Ok(())
+    }
+
+    /// 벡터가 있는 모든 문서 ID (전체 테이블의 doc_id 컬럼만 읽음)
+    pub async fn doc_ids(&self) -> Result<HashSet<i64>> {
+        self.scan_doc_ids(TABLE_NAME).await
+    }
+
+    /// 테이블의 모든 doc_id
+    async fn scan_doc_ids(&self, name: &str) -> Result<HashSet<i64>> {
+        if !self.table_exists(name).await {
+            return Ok(HashSet::new());
+        }
+
+        let table = self
+            .db
+            .open_table(name)
+            .execute()
+            .await
+            .context("Failed to open table for scan")?;
+
+        // 일반 쿼리도 기본 limit이 있으므로 전체 행 수로 지정
+        let rows = table
+            .count_rows(None)
+            .await
+            .context("Failed to count rows")?;
+        if rows == 0 {
+            return Ok(HashSet::new());
+        }
+
+        use futures::TryStreamExt;
+        let batches: Vec<RecordBatch> = table
+            .query()
+            .select(Select::columns(&["doc_id"]))
+            .limit(rows)
+            .execute()
+            .await
+            .context("Failed to scan vector table")?
+            .try_collect()
+            .await
+            .context("Failed to read vector table data")?;
+
+        let mut ids = HashSet::new();
+        for batch in batches {
+            let doc_ids = batch
+                .column_by_name("doc_id")
+                .and_then(|c| c.as_any().downcast_ref::<Int64Array>())
+                .ok_or_else(|| anyhow::anyhow!("Missing doc_id column"))?;
+            ids.extend(doc_ids.values().iter().copied());
+        }
+
+        Ok(ids)
+    }
+
+    /// 테이블 존재 여부 확인
+    async fn table_exists(&self, name: &str) -> bool {
+        self.db
+            .table_names()
+            .execute()
+            .await
+            .map(|names| names.iter().any(|n| n == name))
+            .unwrap_or(false)
+    }
+
+    /// 테이블에 행 추가 (테이블이 없으면 생성)
+    ///
+    /// 다른 프로세스가 먼저 테이블을 만들었으면 다음 시도에서 기존 테이블에 추가합니다.
+    async fn append_rows(&self, name: &str, batch: RecordBatch) -> Result<()> {
+        let batch = &batch;
+        self.retry_write("add", move || async move {
+            // RecordBatchIterator로 감싸서 전달
+            let batches = RecordBatchIterator::new(vec![Ok(batch.clone())], batch.schema());
+            if self.table_exists(name).await {
+                let table = self
+                    .db
+                    .open_table(name)
+                    .execute()
+                    .await
+                    .context("Failed to open table")?;
+                table
+                    .add(batches)
+                    .execute()
+                    .await
+                    .with_context(|| format!("Failed to add rows to {} table", name))?;
+            } else {
+                self.db
+                    .create_table(name, batches)
+                    .execute()
+                    .await
+                    .context("Failed to create table")?;
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    /// 동시 쓰기 충돌이면 최신 버전으로 다시 열어 재시도
+    ///
+    /// watch와 수집이 같은 저장소에 쓰면 커밋 충돌이 날 수 있습니다. 매 시도마다
+    /// 테이블을 새로 열어 다른 쓰기가 반영된 버전 위에 작업을 다시 적용합니다.
+    async fn retry_write<T, F, Fut>(&self, operation: &str, write: F) -> Result<T>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match write().await {
+                Err(e) if attempt < WRITE_RETRIES && is_write_conflict(&e) => {
+                    let backoff = Duration::from_millis(WRITE_RETRY_BACKOFF_MS << attempt);
+                    attempt += 1;
+                    tracing::warn!(
+                        "LanceDB {} conflicted with a concurrent write, retrying in {:?} (attempt {}/{})",
+                        operation,
+                        backoff,
+                        attempt,
+                        WRITE_RETRIES
+                    );
+                    tokio::time::sleep(backoff).await;
+                }
+                Err(e) if attempt > 0 => {
+                    return Err(e.context(format!(
+                        "LanceDB {} failed after {} retries",
+                        operation, attempt
+                    )));
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// 동시 쓰기로 인한 일시적 실패인지 (커밋 충돌, 동시에 만든 테이블)
+fn is_write_conflict(error: &anyhow::Error) -> bool {
+    error
+        .chain()
+        .any(|e| match e.downcast_ref::<lancedb::Error>() {
+            Some(lancedb::Error::TableAlreadyExists { .. }) => true,
+            Some(lancedb::Error::Lance {
+                source: lance::Error::CommitConflict { .. },
+            }) => true,
+            _ => false,
+        })
+}
+
+// ============================================================================
+// Document Embeddings (coarse stage)
+// ============================================================================
+
+impl LanceVectorStore {
+    /// 문서 임베딩 저장 (같은 문서의 기존 임베딩은 교체)
+    ///
+    /// 문서 임베딩은 청크 임베딩의 평균으로, 대규모 코퍼스에서 가까운 문서를 먼저 고르는
+    /// 거친 검색 단계(`search_doc_embeddings`)에 사용합니다.
+    pub async fn upsert_doc_embeddings(&self, docs: &[(i64, Vec<f32>)]) -> Result<usize> {
+        if docs.is_empty() {
+            return Ok(0);
+        }
+
+        let doc_ids: Vec<i64> = docs.iter().map(|(id, _)| *id).collect();
+        self.delete_rows(DOC_TABLE_NAME, &doc_ids).await?;
+
+        let embeddings = embedding_array(docs.iter().map(|(_, e)| e.as_slice()))?;
+        let batch = RecordBatch::try_new(
+            Arc::new(Self::doc_schema()),
+            vec![Arc::new(Int64Array::from(doc_ids)), Arc::new(embeddings)],
+        )
+        .context("Failed to create RecordBatch")?;
+
+        self.append_rows(DOC_TABLE_NAME, batch)
+            .await
+            .context("Failed to add document embeddings")?;
+
+        Ok(docs.len())
+    }
+
+    /// 문서 임베딩으로 가까운 문서 검색 (문서 ID, 유사도)
+    pub async fn search_doc_embeddings(
+        &self,
+        query_embedding: &[f32],
+        limit: usize,
+    ) -> Result<Vec<(i64, f32)>> {
+        if !self.table_exists(DOC_TABLE_NAME).await {
             return Ok(vec![]);
         }
 
         let table = self
             .db
-            .open_table(TABLE_NAME)
+            .open_table(DOC_TABLE_NAME)
             .execute()
             .await
-            .context("Failed to open table for search")?;
+            .context("Failed to open document embedding table")?;
 
-        // 벡터 검색
-        let results = table
+        use futures::TryStreamExt;
+        let batches: Vec<RecordBatch> = table
             .vector_search(query_embedding.to_vec())
             .context("Failed to create vector search")?
             .limit(limit)
             .execute()
             .await
-            .context("Failed to execute vector search")?;
+            .context("Failed to execute document search")?
+            .try_collect()
+            .await?;
 
-        let mut search_results = Vec::new();
+        let mut results = Vec::new();
+        for batch in batches {
+            let doc_ids = batch
+                .column_by_name("doc_id")
+                .and_then(|c| c.as_any().downcast_ref::<Int64Array>())
+                .ok_or_else(|| anyhow::anyhow!("Missing doc_id column"))?;
+            let distances = batch
+                .column_by_name("_distance")
+                .and_then(|c| c.as_any().downcast_ref::<Float32Array>())
+                .ok_or_else(|| anyhow::anyhow!("Missing _distance column"))?;
+
+            for i in 0..batch.num_rows() {
+                // search_filtered와 같은 거리 -> 유사도 변환
+                results.push((doc_ids.value(i), 1.0 / (1.0 + distances.value(i))));
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// 문서 임베딩이 있는 모든 문서 ID
+    pub async fn doc_embedding_ids(&self) -> Result<HashSet<i64>> {
+        self.scan_doc_ids(DOC_TABLE_NAME).await
+    }
+
+    /// 저장된 청크 임베딩의 문서별 평균 (문서 임베딩이 없는 기존 문서 채우기용)
+    ///
+    /// 임베딩 API를 다시 호출하지 않고 청크 테이블만 읽습니다.
+    pub async fn chunk_embedding_means(&self, doc_ids: &[i64]) -> Result<Vec<(i64, Vec<f32>)>> {
+        if doc_ids.is_empty() || !self.table_exists(TABLE_NAME).await {
+            return Ok(Vec::new());
+        }
+
+        let table = self
+            .db
+            .open_table(TABLE_NAME)
+            .execute()
+            .await
+            .context("Failed to open table for scan")?;
+
+        // doc_id는 i64 타입으로 검증됨 - SQL 인젝션 방지
+        let ids: Vec<String> = doc_ids.iter().map(|id| id.to_string()).collect();
+        let filter = format!("doc_id IN ({})", ids.join(", "));
+        let rows = table
+            .count_rows(Some(filter.clone()))
+            .await
+            .context("Failed to count rows")?;
+        if rows == 0 {
+            return Ok(Vec::new());
+        }
 
-        // RecordBatch 스트림에서 결과 추출
         use futures::TryStreamExt;
-        let batches: Vec<RecordBatch> = results.try_collect().await?;
+        let batches: Vec<RecordBatch> = table
+            .query()
+            .only_if(filter)
+            .select(Select::columns(&["doc_id", "embedding"]))
+            .limit(rows)
+            .execute()
+            .await
+            .context("Failed to scan vector table")?
+            .try_collect()
+            .await
+            .context("Failed to read vector table data")?;
 
+        let mut chunks: BTreeMap<i64, Vec<Vec<f32>>> = BTreeMap::new();
         for batch in batches {
             let doc_ids = batch
                 .column_by_name("doc_id")
                 .and_then(|c| c.as_any().downcast_ref::<Int64Array>())
                 .ok_or_else(|| anyhow::anyhow!("Missing doc_id column"))?;
 
+            for (i, embedding) in batch_embeddings(&batch)?.into_iter().enumerate() {
+                chunks.entry(doc_ids.value(i)).or_default().push(embedding);
+            }
+        }
+
+        Ok(chunks
+            .into_iter()
+            .filter_map(|(doc_id, vectors)| {
+                mean_embedding(vectors.iter().map(Vec::as_slice)).map(|mean| (doc_id, mean))
+            })
+            .collect())
+    }
+}
+
+// ============================================================================
+// Export (bundle)
+// ============================================================================
+
+impl LanceVectorStore {
+    /// 모든 청크 벡터 (번들 내보내기용)
+    pub async fn entries(&self) -> Result<Vec<VectorEntry>> {
+        let mut entries = Vec::new();
+        for batch in self.scan_all(TABLE_NAME).await? {
+            let doc_ids = batch
+                .column_by_name("doc_id")
+                .and_then(|c| c.as_any().downcast_ref::<Int64Array>())
+                .ok_or_else(|| anyhow::anyhow!("Missing doc_id column"))?;
             let chunk_indices = batch
                 .column_by_name("chunk_index")
                 .and_then(|c| c.as_any().downcast_ref::<Int32Array>())
                 .ok_or_else(|| anyhow::anyhow!("Missing chunk_index column"))?;
-
             let chunk_texts = batch
                 .column_by_name("chunk_text")
                 .and_then(|c| c.as_any().downcast_ref::<StringArray>())
                 .ok_or_else(|| anyhow::anyhow!("Missing chunk_text column"))?;
 
-            // _distance 컬럼 (LanceDB가 자동 추가)
-            let distances = batch
-                .column_by_name("_distance")
-                .and_then(|c| c.as_any().downcast_ref::<Float32Array>())
-                .ok_or_else(|| anyhow::anyhow!("Missing _distance column"))?;
-
-            for i in 0..batch.num_rows() {
-                let distance = distances.value(i);
-                // 거리를 유사도로 변환 (L2 거리 -> 코사인 유사도 근사)
-                let similarity = 1.0 / (1.0 + distance);
-
-                search_results.push(SearchResult {
+            for (i, embedding) in batch_embeddings(&batch)?.into_iter().enumerate() {
+                entries.push(VectorEntry {
                     doc_id: doc_ids.value(i),
                     chunk_index: chunk_indices.value(i),
                     chunk_text: chunk_texts.value(i).to_string(),
-                    similarity,
+                    embedding,
                 });
             }
         }
 
-        Ok(search_results)
+        Ok(entries)
     }
 
-    async fn delete_by_doc_id(&self, doc_id: i64) -> Result<usize> {
-        if !self.table_exists().await {
-            return Ok(0);
+    /// 모든 문서 임베딩 (번들 내보내기용)
+    pub async fn doc_embeddings(&self) -> Result<Vec<(i64, Vec<f32>)>> {
+        let mut docs = Vec::new();
+        for batch in self.scan_all(DOC_TABLE_NAME).await? {
+            let doc_ids = batch
+                .column_by_name("doc_id")
+                .and_then(|c| c.as_any().downcast_ref::<Int64Array>())
+                .ok_or_else(|| anyhow::anyhow!("Missing doc_id column"))?;
+
+            for (i, embedding) in batch_embeddings(&batch)?.into_iter().enumerate() {
+                docs.push((doc_ids.value(i), embedding));
+            }
+        }
+
+        Ok(docs)
+    }
+
+    /// 테이블 전체 행 읽기 (테이블이 없으면 빈 목록)
+    async fn scan_all(&self, name: &str) -> Result<Vec<RecordBatch>> {
+        if !self.table_exists(name).await {
+            return Ok(Vec::new());
         }
 
         let table = self
             .db
-            .open_table(TABLE_NAME)
+            .open_table(name)
             .execute()
             .await
-            .context("Failed to open table for delete")?;
+            .context("Failed to open table for scan")?;
 
-        // 삭제 전 개수 확인
-        let before_count = self.count().await?;
+        // 일반 쿼리도 기본 limit이 있으므로 전체 행 수로 지정
+        let rows = table
+            .count_rows(None)
+            .await
+            .context("Failed to count rows")?;
+        if rows == 0 {
+            return Ok(Vec::new());
+        }
 
-        // doc_id는 i64 타입으로 검증됨 - SQL 인젝션 방지
-        let filter = format!("doc_id = {}", doc_id as i64);
+        use futures::TryStreamExt;
         table
-            .delete(&filter)
+            .query()
+            .limit(rows)
+            .execute()
+            .await
+            .context("Failed to scan vector table")?
+            .try_collect()
             .await
-            .context("Failed to delete vectors")?;
+            .context("Failed to read vector table data")
+    }
+}
+
+// ============================================================================
+// Describe (status --verbose)
+// ============================================================================
+
+/// 벡터 저장소 상태 (테이블별 통계와 인덱스)
+#[derive(Debug, Clone, Serialize)]
+pub struct VectorStoreDescription {
+    /// 존재하는 테이블 (청크 벡터, 문서 임베딩 순)
+    pub tables: Vec<VectorTableStats>,
+}
+
+/// 벡터 테이블 하나의 통계
+#[derive(Debug, Clone, Serialize)]
+pub struct VectorTableStats {
+    pub name: String,
+    pub rows: usize,
+    /// 데이터셋 버전 (추가/삭제/최적화마다 증가)
+    pub version: u64,
+    /// 현재 버전의 데이터 조각(fragment) 수 (많을수록 검색이 느려지므로 `compact`로 병합)
+    pub fragments: usize,
+    /// 테이블 디렉토리 크기 (정리되지 않은 이전 버전 포함)
+    pub disk_bytes: u64,
+    pub indices: Vec<VectorIndexStats>,
+}
+
+/// 테이블 인덱스 정보
+#[derive(Debug, Clone, Serialize)]
+pub struct VectorIndexStats {
+    pub name: String,
+    /// 인덱스 종류 (`IVF_PQ`, `BTREE` 등)
+    pub index_type: String,
+    pub columns: Vec<String>,
+    /// 거리 함수 (벡터 인덱스만, `l2`, `cosine` 등)
+    pub distance_type: Option<String>,
+    /// 인덱스 조각 수 (추가 후 병합되지 않은 델타 인덱스 포함)
+    pub parts: Option<u32>,
+    /// 인덱스에 반영된 행 수
+    pub indexed_rows: Option<usize>,
+    /// 인덱스 생성 이후 추가되어 전체 스캔으로 검색하는 행 수
+    pub unindexed_rows: Option<usize>,
+}
+
+/// 인덱스 재생성/정리가 필요한 상태
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VectorHealthIssue {
+    /// 조각이 많음 (`compact` 필요)
+    Fragmented { table: String, fragments: usize },
+    /// 인덱스에 없는 행이 있음 (`compact`로 인덱스 갱신)
+    StaleIndex { table: String, unindexed: usize },
+    /// 행이 많은데 벡터 인덱스가 없음 (전체 스캔, 알림만)
+    Unindexed { table: String, rows: usize },
+}
+
+impl VectorStoreDescription {
+    /// 전체 행 수
+    pub fn total_rows(&self) -> usize {
+        self.tables.iter().map(|t| t.rows).sum()
+    }
+
+    /// 전체 디스크 사용량 (바이트)
+    pub fn disk_bytes(&self) -> u64 {
+        self.tables.iter().map(|t| t.disk_bytes).sum()
+    }
+
+    /// 재생성/정리가 필요한 항목
+    pub fn issues(&self) -> Vec<VectorHealthIssue> {
+        let mut issues = Vec::new();
+        for table in &self.tables {
+            if table.fragments > FRAGMENT_COMPACT_THRESHOLD {
+                issues.push(VectorHealthIssue::Fragmented {
+                    table: table.name.clone(),
+                    fragments: table.fragments,
+                });
+            }
+
+            let unindexed: usize = table.indices.iter().filter_map(|i| i.unindexed_rows).sum();
+            if unindexed > 0 {
+                issues.push(VectorHealthIssue::StaleIndex {
+                    table: table.name.clone(),
+                    unindexed,
+                });
+            }
+
+            let has_vector_index = table
+                .indices
+                .iter()
+                .any(|i| i.columns.iter().any(|c| c == "embedding"));
+            if !has_vector_index && table.rows >= INDEX_RECOMMENDED_ROWS {
+                issues.push(VectorHealthIssue::Unindexed {
+                    table: table.name.clone(),
+                    rows: table.rows,
+                });
+            }
+        }
+        issues
+    }
+}
+
+impl LanceVectorStore {
+    /// 테이블별 행 수, 인덱스, 조각 수, 데이터셋 버전, 디스크 사용량
+    ///
+    /// 조각 수와 디스크 사용량은 테이블 디렉토리(`<이름>.lance`)를 직접 읽어 계산합니다.
+    pub async fn describe(&self) -> Result<VectorStoreDescription> {
+        let mut tables = Vec::new();
+        for name in [TABLE_NAME, DOC_TABLE_NAME] {
+            if self.table_exists(name).await {
+                tables.push(self.describe_table(name).await?);
+            }
+        }
+
+        Ok(VectorStoreDescription { tables })
+    }
+
+    async fn describe_table(&self, name: &str) -> Result<VectorTableStats> {
+        let table = self
+            .db
+            .open_table(name)
+            .execute()
+            .await
+            .context("Failed to open table for describe")?;
+
+        let rows = table
+            .count_rows(None)
+            .await
+            .context("Failed to count rows")?;
+        let version = table
+            .version()
+            .await
+            .context("Failed to read dataset version")?;
+
+        let mut indices = Vec::new();
+        for index in table
+            .list_indices()
+            .await
+            .context("Failed to list indices")?
+        {
+            let stats = table
+                .index_stats(&index.name)
+                .await
+                .with_context(|| format!("Failed to read index stats: {}", index.name))?;
+            indices.push(VectorIndexStats {
+                index_type: index.index_type.to_string(),
+                columns: index.columns,
+                distance_type: stats
+                    .as_ref()
+                    .and_then(|s| s.distance_type)
+                    .map(|d| format!("{:?}", d).to_lowercase()),
+                parts: stats.as_ref().and_then(|s| s.num_indices),
+                indexed_rows: stats.as_ref().map(|s| s.num_indexed_rows),
+                unindexed_rows: stats.as_ref().map(|s| s.num_unindexed_rows),
+                name: index.name,
+            });
+        }
+
+        // 현재 버전의 조각 수 (정리되지 않은 이전 버전의 데이터 파일은 세지 않음)
+        let fragments = match table.as_native() {
+            Some(native) => native
+                .count_fragments()
+                .await
+                .context("Failed to count fragments")?,
+            None => 0,
+        };
+
+        let dir = self.path.join(format!("{}.lance", name));
+        Ok(VectorTableStats {
+            name: name.to_string(),
+            rows,
+            version,
+            fragments,
+            disk_bytes: dir_size(&dir),
+            indices,
+        })
+    }
+}
+
+/// 배치의 embedding 컬럼을 행별 벡터로
+fn batch_embeddings(batch: &RecordBatch) -> Result<Vec<Vec<f32>>> {
+    let embeddings = batch
+        .column_by_name("embedding")
+        .and_then(|c| c.as_any().downcast_ref::<FixedSizeListArray>())
+        .ok_or_else(|| anyhow::anyhow!("Missing embedding column"))?;
+    let values = embeddings
+        .values()
+        .as_any()
+        .downcast_ref::<Float32Array>()
+        .ok_or_else(|| anyhow::anyhow!("Invalid embedding values"))?;
+
+    let dimension = EMBEDDING_DIMENSION as usize;
+    Ok((0..batch.num_rows())
+        .map(|i| {
+            let start = (embeddings.offset() + i) * dimension;
+            values.values()[start..start + dimension].to_vec()
+        })
+        .collect())
+}
+
+/// 임베딩 컬럼 타입 (차원 고정 리스트)
+fn embedding_type() -> DataType {
+    DataType::FixedSizeList(
+        Arc::new(Field::new("item", DataType::Float32, true)),
+        EMBEDDING_DIMENSION,
+    )
+}
+
+/// 임베딩들을 FixedSizeList 컬럼으로 변환
+fn embedding_array<'a>(embeddings: impl Iterator<Item = &'a [f32]>) -> Result<FixedSizeListArray> {
+    let flat: Vec<f32> = embeddings.flat_map(|e| e.iter().copied()).collect();
+    let field = Arc::new(Field::new("item", DataType::Float32, true));
+    FixedSizeListArray::try_new(
+        field,
+        EMBEDDING_DIMENSION,
+        Arc::new(Float32Array::from(flat)) as Arc<dyn Array>,
+        None,
+    )
+    .context("Failed to create embedding array")
+}
+
+#[async_trait]
+impl VectorStore for LanceVectorStore {
+    async fn insert_batch(&self, entries: &[VectorEntry]) -> Result<usize> {
+        if entries.is_empty() {
+            return Ok(0);
+        }
+
+        let batch = Self::entries_to_batch(entries)?;
+        self.append_rows(TABLE_NAME, batch)
+            .await
+            .context("Failed to add vectors to table")?;
+
+        Ok(entries.len())
+    }
+
+    async fn search(&self, query_embedding: &[f32], limit: usize) -> Result<Vec<SearchResult>> {
+        self.search_filtered(query_embedding, limit, None).await
+    }
+
+    async fn delete_by_doc_id(&self, doc_id: i64) -> Result<usize> {
+        if !self.table_exists(TABLE_NAME).await {
+            return Ok(0);
+        }
+
+        // 삭제 전 개수 확인
+        let before_count = self.count().await?;
+
+        self.delete_by_doc_ids(&[doc_id]).await?;
 
         let after_count = self.count().await?;
         Ok(before_count.saturating_sub(after_count))
     }
 
     async fn count(&self) -> Result<usize> {
-        if !self.table_exists().await {
+        if !self.table_exists(TABLE_NAME).await {
             return Ok(0);
         }
 
@@ -292,7 +1021,7 @@ impl VectorStore for LanceVectorStore {
     }
 
     async fn has_embeddings(&self, doc_id: i64) -> Result<bool> {
-        if !self.table_exists().await {
+        if !self.table_exists(TABLE_NAME).await {
             return Ok(false);
         }
 
@@ -355,6 +1084,117 @@ mod tests {
         assert!(!store.has_embeddings(999).await.unwrap());
     }
 
+    #[tokio::test]
+    async fn test_validate_and_doc_ids() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = LanceVectorStore::open(&temp_dir.path().join("ids.lance"))
+            .await
+            .unwrap();
+
+        // 테이블이 없어도 정상
+        store.validate().await.unwrap();
+        assert!(store.doc_ids().await.unwrap().is_empty());
+
+        // 기본 limit(10)보다 많은 행도 모두 읽음
+        let entries: Vec<VectorEntry> = (1..=12).map(|id| create_test_entry(id, 0)).collect();
+        store.insert_batch(&entries).await.unwrap();
+
+        store.validate().await.unwrap();
+        assert_eq!(store.doc_ids().await.unwrap(), (1..=12).collect());
+    }
+
+    #[tokio::test]
+    async fn test_describe() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = LanceVectorStore::open(&temp_dir.path().join("describe.lance"))
+            .await
+            .unwrap();
+        assert!(store.describe().await.unwrap().tables.is_empty());
+
+        store
+            .insert_batch(&[create_test_entry(1, 0)])
+            .await
+            .unwrap();
+        store
+            .insert_batch(&[create_test_entry(2, 0), create_test_entry(2, 1)])
+            .await
+            .unwrap();
+        // 같은 문서를 다시 저장하면 이전 조각은 현재 버전에서 빠짐 (파일은 compact 전까지 남음)
+        for _ in 0..2 {
+            store
+                .upsert_doc_embeddings(&[(1, vec![0.1; EMBEDDING_DIMENSION as usize])])
+                .await
+                .unwrap();
+        }
+
+        let description = store.describe().await.unwrap();
+        let names: Vec<&str> = description.tables.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec![TABLE_NAME, DOC_TABLE_NAME]);
+
+        let vectors = &description.tables[0];
+        assert_eq!(vectors.rows, 3);
+        assert_eq!(vectors.fragments, 2);
+        assert!(vectors.version >= 2);
+        assert!(vectors.disk_bytes > 0);
+        assert!(vectors.indices.is_empty());
+        assert_eq!(description.tables[1].fragments, 1);
+        assert_eq!(description.total_rows(), 4);
+        assert!(description.issues().is_empty());
+    }
+
+    #[test]
+    fn test_health_issues() {
+        let table = |fragments, rows, indices| VectorTableStats {
+            name: TABLE_NAME.to_string(),
+            rows,
+            version: 1,
+            fragments,
+            disk_bytes: 0,
+            indices,
+        };
+        let index = VectorIndexStats {
+            name: "embedding_idx".to_string(),
+            index_type: "IVF_PQ".to_string(),
+            columns: vec!["embedding".to_string()],
+            distance_type: Some("l2".to_string()),
+            parts: Some(1),
+            indexed_rows: Some(INDEX_RECOMMENDED_ROWS),
+            unindexed_rows: Some(10),
+        };
+
+        let description = VectorStoreDescription {
+            tables: vec![table(
+                FRAGMENT_COMPACT_THRESHOLD + 1,
+                INDEX_RECOMMENDED_ROWS,
+                vec![],
+            )],
+        };
+        assert_eq!(
+            description.issues(),
+            vec![
+                VectorHealthIssue::Fragmented {
+                    table: TABLE_NAME.to_string(),
+                    fragments: FRAGMENT_COMPACT_THRESHOLD + 1,
+                },
+                VectorHealthIssue::Unindexed {
+                    table: TABLE_NAME.to_string(),
+                    rows: INDEX_RECOMMENDED_ROWS,
+                },
+            ]
+        );
+
+        let description = VectorStoreDescription {
+            tables: vec![table(1, INDEX_RECOMMENDED_ROWS + 10, vec![index])],
+        };
+        assert_eq!(
+            description.issues(),
+            vec![VectorHealthIssue::StaleIndex {
+                table: TABLE_NAME.to_string(),
+                unindexed: 10,
+            }]
+        );
+    }
+
     #[tokio::test]
     async fn test_lance_search() {
         let temp_dir = TempDir::new().unwrap();
@@ -399,4 +1239,94 @@ mod tests {
         assert_eq!(deleted, 2);
         assert_eq!(store.count().await.unwrap(), 1);
     }
+
+    #[test]
+    fn test_is_write_conflict() {
+        let exists = lancedb::Error::TableAlreadyExists {
+            name: TABLE_NAME.to_string(),
+        };
+        let err = anyhow::Error::new(exists).context("Failed to add vectors to table");
+        assert!(is_write_conflict(&err));
+
+        let conflict = lancedb::Error::Lance {
+            source: lance::Error::CommitConflict {
+                version: 3,
+                source: "concurrent append".into(),
+                location: Default::default(),
+            },
+        };
+        let err = anyhow::Error::new(conflict).context("Failed to add vectors to table");
+        assert!(is_write_conflict(&err));
+
+        let other = lancedb::Error::TableNotFound {
+            name: TABLE_NAME.to_string(),
+        };
+        assert!(!is_write_conflict(&anyhow::Error::new(other)));
+        assert!(!is_write_conflict(&anyhow::anyhow!("Commit conflict")));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_writers() {
+        let temp_dir = TempDir::new().unwrap();
+        let lance_path = temp_dir.path().join("concurrent.lance");
+
+        // 같은 경로를 연 두 저장소 = 같은 데이터 디렉토리에 쓰는 두 프로세스
+        let first = LanceVectorStore::open(&lance_path).await.unwrap();
+        let second = LanceVectorStore::open(&lance_path).await.unwrap();
+
+        let a = vec![create_test_entry(1, 0), create_test_entry(1, 1)];
+        let b = vec![create_test_entry(2, 0)];
+        let (ra, rb) = tokio::join!(first.insert_batch(&a), second.insert_batch(&b));
+        assert_eq!((ra.unwrap(), rb.unwrap()), (2, 1));
+
+        let (da, db) = tokio::join!(first.delete_by_doc_ids(&[1]), second.optimize());
+        da.unwrap();
+        db.unwrap();
+
+        assert_eq!(first.doc_ids().await.unwrap(), HashSet::from([2]));
+    }
+
+    #[tokio::test]
+    async fn test_doc_embeddings() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = LanceVectorStore::open(&temp_dir.path().join("docs.lance"))
+            .await
+            .unwrap();
+        let dimension = EMBEDDING_DIMENSION as usize;
+
+        // 문서 1은 첫 축, 문서 2는 두 번째 축 방향
+        let mut entries = Vec::new();
+        for (doc_id, axis) in [(1, 0), (2, 1)] {
+            for chunk_index in 0..2 {
+                let mut embedding = vec![0.0; dimension];
+                embedding[axis] = 1.0;
+                embedding[10 + chunk_index as usize] = 0.5;
+                entries.push(VectorEntry {
+                    embedding,
+                    ..create_test_entry(doc_id, chunk_index)
+                });
+            }
+        }
+        store.insert_batch(&entries).await.unwrap();
+        assert!(store.doc_embedding_ids().await.unwrap().is_empty());
+
+        // 기존 청크 벡터의 평균으로 문서 임베딩 채우기
+        let means = store.chunk_embedding_means(&[1, 2]).await.unwrap();
+        assert_eq!(means.len(), 2);
+        assert_eq!(store.upsert_doc_embeddings(&means).await.unwrap(), 2);
+        // 다시 저장해도 문서당 하나
+        store.upsert_doc_embeddings(&means[..1]).await.unwrap();
+        assert_eq!(store.doc_embedding_ids().await.unwrap(), [1, 2].into());
+        store.validate().await.unwrap();
+
+        let mut query = vec![0.0; dimension];
+        query[1] = 1.0;
+        let hits = store.search_doc_embeddings(&query, 1).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].0, 2);
+
+        // 문서 삭제 시 문서 임베딩도 삭제
+        store.delete_by_doc_id(2).await.unwrap();
+        assert_eq!(store.doc_embedding_ids().await.unwrap(), [1].into());
+    }
 }
@@ -0,0 +1,202 @@
+//! 런처 연동 출력 - `query --format alfred|raycast`
+//!
+//! 검색 결과를 데스크톱 런처가 읽는 JSON으로 변환합니다.
+//! Alfred는 Script Filter JSON 형식 그대로라 별도 스크립트 없이 출력을 쓸 수 있습니다.
+//!
+//! Raycast에는 명령어 출력용 표준 형식이 없으므로 `raycast`는 palank-rag 고유 JSON입니다.
+//! 항목 속성 이름만 `List.Item`을 따르며, Raycast 확장 코드에서 이 JSON을 읽어 목록을 그립니다.
+//!
+//! ref: https://www.alfredapp.com/help/workflows/inputs/script-filter/json/
+//! ref: https://developers.raycast.com/api-reference/user-interface/list
+
+use serde::Serialize;
+
+use super::hybrid::HybridSearchResult;
+
+/// 부제목에 넣을 본문 최대 길이 (문자 수)
+const MAX_SUBTITLE_CHARS: usize = 120;
+
+// ============================================================================
+// Alfred
+// ============================================================================
+
+/// Alfred Script Filter 출력
+#[derive(Debug, Clone, Serialize)]
+pub struct AlfredOutput {
+    pub items: Vec<AlfredItem>,
+}
+
+/// Alfred 결과 항목
+#[derive(Debug, Clone, Serialize)]
+pub struct AlfredItem {
+    /// 항목 식별자 (Alfred가 선택 빈도 학습에 사용)
+    pub uid: String,
+    pub title: String,
+    pub subtitle: String,
+    /// 선택 시 다음 동작으로 넘길 값 (문서 URL)
+    pub arg: String,
+    /// Shift/Cmd+Y 미리보기 URL
+    pub quicklookurl: String,
+    pub text: AlfredText,
+}
+
+/// Cmd+C 복사 / Cmd+L 크게 보기 텍스트
+#[derive(Debug, Clone, Serialize)]
+pub struct AlfredText {
+    pub copy: String,
+    pub largetype: String,
+}
+
+impl AlfredOutput {
+    /// 검색 결과로부터 생성
+    pub fn from_results(results: &[HybridSearchResult]) -> Self {
+        let items = results
+            .iter()
+            .map(|result| AlfredItem {
+                uid: format!("palank-rag-{}", result.doc_id),
+                title: display_title(result),
+                subtitle: subtitle(result),
+                arg: result.url.clone(),
+                quicklookurl: result.url.clone(),
+                text: AlfredText {
+                    copy: result.url.clone(),
+                    largetype: body(result).unwrap_or_default(),
+                },
+            })
+            .collect();
+
+        Self { items }
+    }
+}
+
+// ============================================================================
+// Raycast
+// ============================================================================
+
+/// Raycast 확장용 목록 출력 (palank-rag 고유 형식, 항목 속성 이름은 `List.Item`을 따름)
+///
+/// `{"items": [{"id", "title", "subtitle", "url", "accessories": [{"text"}]}]}`
+#[derive(Debug, Clone, Serialize)]
+pub struct RaycastOutput {
+    pub items: Vec<RaycastItem>,
+}
+
+/// Raycast 목록 항목
+#[derive(Debug, Clone, Serialize)]
+pub struct RaycastItem {
+    pub id: String,
+    pub title: String,
+    pub subtitle: String,
+    /// 열기/복사 동작에 쓸 문서 URL
+    pub url: String,
+    /// 오른쪽에 표시할 부가 정보 (검색 경로, 점수)
+    pub accessories: Vec<RaycastAccessory>,
+}
+
+/// Raycast 항목 부가 정보
+#[derive(Debug, Clone, Serialize)]
+pub struct RaycastAccessory {
+    pub text: String,
+}
+
+impl RaycastOutput {
+    /// 검색 결과로부터 생성
+    pub fn from_results(results: &[HybridSearchResult]) -> Self {
+        let items = results
+            .iter()
+            .map(|result| RaycastItem {
+                id: result.doc_id.to_string(),
+                title: display_title(result),
+                subtitle: subtitle(result),
+                url: result.url.clone(),
+                accessories: vec![RaycastAccessory {
+                    text: format!("{:?} {:.4}", result.method, result.rrf_score),
+                }],
+            })
+            .collect();
+
+        Self { items }
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// 제목 (없으면 URL)
+fn display_title(result: &HybridSearchResult) -> String {
+    result.title.clone().unwrap_or_else(|| result.url.clone())
+}
+
+/// 청크 텍스트 또는 스니펫 (FTS5 강조 태그 제거)
+fn body(result: &HybridSearchResult) -> Option<String> {
+    result
+        .chunk_text
+        .as_deref()
+        .or(result.snippet.as_deref())
+        .map(|text| text.replace("<b>", "").replace("</b>", "").trim().to_string())
+}
+
+/// 한 줄 부제목 (본문 앞부분, 없으면 URL)
+fn subtitle(result: &HybridSearchResult) -> String {
+    let Some(body) = body(result) else {
+        return result.url.clone();
+    };
+
+    let line = body.split_whitespace().collect::<Vec<_>>().join(" ");
+    if line.chars().count() <= MAX_SUBTITLE_CHARS {
+        return line;
+    }
+
+    let cut: String = line.chars().take(MAX_SUBTITLE_CHARS).collect();
+    format!("{}...", cut.trim_end())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::SearchMethod;
+
+    fn sample() -> Vec<HybridSearchResult> {
+        vec![HybridSearchResult {
+            doc_id: 3,
+            url: "https://react.dev/reference/react/useState".to_string(),
+            title: Some("useState".to_string()),
+            chunk_text: None,
+            chunk_index: None,
+            snippet: Some("Call <b>useState</b>\n  at the top level".to_string()),
+            rrf_score: 0.0325,
+            method: SearchMethod::Hybrid,
+            citation: None,
+            location: None,
+            partial: false,
+        }]
+    }
+
+    #[test]
+    fn test_alfred_output() {
+        let json = serde_json::to_value(AlfredOutput::from_results(&sample())).unwrap();
+        let item = &json["items"][0];
+        assert_eq!(item["uid"], "palank-rag-3");
+        assert_eq!(item["title"], "useState");
+        assert_eq!(item["subtitle"], "Call useState at the top level");
+        assert_eq!(item["arg"], "https://react.dev/reference/react/useState");
+        assert_eq!(item["text"]["copy"], item["arg"]);
+    }
+
+    #[test]
+    fn test_raycast_output() {
+        let json = serde_json::to_value(RaycastOutput::from_results(&sample())).unwrap();
+        let item = &json["items"][0];
+        assert_eq!(item["id"], "3");
+        assert_eq!(item["url"], "https://react.dev/reference/react/useState");
+        assert_eq!(item["accessories"][0]["text"], "Hybrid 0.0325");
+
+        let empty = serde_json::to_string(&RaycastOutput::from_results(&[])).unwrap();
+        assert_eq!(empty, r#"{"items":[]}"#);
+    }
+}
@@ -0,0 +1,209 @@
+//! 소스 코드 위치 - `query --format quickfix`
+//!
+//! 소스 코드 파일에서 수집한 문서는 청크마다 원본 줄 범위를 저장해 두고,
+//! 검색 결과를 `파일:줄:열: 메시지` 형식으로 출력하여 vim quickfix나
+//! VS Code 터미널 링크로 일치한 코드에 바로 이동할 수 있게 합니다.
+
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use super::hybrid::HybridSearchResult;
+
+/// 줄 범위를 기록할 소스 코드 확장자
+const SOURCE_EXTENSIONS: &[&str] = &[
+    "rs", "ts", "tsx", "js", "jsx", "py", "go", "java", "c", "cpp", "h", "hpp", "sh", "bash",
+    "zsh", "sql", "css", "scss", "html",
+];
+
+/// 검색 결과의 원본 파일 위치
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SourceLocation {
+    /// 원본 파일 경로
+    pub path: PathBuf,
+    /// 시작 줄 (1부터)
+    pub start_line: usize,
+    /// 끝 줄 (포함)
+    pub end_line: usize,
+}
+
+/// `file://` URL이 소스 코드 파일이면 파일 경로
+pub fn source_path(url: &str) -> Option<PathBuf> {
+    let path = url.strip_prefix("file://")?;
+    let path = path.split('#').next().unwrap_or(path);
+    let path = PathBuf::from(path);
+
+    let ext = path.extension()?.to_str()?.to_lowercase();
+    SOURCE_EXTENSIONS.contains(&ext.as_str()).then_some(path)
+}
+
+/// 청크별 원본 줄 범위 (1부터, 끝 줄 포함)
+///
+/// 청커는 줄 단위로 나누므로 각 청크의 첫 줄과 (공백을 뺀) 줄 전체가 같고, 이어지는
+/// 줄들도 차례로 일치하는 위치를 이전 청크 시작 이후에서 찾습니다. 오버랩 구간
+/// (`...` ~ `---`)은 앞 청크의 꼬리이므로 건너뜁니다. 찾지 못하면 이전 청크 시작 줄을 씁니다.
+pub fn chunk_line_ranges(content: &str, chunks: &[String]) -> Vec<(usize, usize)> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut from = 0;
+
+    chunks
+        .iter()
+        .map(|chunk| {
+            let body = strip_overlap(chunk);
+            let wanted: Vec<&str> = body
+                .lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty())
+                .collect();
+            let start = (from..lines.len())
+                .find(|&i| !wanted.is_empty() && lines_match(&lines[i..], &wanted))
+                .unwrap_or(from);
+            let count = body.trim_start_matches('\n').lines().count().max(1);
+            let end = (start + count).min(lines.len()).max(start + 1);
+
+            from = start;
+            (start + 1, end)
+        })
+        .collect()
+}
+
+/// 오버랩 표시(`...\n<앞 청크 꼬리>\n---\n`)를 뗀 청크 본문
+fn strip_overlap(chunk: &str) -> &str {
+    chunk
+        .strip_prefix("...\n")
+        .and_then(|rest| rest.split_once("\n---\n"))
+        .map(|(_, body)| body)
+        .unwrap_or(chunk)
+}
+
+/// `lines`의 빈 줄이 아닌 줄들이 `wanted`로 시작하는지
+///
+/// 첫 줄은 `lines`의 첫 줄과 같아야 합니다. 마지막 줄은 청크 크기 제한으로 잘렸을 수 있어
+/// 앞부분만 비교합니다.
+fn lines_match(lines: &[&str], wanted: &[&str]) -> bool {
+    if lines.first().map(|l| l.trim()) != wanted.first().copied() {
+        return false;
+    }
+
+    let mut content = lines.iter().map(|l| l.trim()).filter(|l| !l.is_empty());
+    wanted
+        .iter()
+        .enumerate()
+        .all(|(i, want)| match content.next() {
+            Some(line) if i + 1 == wanted.len() => line.starts_with(want),
+            Some(line) => line == *want,
+            None => false,
+        })
+}
+
+/// quickfix 형식 줄 (`파일:줄:열: 제목 | 청크 첫 줄`)
+///
+/// 위치가 없는 결과(소스 코드가 아닌 문서)는 건너뜁니다.
+pub fn quickfix_lines(results: &[HybridSearchResult]) -> Vec<String> {
+    results
+        .iter()
+        .filter_map(|result| {
+            let location = result.location.as_ref()?;
+            let first_line = result
+                .chunk_text
+                .as_deref()
+                .or(result.snippet.as_deref())
+                .and_then(|text| text.lines().map(str::trim).find(|l| !l.is_empty()))
+                .unwrap_or_default()
+                .replace("<b>", "")
+                .replace("</b>", "");
+            let title = result.title.as_deref().unwrap_or("-");
+
+            Some(format!(
+                "{}:{}:1: {} | {}",
+                location.path.display(),
+                location.start_line,
+                title,
+                first_line
+            ))
+        })
+        .collect()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_path() {
+        assert_eq!(
+            source_path("file:///repo/src/main.rs"),
+            Some(PathBuf::from("/repo/src/main.rs"))
+        );
+        assert!(source_path("file:///notes/todo.md").is_none());
+        assert!(source_path("https://example.com/main.rs").is_none());
+    }
+
+    #[test]
+    fn test_chunk_line_ranges() {
+        let content = "fn a() {\n    1\n}\n\nfn b() {\n    2\n}\n";
+        let chunks = vec![
+            "fn a() {\n    1\n}".to_string(),
+            "fn b() {\n    2\n}".to_string(),
+        ];
+        assert_eq!(chunk_line_ranges(content, &chunks), vec![(1, 3), (5, 7)]);
+    }
+
+    #[test]
+    fn test_chunk_line_ranges_exact_lines() {
+        // `}`는 앞 줄들에도 포함되고, 같은 첫 줄이 두 번 나오지만 다음 줄로 구분
+        let content = "let x = {};\nimpl A {\n    fn a() {}\n}\nimpl A {\n    fn b() {}\n}\n";
+        assert_eq!(chunk_line_ranges(content, &["}".to_string()]), vec![(4, 4)]);
+        let chunks = vec!["impl A {\n    fn b() {}\n}".to_string()];
+        assert_eq!(chunk_line_ranges(content, &chunks), vec![(5, 7)]);
+    }
+
+    #[test]
+    fn test_chunk_line_ranges_overlap() {
+        let content = "fn a() {\n    1\n}\nfn b() {\n    2\n}\n";
+        let chunks = vec![
+            "fn a() {\n    1\n}".to_string(),
+            "...\n    1\n}\n---\nfn b() {\n    2\n}".to_string(),
+        ];
+        assert_eq!(chunk_line_ranges(content, &chunks), vec![(1, 3), (4, 6)]);
+    }
+
+    #[test]
+    fn test_quickfix_lines() {
+        use crate::knowledge::SearchMethod;
+
+        let result = |url: &str, location| HybridSearchResult {
+            doc_id: 1,
+            url: url.to_string(),
+            title: Some("lib.rs".to_string()),
+            chunk_text: Some("\nfn b() {\n    2\n}".to_string()),
+            chunk_index: Some(1),
+            snippet: None,
+            rrf_score: 0.5,
+            method: SearchMethod::Vector,
+            citation: None,
+            location,
+            partial: false,
+        };
+        let results = vec![
+            result(
+                "file:///repo/src/lib.rs",
+                Some(SourceLocation {
+                    path: PathBuf::from("/repo/src/lib.rs"),
+                    start_line: 5,
+                    end_line: 7,
+                }),
+            ),
+            result("https://example.com", None),
+        ];
+
+        assert_eq!(
+            quickfix_lines(&results),
+            vec!["/repo/src/lib.rs:5:1: lib.rs | fn b() {"]
+        );
+    }
+}
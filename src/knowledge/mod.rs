@@ -12,20 +12,77 @@ mod vector;
 mod lance;
 mod hybrid;
 mod chunker;
+mod chunk_kind;
+mod boilerplate;
+mod collection;
+mod bundle;
+mod compare;
+mod context;
+mod explain;
+mod feedback;
+mod framework;
+mod report;
+mod launcher;
+mod location;
+mod provenance;
+mod trace;
+mod changelog;
+mod integrity;
 
 // Re-exports
 pub use store::{
-    KnowledgeStore, Document, NewDocument, StoreStats, FtsSearchResult,
+    KnowledgeStore, Document, DocumentSummary, NewDocument, StoreStats, FtsSearchResult,
     get_data_dir,
 };
 pub use vector::{
     VectorStore, VectorEntry, SearchResult,
-    cosine_similarity, chunk_text,
+    cosine_similarity, chunk_text, mean_embedding,
     EMBEDDING_DIMENSION,
 };
-pub use lance::LanceVectorStore;
-pub use hybrid::{HybridRetriever, HybridSearchResult, HybridStats, SearchMethod};
+pub use lance::{
+    LanceVectorStore, VectorHealthIssue, VectorIndexStats, VectorStoreDescription,
+    VectorTableStats, FRAGMENT_COMPACT_THRESHOLD, INDEX_RECOMMENDED_ROWS,
+};
+pub use hybrid::{
+    HybridRetriever, HybridSearchResult, HybridStats, SearchMethod, SearchOptions, CompactReport,
+    ReindexReport, FusedCandidate, FusionConfig, PreparedDocuments, ScoreNormalization, rrf_fuse,
+    rrf_fuse_with, RRF_K, FEEDBACK_WEIGHT,
+};
+pub use feedback::{apply_feedback, query_id, FeedbackVotes};
+pub use framework::{
+    apply_version_preference, detect_version, pair_framework, FrameworkTag, VERSION_WEIGHT,
+};
+pub use report::SearchReport;
+pub use provenance::Provenance;
+pub use location::{chunk_line_ranges, quickfix_lines, source_path, SourceLocation};
+pub use launcher::{
+    AlfredItem, AlfredOutput, AlfredText, RaycastAccessory, RaycastItem, RaycastOutput,
+};
+pub use compare::{compare_rankings, RankChange, RankingDiff};
+pub use context::{
+    AskContext, ContextChunk, ContextOptions, SourceCitation, DEFAULT_CONTEXT_CHARS,
+};
+pub use trace::{EmbeddingFingerprint, RetrievalTrace, TRACES_DIR};
+pub use changelog::{changelog_document, diff_lines, ContentDiff, DiffHunk};
+pub use explain::{ExplainedResult, FtsLeg, ResultExplanation, VectorLeg};
+pub use integrity::{
+    check_integrity, is_backup_dir, latest_backup, restore_backup, set_aside, IntegrityReport,
+    StoreCorrupted, BACKUP_STAMP_FORMAT, SQLITE_FILE, VECTORS_DIR,
+};
+pub(crate) use integrity::copy_dir;
+pub use bundle::{
+    create_bundle, open_bundle, read_manifest, BundleManifest, BUNDLES_DIR, BUNDLE_FORMAT_VERSION,
+};
+pub use collection::{
+    collection_dir, fuse_collections, list_collections, resolve_collections, search_collections,
+    CollectionHit, COLLECTIONS_DIR, DEFAULT_COLLECTION,
+};
+pub use boilerplate::{
+    chunk_fingerprint, suppress_boilerplate, BoilerplateConfig, BoilerplateMode,
+    DEFAULT_BOILERPLATE_MIN_DOCS,
+};
+pub use chunk_kind::{classify, classify_chunk, is_coding_question, ChunkClass, ChunkKind};
 pub use chunker::{
-    Chunker, MarkdownChunker, ChunkConfig,
-    default_chunker, markdown_chunker,
+    Chunker, MarkdownChunker, ChunkConfig, ChunkViolation,
+    default_chunker, markdown_chunker, validate_chunks,
 };
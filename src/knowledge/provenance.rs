@@ -0,0 +1,119 @@
+//! 수집 출처 기록 - 문서가 어떻게 들어왔는지
+//!
+//! 문서마다 수집 경로(URL 스크랩, 파일, 직접 입력 등), 추출 방식, 실행 명령,
+//! 호스트 이름, 도구 버전을 저장하여 `show`에서 확인할 수 있게 합니다.
+//! 몇 달 뒤 이상한 문서를 발견했을 때 어디서 왔는지 추적하는 용도입니다.
+
+use serde::{Deserialize, Serialize};
+
+/// 수집 출처
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Provenance {
+    /// 수집 경로 (url, text, file, bibtex, sql, bookmarks, watch, daemon 등)
+    pub source: String,
+    /// 콘텐츠 추출 방식 (scraper, text, pdf, vision, table 등)
+    pub extractor: Option<String>,
+    /// 실행한 명령 (옵션 이름만, 값은 기록하지 않음)
+    pub command: Option<String>,
+    /// 수집한 호스트 이름
+    pub hostname: Option<String>,
+    /// palank-rag 버전
+    pub tool_version: String,
+}
+
+impl Provenance {
+    /// 현재 프로세스 기준 출처
+    pub fn current(source: &str) -> Self {
+        Self {
+            source: source.to_string(),
+            extractor: None,
+            command: current_command(),
+            hostname: hostname(),
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+
+    /// 추출 방식 지정
+    pub fn with_extractor(mut self, extractor: &str) -> Self {
+        self.extractor = Some(extractor.to_string());
+        self
+    }
+}
+
+/// 실행 명령 (하위 명령과 옵션 이름만)
+///
+/// `--sql` 연결 문자열, `--text` 본문처럼 비밀번호나 긴 값이 들어갈 수 있으므로
+/// 옵션 값은 버립니다. 어떤 옵션으로 수집했는지는 이것으로 충분합니다.
+fn current_command() -> Option<String> {
+    command_line(std::env::args())
+}
+
+fn command_line(args: impl IntoIterator<Item = String>) -> Option<String> {
+    let mut args = args.into_iter();
+    let program = args.next()?;
+    let program = std::path::Path::new(&program)
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or(program);
+
+    let mut words = vec![program];
+    let mut seen_option = false;
+    for arg in args {
+        if arg.starts_with('-') {
+            seen_option = true;
+            words.push(arg.split('=').next().unwrap_or(&arg).to_string());
+        } else if !seen_option {
+            // 하위 명령 (ingest, watch 등)
+            words.push(arg);
+        }
+    }
+
+    Some(words.join(" "))
+}
+
+/// 호스트 이름 (환경 변수, 없으면 /etc/hostname)
+fn hostname() -> Option<String> {
+    ["HOSTNAME", "COMPUTERNAME"]
+        .iter()
+        .find_map(|key| std::env::var(key).ok())
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|h| h.trim().to_string())
+        .filter(|h| !h.is_empty())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_line_drops_values() {
+        let args = [
+            "/usr/local/bin/palank-rag",
+            "ingest",
+            "--sql",
+            "postgres://user:secret@db/app",
+            "--query=SELECT 1",
+            "-f",
+            "react",
+        ]
+        .map(str::to_string);
+
+        assert_eq!(
+            command_line(args).as_deref(),
+            Some("palank-rag ingest --sql --query -f")
+        );
+        assert!(command_line(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn test_current() {
+        let provenance = Provenance::current("file").with_extractor("pdf");
+        assert_eq!(provenance.source, "file");
+        assert_eq!(provenance.extractor.as_deref(), Some("pdf"));
+        assert_eq!(provenance.tool_version, env!("CARGO_PKG_VERSION"));
+    }
+}
@@ -0,0 +1,152 @@
+//! 검색 결과 Markdown 보고서 - `query --export report.md`
+//!
+//! 쿼리, 검색 시각, 순위별 결과(제목 링크, 점수, 스니펫)를 Markdown 문서로 만듭니다.
+//! 도구를 쓰지 않는 팀원에게 공유하거나 이슈 트래커에 그대로 붙여넣을 수 있습니다.
+
+use chrono::{DateTime, Utc};
+
+use super::feedback::query_id;
+use super::hybrid::{HybridSearchResult, SearchMethod};
+
+/// 결과마다 넣을 본문 최대 길이 (문자 수)
+const MAX_EXCERPT_CHARS: usize = 500;
+
+/// 검색 결과 보고서
+#[derive(Debug, Clone)]
+pub struct SearchReport<'a> {
+    pub query: &'a str,
+    pub timestamp: DateTime<Utc>,
+    pub results: &'a [HybridSearchResult],
+}
+
+impl SearchReport<'_> {
+    /// Markdown으로 렌더링
+    pub fn render(&self) -> String {
+        let mut out = format!("# 검색 결과: {}\n\n", self.query);
+        out.push_str(&format!(
+            "- 시각: {}\n",
+            self.timestamp.format("%Y-%m-%d %H:%M UTC")
+        ));
+        out.push_str(&format!("- 쿼리 ID: `{}`\n", query_id(self.query)));
+        out.push_str(&format!("- 결과: {} 건\n", self.results.len()));
+
+        for (i, result) in self.results.iter().enumerate() {
+            let title = result.title.as_deref().unwrap_or(&result.url);
+            out.push_str(&format!(
+                "\n## {}. [{}]({})\n\n",
+                i + 1,
+                escape_link_text(title),
+                link_destination(&result.url)
+            ));
+
+            let method = match result.method {
+                SearchMethod::Vector => "vector",
+                SearchMethod::Fts => "fts",
+                SearchMethod::Hybrid => "hybrid",
+            };
+            out.push_str(&format!(
+                "- 문서 ID: {} · 검색: {} · 점수: {:.4}\n",
+                result.doc_id, method, result.rrf_score
+            ));
+            if let Some(ref citation) = result.citation {
+                out.push_str(&format!("- 인용: {}\n", citation.format()));
+            }
+
+            let body = result
+                .chunk_text
+                .as_deref()
+                .or(result.snippet.as_deref())
+                .map(excerpt)
+                .unwrap_or_default();
+            if !body.is_empty() {
+                out.push('\n');
+                for line in body.lines() {
+                    if line.trim().is_empty() {
+                        out.push_str(">\n");
+                    } else {
+                        out.push_str(&format!("> {}\n", line));
+                    }
+                }
+            }
+        }
+
+        out
+    }
+}
+
+/// 링크 텍스트의 대괄호 이스케이프
+fn escape_link_text(text: &str) -> String {
+    text.replace('[', "\\[").replace(']', "\\]")
+}
+
+/// 링크 대상 (`<...>`로 감싸 공백/괄호가 있는 파일 경로도 깨지지 않게)
+fn link_destination(url: &str) -> String {
+    format!("<{}>", url.replace('<', "\\<").replace('>', "\\>"))
+}
+
+/// 인용 블록용 본문 (FTS5 강조 -> 굵게, 길이 제한)
+fn excerpt(text: &str) -> String {
+    let text = text.trim().replace("<b>", "**").replace("</b>", "**");
+    if text.chars().count() <= MAX_EXCERPT_CHARS {
+        return text;
+    }
+
+    let cut: String = text.chars().take(MAX_EXCERPT_CHARS).collect();
+    format!("{}...", cut.trim_end())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(doc_id: i64, title: Option<&str>, snippet: &str) -> HybridSearchResult {
+        HybridSearchResult {
+            doc_id,
+            url: format!("https://example.com/{}", doc_id),
+            title: title.map(str::to_string),
+            chunk_text: None,
+            chunk_index: None,
+            snippet: Some(snippet.to_string()),
+            rrf_score: 0.0325,
+            method: SearchMethod::Hybrid,
+            citation: None,
+            location: None,
+            partial: false,
+        }
+    }
+
+    #[test]
+    fn test_render_report() {
+        let results = vec![
+            result(1, Some("Hooks [intro]"), "use <b>hooks</b>\n\nfor state"),
+            result(2, None, "plain"),
+        ];
+        let report = SearchReport {
+            query: "react hooks",
+            timestamp: "2026-10-17T09:30:00Z".parse().unwrap(),
+            results: &results,
+        };
+        let markdown = report.render();
+
+        assert!(markdown.starts_with("# 검색 결과: react hooks\n"));
+        assert!(markdown.contains("- 시각: 2026-10-17 09:30 UTC"));
+        assert!(markdown.contains("- 결과: 2 건"));
+        assert!(markdown.contains("## 1. [Hooks \\[intro\\]](<https://example.com/1>)"));
+        assert!(markdown.contains("> use **hooks**\n>\n> for state\n"));
+        // 제목이 없으면 URL을 링크 텍스트로
+        assert!(markdown.contains("## 2. [https://example.com/2](<https://example.com/2>)"));
+    }
+
+    #[test]
+    fn test_link_destination() {
+        assert_eq!(
+            link_destination("file:///notes/My Notes (draft).md"),
+            "<file:///notes/My Notes (draft).md>"
+        );
+        assert_eq!(link_destination("a<b>"), "<a\\<b\\>>");
+    }
+}
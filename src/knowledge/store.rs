@@ -5,14 +5,23 @@
 //! 학습된 지식(URL에서 가져온 콘텐츠)을 저장하고 검색합니다.
 //! 저장 위치: ~/.palank-rag/knowledge.db
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
 use anyhow::{Context, Result};
-use chrono::{DateTime, Utc};
-use rusqlite::{params, Connection, OpenFlags};
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::{params, params_from_iter, Connection, OpenFlags};
 use serde::{Deserialize, Serialize};
 
+use super::chunk_kind::{ChunkClass, ChunkKind};
+use super::feedback::FeedbackVotes;
+use super::integrity::{sqlite_problems, StoreCorrupted};
+use super::provenance::Provenance;
+use crate::citation::Citation;
+use crate::normalize::{normalize_document, normalize_query};
+use crate::scraper::PageMetadata;
+
 // ============================================================================
 // Data Directory
 // ============================================================================
@@ -25,6 +34,50 @@ pub fn get_data_dir() -> PathBuf {
         .join(".palank-rag")
 }
 
+/// 문서 조회 컬럼 (`row_to_document`와 순서 일치)
+const DOCUMENT_COLUMNS: &str =
+    "id, url, title, content, framework, created_at, expires_at, pinned, citation, metadata, \
+     provenance";
+
+/// 요약 조회 컬럼 (`row_to_summary`와 순서 일치)
+const SUMMARY_COLUMNS: &str = "id, url, title, framework, LENGTH(content), created_at, \
+     expires_at, pinned, citation, published_at";
+
+/// 문서 저장 SQL (URL이 같으면 같은 ID로 갱신, 고정 여부와 기존 인용 정보/메타데이터/수집 출처는 유지)
+const INSERT_DOCUMENT_SQL: &str =
+    "INSERT INTO documents
+         (url, title, content, framework, created_at, expires_at, citation,
+          metadata, published_at, provenance)
+     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+     ON CONFLICT(url) DO UPDATE SET
+         title = excluded.title,
+         content = excluded.content,
+         framework = excluded.framework,
+         created_at = excluded.created_at,
+         expires_at = excluded.expires_at,
+         citation = COALESCE(excluded.citation, citation),
+         metadata = COALESCE(excluded.metadata, metadata),
+         published_at = CASE WHEN excluded.metadata IS NULL THEN published_at
+                             ELSE excluded.published_at END,
+         provenance = COALESCE(excluded.provenance, provenance)
+     RETURNING id";
+
+/// 문서 ID별 청크 부가 기록 테이블 (문서가 교체/삭제되면 함께 정리)
+const CHUNK_TABLES: &[&str] = &[
+    "chunk_lines",
+    "chunk_kinds",
+    "chunk_fingerprints",
+    "chunk_models",
+];
+
+/// 청크가 없는 문서의 모델 기록 청크 번호 (재임베딩 대상에서 빠지도록 남기는 표시)
+const NO_CHUNKS_INDEX: i32 = -1;
+
+/// 준비된 구문(prepared statement) 캐시 크기
+///
+/// 조회/검색/목록 등 반복 호출되는 쿼리는 `prepare_cached`로 재사용합니다.
+const STATEMENT_CACHE_CAPACITY: usize = 32;
+
 // ============================================================================
 // Types
 // ============================================================================
@@ -38,19 +91,74 @@ pub struct Document {
     pub content: String,
     pub framework: Option<String>,
     pub created_at: DateTime<Utc>,
+    /// 만료 시각 (지나면 검색에서 제외)
+    pub expires_at: Option<DateTime<Utc>>,
+    /// 고정(즐겨찾기) 여부
+    pub pinned: bool,
+    /// 인용 정보 (BibTeX/Zotero에서 가져온 경우)
+    pub citation: Option<Citation>,
+    /// 페이지 메타데이터 (스크랩한 경우)
+    pub metadata: Option<PageMetadata>,
+    /// 수집 출처 (기록 이전에 수집한 문서는 None)
+    pub provenance: Option<Provenance>,
+}
+
+impl Document {
+    /// 만료 여부
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|t| t <= Utc::now())
+    }
+}
+
+/// 문서 요약 (목록 조회용)
+///
+/// 본문(`content`)을 읽지 않고 길이만 가져오므로
+/// 대량 문서 목록 조회 시 메모리 사용량이 작습니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentSummary {
+    pub id: i64,
+    pub url: String,
+    pub title: Option<String>,
+    pub framework: Option<String>,
+    /// 본문 길이 (문자 수)
+    pub content_length: usize,
+    pub created_at: DateTime<Utc>,
+    /// 만료 시각 (지나면 검색에서 제외)
+    pub expires_at: Option<DateTime<Utc>>,
+    /// 고정(즐겨찾기) 여부
+    pub pinned: bool,
+    /// 인용 정보 (BibTeX/Zotero에서 가져온 경우)
+    pub citation: Option<Citation>,
+    /// 게시 시각 (페이지 메타데이터)
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+impl DocumentSummary {
+    /// 만료 여부
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|t| t <= Utc::now())
+    }
 }
 
 /// 새 문서 입력용 구조체
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct NewDocument {
     pub url: String,
     pub title: Option<String>,
     pub content: String,
     pub framework: Option<String>,
+    /// 만료 시각 (임시 메모 등, 지나면 검색에서 제외되고 compact 시 삭제)
+    pub expires_at: Option<DateTime<Utc>>,
+    /// 인용 정보 (None이면 같은 URL의 기존 인용 정보 유지)
+    pub citation: Option<Citation>,
+    /// 페이지 메타데이터 (None이면 같은 URL의 기존 메타데이터 유지)
+    pub metadata: Option<PageMetadata>,
+    /// 수집 출처 (None이면 같은 URL의 기존 출처 유지)
+    pub provenance: Option<Provenance>,
 }
 
 /// FTS5 검색 결과
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct FtsSearchResult {
     pub doc_id: i64,
     pub title: Option<String>,
@@ -73,6 +181,8 @@ pub struct StoreStats {
 /// Knowledge Store - 동기 지식 저장소
 ///
 /// SQLite 기반 문서 저장 및 FTS5 키워드 검색을 제공합니다.
+/// 복제 시 같은 연결을 공유합니다 (`spawn_blocking` 등으로 넘길 때 사용).
+#[derive(Clone)]
 pub struct KnowledgeStore {
     conn: Arc<Mutex<Connection>>,
     db_path: PathBuf,
@@ -100,6 +210,18 @@ impl KnowledgeStore {
         )
         .context("Failed to open SQLite database")?;
 
+        // 손상된 DB는 쿼리 도중이 아니라 열 때 실패
+        let problems = sqlite_problems(&conn);
+        if !problems.is_empty() {
+            return Err(StoreCorrupted {
+                path: path.to_path_buf(),
+                detail: problems.join("; "),
+            }
+            .into());
+        }
+
+        conn.set_prepared_statement_cache_capacity(STATEMENT_CACHE_CAPACITY);
+
         let store = Self {
             conn: Arc::new(Mutex::new(conn)),
             db_path: path.to_path_buf(),
@@ -144,6 +266,15 @@ impl KnowledgeStore {
         )
         .context("Failed to create documents table")?;
 
+        // 추가 컬럼 (기존 DB 마이그레이션)
+        ensure_column(&conn, "expires_at", "TEXT")?;
+        ensure_column(&conn, "pinned", "INTEGER NOT NULL DEFAULT 0")?;
+        ensure_column(&conn, "citation", "TEXT")?;
+        ensure_column(&conn, "metadata", "TEXT")?;
+        ensure_column(&conn, "published_at", "TEXT")?;
+        ensure_column(&conn, "provenance", "TEXT")?;
+        ensure_column(&conn, "chunker", "TEXT")?;
+
         // URL 인덱스
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_documents_url ON documents(url)",
@@ -158,6 +289,99 @@ impl KnowledgeStore {
         )
         .context("Failed to create framework index")?;
 
+        // 게시 시각 인덱스 (list --since)
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_documents_published ON documents(published_at)",
+            [],
+        )
+        .context("Failed to create published_at index")?;
+
+        // 만료 인덱스
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_documents_expires_at ON documents(expires_at)",
+            [],
+        )
+        .context("Failed to create expires_at index")?;
+
+        // 검색 피드백 (feedback 명령어)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS feedback (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                query_id TEXT NOT NULL,
+                doc_id INTEGER NOT NULL,
+                relevant INTEGER NOT NULL,
+                created_at TEXT NOT NULL
+            )",
+            [],
+        )
+        .context("Failed to create feedback table")?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_feedback_doc_id ON feedback(doc_id)",
+            [],
+        )
+        .context("Failed to create feedback index")?;
+
+        // 소스 코드 문서의 청크별 원본 줄 범위 (query --format quickfix)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS chunk_lines (
+                doc_id INTEGER NOT NULL,
+                chunk_index INTEGER NOT NULL,
+                start_line INTEGER NOT NULL,
+                end_line INTEGER NOT NULL,
+                PRIMARY KEY (doc_id, chunk_index)
+            )",
+            [],
+        )
+        .context("Failed to create chunk_lines table")?;
+
+        // 청크 종류 (설명문/코드, 코드 언어) - query --only-code/--only-prose
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS chunk_kinds (
+                doc_id INTEGER NOT NULL,
+                chunk_index INTEGER NOT NULL,
+                kind TEXT NOT NULL,
+                language TEXT,
+                PRIMARY KEY (doc_id, chunk_index)
+            )",
+            [],
+        )
+        .context("Failed to create chunk_kinds table")?;
+
+        // 청크 지문 (정규화한 텍스트 해시) - 상용구 청크 판별용 빈도 색인
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS chunk_fingerprints (
+                doc_id INTEGER NOT NULL,
+                chunk_index INTEGER NOT NULL,
+                fingerprint TEXT NOT NULL,
+                PRIMARY KEY (doc_id, chunk_index)
+            )",
+            [],
+        )
+        .context("Failed to create chunk_fingerprints table")?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_chunk_fingerprints ON chunk_fingerprints(fingerprint)",
+            [],
+        )
+        .context("Failed to create chunk_fingerprints index")?;
+
+        // 청크 벡터를 만든 임베딩 모델 (`EmbeddingProvider::model_version`)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS chunk_models (
+                doc_id INTEGER NOT NULL,
+                chunk_index INTEGER NOT NULL,
+                model TEXT NOT NULL,
+                PRIMARY KEY (doc_id, chunk_index)
+            )",
+            [],
+        )
+        .context("Failed to create chunk_models table")?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_chunk_models ON chunk_models(model)",
+            [],
+        )
+        .context("Failed to create chunk_models index")?;
+
         // FTS5 가상 테이블 (키워드 검색용)
         // source: https://www.sqlite.org/fts5.html
         let fts_result = conn.execute(
@@ -200,143 +424,694 @@ impl KnowledgeStore {
         Ok(())
     }
 
-    /// 문서 저장 (URL이 같으면 업데이트)
+    /// 문서 저장 (URL이 같으면 같은 ID로 업데이트, 제목과 본문은 정규화하여 저장)
+    ///
+    /// 다시 수집해도 ID가 바뀌지 않으므로 피드백과 청크 기록은 그대로 남습니다.
+    /// 청크 기록은 새 본문으로 청킹한 호출자가 교체합니다.
     pub fn add_document(&self, doc: NewDocument) -> Result<i64> {
         let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
         let now = Utc::now().to_rfc3339();
 
-        conn.execute(
-            "INSERT OR REPLACE INTO documents (url, title, content, framework, created_at)
-             VALUES (?1, ?2, ?3, ?4, ?5)",
-            params![doc.url, doc.title, doc.content, doc.framework, now],
-        )
-        .context("Failed to insert document")?;
+        let id = conn
+            .query_row(
+                INSERT_DOCUMENT_SQL,
+                params![
+                    doc.url,
+                    doc.title.as_deref().map(normalize_document),
+                    normalize_document(&doc.content),
+                    doc.framework,
+                    now,
+                    doc.expires_at.map(format_timestamp),
+                    citation_json(doc.citation.as_ref())?,
+                    metadata_json(doc.metadata.as_ref())?,
+                    published_at(doc.metadata.as_ref()),
+                    provenance_json(doc.provenance.as_ref())?
+                ],
+                |row| row.get(0),
+            )
+            .context("Failed to insert document")?;
 
-        let id = conn.last_insert_rowid();
         tracing::info!("Added document: {} (id={})", doc.url, id);
 
         Ok(id)
     }
 
+    /// 여러 문서를 하나의 트랜잭션으로 저장 (URL이 같으면 업데이트)
+    ///
+    /// 폴더 수집처럼 문서가 많을 때 문서마다 커밋하지 않도록 묶어서 저장합니다.
+    /// 하나라도 실패하면 전체가 롤백됩니다.
+    ///
+    /// # Returns
+    /// 입력 순서와 같은 순서의 문서 ID 목록
+    pub fn add_documents(&self, docs: &[NewDocument]) -> Result<Vec<i64>> {
+        if docs.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+        let now = Utc::now().to_rfc3339();
+
+        let tx = conn.transaction().context("Failed to begin transaction")?;
+        let mut ids = Vec::with_capacity(docs.len());
+        {
+            let mut stmt = tx.prepare_cached(INSERT_DOCUMENT_SQL)?;
+
+            for doc in docs {
+                let id: i64 = stmt
+                    .query_row(
+                        params![
+                            doc.url,
+                            doc.title.as_deref().map(normalize_document),
+                            normalize_document(&doc.content),
+                            doc.framework,
+                            now,
+                            doc.expires_at.map(format_timestamp),
+                            citation_json(doc.citation.as_ref())?,
+                            metadata_json(doc.metadata.as_ref())?,
+                            published_at(doc.metadata.as_ref()),
+                            provenance_json(doc.provenance.as_ref())?
+                        ],
+                        |row| row.get(0),
+                    )
+                    .with_context(|| format!("Failed to insert document: {}", doc.url))?;
+                ids.push(id);
+            }
+        }
+        tx.commit().context("Failed to commit documents")?;
+
+        tracing::info!("Added {} documents in one transaction", ids.len());
+        Ok(ids)
+    }
+
+    /// 다시 수집하기 전의 문서 행으로 되돌리기 (같은 ID, 색인 실패 시 롤백용)
+    ///
+    /// # Returns
+    /// 문서가 있으면 true
+    pub fn restore_document(&self, doc: &Document) -> Result<bool> {
+        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let rows = conn
+            .execute(
+                "UPDATE documents SET title = ?2, content = ?3, framework = ?4, created_at = ?5,
+                     expires_at = ?6, pinned = ?7, citation = ?8, metadata = ?9,
+                     published_at = ?10, provenance = ?11
+                 WHERE id = ?1",
+                params![
+                    doc.id,
+                    doc.title,
+                    doc.content,
+                    doc.framework,
+                    doc.created_at.to_rfc3339(),
+                    doc.expires_at.map(format_timestamp),
+                    doc.pinned,
+                    citation_json(doc.citation.as_ref())?,
+                    metadata_json(doc.metadata.as_ref())?,
+                    published_at(doc.metadata.as_ref()),
+                    provenance_json(doc.provenance.as_ref())?
+                ],
+            )
+            .context("Failed to restore document")?;
+
+        Ok(rows > 0)
+    }
+
     /// ID로 문서 조회
     pub fn get_document(&self, id: i64) -> Result<Option<Document>> {
         let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
 
-        let mut stmt = conn.prepare(
-            "SELECT id, url, title, content, framework, created_at FROM documents WHERE id = ?1",
-        )?;
+        let mut stmt = conn.prepare_cached(&format!(
+            "SELECT {} FROM documents WHERE id = ?1",
+            DOCUMENT_COLUMNS
+        ))?;
 
-        let doc = stmt
-            .query_row(params![id], |row| {
-                Ok(Document {
-                    id: row.get(0)?,
-                    url: row.get(1)?,
-                    title: row.get(2)?,
-                    content: row.get(3)?,
-                    framework: row.get(4)?,
-                    created_at: parse_datetime(row.get::<_, String>(5)?),
-                })
-            })
-            .ok();
+        let doc = stmt.query_row(params![id], row_to_document).ok();
 
         Ok(doc)
     }
 
+    /// 여러 문서의 요약을 한 번의 쿼리로 조회
+    ///
+    /// 검색 결과에 URL/제목을 붙일 때 문서마다 조회하지 않도록
+    /// `WHERE id IN (...)` 한 번으로 가져옵니다. 없는 ID는 결과에서 빠집니다.
+    pub fn get_summaries(&self, ids: &[i64]) -> Result<HashMap<i64, DocumentSummary>> {
+        if ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let placeholders = vec!["?"; ids.len()].join(", ");
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM documents WHERE id IN ({})",
+            SUMMARY_COLUMNS, placeholders
+        ))?;
+
+        let summaries = stmt
+            .query_map(params_from_iter(ids.iter()), row_to_summary)?
+            .filter_map(|r| r.ok())
+            .map(|summary| (summary.id, summary))
+            .collect();
+
+        Ok(summaries)
+    }
+
     /// URL로 문서 조회
     pub fn get_by_url(&self, url: &str) -> Result<Option<Document>> {
         let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
 
-        let mut stmt = conn.prepare(
-            "SELECT id, url, title, content, framework, created_at FROM documents WHERE url = ?1",
-        )?;
+        let mut stmt = conn.prepare_cached(&format!(
+            "SELECT {} FROM documents WHERE url = ?1",
+            DOCUMENT_COLUMNS
+        ))?;
 
-        let doc = stmt
-            .query_row(params![url], |row| {
-                Ok(Document {
-                    id: row.get(0)?,
-                    url: row.get(1)?,
-                    title: row.get(2)?,
-                    content: row.get(3)?,
-                    framework: row.get(4)?,
-                    created_at: parse_datetime(row.get::<_, String>(5)?),
-                })
-            })
-            .ok();
+        let doc = stmt.query_row(params![url], row_to_document).ok();
 
         Ok(doc)
     }
 
+    /// 원본 URL에서 나온 문서 ID 목록 (`url` 자체와 `url#...` 파생 문서)
+    pub fn ids_for_source(&self, url: &str) -> Result<Vec<i64>> {
+        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let mut stmt = conn.prepare_cached(
+            "SELECT id FROM documents
+             WHERE url = ?1 OR substr(url, 1, length(?1) + 1) = ?1 || '#'
+             ORDER BY id",
+        )?;
+        let ids = stmt
+            .query_map(params![url], |row| row.get(0))?
+            .collect::<std::result::Result<Vec<i64>, _>>()?;
+
+        Ok(ids)
+    }
+
+    /// 모든 문서 ID (오름차순)
+    pub fn document_ids(&self) -> Result<Vec<i64>> {
+        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let mut stmt = conn.prepare_cached("SELECT id FROM documents ORDER BY id")?;
+        let ids = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<std::result::Result<Vec<i64>, _>>()?;
+
+        Ok(ids)
+    }
+
     /// 문서 목록 조회
-    pub fn list_documents(&self, limit: usize, framework: Option<&str>) -> Result<Vec<Document>> {
+    ///
+    /// 필요한 컬럼만 조회(projection)하며 본문은 길이만 반환합니다.
+    /// `published_since`를 지정하면 게시 시각이 그 이후인 문서만 반환합니다
+    /// (게시 시각이 없는 문서는 제외).
+    pub fn list_documents(
+        &self,
+        limit: usize,
+        framework: Option<&str>,
+        published_since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<DocumentSummary>> {
         let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
 
-        let docs: Vec<Document> = if let Some(fw) = framework {
-            let mut stmt = conn.prepare(
-                "SELECT id, url, title, content, framework, created_at FROM documents
-                 WHERE framework = ?1
-                 ORDER BY created_at DESC
-                 LIMIT ?2",
+        let mut stmt = conn.prepare_cached(&format!(
+            "SELECT {} FROM documents
+             WHERE (?1 IS NULL OR framework = ?1
+                    OR substr(framework, 1, length(?1) + 1)
+                       = ?1 || CASE WHEN instr(?1, '@') > 1 THEN '.' ELSE '@' END)
+               AND (?3 IS NULL OR published_at >= ?3)
+             ORDER BY created_at DESC
+             LIMIT ?2",
+            SUMMARY_COLUMNS
+        ))?;
+
+        let since = published_since.map(format_timestamp);
+        let docs = stmt
+            .query_map(params![framework, limit as i64, since], row_to_summary)?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(docs)
+    }
+
+    /// 고정(즐겨찾기) 설정/해제
+    ///
+    /// 고정된 문서는 ask 컨텍스트에 항상 포함될 수 있습니다.
+    ///
+    /// # Returns
+    /// 문서가 존재하면 true
+    pub fn set_pinned(&self, id: i64, pinned: bool) -> Result<bool> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let rows = conn.execute(
+            "UPDATE documents SET pinned = ?2 WHERE id = ?1",
+            params![id, pinned],
+        )?;
+
+        Ok(rows > 0)
+    }
+
+    /// 인용 정보 설정 (None이면 삭제)
+    ///
+    /// # Returns
+    /// 문서가 있으면 true
+    pub fn set_citation(&self, id: i64, citation: Option<&Citation>) -> Result<bool> {
+        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let rows = conn.execute(
+            "UPDATE documents SET citation = ?2 WHERE id = ?1",
+            params![id, citation_json(citation)?],
+        )?;
+
+        Ok(rows > 0)
+    }
+
+    /// 고정된 문서 목록 (만료 문서 제외)
+    pub fn list_pinned(&self) -> Result<Vec<DocumentSummary>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let mut stmt = conn.prepare_cached(&format!(
+            "SELECT {} FROM documents
+             WHERE pinned = 1 AND (expires_at IS NULL OR expires_at > ?1)
+             ORDER BY created_at DESC",
+            SUMMARY_COLUMNS
+        ))?;
+
+        let now = format_timestamp(Utc::now());
+        let docs = stmt
+            .query_map(params![now], row_to_summary)?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(docs)
+    }
+
+    /// 문서 삭제
+    pub fn delete_document(&self, id: i64) -> Result<bool> {
+        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let rows = conn.execute("DELETE FROM documents WHERE id = ?1", params![id])?;
+        conn.execute("DELETE FROM feedback WHERE doc_id = ?1", params![id])?;
+        for table in CHUNK_TABLES {
+            conn.execute(
+                &format!("DELETE FROM {} WHERE doc_id = ?1", table),
+                params![id],
             )?;
+        }
 
-            let rows = stmt.query_map(params![fw, limit as i64], |row| {
-                Ok(Document {
-                    id: row.get(0)?,
-                    url: row.get(1)?,
-                    title: row.get(2)?,
-                    content: row.get(3)?,
-                    framework: row.get(4)?,
-                    created_at: parse_datetime(row.get::<_, String>(5)?),
-                })
-            })?;
+        Ok(rows > 0)
+    }
 
-            rows.filter_map(|r| r.ok()).collect()
-        } else {
-            let mut stmt = conn.prepare(
-                "SELECT id, url, title, content, framework, created_at FROM documents
-                 ORDER BY created_at DESC
-                 LIMIT ?1",
+    /// 문서의 청크별 원본 줄 범위 저장 (기존 범위는 교체)
+    pub fn set_chunk_lines(&self, doc_id: i64, ranges: &[(usize, usize)]) -> Result<()> {
+        let mut conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+        let tx = conn.transaction().context("Failed to begin transaction")?;
+
+        tx.execute("DELETE FROM chunk_lines WHERE doc_id = ?1", params![doc_id])?;
+        {
+            let mut stmt = tx.prepare_cached(
+                "INSERT INTO chunk_lines (doc_id, chunk_index, start_line, end_line)
+                 VALUES (?1, ?2, ?3, ?4)",
             )?;
+            for (i, (start, end)) in ranges.iter().enumerate() {
+                stmt.execute(params![doc_id, i as i64, *start as i64, *end as i64])?;
+            }
+        }
 
-            let rows = stmt.query_map(params![limit as i64], |row| {
-                Ok(Document {
-                    id: row.get(0)?,
-                    url: row.get(1)?,
-                    title: row.get(2)?,
-                    content: row.get(3)?,
-                    framework: row.get(4)?,
-                    created_at: parse_datetime(row.get::<_, String>(5)?),
-                })
-            })?;
+        tx.commit()?;
+        Ok(())
+    }
 
-            rows.filter_map(|r| r.ok()).collect()
-        };
+    /// 문서의 청크별 종류 저장 (기존 기록은 교체)
+    pub fn set_chunk_kinds(&self, doc_id: i64, classes: &[ChunkClass]) -> Result<()> {
+        let mut conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+        let tx = conn.transaction().context("Failed to begin transaction")?;
+
+        tx.execute("DELETE FROM chunk_kinds WHERE doc_id = ?1", params![doc_id])?;
+        {
+            let mut stmt = tx.prepare_cached(
+                "INSERT INTO chunk_kinds (doc_id, chunk_index, kind, language)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for (i, class) in classes.iter().enumerate() {
+                stmt.execute(params![
+                    doc_id,
+                    i as i64,
+                    class.kind.as_str(),
+                    class.language
+                ])?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// 여러 문서의 청크 종류 ((문서 ID, 청크 순번) → 종류, 기록이 없는 청크는 빠짐)
+    pub fn chunk_kinds(&self, doc_ids: &[i64]) -> Result<HashMap<(i64, i32), ChunkClass>> {
+        if doc_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let placeholders = vec!["?"; doc_ids.len()].join(", ");
+        let mut stmt = conn.prepare(&format!(
+            "SELECT doc_id, chunk_index, kind, language FROM chunk_kinds WHERE doc_id IN ({})",
+            placeholders
+        ))?;
+
+        let kinds = stmt
+            .query_map(params_from_iter(doc_ids.iter()), |row| {
+                Ok((
+                    (row.get::<_, i64>(0)?, row.get::<_, i32>(1)?),
+                    row.get::<_, String>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                ))
+            })?
+            .filter_map(|r| r.ok())
+            .filter_map(|(key, kind, language)| {
+                let kind = ChunkKind::parse(&kind)?;
+                Some((key, ChunkClass { kind, language }))
+            })
+            .collect();
+
+        Ok(kinds)
+    }
+
+    /// 문서의 청크별 지문 저장 (기존 기록은 교체, 지문이 없는 청크는 건너뜀)
+    pub fn set_chunk_fingerprints(
+        &self,
+        doc_id: i64,
+        fingerprints: &[Option<String>],
+    ) -> Result<()> {
+        let mut conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+        let tx = conn.transaction().context("Failed to begin transaction")?;
+
+        tx.execute("DELETE FROM chunk_fingerprints WHERE doc_id = ?1", params![doc_id])?;
+        {
+            let mut stmt = tx.prepare_cached(
+                "INSERT INTO chunk_fingerprints (doc_id, chunk_index, fingerprint)
+                 VALUES (?1, ?2, ?3)",
+            )?;
+            for (i, fingerprint) in fingerprints.iter().enumerate() {
+                if let Some(fingerprint) = fingerprint {
+                    stmt.execute(params![doc_id, i as i64, fingerprint])?;
+                }
+            }
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// 지문별로 그 지문의 청크가 있는 문서 수 (기록이 없는 지문은 빠짐)
+    pub fn fingerprint_doc_counts(
+        &self,
+        fingerprints: &[String],
+    ) -> Result<HashMap<String, usize>> {
+        if fingerprints.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let placeholders = vec!["?"; fingerprints.len()].join(", ");
+        let mut stmt = conn.prepare(&format!(
+            "SELECT fingerprint, COUNT(DISTINCT doc_id) FROM chunk_fingerprints
+             WHERE fingerprint IN ({}) AND doc_id IN (SELECT id FROM documents)
+             GROUP BY fingerprint",
+            placeholders
+        ))?;
+
+        let counts = stmt
+            .query_map(params_from_iter(fingerprints.iter()), |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as usize))
+            })?
+            .collect::<std::result::Result<HashMap<_, _>, _>>()?;
+
+        Ok(counts)
+    }
+
+    /// 없는 문서 ID에 남은 청크 부가 기록 삭제 (삭제한 행 수)
+    pub fn delete_orphan_chunk_rows(&self) -> Result<usize> {
+        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let mut removed = 0;
+        for table in CHUNK_TABLES {
+            removed += conn.execute(
+                &format!(
+                    "DELETE FROM {} WHERE doc_id NOT IN (SELECT id FROM documents)",
+                    table
+                ),
+                [],
+            )?;
+        }
+
+        Ok(removed)
+    }
+
+    /// 청크 지문 기록이 없는 문서 ID
+    pub fn ids_without_fingerprints(&self) -> Result<Vec<i64>> {
+        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let mut stmt = conn.prepare_cached(
+            "SELECT id FROM documents
+             WHERE id NOT IN (SELECT DISTINCT doc_id FROM chunk_fingerprints)
+             ORDER BY id",
+        )?;
+        let ids = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<std::result::Result<Vec<i64>, _>>()?;
+
+        Ok(ids)
+    }
+
+    /// 청크 벡터의 임베딩 모델 기록 (`doc_ids` 문서의 기존 기록은 교체)
+    ///
+    /// 청크가 하나도 없는 문서는 `NO_CHUNKS_INDEX` 행으로 현재 모델을 표시하여
+    /// `reembed --outdated-model`이 매번 다시 고르지 않게 합니다.
+    pub fn set_chunk_models(
+        &self,
+        doc_ids: &[i64],
+        chunks: &[(i64, i32)],
+        model: &str,
+    ) -> Result<()> {
+        let mut conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+        let tx = conn.transaction().context("Failed to begin transaction")?;
+        {
+            let mut stmt = tx.prepare_cached("DELETE FROM chunk_models WHERE doc_id = ?1")?;
+            for &id in doc_ids {
+                stmt.execute(params![id])?;
+            }
+            let mut stmt = tx.prepare_cached(
+                "INSERT OR REPLACE INTO chunk_models (doc_id, chunk_index, model)
+                 VALUES (?1, ?2, ?3)",
+            )?;
+            for &(doc_id, chunk_index) in chunks {
+                stmt.execute(params![doc_id, chunk_index, model])?;
+            }
+            for &id in doc_ids {
+                if !chunks.iter().any(|&(doc_id, _)| doc_id == id) {
+                    stmt.execute(params![id, NO_CHUNKS_INDEX, model])?;
+                }
+            }
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// 다른 임베딩 모델로 만든 청크 벡터가 있거나 모델 기록이 없는 문서 ID
+    pub fn ids_with_other_model(&self, model: &str) -> Result<Vec<i64>> {
+        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let mut stmt = conn.prepare_cached(
+            "SELECT id FROM documents
+             WHERE id IN (SELECT doc_id FROM chunk_models WHERE model != ?1)
+                OR id NOT IN (SELECT DISTINCT doc_id FROM chunk_models)
+             ORDER BY id",
+        )?;
+        let ids = stmt
+            .query_map(params![model], |row| row.get(0))?
+            .collect::<std::result::Result<Vec<i64>, _>>()?;
+
+        Ok(ids)
+    }
+
+    /// 임베딩 모델별 청크 수 (많은 순, 있는 문서의 청크만)
+    pub fn chunk_model_counts(&self) -> Result<Vec<(String, usize)>> {
+        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let mut stmt = conn.prepare_cached(
+            "SELECT m.model, COUNT(*) FROM chunk_models m
+             JOIN documents d ON d.id = m.doc_id
+             WHERE m.chunk_index != ?1
+             GROUP BY m.model
+             ORDER BY COUNT(*) DESC, m.model",
+        )?;
+        let counts = stmt
+            .query_map(params![NO_CHUNKS_INDEX], |row| {
+                Ok((row.get(0)?, row.get::<_, i64>(1)? as usize))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(counts)
+    }
+
+    /// 문서를 청킹한 청커 기록 (`Chunker::fingerprint`)
+    pub fn set_chunker(&self, doc_ids: &[i64], fingerprint: &str) -> Result<()> {
+        let mut conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+        let tx = conn.transaction().context("Failed to begin transaction")?;
+        {
+            let mut stmt = tx.prepare_cached("UPDATE documents SET chunker = ?2 WHERE id = ?1")?;
+            for &id in doc_ids {
+                stmt.execute(params![id, fingerprint])?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// 다른 청커(또는 설정)로 청킹된 문서 ID
+    ///
+    /// 청커 기록이 없는 문서(기록 이전에 수집)도 어떤 설정이었는지 알 수 없으므로 포함합니다.
+    pub fn ids_with_other_chunker(&self, fingerprint: &str) -> Result<Vec<i64>> {
+        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let mut stmt = conn.prepare_cached(
+            "SELECT id FROM documents WHERE chunker IS NULL OR chunker != ?1 ORDER BY id",
+        )?;
+        let ids = stmt
+            .query_map(params![fingerprint], |row| row.get(0))?
+            .collect::<std::result::Result<Vec<i64>, _>>()?;
+
+        Ok(ids)
+    }
+
+    /// 청크의 원본 줄 범위 (시작 줄, 끝 줄)
+    pub fn chunk_lines(&self, doc_id: i64, chunk_index: i32) -> Result<Option<(usize, usize)>> {
+        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let range = conn
+            .query_row(
+                "SELECT start_line, end_line FROM chunk_lines
+                 WHERE doc_id = ?1 AND chunk_index = ?2",
+                params![doc_id, chunk_index],
+                |row| Ok((row.get::<_, i64>(0)? as usize, row.get::<_, i64>(1)? as usize)),
+            )
+            .ok();
+
+        Ok(range)
+    }
+
+    /// 여러 청크의 줄 범위 ((문서 ID, 청크 순번) → 줄 범위, 기록이 없는 청크는 빠짐)
+    pub fn chunk_lines_batch(
+        &self,
+        keys: &[(i64, i32)],
+    ) -> Result<HashMap<(i64, i32), (usize, usize)>> {
+        if keys.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let mut doc_ids: Vec<i64> = keys.iter().map(|(doc_id, _)| *doc_id).collect();
+        doc_ids.sort_unstable();
+        doc_ids.dedup();
+
+        let placeholders = vec!["?"; doc_ids.len()].join(", ");
+        let mut stmt = conn.prepare(&format!(
+            "SELECT doc_id, chunk_index, start_line, end_line FROM chunk_lines
+             WHERE doc_id IN ({})",
+            placeholders
+        ))?;
+
+        let ranges = stmt
+            .query_map(params_from_iter(doc_ids.iter()), |row| {
+                Ok((
+                    (row.get::<_, i64>(0)?, row.get::<_, i32>(1)?),
+                    (
+                        row.get::<_, i64>(2)? as usize,
+                        row.get::<_, i64>(3)? as usize,
+                    ),
+                ))
+            })?
+            .filter_map(|r| r.ok())
+            .filter(|(key, _)| keys.contains(key))
+            .collect();
+
+        Ok(ranges)
+    }
+
+    /// 검색 피드백 저장 (관련/무관 판정)
+    ///
+    /// # Returns
+    /// 문서가 있으면 true
+    pub fn add_feedback(&self, query_id: &str, doc_id: i64, relevant: bool) -> Result<bool> {
+        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let rows = conn.execute(
+            "INSERT INTO feedback (query_id, doc_id, relevant, created_at)
+             SELECT ?1, id, ?3, ?4 FROM documents WHERE id = ?2",
+            params![query_id, doc_id, relevant, format_timestamp(Utc::now())],
+        )?;
 
-        Ok(docs)
+        Ok(rows > 0)
     }
 
-    /// 문서 삭제
-    pub fn delete_document(&self, id: i64) -> Result<bool> {
+    /// 문서별 순 판정 (같은 쿼리 / 다른 쿼리로 나눔)
+    pub fn feedback_votes(&self, query_id: &str) -> Result<HashMap<i64, FeedbackVotes>> {
         let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
 
-        let rows = conn.execute("DELETE FROM documents WHERE id = ?1", params![id])?;
+        let mut stmt = conn.prepare_cached(
+            "SELECT doc_id,
+                    SUM(CASE WHEN query_id = ?1 THEN (CASE WHEN relevant THEN 1 ELSE -1 END) ELSE 0 END),
+                    SUM(CASE WHEN query_id != ?1 THEN (CASE WHEN relevant THEN 1 ELSE -1 END) ELSE 0 END)
+             FROM feedback
+             WHERE doc_id IN (SELECT id FROM documents)
+             GROUP BY doc_id",
+        )?;
 
-        Ok(rows > 0)
+        let votes = stmt
+            .query_map(params![query_id], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    FeedbackVotes {
+                        query: row.get(1)?,
+                        other: row.get(2)?,
+                    },
+                ))
+            })?
+            .collect::<rusqlite::Result<HashMap<_, _>>>()?;
+
+        Ok(votes)
     }
 
     /// FTS5 키워드 검색
     ///
     /// BM25 알고리즘으로 스코어링된 검색 결과를 반환합니다.
+    /// `framework`가 주어지면 해당 프레임워크 문서만 검색합니다
+    /// (`react`는 모든 버전, `react@18`은 18.x만 - `FrameworkTag::matches`와 같은 규칙).
     /// source: https://www.sqlite.org/fts5.html#the_bm25_function
-    pub fn search_fts(&self, query: &str, limit: usize) -> Result<Vec<FtsSearchResult>> {
+    pub fn search_fts(
+        &self,
+        query: &str,
+        limit: usize,
+        framework: Option<&str>,
+    ) -> Result<Vec<FtsSearchResult>> {
         let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
 
-        // FTS5 쿼리 이스케이프
-        let escaped_query = escape_fts5_query(query);
+        // FTS5 쿼리 이스케이프 (본문과 같은 규칙으로 정규화한 뒤)
+        let escaped_query = escape_fts5_query(&normalize_query(query));
         if escaped_query.is_empty() {
             return Ok(vec![]);
         }
 
-        let mut stmt = conn.prepare(
+        let mut stmt = conn.prepare_cached(
             r#"
             SELECT
                 d.id as doc_id,
@@ -346,13 +1121,18 @@ impl KnowledgeStore {
             FROM documents_fts
             JOIN documents d ON d.id = documents_fts.rowid
             WHERE documents_fts MATCH ?1
+              AND (?3 IS NULL OR d.framework = ?3
+                   OR substr(d.framework, 1, length(?3) + 1)
+                      = ?3 || CASE WHEN instr(?3, '@') > 1 THEN '.' ELSE '@' END)
+              AND (d.expires_at IS NULL OR d.expires_at > ?4)
             ORDER BY bm25(documents_fts)
             LIMIT ?2
             "#,
         )?;
 
+        let now = format_timestamp(Utc::now());
         let results = stmt
-            .query_map(params![escaped_query, limit as i64], |row| {
+            .query_map(params![escaped_query, limit as i64, framework, now], |row| {
                 Ok(FtsSearchResult {
                     doc_id: row.get(0)?,
                     title: row.get(1)?,
@@ -372,30 +1152,82 @@ impl KnowledgeStore {
 
         let pattern = format!("%{}%", keyword.to_lowercase());
 
-        let mut stmt = conn.prepare(
-            "SELECT id, url, title, content, framework, created_at FROM documents
-             WHERE LOWER(content) LIKE ?1 OR LOWER(title) LIKE ?1
+        let mut stmt = conn.prepare_cached(&format!(
+            "SELECT {} FROM documents
+             WHERE (LOWER(content) LIKE ?1 OR LOWER(title) LIKE ?1)
+               AND (expires_at IS NULL OR expires_at > ?3)
              ORDER BY created_at DESC
              LIMIT ?2",
-        )?;
+            DOCUMENT_COLUMNS
+        ))?;
 
+        let now = format_timestamp(Utc::now());
         let docs = stmt
-            .query_map(params![pattern, limit as i64], |row| {
-                Ok(Document {
-                    id: row.get(0)?,
-                    url: row.get(1)?,
-                    title: row.get(2)?,
-                    content: row.get(3)?,
-                    framework: row.get(4)?,
-                    created_at: parse_datetime(row.get::<_, String>(5)?),
-                })
-            })?
+            .query_map(params![pattern, limit as i64, now], row_to_document)?
             .filter_map(|r| r.ok())
             .collect();
 
         Ok(docs)
     }
 
+    /// 만료된 문서 삭제
+    ///
+    /// # Returns
+    /// 삭제된 문서 ID 목록 (벡터 정리용)
+    pub fn delete_expired(&self) -> Result<Vec<i64>> {
+        let mut conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+        let now = format_timestamp(Utc::now());
+
+        let tx = conn.transaction().context("Failed to begin transaction")?;
+        let ids: Vec<i64> = {
+            let mut stmt = tx.prepare(
+                "SELECT id FROM documents WHERE expires_at IS NOT NULL AND expires_at <= ?1",
+            )?;
+            let ids = stmt
+                .query_map(params![now], |row| row.get(0))?
+                .filter_map(|r| r.ok())
+                .collect();
+            ids
+        };
+        tx.execute(
+            "DELETE FROM documents WHERE expires_at IS NOT NULL AND expires_at <= ?1",
+            params![now],
+        )
+        .context("Failed to delete expired documents")?;
+        tx.commit().context("Failed to commit expired document cleanup")?;
+
+        if !ids.is_empty() {
+            tracing::info!("Deleted {} expired documents", ids.len());
+        }
+        Ok(ids)
+    }
+
+    /// DB 최적화 (FTS5 세그먼트 병합 + VACUUM)
+    pub fn optimize(&self) -> Result<()> {
+        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        // FTS5가 없는 환경에서는 무시
+        let _ = conn.execute("INSERT INTO documents_fts(documents_fts) VALUES('optimize')", []);
+        conn.execute_batch("VACUUM").context("Failed to vacuum database")?;
+
+        Ok(())
+    }
+
+    /// 일관된 스냅샷으로 DB 백업 (`VACUUM INTO`)
+    ///
+    /// 대상 파일이 이미 있으면 실패합니다.
+    pub fn backup_to(&self, dest: &Path) -> Result<()> {
+        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+
+        let dest_str = dest
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Non UTF-8 backup path: {:?}", dest))?;
+        conn.execute("VACUUM INTO ?1", params![dest_str])
+            .with_context(|| format!("Failed to back up database to {:?}", dest))?;
+
+        Ok(())
+    }
+
     /// 저장소 통계
     pub fn stats(&self) -> Result<StoreStats> {
         let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
@@ -419,24 +1251,77 @@ impl KnowledgeStore {
         })
     }
 
+    /// 저장된 제목/본문을 현재 정규화 규칙(`normalize_document`)으로 다시 쓰기
+    ///
+    /// 정규화 이전에 수집한 문서도 새로 수집한 문서와 같은 텍스트로 검색되도록 합니다.
+    /// `ids`가 None이면 전체 문서가 대상입니다. 본문이 바뀐 문서는 청커 기록을 지워
+    /// `reembed --changed-chunker`가 다시 청킹/임베딩하도록 하며, FTS5 인덱스는
+    /// UPDATE 트리거로 함께 갱신됩니다.
+    ///
+    /// # Returns
+    /// 다시 쓴 문서 수
+    pub fn normalize_stored_text(&self, ids: Option<&[i64]>) -> Result<usize> {
+        let filter = match ids {
+            Some([]) => return Ok(0),
+            Some(ids) => format!("WHERE id IN ({})", vec!["?"; ids.len()].join(", ")),
+            None => String::new(),
+        };
+
+        let mut conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+        let tx = conn.transaction().context("Failed to begin transaction")?;
+
+        // 바뀌는 문서만 메모리에 보관 (전체 본문을 한꺼번에 읽지 않음)
+        let mut changed = Vec::new();
+        {
+            let mut stmt = tx.prepare(&format!(
+                "SELECT id, title, content FROM documents {}",
+                filter
+            ))?;
+            let mut rows = stmt.query(params_from_iter(ids.unwrap_or_default()))?;
+            while let Some(row) = rows.next()? {
+                let id: i64 = row.get(0)?;
+                let title: Option<String> = row.get(1)?;
+                let content: String = row.get(2)?;
+                let new_title = title.as_deref().map(normalize_document);
+                let new_content = normalize_document(&content);
+                let content_changed = new_content != content;
+                if content_changed || new_title != title {
+                    changed.push((id, new_title, new_content, content_changed));
+                }
+            }
+        }
+
+        for (id, title, content, content_changed) in &changed {
+            if *content_changed {
+                tx.execute(
+                    "UPDATE documents SET title = ?2, content = ?3, chunker = NULL WHERE id = ?1",
+                    params![id, title, content],
+                )?;
+            } else {
+                tx.execute(
+                    "UPDATE documents SET title = ?2 WHERE id = ?1",
+                    params![id, title],
+                )?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(changed.len())
+    }
+
     /// FTS5 인덱스 리빌드
     ///
     /// 트리거가 동작하지 않는 경우 수동으로 인덱스를 재생성합니다.
     pub fn rebuild_fts_index(&self) -> Result<usize> {
         let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
 
-        // 기존 인덱스 삭제
-        conn.execute("DELETE FROM documents_fts", [])?;
+        // 본문 테이블에서 인덱스 전체 재생성 (인덱스가 손상되어도 동작)
+        // source: https://www.sqlite.org/fts5.html#the_rebuild_command
+        conn.execute("INSERT INTO documents_fts(documents_fts) VALUES('rebuild')", [])
+            .context("Failed to rebuild FTS5 index")?;
 
-        // 재삽입
-        let count = conn.execute(
-            r#"
-            INSERT INTO documents_fts(rowid, title, content)
-            SELECT id, COALESCE(title, ''), content
-            FROM documents
-            "#,
-            [],
-        )?;
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM documents", [], |row| row.get(0))?;
+        let count = count as usize;
 
         tracing::info!("Rebuilt FTS5 index with {} documents", count);
         Ok(count)
@@ -454,6 +1339,108 @@ fn parse_datetime(s: String) -> DateTime<Utc> {
         .unwrap_or_else(|_| Utc::now())
 }
 
+/// 비교용 고정 형식 타임스탬프 (초 단위, `Z` 접미사)
+///
+/// 문자열 비교가 시간 순서와 일치하도록 항상 같은 길이로 저장합니다.
+fn format_timestamp(dt: DateTime<Utc>) -> String {
+    dt.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// 선택적 RFC3339 문자열 파싱
+fn parse_optional_datetime(s: Option<String>) -> Option<DateTime<Utc>> {
+    s.and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// `DOCUMENT_COLUMNS` 행을 Document로 변환
+fn row_to_document(row: &rusqlite::Row<'_>) -> rusqlite::Result<Document> {
+    Ok(Document {
+        id: row.get(0)?,
+        url: row.get(1)?,
+        title: row.get(2)?,
+        content: row.get(3)?,
+        framework: row.get(4)?,
+        created_at: parse_datetime(row.get::<_, String>(5)?),
+        expires_at: parse_optional_datetime(row.get(6)?),
+        pinned: row.get(7)?,
+        citation: parse_citation(row.get(8)?),
+        metadata: row
+            .get::<_, Option<String>>(9)?
+            .and_then(|j| serde_json::from_str(&j).ok()),
+        provenance: row
+            .get::<_, Option<String>>(10)?
+            .and_then(|j| serde_json::from_str(&j).ok()),
+    })
+}
+
+/// `SUMMARY_COLUMNS` 행을 DocumentSummary로 변환
+fn row_to_summary(row: &rusqlite::Row<'_>) -> rusqlite::Result<DocumentSummary> {
+    Ok(DocumentSummary {
+        id: row.get(0)?,
+        url: row.get(1)?,
+        title: row.get(2)?,
+        framework: row.get(3)?,
+        content_length: row.get::<_, i64>(4)? as usize,
+        created_at: parse_datetime(row.get::<_, String>(5)?),
+        expires_at: parse_optional_datetime(row.get(6)?),
+        pinned: row.get(7)?,
+        citation: parse_citation(row.get(8)?),
+        published_at: parse_optional_datetime(row.get(9)?),
+    })
+}
+
+/// 인용 정보를 JSON 컬럼 값으로 변환
+fn citation_json(citation: Option<&Citation>) -> Result<Option<String>> {
+    citation
+        .map(serde_json::to_string)
+        .transpose()
+        .context("Failed to serialize citation")
+}
+
+/// JSON 컬럼 값을 인용 정보로 변환 (형식이 깨졌으면 None)
+fn parse_citation(json: Option<String>) -> Option<Citation> {
+    json.and_then(|j| serde_json::from_str(&j).ok())
+}
+
+/// 페이지 메타데이터를 JSON 컬럼 값으로 변환
+fn metadata_json(metadata: Option<&PageMetadata>) -> Result<Option<String>> {
+    metadata
+        .map(serde_json::to_string)
+        .transpose()
+        .context("Failed to serialize page metadata")
+}
+
+/// 수집 출처를 JSON 컬럼 값으로 변환
+fn provenance_json(provenance: Option<&Provenance>) -> Result<Option<String>> {
+    provenance
+        .map(serde_json::to_string)
+        .transpose()
+        .context("Failed to serialize provenance")
+}
+
+/// 게시 시각 컬럼 값 (필터/정렬용으로 메타데이터와 별도 저장)
+fn published_at(metadata: Option<&PageMetadata>) -> Option<String> {
+    metadata.and_then(|m| m.published_at).map(format_timestamp)
+}
+
+/// 컬럼이 없으면 추가 (기존 DB 마이그레이션)
+fn ensure_column(conn: &Connection, column: &str, definition: &str) -> Result<()> {
+    let exists = conn
+        .prepare("SELECT 1 FROM pragma_table_info('documents') WHERE name = ?1")?
+        .exists(params![column])?;
+
+    if !exists {
+        conn.execute(
+            &format!("ALTER TABLE documents ADD COLUMN {} {}", column, definition),
+            [],
+        )
+        .with_context(|| format!("Failed to add column: {}", column))?;
+        tracing::info!("Migrated documents table: added column {}", column);
+    }
+
+    Ok(())
+}
+
 /// FTS5 쿼리 이스케이프
 ///
 /// 특수 문자를 제거하고 단어만 추출합니다.
@@ -503,6 +1490,7 @@ mod tests {
             title: Some("Example Doc".to_string()),
             content: "This is test content".to_string(),
             framework: Some("rust".to_string()),
+            ..Default::default()
         };
 
         let id = store.add_document(doc).unwrap();
@@ -516,6 +1504,30 @@ mod tests {
         assert_eq!(retrieved.framework, Some("rust".to_string()));
     }
 
+    #[test]
+    fn test_add_documents_bulk() {
+        let (_dir, store) = create_test_store();
+
+        let docs: Vec<NewDocument> = (0..3)
+            .map(|i| NewDocument {
+                url: format!("https://example.com/bulk/{}", i),
+                title: Some(format!("Bulk {}", i)),
+                content: format!("Bulk content {}", i),
+                framework: None,
+                ..Default::default()
+            })
+            .collect();
+
+        let ids = store.add_documents(&docs).unwrap();
+        assert_eq!(ids.len(), 3);
+        assert_eq!(store.stats().unwrap().document_count, 3);
+
+        let doc = store.get_document(ids[2]).unwrap().unwrap();
+        assert_eq!(doc.url, "https://example.com/bulk/2");
+
+        assert!(store.add_documents(&[]).unwrap().is_empty());
+    }
+
     #[test]
     fn test_get_by_url() {
         let (_dir, store) = create_test_store();
@@ -525,6 +1537,7 @@ mod tests {
             title: Some("Test".to_string()),
             content: "Content".to_string(),
             framework: None,
+            ..Default::default()
         }).unwrap();
 
         let doc = store.get_by_url("https://example.com/test").unwrap();
@@ -534,6 +1547,49 @@ mod tests {
         assert!(doc.is_none());
     }
 
+    #[test]
+    fn test_reingest_keeps_id_and_restore() {
+        let (_dir, store) = create_test_store();
+
+        let doc = |content: &str| NewDocument {
+            url: "https://example.com/guide".to_string(),
+            title: Some("Guide".to_string()),
+            content: content.to_string(),
+            ..Default::default()
+        };
+        let id = store.add_document(doc("original walrus")).unwrap();
+        store.set_pinned(id, true).unwrap();
+        let previous = store.get_document(id).unwrap().unwrap();
+
+        assert_eq!(store.add_document(doc("updated narwhal")).unwrap(), id);
+        assert!(store.search_fts("walrus", 10, None).unwrap().is_empty());
+        assert_eq!(store.search_fts("narwhal", 10, None).unwrap().len(), 1);
+
+        assert!(store.restore_document(&previous).unwrap());
+        let restored = store.get_document(id).unwrap().unwrap();
+        assert_eq!(restored.content, "original walrus");
+        assert!(restored.pinned);
+        assert_eq!(store.search_fts("walrus", 10, None).unwrap().len(), 1);
+        assert!(store.search_fts("narwhal", 10, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_ids_for_source() {
+        let (_dir, store) = create_test_store();
+
+        let urls = ["file:///data.csv", "file:///data.csv#row=1", "file:///data.csv2"];
+        for url in urls {
+            store.add_document(NewDocument {
+                url: url.to_string(),
+                content: "Content".to_string(),
+                ..Default::default()
+            }).unwrap();
+        }
+
+        assert_eq!(store.ids_for_source("file:///data.csv").unwrap().len(), 2);
+        assert!(store.ids_for_source("file:///other.csv").unwrap().is_empty());
+    }
+
     #[test]
     fn test_list_documents() {
         let (_dir, store) = create_test_store();
@@ -544,16 +1600,44 @@ mod tests {
                 title: Some(format!("Doc {}", i)),
                 content: format!("Content {}", i),
                 framework: if i % 2 == 0 { Some("rust".to_string()) } else { None },
+                ..Default::default()
             }).unwrap();
         }
 
         // 전체 목록
-        let list = store.list_documents(10, None).unwrap();
+        let list = store.list_documents(10, None, None).unwrap();
         assert_eq!(list.len(), 5);
 
         // Framework 필터
-        let rust_list = store.list_documents(10, Some("rust")).unwrap();
+        let rust_list = store.list_documents(10, Some("rust"), None).unwrap();
         assert_eq!(rust_list.len(), 3); // 0, 2, 4
+
+        // 본문 대신 길이만 반환
+        assert!(list.iter().all(|d| d.content_length == "Content 0".len()));
+    }
+
+    #[test]
+    fn test_get_summaries() {
+        let (_dir, store) = create_test_store();
+
+        let ids: Vec<i64> = (0..3)
+            .map(|i| {
+                store.add_document(NewDocument {
+                    url: format!("https://example.com/s{}", i),
+                    title: Some(format!("S {}", i)),
+                    content: "abc".to_string(),
+                    framework: None,
+                    ..Default::default()
+                }).unwrap()
+            })
+            .collect();
+
+        let summaries = store.get_summaries(&[ids[0], ids[2], 9999]).unwrap();
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[&ids[2]].url, "https://example.com/s2");
+        assert_eq!(summaries[&ids[0]].content_length, 3);
+
+        assert!(store.get_summaries(&[]).unwrap().is_empty());
     }
 
     #[test]
@@ -565,6 +1649,7 @@ mod tests {
             title: None,
             content: "To be deleted".to_string(),
             framework: None,
+            ..Default::default()
         }).unwrap();
 
         assert!(store.get_document(id).unwrap().is_some());
@@ -584,6 +1669,7 @@ mod tests {
             title: Some("Test".to_string()),
             content: "1234567890".to_string(), // 10 bytes
             framework: None,
+            ..Default::default()
         }).unwrap();
 
         let stats = store.stats().unwrap();
@@ -600,6 +1686,7 @@ mod tests {
             title: Some("React Guide".to_string()),
             content: "React is a JavaScript library".to_string(),
             framework: Some("react".to_string()),
+            ..Default::default()
         }).unwrap();
 
         store.add_document(NewDocument {
@@ -607,6 +1694,7 @@ mod tests {
             title: Some("Vue Guide".to_string()),
             content: "Vue is a JavaScript framework".to_string(),
             framework: Some("vue".to_string()),
+            ..Default::default()
         }).unwrap();
 
         let results = store.search_like("JavaScript", 10).unwrap();
@@ -616,6 +1704,555 @@ mod tests {
         assert_eq!(results.len(), 1);
     }
 
+    #[test]
+    fn test_search_fts_normalized() {
+        let (_dir, store) = create_test_store();
+
+        // 조합형 한글, zero-width space, CRLF가 섞인 본문
+        let hangul = "\u{1112}\u{1161}\u{11AB}\u{1100}\u{1173}\u{11AF}";
+        let id = store
+            .add_document(NewDocument {
+                url: "https://example.com/pasted".to_string(),
+                title: Some(format!("{} 가이드", hangul)),
+                content: format!("Ownership\u{200B}Rules  \r\n{}", hangul),
+                ..Default::default()
+            })
+            .unwrap();
+
+        let doc = store.get_document(id).unwrap().unwrap();
+        assert_eq!(doc.title.as_deref(), Some("한글 가이드"));
+        assert_eq!(doc.content, "OwnershipRules\n한글");
+
+        for query in ["한글", "OwnershipRules", "ownership\u{200B}rules"] {
+            let results = store.search_fts(query, 10, None).unwrap();
+            assert_eq!(results.len(), 1, "query {:?}", query);
+        }
+    }
+
+    #[test]
+    fn test_normalize_stored_text() {
+        let (_dir, store) = create_test_store();
+
+        let id = store
+            .add_document(NewDocument {
+                url: "https://example.com/old".to_string(),
+                title: Some("Old".to_string()),
+                content: "placeholder".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        let clean = store
+            .add_document(NewDocument {
+                url: "https://example.com/clean".to_string(),
+                title: Some("Clean".to_string()),
+                content: "already normalized".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        store.set_chunker(&[id, clean], "v1").unwrap();
+
+        // 정규화 이전에 수집한 문서 (조합형 한글, zero-width space, CRLF)
+        let hangul = "\u{1112}\u{1161}\u{11AB}\u{1100}\u{1173}\u{11AF}";
+        store
+            .conn
+            .lock()
+            .unwrap()
+            .execute(
+                "UPDATE documents SET title = ?2, content = ?3 WHERE id = ?1",
+                params![
+                    id,
+                    format!("{} 가이드", hangul),
+                    format!("Ownership\u{200B}Rules  \r\n{}", hangul)
+                ],
+            )
+            .unwrap();
+        let before = store.search_fts("OwnershipRules", 10, None).unwrap();
+        assert!(before.is_empty());
+
+        assert_eq!(store.normalize_stored_text(None).unwrap(), 1);
+        let doc = store.get_document(id).unwrap().unwrap();
+        assert_eq!(doc.title.as_deref(), Some("한글 가이드"));
+        assert_eq!(doc.content, "OwnershipRules\n한글");
+        for query in ["한글", "OwnershipRules"] {
+            let results = store.search_fts(query, 10, None).unwrap();
+            assert_eq!(results.len(), 1, "query {:?}", query);
+        }
+
+        // 본문이 바뀐 문서만 다시 청킹 대상
+        assert_eq!(store.ids_with_other_chunker("v1").unwrap(), vec![id]);
+        assert_eq!(store.normalize_stored_text(None).unwrap(), 0);
+        assert_eq!(store.normalize_stored_text(Some(&[])).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_search_fts_framework_filter() {
+        let (_dir, store) = create_test_store();
+
+        for (url, framework) in [("https://a.dev/state", "react"), ("https://b.dev/state", "vue")] {
+            store.add_document(NewDocument {
+                url: url.to_string(),
+                title: None,
+                content: "Managing component state".to_string(),
+                framework: Some(framework.to_string()),
+                ..Default::default()
+            }).unwrap();
+        }
+
+        let all = store.search_fts("state", 10, None).unwrap();
+        assert_eq!(all.len(), 2);
+
+        let scoped = store.search_fts("state", 10, Some("vue")).unwrap();
+        assert_eq!(scoped.len(), 1);
+        let doc = store.get_document(scoped[0].doc_id).unwrap().unwrap();
+        assert_eq!(doc.url, "https://b.dev/state");
+    }
+
+    #[test]
+    fn test_search_fts_framework_version_filter() {
+        let (_dir, store) = create_test_store();
+
+        for (url, framework) in [
+            ("https://a.dev/18", "react@18.2"),
+            ("https://a.dev/19", "react@19"),
+            ("https://a.dev/native", "react-native"),
+        ] {
+            store.add_document(NewDocument {
+                url: url.to_string(),
+                title: None,
+                content: "Managing component state".to_string(),
+                framework: Some(framework.to_string()),
+                ..Default::default()
+            }).unwrap();
+        }
+
+        // 버전 없는 필터는 모든 버전, 다른 이름은 제외
+        assert_eq!(store.search_fts("state", 10, Some("react")).unwrap().len(), 2);
+        assert_eq!(store.list_documents(10, Some("react"), None).unwrap().len(), 2);
+
+        let scoped = store.search_fts("state", 10, Some("react@18")).unwrap();
+        assert_eq!(scoped.len(), 1);
+        let doc = store.get_document(scoped[0].doc_id).unwrap().unwrap();
+        assert_eq!(doc.url, "https://a.dev/18");
+    }
+
+    #[test]
+    fn test_migrates_legacy_schema() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("legacy.db");
+        {
+            let conn = Connection::open(&db_path).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE documents (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    url TEXT NOT NULL UNIQUE,
+                    title TEXT,
+                    content TEXT NOT NULL,
+                    framework TEXT,
+                    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+                );
+                INSERT INTO documents (url, content, created_at)
+                VALUES ('https://legacy.dev', 'legacy', '2024-01-01T00:00:00+00:00');",
+            )
+            .unwrap();
+        }
+
+        let store = KnowledgeStore::open(&db_path).unwrap();
+        let doc = store.get_by_url("https://legacy.dev").unwrap().unwrap();
+        assert!(doc.expires_at.is_none());
+    }
+
+    #[test]
+    fn test_delete_expired() {
+        let (_dir, store) = create_test_store();
+
+        let expired = store.add_document(NewDocument {
+            url: "note://old".to_string(),
+            content: "stale ticket".to_string(),
+            expires_at: Some(Utc::now() - chrono::Duration::minutes(1)),
+            ..Default::default()
+        }).unwrap();
+        store.add_document(NewDocument {
+            url: "note://new".to_string(),
+            content: "fresh ticket".to_string(),
+            expires_at: Some(Utc::now() + chrono::Duration::days(1)),
+            ..Default::default()
+        }).unwrap();
+
+        assert_eq!(store.search_fts("ticket", 10, None).unwrap().len(), 1);
+        assert_eq!(store.delete_expired().unwrap(), vec![expired]);
+        assert_eq!(store.stats().unwrap().document_count, 1);
+    }
+
+    #[test]
+    fn test_pinned_documents() {
+        let (_dir, store) = create_test_store();
+
+        let id = store
+            .add_document(NewDocument {
+                url: "https://wiki.internal/guide".to_string(),
+                content: "Canonical deployment guide".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(store.list_pinned().unwrap().is_empty());
+
+        assert!(store.set_pinned(id, true).unwrap());
+        let pins = store.list_pinned().unwrap();
+        assert_eq!(pins.len(), 1);
+        assert!(pins[0].pinned);
+        assert!(store.get_document(id).unwrap().unwrap().pinned);
+
+        // 재수집해도 고정 유지
+        let id = store
+            .add_document(NewDocument {
+                url: "https://wiki.internal/guide".to_string(),
+                content: "Canonical deployment guide v2".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(store.get_document(id).unwrap().unwrap().pinned);
+
+        assert!(store.set_pinned(id, false).unwrap());
+        assert!(store.list_pinned().unwrap().is_empty());
+        assert!(!store.set_pinned(9999, true).unwrap());
+    }
+
+    #[test]
+    fn test_feedback_votes() {
+        let (_dir, store) = create_test_store();
+
+        let id = store
+            .add_document(NewDocument {
+                url: "https://example.com/hooks".to_string(),
+                content: "React hooks".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert!(store.add_feedback("aaaa0000", id, true).unwrap());
+        assert!(store.add_feedback("aaaa0000", id, true).unwrap());
+        assert!(store.add_feedback("bbbb1111", id, false).unwrap());
+        assert!(!store.add_feedback("aaaa0000", 9999, true).unwrap());
+
+        let votes = store.feedback_votes("aaaa0000").unwrap();
+        assert_eq!(votes[&id], FeedbackVotes { query: 2, other: -1 });
+
+        // 다시 수집해도 ID가 유지되어 판정이 계속 적용됨
+        let reingested = store
+            .add_document(NewDocument {
+                url: "https://example.com/hooks".to_string(),
+                content: "React hooks, updated".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(reingested, id);
+        let votes = store.feedback_votes("aaaa0000").unwrap();
+        assert_eq!(votes.len(), 1);
+        assert_eq!(votes[&id], FeedbackVotes { query: 2, other: -1 });
+
+        store.delete_document(id).unwrap();
+        assert!(store.feedback_votes("aaaa0000").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_chunk_lines() {
+        let (_dir, store) = create_test_store();
+
+        let id = store
+            .add_document(NewDocument {
+                url: "file:///repo/src/lib.rs".to_string(),
+                content: "fn a() {}\n\nfn b() {}".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(store.chunk_lines(id, 0).unwrap(), None);
+
+        store.set_chunk_lines(id, &[(1, 1), (3, 3)]).unwrap();
+        assert_eq!(store.chunk_lines(id, 1).unwrap(), Some((3, 3)));
+
+        let ranges = store.chunk_lines_batch(&[(id, 1), (id, 5)]).unwrap();
+        assert_eq!(ranges.len(), 1);
+        assert_eq!(ranges[&(id, 1)], (3, 3));
+
+        // 다시 저장하면 교체
+        store.set_chunk_lines(id, &[(1, 3)]).unwrap();
+        assert_eq!(store.chunk_lines(id, 0).unwrap(), Some((1, 3)));
+        assert_eq!(store.chunk_lines(id, 1).unwrap(), None);
+
+        store.delete_document(id).unwrap();
+        assert_eq!(store.chunk_lines(id, 0).unwrap(), None);
+    }
+
+    #[test]
+    fn test_chunk_kinds() {
+        let (_dir, store) = create_test_store();
+
+        let id = store
+            .add_document(NewDocument {
+                url: "https://example.com/tutorial".to_string(),
+                content: "설명\n\n```rust\nfn main() {}\n```".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(store.chunk_kinds(&[id]).unwrap().is_empty());
+
+        store
+            .set_chunk_kinds(id, &[ChunkClass::prose(), ChunkClass::code(Some("rust"))])
+            .unwrap();
+        let kinds = store.chunk_kinds(&[id]).unwrap();
+        assert_eq!(kinds[&(id, 0)], ChunkClass::prose());
+        assert_eq!(kinds[&(id, 1)], ChunkClass::code(Some("rust")));
+
+        store.delete_document(id).unwrap();
+        assert!(store.chunk_kinds(&[id]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_chunk_fingerprints() {
+        let (_dir, store) = create_test_store();
+
+        let mut ids = Vec::new();
+        for page in ["a", "b", "c"] {
+            let id = store
+                .add_document(NewDocument {
+                    url: format!("https://example.com/{}", page),
+                    content: format!("Page {}\n\nWe use cookies.", page),
+                    ..Default::default()
+                })
+                .unwrap();
+            ids.push(id);
+        }
+        assert_eq!(store.ids_without_fingerprints().unwrap(), ids);
+
+        let banner = "cookie0000000000".to_string();
+        for (i, &id) in ids.iter().enumerate() {
+            let own = Some(format!("page{:012}", i));
+            store
+                .set_chunk_fingerprints(id, &[own, None, Some(banner.clone())])
+                .unwrap();
+        }
+        assert!(store.ids_without_fingerprints().unwrap().is_empty());
+
+        let counts = store
+            .fingerprint_doc_counts(&[banner.clone(), "page000000000000".to_string()])
+            .unwrap();
+        assert_eq!(counts[&banner], 3);
+        assert_eq!(counts["page000000000000"], 1);
+
+        store.delete_document(ids[0]).unwrap();
+        let counts = store.fingerprint_doc_counts(&[banner.clone()]).unwrap();
+        assert_eq!(counts[&banner], 2);
+    }
+
+    #[test]
+    fn test_reingest_keeps_fingerprint_counts() {
+        let (_dir, store) = create_test_store();
+
+        // 같은 URL을 5번 다시 수집 (ID 유지, 지문 기록은 교체)
+        let page = "page000000000000".to_string();
+        for _ in 0..5 {
+            let id = store
+                .add_document(NewDocument {
+                    url: "https://example.com/page".to_string(),
+                    content: "Page".to_string(),
+                    ..Default::default()
+                })
+                .unwrap();
+            store
+                .set_chunk_fingerprints(id, &[Some(page.clone())])
+                .unwrap();
+        }
+
+        let counts = store.fingerprint_doc_counts(&[page.clone()]).unwrap();
+        assert_eq!(counts[&page], 1);
+
+        // 정리 이전 버전이 남긴 고아 기록은 세지 않고 repair에서 삭제
+        store
+            .set_chunk_fingerprints(999, &[Some(page.clone())])
+            .unwrap();
+        let counts = store.fingerprint_doc_counts(&[page.clone()]).unwrap();
+        assert_eq!(counts[&page], 1);
+        assert_eq!(store.delete_orphan_chunk_rows().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_chunk_models() {
+        let (_dir, store) = create_test_store();
+
+        let mut ids = Vec::new();
+        for page in ["a", "b", "c"] {
+            let id = store
+                .add_document(NewDocument {
+                    url: format!("https://example.com/{}", page),
+                    content: format!("Page {}", page),
+                    ..Default::default()
+                })
+                .unwrap();
+            ids.push(id);
+        }
+        assert_eq!(store.ids_with_other_model("new/768").unwrap(), ids);
+
+        store
+            .set_chunk_models(
+                &ids[..2],
+                &[(ids[0], 0), (ids[0], 1), (ids[1], 0)],
+                "old/768",
+            )
+            .unwrap();
+        store
+            .set_chunk_models(&[ids[1]], &[(ids[1], 0)], "new/768")
+            .unwrap();
+        // 기록이 없는 문서(c)도 대상
+        assert_eq!(
+            store.ids_with_other_model("new/768").unwrap(),
+            vec![ids[0], ids[2]]
+        );
+        assert_eq!(
+            store.chunk_model_counts().unwrap(),
+            vec![("old/768".to_string(), 2), ("new/768".to_string(), 1)]
+        );
+
+        // 청크가 없는 문서도 한 번 기록하면 더 이상 대상이 아님
+        store.set_chunk_models(&[ids[2]], &[], "new/768").unwrap();
+        assert_eq!(store.ids_with_other_model("new/768").unwrap(), vec![ids[0]]);
+        assert_eq!(store.chunk_model_counts().unwrap().len(), 2);
+
+        // 교체 저장/삭제된 문서의 기록은 세지 않음
+        store
+            .add_document(NewDocument {
+                url: "https://example.com/a".to_string(),
+                content: "Page a, updated".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(
+            store.chunk_model_counts().unwrap(),
+            vec![("new/768".to_string(), 1)]
+        );
+        store.delete_document(ids[1]).unwrap();
+        assert!(store.chunk_model_counts().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_citation_roundtrip() {
+        let (_dir, store) = create_test_store();
+        let citation = Citation {
+            key: "karpukhin2020".to_string(),
+            entry_type: "inproceedings".to_string(),
+            title: Some("Dense Passage Retrieval".to_string()),
+            authors: vec!["Karpukhin, Vladimir".to_string()],
+            year: Some(2020),
+            ..Default::default()
+        };
+
+        let id = store
+            .add_document(NewDocument {
+                url: "file:///papers/dpr.pdf".to_string(),
+                content: "Dense retrieval with dual encoders".to_string(),
+                citation: Some(citation.clone()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(store.get_document(id).unwrap().unwrap().citation, Some(citation.clone()));
+        let summaries = store.get_summaries(&[id]).unwrap();
+        assert_eq!(summaries[&id].citation, Some(citation.clone()));
+
+        // 인용 정보 없이 재수집해도 유지
+        let id = store
+            .add_document(NewDocument {
+                url: "file:///papers/dpr.pdf".to_string(),
+                content: "Dense retrieval with dual encoders (v2)".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(store.get_document(id).unwrap().unwrap().citation, Some(citation));
+
+        assert!(store.set_citation(id, None).unwrap());
+        assert!(store.get_document(id).unwrap().unwrap().citation.is_none());
+    }
+
+    #[test]
+    fn test_provenance_kept_on_reingest() {
+        let (_dir, store) = create_test_store();
+        let provenance = Provenance::current("file").with_extractor("pdf");
+
+        store
+            .add_document(NewDocument {
+                url: "file:///papers/a.pdf".to_string(),
+                content: "page".to_string(),
+                provenance: Some(provenance.clone()),
+                ..Default::default()
+            })
+            .unwrap();
+
+        // 출처 없이 재저장해도 유지
+        let id = store
+            .add_document(NewDocument {
+                url: "file:///papers/a.pdf".to_string(),
+                content: "page v2".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(
+            store.get_document(id).unwrap().unwrap().provenance,
+            Some(provenance)
+        );
+    }
+
+    #[test]
+    fn test_page_metadata_and_published_filter() {
+        let (_dir, store) = create_test_store();
+        let published = DateTime::parse_from_rfc3339("2024-03-05T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let metadata = PageMetadata {
+            author: Some("Jane Doe".to_string()),
+            published_at: Some(published),
+            ..Default::default()
+        };
+
+        store
+            .add_document(NewDocument {
+                url: "https://blog.dev/post".to_string(),
+                content: "post".to_string(),
+                metadata: Some(metadata.clone()),
+                ..Default::default()
+            })
+            .unwrap();
+        store
+            .add_document(NewDocument {
+                url: "https://blog.dev/undated".to_string(),
+                content: "undated".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+
+        // 메타데이터 없이 재수집해도 유지
+        let id = store
+            .add_document(NewDocument {
+                url: "https://blog.dev/post".to_string(),
+                content: "post v2".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(
+            store.get_document(id).unwrap().unwrap().metadata,
+            Some(metadata)
+        );
+
+        let since = |s: &str| Some(DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc));
+        let recent = store
+            .list_documents(10, None, since("2024-01-01T00:00:00Z"))
+            .unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].published_at, Some(published));
+        assert!(store
+            .list_documents(10, None, since("2024-06-01T00:00:00Z"))
+            .unwrap()
+            .is_empty());
+        assert_eq!(store.list_documents(10, None, None).unwrap().len(), 2);
+    }
+
     #[test]
     fn test_escape_fts5_query() {
         assert_eq!(escape_fts5_query("hello world"), "hello world");
@@ -0,0 +1,118 @@
+//! 검색 트레이스 - 쿼리별 검색 과정 JSON 덤프
+//!
+//! 쿼리, 임베딩 해시, FTS5/벡터 후보 목록과 점수, RRF 통합 결과를
+//! 트레이스 디렉토리에 쿼리마다 JSON 파일 하나로 저장합니다.
+//! 순위가 이상하다는 버그 리포트에 그대로 첨부할 수 있습니다.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use super::hybrid::{HybridSearchResult, SearchOptions};
+use super::store::FtsSearchResult;
+use super::vector::SearchResult;
+
+/// 기본 트레이스 디렉토리 이름 (데이터 디렉토리 아래)
+pub const TRACES_DIR: &str = "traces";
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// 한 쿼리의 검색 트레이스
+#[derive(Debug, Clone, Serialize)]
+pub struct RetrievalTrace {
+    pub timestamp: DateTime<Utc>,
+    pub query: String,
+    /// 검색 옵션 (결과 수, 프레임워크, RRF 설정)
+    pub options: SearchOptions,
+    /// 쿼리 임베딩 요약 (벡터 전체 대신 해시)
+    pub embedding: EmbeddingFingerprint,
+    /// FTS5 후보 (BM25 순)
+    pub fts_candidates: Vec<FtsSearchResult>,
+    /// 벡터 후보 (필터링 후, 유사도 순)
+    pub vector_candidates: Vec<SearchResult>,
+    /// RRF 통합 결과 (최종 순위)
+    pub fused: Vec<HybridSearchResult>,
+}
+
+/// 임베딩 요약
+#[derive(Debug, Clone, Serialize)]
+pub struct EmbeddingFingerprint {
+    pub dimension: usize,
+    /// 리틀 엔디언 f32 바이트의 SHA-256 (hex)
+    pub sha256: String,
+}
+
+impl EmbeddingFingerprint {
+    /// 임베딩 벡터로부터 생성
+    pub fn of(embedding: &[f32]) -> Self {
+        let mut hasher = Sha256::new();
+        for value in embedding {
+            hasher.update(value.to_le_bytes());
+        }
+
+        Self {
+            dimension: embedding.len(),
+            sha256: hasher
+                .finalize()
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect(),
+        }
+    }
+}
+
+// ============================================================================
+// RetrievalTrace
+// ============================================================================
+
+impl RetrievalTrace {
+    /// 트레이스 디렉토리에 JSON 파일로 저장
+    ///
+    /// 파일 이름: `<UTC 시각>-<임베딩 해시 앞 8자>.json`
+    ///
+    /// # Returns
+    /// 저장된 파일 경로
+    pub fn write_to(&self, dir: &Path) -> Result<PathBuf> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create traces directory: {:?}", dir))?;
+
+        let file_name = format!(
+            "{}-{}.json",
+            self.timestamp.format("%Y%m%dT%H%M%S%.3fZ"),
+            &self.embedding.sha256[..8]
+        );
+        let path = dir.join(file_name);
+
+        let json = serde_json::to_string_pretty(self).context("Failed to serialize trace")?;
+        std::fs::write(&path, json)
+            .with_context(|| format!("Failed to write trace: {:?}", path))?;
+
+        Ok(path)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedding_fingerprint() {
+        let a = EmbeddingFingerprint::of(&[0.1, 0.2, 0.3]);
+        let b = EmbeddingFingerprint::of(&[0.1, 0.2, 0.3]);
+        let c = EmbeddingFingerprint::of(&[0.1, 0.2, 0.4]);
+
+        assert_eq!(a.dimension, 3);
+        assert_eq!(a.sha256.len(), 64);
+        assert_eq!(a.sha256, b.sha256);
+        assert_ne!(a.sha256, c.sha256);
+    }
+}
@@ -6,6 +6,7 @@
 
 use anyhow::Result;
 use async_trait::async_trait;
+use serde::Serialize;
 
 /// 벡터 임베딩 차원 (Gemini gemini-embedding-001 기본값)
 /// source: https://ai.google.dev/gemini-api/docs/embeddings
@@ -29,7 +30,7 @@ pub struct VectorEntry {
 }
 
 /// 검색 결과
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SearchResult {
     /// 문서 ID
     pub doc_id: i64,
@@ -97,6 +98,27 @@ pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
     dot_product / (norm_a * norm_b)
 }
 
+/// 여러 임베딩의 평균 (L2 정규화)
+///
+/// 청크 임베딩들로 문서 단위 임베딩을 만들 때 사용합니다.
+/// 비어 있거나 차원이 다른 벡터가 섞여 있으면 None을 반환합니다.
+pub fn mean_embedding<'a>(vectors: impl IntoIterator<Item = &'a [f32]>) -> Option<Vec<f32>> {
+    let mut vectors = vectors.into_iter();
+    let mut sum = vectors.next()?.to_vec();
+    for vector in vectors {
+        if vector.len() != sum.len() {
+            return None;
+        }
+        sum.iter_mut().zip(vector).for_each(|(s, v)| *s += v);
+    }
+
+    let norm: f32 = sum.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        sum.iter_mut().for_each(|x| *x /= norm);
+    }
+    Some(sum)
+}
+
 /// 텍스트를 청크로 분할
 ///
 /// 문서를 지정된 크기의 청크로 나눕니다.
@@ -174,6 +196,18 @@ mod tests {
         assert_eq!(cosine_similarity(&a, &b), 0.0);
     }
 
+    #[test]
+    fn test_mean_embedding() {
+        let a = [1.0, 0.0];
+        let b = [0.0, 1.0];
+        let mean = mean_embedding([&a[..], &b[..]]).unwrap();
+        assert!((mean[0] - mean[1]).abs() < 1e-6);
+        assert!((cosine_similarity(&mean, &[1.0, 1.0]) - 1.0).abs() < 1e-6);
+
+        assert!(mean_embedding(std::iter::empty()).is_none());
+        assert!(mean_embedding([&a[..], &[1.0][..]]).is_none());
+    }
+
     #[test]
     fn test_chunk_text() {
         let text = "a b c d e f g h i j";
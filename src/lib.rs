@@ -5,21 +5,51 @@
 //!
 //! source: D:\010 Web Applicaton\PALAN-K-palank-rag
 
+pub mod audit;
+pub mod citation;
 pub mod cli;
 pub mod collector;
+pub mod config;
+pub mod connector;
+pub mod daemon;
 pub mod embedding;
 pub mod extractor;
+pub mod generation;
+pub mod i18n;
 pub mod knowledge;
+pub mod limits;
+pub mod normalize;
+pub mod notify;
+pub mod opener;
 pub mod scraper;
+pub mod service;
+pub mod tokens;
+pub mod watch;
+
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
 
 // Re-exports
+pub use citation::{BibEntry, Citation};
 pub use collector::{CollectedFile, CollectionStats, CollectorConfig, FileCollector, FileType};
-pub use embedding::{EmbeddingProvider, GeminiEmbedding, get_api_key, has_api_key};
+pub use config::Config;
+pub use connector::{Bookmark, RowTemplate, SqlConnection, SqlRow};
+pub use daemon::{Daemon, JobAction, JobConfig, Schedule};
+pub use embedding::{
+    ApiKeySource, EmbeddingProvider, GeminiEmbedding, KeyUsage, OversizeStrategy, get_api_key,
+    get_api_keys, has_api_key,
+};
 pub use extractor::{ContentExtractor, ContentMetadata, ExtractedContent};
+pub use generation::GeminiGenerator;
 pub use knowledge::{
-    ChunkConfig, Chunker, Document, FtsSearchResult, HybridRetriever, HybridSearchResult,
-    HybridStats, KnowledgeStore, LanceVectorStore, MarkdownChunker, NewDocument, SearchMethod,
-    SearchResult, StoreStats, VectorEntry, VectorStore, default_chunker, get_data_dir,
-    markdown_chunker,
+    AskContext, ChunkClass, ChunkConfig, ChunkKind, ChunkViolation, Chunker, CollectionHit,
+    CompactReport, ContentDiff, ContextChunk, ContextOptions, Document, DocumentSummary,
+    ExplainedResult,
+    FtsSearchResult, FusionConfig, HybridRetriever, HybridSearchResult, HybridStats,
+    KnowledgeStore, LanceVectorStore, MarkdownChunker, NewDocument, RankingDiff, ReindexReport,
+    ResultExplanation, RetrievalTrace, SearchMethod, SearchOptions, SearchResult, SourceCitation,
+    StoreCorrupted, StoreStats, VectorEntry, VectorStore, default_chunker,
+    get_data_dir, markdown_chunker, validate_chunks,
 };
-pub use scraper::{ScrapedContent, WebScraper};
+pub use scraper::{PageMetadata, PolicyViolation, ScrapedContent, UrlPolicy, WebScraper};
+pub use watch::ScreenshotWatcher;
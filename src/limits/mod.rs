@@ -0,0 +1,362 @@
+//! 실행당 자원 사용 한도 - 잘못 지정한 대량 수집 방지
+//!
+//! 범위를 잘못 잡은 북마크 스크랩이나 폴더 수집이 밤새 일일 API 할당량과 디스크를
+//! 다 써버리지 않도록 한 번의 실행에서 쓸 수 있는 양을 제한합니다.
+//! 한도를 넘으면 `LimitExceeded`로 바로 중단하며, 그때까지 저장한 문서는 남습니다.
+//!
+//! ```toml
+//! [limits]
+//! max_pages = 500           # 실행당 가져올 웹 페이지 수
+//! max_embeddings = 20000    # 실행당 임베딩 요청 수 (청크 수)
+//! max_disk_growth_mb = 2048 # 실행당 저장소(SQLite + 벡터) 증가량
+//! ```
+//!
+//! 각 값이 0이면 해당 한도를 끕니다.
+//! 상주 명령(`daemon`, `watch`)은 예약 작업 실행과 감시 주기를 한 번의 실행으로 셉니다.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::embedding::EmbeddingProvider;
+use crate::knowledge::{SQLITE_FILE, VECTORS_DIR};
+
+/// 기본 실행당 웹 페이지 수
+pub const DEFAULT_MAX_PAGES: usize = 500;
+
+/// 기본 실행당 임베딩 요청 수
+pub const DEFAULT_MAX_EMBEDDINGS: usize = 20_000;
+
+/// 기본 실행당 저장소 증가량 (MB)
+pub const DEFAULT_MAX_DISK_GROWTH_MB: u64 = 2048;
+
+// ============================================================================
+// ResourceLimits
+// ============================================================================
+
+/// 실행당 자원 사용 한도 (0이면 제한 없음)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResourceLimits {
+    /// 실행당 가져올 웹 페이지 수
+    pub max_pages: usize,
+    /// 실행당 임베딩 요청 수
+    pub max_embeddings: usize,
+    /// 실행당 저장소(SQLite + 벡터) 증가량 (MB)
+    pub max_disk_growth_mb: u64,
+}
+
+impl Default for ResourceLimits {
+    fn default() -> Self {
+        Self {
+            max_pages: DEFAULT_MAX_PAGES,
+            max_embeddings: DEFAULT_MAX_EMBEDDINGS,
+            max_disk_growth_mb: DEFAULT_MAX_DISK_GROWTH_MB,
+        }
+    }
+}
+
+impl ResourceLimits {
+    /// 가져올 페이지 수가 한도 이내인지 (가져오기 전에 검사)
+    pub fn check_pages(&self, pages: usize) -> Result<(), LimitExceeded> {
+        check(LimitKind::Pages, self.max_pages as u64, pages as u64)
+    }
+}
+
+/// 한도 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitKind {
+    Pages,
+    Embeddings,
+    DiskGrowth,
+}
+
+impl LimitKind {
+    /// 설정 파일 키
+    pub fn config_key(self) -> &'static str {
+        match self {
+            Self::Pages => "max_pages",
+            Self::Embeddings => "max_embeddings",
+            Self::DiskGrowth => "max_disk_growth_mb",
+        }
+    }
+}
+
+impl fmt::Display for LimitKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Pages => "Page",
+            Self::Embeddings => "Embedding",
+            Self::DiskGrowth => "Disk growth",
+        })
+    }
+}
+
+/// 실행당 한도 초과
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error(
+    "{kind} limit per run exceeded ({used} > {limit}); narrow the scope or raise [limits] {} in config.toml",
+    kind.config_key()
+)]
+pub struct LimitExceeded {
+    pub kind: LimitKind,
+    /// 설정된 한도 (디스크는 MB)
+    pub limit: u64,
+    /// 요청한 양 (디스크는 MB)
+    pub used: u64,
+}
+
+impl LimitExceeded {
+    /// 오류 체인에 한도 초과가 있는지 (context로 감싼 경우 포함)
+    pub fn is_cause_of(error: &anyhow::Error) -> bool {
+        error.chain().any(|e| e.downcast_ref::<Self>().is_some())
+    }
+}
+
+fn check(kind: LimitKind, limit: u64, used: u64) -> Result<(), LimitExceeded> {
+    if limit > 0 && used > limit {
+        return Err(LimitExceeded { kind, limit, used });
+    }
+    Ok(())
+}
+
+// ============================================================================
+// EmbeddingBudget
+// ============================================================================
+
+/// 임베딩 요청 수를 제한하는 프로바이더
+///
+/// 한도를 넘는 요청은 API를 호출하지 않고 바로 실패합니다.
+pub struct EmbeddingBudget<P> {
+    inner: P,
+    max: usize,
+    used: BudgetCounter,
+}
+
+/// 임베딩 한도 사용량 (검색기에 넘긴 `EmbeddingBudget`의 사용량을 조회하거나 되돌릴 때 사용)
+#[derive(Debug, Clone, Default)]
+pub struct BudgetCounter(Arc<AtomicUsize>);
+
+impl BudgetCounter {
+    /// 지금까지 요청한 텍스트 수
+    pub fn used(&self) -> usize {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// 사용량을 0으로 (상주 명령에서 새 실행을 시작할 때)
+    pub fn reset(&self) {
+        self.0.store(0, Ordering::Relaxed);
+    }
+}
+
+impl<P: EmbeddingProvider> EmbeddingBudget<P> {
+    /// 최대 요청 수 지정 (0이면 제한 없음)
+    pub fn new(inner: P, max: usize) -> Self {
+        Self {
+            inner,
+            max,
+            used: BudgetCounter::default(),
+        }
+    }
+
+    /// 지금까지 요청한 텍스트 수
+    pub fn used(&self) -> usize {
+        self.used.used()
+    }
+
+    /// 공유 사용량 (검색기에 넘긴 뒤에도 실행마다 되돌릴 수 있음)
+    pub fn counter(&self) -> BudgetCounter {
+        self.used.clone()
+    }
+
+    fn reserve(&self, count: usize) -> Result<(), LimitExceeded> {
+        let used = self.used.0.fetch_add(count, Ordering::Relaxed) + count;
+        let result = check(LimitKind::Embeddings, self.max as u64, used as u64);
+        if result.is_err() {
+            // 거절한 요청은 사용량에 넣지 않음
+            self.used.0.fetch_sub(count, Ordering::Relaxed);
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl<P: EmbeddingProvider> EmbeddingProvider for EmbeddingBudget<P> {
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        self.reserve(1)?;
+        self.inner.embed(text).await
+    }
+
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        self.reserve(texts.len())?;
+        self.inner.embed_batch(texts).await
+    }
+
+    fn dimension(&self) -> usize {
+        self.inner.dimension()
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn model_version(&self) -> String {
+        self.inner.model_version()
+    }
+}
+
+// ============================================================================
+// DiskGuard
+// ============================================================================
+
+/// 저장소 증가량 검사
+///
+/// 데이터 디렉토리의 SQLite 파일(WAL 포함)과 벡터 저장소만 잽니다.
+/// 백업, 로그, 트레이스처럼 수집과 무관하게 늘어나는 파일은 세지 않습니다.
+/// 생성 시점의 크기를 기준으로 삼으며, 배치 저장 뒤마다 `check`를 호출합니다.
+pub struct DiskGuard {
+    dir: PathBuf,
+    baseline: u64,
+    max_bytes: u64,
+}
+
+impl DiskGuard {
+    /// 현재 크기를 기준으로 생성 (`max_mb`가 0이면 검사하지 않음)
+    pub fn new(dir: &Path, max_mb: u64) -> Self {
+        let baseline = if max_mb > 0 { store_size(dir) } else { 0 };
+        Self {
+            dir: dir.to_path_buf(),
+            baseline,
+            max_bytes: max_mb.saturating_mul(1024 * 1024),
+        }
+    }
+
+    /// 기준 이후 증가량 (바이트)
+    pub fn growth(&self) -> u64 {
+        store_size(&self.dir).saturating_sub(self.baseline)
+    }
+
+    /// 증가량이 한도 이내인지
+    pub fn check(&self) -> Result<(), LimitExceeded> {
+        if self.max_bytes == 0 {
+            return Ok(());
+        }
+        let growth = self.growth();
+        if growth > self.max_bytes {
+            return Err(LimitExceeded {
+                kind: LimitKind::DiskGrowth,
+                limit: self.max_bytes / (1024 * 1024),
+                used: growth.div_ceil(1024 * 1024),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// 데이터 디렉토리의 저장소 크기 (SQLite 파일 + 벡터 저장소)
+fn store_size(data_dir: &Path) -> u64 {
+    let sqlite: u64 = ["", "-wal", "-shm"]
+        .iter()
+        .filter_map(|suffix| {
+            std::fs::metadata(data_dir.join(format!("{}{}", SQLITE_FILE, suffix))).ok()
+        })
+        .map(|meta| meta.len())
+        .sum();
+    sqlite + dir_size(&data_dir.join(VECTORS_DIR))
+}
+
+/// 디렉토리 전체 크기 (읽을 수 없는 항목은 건너뜀)
+pub(crate) fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return 0;
+    };
+
+    entries
+        .flatten()
+        .map(|entry| match entry.metadata() {
+            Ok(meta) if meta.is_dir() => dir_size(&entry.path()),
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::MockEmbedding;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_check_pages() {
+        let limits = ResourceLimits {
+            max_pages: 10,
+            ..Default::default()
+        };
+        assert!(limits.check_pages(10).is_ok());
+
+        let err = limits.check_pages(11).unwrap_err();
+        assert_eq!(err.kind, LimitKind::Pages);
+        assert!(err.to_string().contains("max_pages"));
+
+        let unlimited = ResourceLimits {
+            max_pages: 0,
+            ..Default::default()
+        };
+        assert!(unlimited.check_pages(1_000_000).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_embedding_budget() {
+        let budget = EmbeddingBudget::new(MockEmbedding::new(), 3);
+
+        let texts = vec!["a".to_string(), "b".to_string()];
+        assert_eq!(budget.embed_batch(&texts).await.unwrap().len(), 2);
+        assert!(budget.embed("c").await.is_ok());
+
+        let err = budget.embed("d").await.unwrap_err();
+        assert!(LimitExceeded::is_cause_of(&err));
+        assert_eq!(budget.used(), 3);
+
+        // 상주 명령은 실행마다 사용량을 되돌림
+        budget.counter().reset();
+        assert!(budget.embed("d").await.is_ok());
+        assert_eq!(budget.used(), 1);
+    }
+
+    #[test]
+    fn test_disk_guard() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join(SQLITE_FILE), vec![0u8; 4096]).unwrap();
+
+        let guard = DiskGuard::new(dir.path(), 1);
+        assert_eq!(guard.growth(), 0);
+
+        let vectors = dir.path().join(VECTORS_DIR);
+        std::fs::create_dir(&vectors).unwrap();
+        std::fs::write(vectors.join("a.bin"), vec![0u8; 512 * 1024]).unwrap();
+        assert!(guard.check().is_ok());
+
+        // 저장소 밖의 파일(백업 등)은 세지 않음
+        std::fs::create_dir(dir.path().join("backups")).unwrap();
+        std::fs::write(dir.path().join("backups/old.bin"), vec![0u8; 2048 * 1024]).unwrap();
+        assert!(guard.check().is_ok());
+
+        std::fs::write(vectors.join("b.bin"), vec![0u8; 600 * 1024]).unwrap();
+        let err = guard.check().unwrap_err();
+        assert_eq!(
+            (err.kind, err.limit, err.used),
+            (LimitKind::DiskGrowth, 1, 2)
+        );
+    }
+}
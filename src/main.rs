@@ -1,22 +1,37 @@
 //! palank-rag CLI 진입점
 
-use anyhow::Result;
+use std::io::IsTerminal;
+use std::process::ExitCode;
+
 use clap::Parser;
+use palank_rag::cli::report_error;
 
-fn main() -> Result<()> {
-    // 로깅 초기화
+fn main() -> ExitCode {
+    // 로깅 초기화 (stdout은 명령 출력 전용 - JSON 출력에 로그가 섞이지 않도록 stderr로,
+    // 서비스로 실행되어 로그 파일에 기록될 때는 색상 코드 제외)
     tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_ansi(std::io::stderr().is_terminal())
         .with_env_filter(
             tracing_subscriber::EnvFilter::from_default_env()
                 .add_directive(tracing::Level::INFO.into()),
         )
         .init();
 
-    // CLI 실행
+    // CLI 실행 (오류는 유형별 종료 코드로 끝냄)
     let cli = palank_rag::cli::Cli::parse();
+    let error_format = cli.error_format;
 
-    tokio::runtime::Builder::new_multi_thread()
+    let runtime = match tokio::runtime::Builder::new_multi_thread()
         .enable_all()
-        .build()?
-        .block_on(palank_rag::cli::run(cli))
+        .build()
+    {
+        Ok(runtime) => runtime,
+        Err(e) => return report_error(&e.into(), error_format),
+    };
+
+    match runtime.block_on(palank_rag::cli::run(cli)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => report_error(&e, error_format),
+    }
 }
@@ -0,0 +1,176 @@
+//! 텍스트 정규화 - 수집과 검색, FTS5와 임베딩이 같은 텍스트를 보도록
+//!
+//! 같은 글이라도 복사한 곳에 따라 유니코드 조합형/완성형, 줄바꿈(`\r\n`), 보이지 않는
+//! 문자(zero-width space, BOM, soft hyphen), NBSP 같은 특수 공백이 달라 자기 자신과도
+//! 일치하지 않는 일이 생깁니다. 수집 시 본문과 검색 시 쿼리를 같은 규칙으로 정규화합니다.
+//!
+//! 1. 유니코드 NFC (조합형 한글 `ᄒ ᅡ ᆫ` → `한`)
+//! 2. 보이지 않는 문자(zero-width space, word joiner, BOM, soft hyphen)와 제어 문자 제거
+//!    (줄바꿈과 탭은 유지, ZWJ/ZWNJ는 비교 키에서만 제거)
+//! 3. 줄바꿈 통일 (`\r\n`, `\r`, U+2028/U+2029 → `\n`), 특수 공백 → 일반 공백
+//! 4. 줄 끝 공백 제거 (줄 수와 들여쓰기는 그대로 - 코드 청크, 원본 줄 번호 유지)
+//! 5. 선택: 모든 공백을 공백 하나로 합치기, 소문자 변환
+//!
+//! 문서 본문은 구조를 유지하고(`normalize_document`), 쿼리는 한 줄로 합칩니다(`normalize_query`).
+//! 대소문자는 FTS5(unicode61)가 색인 시 무시하고 임베딩 모델은 구분하므로 본문과 쿼리에서는
+//! 유지하며, 쿼리 ID나 청크 지문처럼 대소문자를 무시해야 하는 키에만 소문자 변환을 씁니다.
+//! ZWNJ/ZWJ(U+200C/U+200D)는 페르시아어/인도계 문자의 글자 모양과 이모지 조합에 쓰이므로
+//! 본문과 쿼리에서는 유지합니다.
+
+use unicode_normalization::UnicodeNormalization;
+
+// ============================================================================
+// Options
+// ============================================================================
+
+/// 정규화 옵션
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NormalizeOptions {
+    /// 줄바꿈을 포함한 모든 공백을 공백 하나로 합치고 앞뒤 공백 제거
+    pub collapse_whitespace: bool,
+    /// 소문자 변환
+    pub lowercase: bool,
+    /// ZWNJ/ZWJ 제거 (글자 모양만 바꾸므로 비교 키에서는 무시)
+    pub strip_joiners: bool,
+}
+
+impl NormalizeOptions {
+    /// 문서 본문/제목 (줄 구조 유지)
+    pub const DOCUMENT: Self = Self {
+        collapse_whitespace: false,
+        lowercase: false,
+        strip_joiners: false,
+    };
+
+    /// 검색 쿼리 (한 줄)
+    pub const QUERY: Self = Self {
+        collapse_whitespace: true,
+        lowercase: false,
+        strip_joiners: false,
+    };
+
+    /// 대소문자를 무시하는 비교 키 (쿼리 ID, 청크 지문)
+    pub const KEY: Self = Self {
+        collapse_whitespace: true,
+        lowercase: true,
+        strip_joiners: true,
+    };
+}
+
+// ============================================================================
+// Functions
+// ============================================================================
+
+/// 텍스트 정규화 (여러 번 적용해도 결과가 같음)
+pub fn normalize(text: &str, options: NormalizeOptions) -> String {
+    let mut cleaned = String::with_capacity(text.len());
+    let mut chars = text.nfc().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\r' => {
+                if chars.peek() != Some(&'\n') {
+                    cleaned.push('\n');
+                }
+            }
+            '\n' | '\t' => cleaned.push(c),
+            '\u{2028}' | '\u{2029}' => cleaned.push('\n'),
+            '\u{200C}' | '\u{200D}' if !options.strip_joiners => cleaned.push(c),
+            c if is_invisible(c) || c.is_control() => {}
+            c if c.is_whitespace() => cleaned.push(' '),
+            c => cleaned.push(c),
+        }
+    }
+
+    let normalized = if options.collapse_whitespace {
+        cleaned.split_whitespace().collect::<Vec<_>>().join(" ")
+    } else {
+        cleaned
+            .split('\n')
+            .map(|line| line.trim_end_matches([' ', '\t']))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    if options.lowercase {
+        normalized.to_lowercase()
+    } else {
+        normalized
+    }
+}
+
+/// 문서 본문/제목 정규화 (`NormalizeOptions::DOCUMENT`)
+pub fn normalize_document(text: &str) -> String {
+    normalize(text, NormalizeOptions::DOCUMENT)
+}
+
+/// 검색 쿼리 정규화 (`NormalizeOptions::QUERY`)
+pub fn normalize_query(text: &str) -> String {
+    normalize(text, NormalizeOptions::QUERY)
+}
+
+/// 폭이 없는 보이지 않는 문자 (zero-width space/non-joiner/joiner, word joiner, BOM, soft hyphen)
+fn is_invisible(c: char) -> bool {
+    matches!(
+        c,
+        '\u{200B}' | '\u{200C}' | '\u{200D}' | '\u{2060}' | '\u{FEFF}' | '\u{00AD}'
+    )
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_document() {
+        // 조합형 한글 → 완성형
+        assert_eq!(normalize_document("\u{1112}\u{1161}\u{11AB}글"), "한글");
+        assert_eq!(
+            normalize_document("\u{FEFF}zero\u{200B}width soft\u{00AD}hyphen"),
+            "zerowidth softhyphen"
+        );
+        assert_eq!(
+            normalize_document("line one  \r\nline\u{00A0}two\r\n\n    indented\x07"),
+            "line one\nline two\n\n    indented"
+        );
+        assert_eq!(normalize_document("a\rb\u{2028}c"), "a\nb\nc");
+        // ZWNJ(페르시아어), ZWJ(이모지 조합)는 유지
+        assert_eq!(normalize_document("می\u{200C}خواهم"), "می\u{200C}خواهم");
+        assert_eq!(
+            normalize_document("\u{1F469}\u{200D}\u{1F4BB}"),
+            "\u{1F469}\u{200D}\u{1F4BB}"
+        );
+        assert_eq!(
+            normalize("می\u{200C}خواهم", NormalizeOptions::KEY),
+            "میخواهم"
+        );
+    }
+
+    #[test]
+    fn test_normalize_query() {
+        assert_eq!(
+            normalize_query("  Rust\u{3000}ownership\n\tborrow\u{200B}ing "),
+            "Rust ownership borrowing"
+        );
+        assert_eq!(
+            normalize("  React\tHOOKS ", NormalizeOptions::KEY),
+            "react hooks"
+        );
+    }
+
+    #[test]
+    fn test_normalize_idempotent() {
+        let text = "# Title\u{00A0}\r\n\r\n  code();\u{200D}  \n\u{1100}\u{1161}";
+        for options in [
+            NormalizeOptions::DOCUMENT,
+            NormalizeOptions::QUERY,
+            NormalizeOptions::KEY,
+        ] {
+            let once = normalize(text, options);
+            assert_eq!(normalize(&once, options), once);
+        }
+    }
+}
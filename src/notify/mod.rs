@@ -0,0 +1,333 @@
+//! 알림 모듈 - `notify`
+//!
+//! 데몬/감시 모드에서 발생한 이벤트(새 문서 수집, 수집 실패, 예약 작업 실패)를
+//! 데스크톱 알림 또는 웹훅(JSON POST)으로 전달하여 무인 동기화의 문제를 드러냅니다.
+//!
+//! ```toml
+//! [notify]
+//! desktop = true
+//! webhook = "https://hooks.slack.com/services/..."
+//! events = ["documents-added", "ingest-failed", "job-failed"]
+//! ```
+//!
+//! 데스크톱 알림은 OS 기본 도구를 사용합니다 (Linux `notify-send`, macOS `osascript`,
+//! Windows PowerShell). 알림 실패는 경고 로그만 남기고 작업을 중단하지 않습니다.
+
+use std::collections::HashMap;
+use std::process::Command;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// 같은 실패 알림을 다시 보내기까지의 최소 간격 (재시도마다 같은 실패가 반복되는 경우)
+const REPEAT_COOLDOWN: Duration = Duration::from_secs(30 * 60);
+
+/// 웹훅 요청 타임아웃
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+// ============================================================================
+// Config
+// ============================================================================
+
+/// 알림 설정 (`[notify]`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotifyConfig {
+    /// 데스크톱 알림 사용
+    pub desktop: bool,
+    /// 이벤트를 JSON으로 POST할 URL
+    pub webhook: Option<String>,
+    /// 알림을 보낼 이벤트 종류
+    pub events: Vec<EventKind>,
+}
+
+impl Default for NotifyConfig {
+    fn default() -> Self {
+        Self {
+            desktop: false,
+            webhook: None,
+            events: vec![
+                EventKind::DocumentsAdded,
+                EventKind::IngestFailed,
+                EventKind::JobFailed,
+            ],
+        }
+    }
+}
+
+// ============================================================================
+// Events
+// ============================================================================
+
+/// 이벤트 종류 (설정의 `events` 필터)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EventKind {
+    DocumentsAdded,
+    IngestFailed,
+    JobFinished,
+    JobFailed,
+}
+
+/// 알림 이벤트
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub enum NotifyEvent {
+    /// 새 문서 수집
+    DocumentsAdded { source: String, count: usize },
+    /// 수집 실패 (감시 폴더의 파일 등)
+    IngestFailed {
+        source: String,
+        count: usize,
+        error: String,
+    },
+    /// 예약 작업 완료
+    JobFinished { job: String, summary: String },
+    /// 예약 작업 실패
+    JobFailed { job: String, error: String },
+}
+
+impl NotifyEvent {
+    pub fn kind(&self) -> EventKind {
+        match self {
+            Self::DocumentsAdded { .. } => EventKind::DocumentsAdded,
+            Self::IngestFailed { .. } => EventKind::IngestFailed,
+            Self::JobFinished { .. } => EventKind::JobFinished,
+            Self::JobFailed { .. } => EventKind::JobFailed,
+        }
+    }
+
+    /// 실패 이벤트인지 (같은 실패는 재시도마다 반복되므로 쿨다운 적용)
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Self::IngestFailed { .. } | Self::JobFailed { .. })
+    }
+
+    /// 알림 제목
+    pub fn title(&self) -> String {
+        match self {
+            Self::DocumentsAdded { .. } => "palank-rag: 새 문서 수집".to_string(),
+            Self::IngestFailed { .. } => "palank-rag: 수집 실패".to_string(),
+            Self::JobFinished { job, .. } => format!("palank-rag: {} 완료", job),
+            Self::JobFailed { job, .. } => format!("palank-rag: {} 실패", job),
+        }
+    }
+
+    /// 알림 본문
+    pub fn message(&self) -> String {
+        match self {
+            Self::DocumentsAdded { source, count } => {
+                format!("{}: 문서 {} 건 추가", source, count)
+            }
+            Self::IngestFailed {
+                source,
+                count,
+                error,
+            } => format!("{}: {} 건 실패 ({})", source, count, error),
+            Self::JobFinished { summary, .. } => summary.clone(),
+            Self::JobFailed { error, .. } => error.clone(),
+        }
+    }
+}
+
+/// 웹훅 본문 (Slack 호환 `text` 필드 포함)
+#[derive(Debug, Serialize)]
+struct WebhookPayload<'a> {
+    #[serde(flatten)]
+    event: &'a NotifyEvent,
+    title: String,
+    text: String,
+    timestamp: String,
+}
+
+impl<'a> WebhookPayload<'a> {
+    fn new(event: &'a NotifyEvent) -> Self {
+        Self {
+            event,
+            title: event.title(),
+            text: format!("{}\n{}", event.title(), event.message()),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+// ============================================================================
+// Notifier
+// ============================================================================
+
+/// 알림 전송기
+pub struct Notifier {
+    config: NotifyConfig,
+    client: reqwest::Client,
+    /// 최근 보낸 알림 (제목+본문 → 시각)
+    recent: Mutex<HashMap<String, Instant>>,
+}
+
+impl Notifier {
+    pub fn new(config: NotifyConfig) -> Self {
+        Self {
+            config,
+            client: reqwest::Client::new(),
+            recent: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 알림 채널이 하나라도 설정되어 있는지
+    pub fn is_enabled(&self) -> bool {
+        self.config.desktop || self.config.webhook.is_some()
+    }
+
+    /// 이벤트 알림 (설정에 없는 종류이거나 최근 같은 실패 알림을 보냈으면 건너뜀)
+    pub async fn notify(&self, event: &NotifyEvent) {
+        if !self.is_enabled() || !self.should_send(event) {
+            return;
+        }
+
+        if self.config.desktop {
+            let (title, message) = (event.title(), event.message());
+            let result =
+                tokio::task::spawn_blocking(move || show_desktop_notification(&title, &message))
+                    .await;
+            match result {
+                Ok(Err(e)) => tracing::warn!("Desktop notification failed: {}", e),
+                Err(e) => tracing::warn!("Desktop notification failed: {}", e),
+                Ok(Ok(())) => {}
+            }
+        }
+
+        if let Some(ref url) = self.config.webhook {
+            let result = self
+                .client
+                .post(url)
+                .timeout(WEBHOOK_TIMEOUT)
+                .json(&WebhookPayload::new(event))
+                .send()
+                .await;
+            match result {
+                Ok(response) if !response.status().is_success() => {
+                    tracing::warn!("Webhook returned {}", response.status())
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Webhook failed: {}", e),
+            }
+        }
+    }
+
+    fn should_send(&self, event: &NotifyEvent) -> bool {
+        if !self.config.events.contains(&event.kind()) {
+            return false;
+        }
+        // 수집/완료 알림은 매번 새 소식이므로 그대로 보냄
+        if !event.is_failure() {
+            return true;
+        }
+
+        let key = format!("{}\n{}", event.title(), event.message());
+        let mut recent = self.recent.lock().unwrap();
+        recent.retain(|_, sent| sent.elapsed() < REPEAT_COOLDOWN);
+        if recent.contains_key(&key) {
+            return false;
+        }
+        recent.insert(key, Instant::now());
+        true
+    }
+}
+
+/// OS 기본 도구로 데스크톱 알림 표시
+///
+/// 제목/본문은 환경 변수로 넘겨 스크립트 인용 문제를 피합니다.
+fn show_desktop_notification(title: &str, message: &str) -> std::io::Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        let mut c = Command::new("osascript");
+        c.args([
+            "-e",
+            "display notification (system attribute \"PALANK_NOTIFY_MESSAGE\") \
+             with title (system attribute \"PALANK_NOTIFY_TITLE\")",
+        ]);
+        c
+    } else if cfg!(windows) {
+        let mut c = Command::new("powershell");
+        c.args([
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            "Add-Type -AssemblyName System.Windows.Forms; \
+             $n = New-Object System.Windows.Forms.NotifyIcon; \
+             $n.Icon = [System.Drawing.SystemIcons]::Information; \
+             $n.Visible = $true; \
+             $n.ShowBalloonTip(10000, $env:PALANK_NOTIFY_TITLE, $env:PALANK_NOTIFY_MESSAGE, 'Info'); \
+             Start-Sleep -Seconds 10; $n.Dispose()",
+        ]);
+        c
+    } else {
+        let mut c = Command::new("notify-send");
+        c.args(["--app-name=palank-rag", title, message]);
+        c
+    };
+
+    let status = command
+        .env("PALANK_NOTIFY_TITLE", title)
+        .env("PALANK_NOTIFY_MESSAGE", message)
+        .status()?;
+    if !status.success() {
+        return Err(std::io::Error::other(format!(
+            "notifier exited with {}",
+            status
+        )));
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_filter_and_cooldown() {
+        let notifier = Notifier::new(NotifyConfig {
+            desktop: true,
+            ..Default::default()
+        });
+
+        let added = NotifyEvent::DocumentsAdded {
+            source: "/shots".to_string(),
+            count: 2,
+        };
+        assert!(notifier.should_send(&added));
+        // 수집 알림은 같은 내용이어도 매번 보냄
+        assert!(notifier.should_send(&added));
+
+        // 같은 실패 알림은 쿨다운 동안 다시 보내지 않음
+        let failed = NotifyEvent::IngestFailed {
+            source: "/shots".to_string(),
+            count: 1,
+            error: "a.png: quota".to_string(),
+        };
+        assert!(notifier.should_send(&failed));
+        assert!(!notifier.should_send(&failed));
+
+        // 기본 설정에서 작업 완료는 알리지 않음
+        let finished = NotifyEvent::JobFinished {
+            job: "backup".to_string(),
+            summary: "ok".to_string(),
+        };
+        assert!(!notifier.should_send(&finished));
+    }
+
+    #[test]
+    fn test_webhook_payload() {
+        let event = NotifyEvent::JobFailed {
+            job: "docs-refresh".to_string(),
+            error: "timeout".to_string(),
+        };
+        let json = serde_json::to_value(WebhookPayload::new(&event)).unwrap();
+
+        assert_eq!(json["event"], "job-failed");
+        assert_eq!(json["job"], "docs-refresh");
+        assert_eq!(json["text"], "palank-rag: docs-refresh 실패\ntimeout");
+    }
+}
@@ -0,0 +1,177 @@
+//! 결과 열기 - `open <번호>` / `open --id <문서 ID>`
+//!
+//! 마지막 `query` 결과 목록을 데이터 디렉토리에 저장해 두고, 번호로 고른 결과의 원본을 엽니다.
+//! http(s) URL은 기본 브라우저로, `file://` 문서는 `$EDITOR`(없으면 OS 기본 앱)로 엽니다.
+//! 소스 코드 결과는 일치한 청크의 시작 줄로 바로 이동합니다.
+
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::knowledge::HybridSearchResult;
+
+/// 마지막 검색 결과 목록 (데이터 디렉토리 기준)
+pub const LAST_RESULTS_FILE: &str = "last-results.json";
+
+/// `--goto 파일:줄` 형식을 쓰는 편집기 (VS Code 계열)
+const GOTO_EDITORS: &[&str] = &["code", "code-insiders", "codium", "cursor"];
+
+// ============================================================================
+// OpenTarget
+// ============================================================================
+
+/// 열 수 있는 검색 결과
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpenTarget {
+    pub doc_id: i64,
+    pub url: String,
+    /// 이동할 줄 (소스 코드 결과)
+    pub line: Option<usize>,
+}
+
+impl OpenTarget {
+    /// 검색 결과로부터 생성
+    pub fn from_result(result: &HybridSearchResult) -> Self {
+        Self {
+            doc_id: result.doc_id,
+            url: result.url.clone(),
+            line: result.location.as_ref().map(|l| l.start_line),
+        }
+    }
+
+    /// 실행할 명령 (프로그램, 인자)
+    ///
+    /// `editor`는 `$EDITOR` 값이며 인자를 포함할 수 있습니다 (예: `code -w`).
+    pub fn command(&self, editor: Option<&str>) -> Result<(String, Vec<String>)> {
+        if self.url.starts_with("http://") || self.url.starts_with("https://") {
+            return Ok(system_opener(&self.url));
+        }
+
+        let Some(path) = self.url.strip_prefix("file://") else {
+            bail!("열 수 있는 원본이 없습니다 (URL: {})", self.url);
+        };
+        let path = path.split('#').next().unwrap_or(path);
+
+        let Some(mut words) = editor.map(str::split_whitespace) else {
+            return Ok(system_opener(path));
+        };
+        let Some(program) = words.next() else {
+            return Ok(system_opener(path));
+        };
+        let mut args: Vec<String> = words.map(str::to_string).collect();
+
+        let name = Path::new(program)
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or(program);
+        match self.line {
+            Some(line) if GOTO_EDITORS.contains(&name) => {
+                args.extend(["--goto".to_string(), format!("{}:{}", path, line)]);
+            }
+            Some(line) => args.extend([format!("+{}", line), path.to_string()]),
+            None => args.push(path.to_string()),
+        }
+
+        Ok((program.to_string(), args))
+    }
+
+    /// 원본 열기 (편집기는 종료될 때까지 대기)
+    pub fn open(&self, editor: Option<&str>) -> Result<()> {
+        let (program, args) = self.command(editor)?;
+        let status = Command::new(&program)
+            .args(&args)
+            .status()
+            .with_context(|| format!("Failed to run {}", program))?;
+        if !status.success() {
+            bail!("{} exited with {}", program, status);
+        }
+        Ok(())
+    }
+}
+
+/// OS 기본 앱으로 여는 명령
+///
+/// Windows에서는 `cmd /C start`를 쓰지 않습니다. cmd가 URL의 `&`, `|`, `^`를 해석하여
+/// 쿼리 문자열이 잘리거나 저장된 URL로 명령이 실행될 수 있기 때문입니다.
+fn system_opener(target: &str) -> (String, Vec<String>) {
+    if cfg!(target_os = "macos") {
+        ("open".to_string(), vec![target.to_string()])
+    } else if cfg!(windows) {
+        (
+            "rundll32".to_string(),
+            vec!["url.dll,FileProtocolHandler".into(), target.to_string()],
+        )
+    } else {
+        ("xdg-open".to_string(), vec![target.to_string()])
+    }
+}
+
+// ============================================================================
+// Last Results
+// ============================================================================
+
+/// 검색 결과 목록 저장 (`open <번호>`용)
+pub fn save_last_results(path: &Path, results: &[HybridSearchResult]) -> Result<()> {
+    let targets: Vec<OpenTarget> = results.iter().map(OpenTarget::from_result).collect();
+    let json = serde_json::to_string_pretty(&targets)?;
+    std::fs::write(path, json).with_context(|| format!("Failed to write {:?}", path))
+}
+
+/// 저장된 검색 결과 목록 읽기
+pub fn load_last_results(path: &Path) -> Result<Vec<OpenTarget>> {
+    let json =
+        std::fs::read_to_string(path).with_context(|| format!("Failed to read {:?}", path))?;
+    serde_json::from_str(&json).with_context(|| format!("Failed to parse {:?}", path))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(url: &str, line: Option<usize>) -> OpenTarget {
+        OpenTarget {
+            doc_id: 1,
+            url: url.to_string(),
+            line,
+        }
+    }
+
+    #[test]
+    fn test_editor_command() {
+        let code = target("file:///repo/src/lib.rs", Some(42));
+        assert_eq!(
+            code.command(Some("vim")).unwrap(),
+            ("vim".to_string(), vec!["+42".to_string(), "/repo/src/lib.rs".to_string()])
+        );
+        assert_eq!(
+            code.command(Some("/usr/bin/code -w")).unwrap().1,
+            vec!["-w", "--goto", "/repo/src/lib.rs:42"]
+        );
+
+        let notes = target("file:///notes/todo.md", None);
+        assert_eq!(notes.command(Some("nano")).unwrap().1, vec!["/notes/todo.md"]);
+    }
+
+    #[test]
+    fn test_unsupported_url() {
+        assert!(target("clipboard://2026-10-17", None).command(None).is_err());
+        let (_, args) = target("https://example.com", None).command(Some("vim")).unwrap();
+        assert_eq!(args.last().unwrap(), "https://example.com");
+    }
+
+    #[test]
+    fn test_last_results_roundtrip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(LAST_RESULTS_FILE);
+
+        save_last_results(&path, &[]).unwrap();
+        assert!(load_last_results(&path).unwrap().is_empty());
+        assert!(load_last_results(&dir.path().join("missing.json")).is_err());
+    }
+}
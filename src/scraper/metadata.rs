@@ -0,0 +1,152 @@
+//! 페이지 메타데이터 추출 - OpenGraph, `<meta>`, canonical 링크
+//!
+//! 설명, 작성자, 게시일, canonical URL을 문서와 함께 저장합니다.
+//! 게시일은 `list --since` 필터에 사용됩니다.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// 스크랩한 페이지의 메타데이터
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PageMetadata {
+    /// `og:description` 또는 `<meta name="description">`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// `<meta name="author">` 또는 `article:author`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// `article:published_time` 등 게시 시각
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub published_at: Option<DateTime<Utc>>,
+    /// `<link rel="canonical">` (절대 URL)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub canonical_url: Option<String>,
+}
+
+impl PageMetadata {
+    /// 추출된 항목이 하나도 없는지
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// HTML 문서에서 메타데이터 추출 (상대 canonical URL은 `page_url` 기준으로 변환)
+    pub fn from_html(document: &Html, page_url: &str) -> Self {
+        let description = meta_content(
+            document,
+            &[
+                r#"meta[property="og:description"]"#,
+                r#"meta[name="description"]"#,
+            ],
+        );
+        let author = meta_content(
+            document,
+            &[
+                r#"meta[name="author"]"#,
+                r#"meta[property="article:author"]"#,
+            ],
+        );
+        let published_at = meta_content(
+            document,
+            &[
+                r#"meta[property="article:published_time"]"#,
+                r#"meta[itemprop="datePublished"]"#,
+                r#"meta[name="date"]"#,
+            ],
+        )
+        .and_then(|value| parse_published(&value));
+        let canonical_url = first_attr(document, r#"link[rel="canonical"]"#, "href")
+            .and_then(|href| Url::parse(page_url).ok()?.join(&href).ok())
+            .map(String::from);
+
+        Self {
+            description,
+            author,
+            published_at,
+            canonical_url,
+        }
+    }
+}
+
+/// 선택자 순서대로 첫 번째로 값이 있는 `content` 속성
+fn meta_content(document: &Html, selectors: &[&str]) -> Option<String> {
+    selectors
+        .iter()
+        .find_map(|selector| first_attr(document, selector, "content"))
+}
+
+/// 선택자에 맞는 요소 중 첫 번째로 비어 있지 않은 속성 값
+fn first_attr(document: &Html, selector: &str, attr: &str) -> Option<String> {
+    let selector = Selector::parse(selector).ok()?;
+    document
+        .select(&selector)
+        .filter_map(|e| e.value().attr(attr))
+        .map(str::trim)
+        .find(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// 게시 시각 파싱 (RFC3339 또는 날짜만 있는 `YYYY-MM-DD`)
+fn parse_published(value: &str) -> Option<DateTime<Utc>> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    let date = value.get(..10).unwrap_or(value);
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|dt| dt.and_utc())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_html() {
+        let html = r#"
+            <html><head>
+                <meta name="description" content="fallback">
+                <meta property="og:description" content="Streams in async Rust">
+                <meta name="author" content="Jane Doe">
+                <meta property="article:published_time" content="2024-03-05T09:30:00+09:00">
+                <link rel="canonical" href="/guide/streams">
+            </head><body></body></html>
+        "#;
+        let metadata =
+            PageMetadata::from_html(&Html::parse_document(html), "https://example.com/a?b=1");
+
+        assert_eq!(
+            metadata.description.as_deref(),
+            Some("Streams in async Rust")
+        );
+        assert_eq!(metadata.author.as_deref(), Some("Jane Doe"));
+        assert_eq!(
+            metadata.published_at.unwrap().to_rfc3339(),
+            "2024-03-05T00:30:00+00:00"
+        );
+        assert_eq!(
+            metadata.canonical_url.as_deref(),
+            Some("https://example.com/guide/streams")
+        );
+    }
+
+    #[test]
+    fn test_date_only_and_empty() {
+        let html = r#"<html><head><meta name="date" content="2023-11-02"></head></html>"#;
+        let metadata = PageMetadata::from_html(&Html::parse_document(html), "https://example.com");
+        assert_eq!(
+            metadata.published_at.unwrap().to_rfc3339(),
+            "2023-11-02T00:00:00+00:00"
+        );
+
+        let empty = PageMetadata::from_html(&Html::parse_document("<p>x</p>"), "https://a.dev");
+        assert!(empty.is_empty());
+    }
+}
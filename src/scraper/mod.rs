@@ -5,9 +5,19 @@
 //! palan-k의 복잡한 ContentClassifier, DomainSelectors, RateLimiter 등을 제거하고
 //! 순수 HTML 콘텐츠 추출에만 집중합니다.
 
+mod metadata;
+mod policy;
+
+pub use metadata::PageMetadata;
+pub use policy::{is_private_ip, PolicyResolver, PolicyViolation, UrlPolicy};
+
+use std::sync::Arc;
+
 use anyhow::{Context, Result};
 use scraper::{Html, Selector};
 
+use crate::extractor::{TitleRules, TitleSource};
+
 /// 스크랩된 콘텐츠
 #[derive(Debug, Clone)]
 pub struct ScrapedContent {
@@ -17,27 +27,73 @@ pub struct ScrapedContent {
     pub content: String,
     /// 원본 URL
     pub url: String,
+    /// OpenGraph 등 페이지 메타데이터
+    pub metadata: PageMetadata,
 }
 
+/// 리다이렉트 최대 횟수
+const MAX_REDIRECTS: usize = 10;
+
 /// 웹 스크래퍼
 pub struct WebScraper {
     client: reqwest::Client,
+    policy: UrlPolicy,
+    titles: TitleRules,
 }
 
 impl WebScraper {
-    /// 새 스크래퍼 생성
+    /// 새 스크래퍼 생성 (기본 정책: 내부망 주소 차단)
     pub fn new() -> Result<Self> {
+        Self::with_policy(UrlPolicy::default())
+    }
+
+    /// URL 정책을 지정하여 생성
+    ///
+    /// 리다이렉트 대상도 같은 정책으로 검사하며, 호스트 이름의 주소는 연결할 때
+    /// `PolicyResolver`로 검사합니다.
+    pub fn with_policy(policy: UrlPolicy) -> Result<Self> {
+        let redirect_policy = policy.clone();
         let client = reqwest::Client::builder()
             .user_agent("palank-rag/0.1")
             .timeout(std::time::Duration::from_secs(30))
+            .dns_resolver(Arc::new(PolicyResolver::new(policy.clone())))
+            .redirect(reqwest::redirect::Policy::custom(move |attempt| {
+                if attempt.previous().len() >= MAX_REDIRECTS {
+                    attempt.error("too many redirects")
+                } else if let Err(violation) = redirect_policy.check_url(attempt.url()) {
+                    attempt.error(violation)
+                } else {
+                    attempt.follow()
+                }
+            }))
             .build()
             .context("HTTP 클라이언트 생성 실패")?;
 
-        Ok(Self { client })
+        Ok(Self {
+            client,
+            policy,
+            titles: TitleRules::default(),
+        })
+    }
+
+    /// 제목 결정 규칙 지정 (기본: `<title>` → `<h1>`)
+    pub fn with_titles(mut self, titles: TitleRules) -> Self {
+        self.titles = titles;
+        self
+    }
+
+    /// 적용 중인 URL 정책
+    pub fn policy(&self) -> &UrlPolicy {
+        &self.policy
     }
 
     /// URL에서 콘텐츠 추출
+    ///
+    /// 요청 전에 URL 정책을 검사하며, 위반 시 요청하지 않고 에러를 반환합니다.
+    /// 내부 주소로 풀리는 호스트는 연결 단계에서 요청 실패로 끝납니다.
     pub async fn scrape(&self, url: &str) -> Result<ScrapedContent> {
+        self.policy.check(url).context("URL 정책 위반")?;
+
         tracing::info!("Scraping: {}", url);
 
         let response = self
@@ -57,36 +113,31 @@ impl WebScraper {
         // 본문 추출
         let content = self.extract_content(&document);
 
+        // 설명/작성자/게시일/canonical URL
+        let metadata = PageMetadata::from_html(&document, url);
+
         Ok(ScrapedContent {
             title,
             content,
             url: url.to_string(),
+            metadata,
         })
     }
 
-    /// 제목 추출
+    /// 제목 추출 (`TitleRules` 우선순위)
     fn extract_title(&self, document: &Html) -> Option<String> {
-        // <title> 태그
-        if let Ok(title_selector) = Selector::parse("title") {
-            if let Some(element) = document.select(&title_selector).next() {
-                let title = element.text().collect::<String>().trim().to_string();
-                if !title.is_empty() {
-                    return Some(title);
-                }
-            }
-        }
-
-        // <h1> 태그
-        if let Ok(h1_selector) = Selector::parse("h1") {
-            if let Some(element) = document.select(&h1_selector).next() {
-                let title = element.text().collect::<String>().trim().to_string();
-                if !title.is_empty() {
-                    return Some(title);
-                }
+        self.titles.pick(|source| match source {
+            TitleSource::HtmlTitle => select_text(document, "title"),
+            TitleSource::H1 => select_text(document, "h1"),
+            TitleSource::OgTitle => {
+                let selector = Selector::parse(r#"meta[property="og:title"]"#).ok()?;
+                document
+                    .select(&selector)
+                    .find_map(|e| e.value().attr("content"))
+                    .map(str::to_string)
             }
-        }
-
-        None
+            TitleSource::FrontMatter | TitleSource::Filename => None,
+        })
     }
 
     /// 본문 추출 (HTML 태그 제거)
@@ -146,17 +197,21 @@ impl WebScraper {
 }
 
 impl Default for WebScraper {
+    /// 기본 정책의 스크래퍼 (정책 검사 없는 클라이언트로 폴백하지 않음)
     fn default() -> Self {
-        Self::new().unwrap_or_else(|e| {
-            tracing::error!("WebScraper 생성 실패: {}", e);
-            // 최소한의 클라이언트로 폴백
-            Self {
-                client: reqwest::Client::new(),
-            }
-        })
+        Self::new().expect("WebScraper 생성 실패")
     }
 }
 
+/// 선택자에 맞는 첫 요소 중 텍스트가 있는 것
+fn select_text(document: &Html, selector: &str) -> Option<String> {
+    let selector = Selector::parse(selector).ok()?;
+    document
+        .select(&selector)
+        .map(|e| e.text().collect::<String>().trim().to_string())
+        .find(|text| !text.is_empty())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -195,6 +250,39 @@ mod tests {
         assert_eq!(title, Some("H1 Heading".to_string()));
     }
 
+    #[test]
+    fn test_extract_title_configured_order() {
+        let html = r#"
+            <html>
+                <head>
+                    <title>Docs</title>
+                    <meta property="og:title" content="Async Streams">
+                </head>
+                <body><h1>Overview</h1></body>
+            </html>
+        "#;
+        let document = Html::parse_document(html);
+
+        let og_first = WebScraper::new().unwrap().with_titles(TitleRules {
+            order: vec![TitleSource::OgTitle, TitleSource::HtmlTitle],
+            ignore: Vec::new(),
+        });
+        assert_eq!(
+            og_first.extract_title(&document),
+            Some("Async Streams".to_string())
+        );
+
+        // 사이트 공통 제목은 무시하고 다음 후보 사용
+        let ignore_docs = WebScraper::new().unwrap().with_titles(TitleRules {
+            ignore: vec!["Docs".to_string()],
+            ..Default::default()
+        });
+        assert_eq!(
+            ignore_docs.extract_title(&document),
+            Some("Overview".to_string())
+        );
+    }
+
     #[test]
     fn test_extract_content_from_article() {
         let scraper = WebScraper::new().expect("scraper creation failed");
@@ -236,6 +324,13 @@ mod tests {
         assert!(content.contains("Main content area"));
     }
 
+    #[tokio::test]
+    async fn test_scrape_rejects_private_url() {
+        let scraper = WebScraper::new().expect("scraper creation failed");
+        let err = scraper.scrape("http://127.0.0.1:9/").await.unwrap_err();
+        assert!(err.downcast_ref::<PolicyViolation>().is_some());
+    }
+
     #[test]
     fn test_default_implementation() {
         let scraper = WebScraper::default();
@@ -0,0 +1,294 @@
+//! URL 수집 정책 - 허용/차단 목록
+//!
+//! 스크랩/크롤 전에 URL을 검사하여 내부망 주소나 허용되지 않은 도메인으로의
+//! 요청을 막습니다 (SSRF 방지). 호스트 이름이 내부 IP로 풀리는 경우는 HTTP 클라이언트가
+//! 연결할 때 쓰는 DNS 조회(`PolicyResolver`)에서 차단하므로 리다이렉트 대상에도 적용됩니다.
+//! 설정: `~/.palank-rag/config.toml`의 `[url_policy]`
+//!
+//! ```toml
+//! [url_policy]
+//! allow_domains = ["docs.rs", "rust-lang.org"]   # 비어 있으면 모든 도메인 허용
+//! deny_domains = ["internal.example.com"]
+//! allow_private_networks = false
+//! ```
+
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+use hyper::client::connect::dns::Name;
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use url::{Host, Url};
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// 정책 위반 사유
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PolicyViolation {
+    #[error("잘못된 URL: {0}")]
+    InvalidUrl(String),
+
+    #[error("지원하지 않는 스킴: {0} (http/https만 허용)")]
+    UnsupportedScheme(String),
+
+    #[error("차단된 도메인: {0}")]
+    DeniedDomain(String),
+
+    #[error("허용 목록에 없는 도메인: {0}")]
+    NotAllowedDomain(String),
+
+    #[error("내부망 주소는 허용되지 않습니다: {0}")]
+    PrivateAddress(String),
+}
+
+/// URL 수집 정책
+///
+/// 도메인 항목은 해당 도메인과 모든 하위 도메인에 적용됩니다
+/// (`rust-lang.org`는 `doc.rust-lang.org`도 포함).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UrlPolicy {
+    /// 허용 도메인 목록 (비어 있으면 차단 목록 외 모두 허용)
+    pub allow_domains: Vec<String>,
+    /// 차단 도메인 목록 (허용 목록보다 우선)
+    pub deny_domains: Vec<String>,
+    /// 내부망/루프백 주소 허용 여부 (기본: 차단)
+    pub allow_private_networks: bool,
+}
+
+// ============================================================================
+// UrlPolicy
+// ============================================================================
+
+impl UrlPolicy {
+    /// URL 검사 (DNS 조회 없이)
+    ///
+    /// 스킴, 도메인 허용/차단 목록, IP 리터럴 주소를 검사합니다.
+    /// 호스트 이름이 내부 IP로 풀리는 경우는 연결 시 `PolicyResolver`가 차단합니다.
+    pub fn check(&self, url: &str) -> Result<Url, PolicyViolation> {
+        let parsed = Url::parse(url).map_err(|e| PolicyViolation::InvalidUrl(e.to_string()))?;
+        self.check_url(&parsed)?;
+        Ok(parsed)
+    }
+
+    /// 파싱된 URL 검사
+    pub fn check_url(&self, url: &Url) -> Result<(), PolicyViolation> {
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(PolicyViolation::UnsupportedScheme(url.scheme().to_string()));
+        }
+
+        let host = url
+            .host()
+            .ok_or_else(|| PolicyViolation::InvalidUrl(format!("호스트 없음: {}", url)))?;
+
+        match host {
+            Host::Domain(domain) => {
+                let domain = domain.trim_end_matches('.').to_lowercase();
+
+                if self.deny_domains.iter().any(|d| domain_matches(&domain, d)) {
+                    return Err(PolicyViolation::DeniedDomain(domain));
+                }
+                if !self.allow_domains.is_empty()
+                    && !self.allow_domains.iter().any(|d| domain_matches(&domain, d))
+                {
+                    return Err(PolicyViolation::NotAllowedDomain(domain));
+                }
+                if !self.allow_private_networks
+                    && (domain == "localhost" || domain.ends_with(".localhost"))
+                {
+                    return Err(PolicyViolation::PrivateAddress(domain));
+                }
+            }
+            Host::Ipv4(ip) => self.check_ip(IpAddr::V4(ip))?,
+            Host::Ipv6(ip) => self.check_ip(IpAddr::V6(ip))?,
+        }
+
+        // IP 리터럴은 도메인 허용 목록에 포함될 수 없음
+        if !self.allow_domains.is_empty() && !matches!(host, Host::Domain(_)) {
+            return Err(PolicyViolation::NotAllowedDomain(host.to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// IP 주소 검사
+    pub fn check_ip(&self, ip: IpAddr) -> Result<(), PolicyViolation> {
+        if !self.allow_private_networks && is_private_ip(ip) {
+            return Err(PolicyViolation::PrivateAddress(ip.to_string()));
+        }
+        Ok(())
+    }
+
+    /// DNS 조회 결과 검사 (하나라도 내부 주소면 위반)
+    pub fn check_addrs(&self, addrs: &[SocketAddr]) -> Result<(), PolicyViolation> {
+        for addr in addrs {
+            self.check_ip(addr.ip())?;
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// PolicyResolver
+// ============================================================================
+
+/// URL 정책을 적용하는 DNS 리졸버
+///
+/// HTTP 클라이언트가 연결에 쓰는 바로 그 조회 결과를 검사하므로, 사전 조회와 연결 사이에
+/// 레코드가 바뀌는 호스트나 리다이렉트로 넘어간 호스트도 내부 주소로는 연결되지 않습니다.
+/// 조회 실패는 요청 실패로 처리합니다.
+#[derive(Debug, Clone)]
+pub struct PolicyResolver {
+    policy: UrlPolicy,
+}
+
+impl PolicyResolver {
+    pub fn new(policy: UrlPolicy) -> Self {
+        Self { policy }
+    }
+}
+
+impl Resolve for PolicyResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let policy = self.policy.clone();
+        Box::pin(async move {
+            // 포트는 클라이언트가 URL 기준으로 다시 지정함
+            let addrs: Vec<SocketAddr> =
+                tokio::net::lookup_host((name.as_str(), 0)).await?.collect();
+            policy.check_addrs(&addrs)?;
+            Ok::<Addrs, Box<dyn std::error::Error + Send + Sync>>(Box::new(addrs.into_iter()))
+        })
+    }
+}
+
+/// 도메인이 패턴과 같거나 하위 도메인인지 확인
+fn domain_matches(domain: &str, pattern: &str) -> bool {
+    let pattern = pattern.trim().trim_start_matches("*.").trim_end_matches('.').to_lowercase();
+    if pattern.is_empty() {
+        return false;
+    }
+    domain == pattern || domain.ends_with(&format!(".{}", pattern))
+}
+
+/// 내부망/루프백/링크 로컬 등 외부에서 접근하면 안 되는 주소인지 확인
+pub fn is_private_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_private_ipv4(ip),
+        IpAddr::V6(ip) => {
+            if let Some(v4) = ip.to_ipv4_mapped() {
+                return is_private_ipv4(v4);
+            }
+            let first = ip.segments()[0];
+            ip.is_loopback()
+                || ip.is_unspecified()
+                || (first & 0xfe00) == 0xfc00 // fc00::/7 (unique local)
+                || (first & 0xffc0) == 0xfe80 // fe80::/10 (link local)
+        }
+    }
+}
+
+fn is_private_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local() // 169.254.0.0/16 (클라우드 메타데이터 포함)
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || (a == 100 && (64..128).contains(&b)) // 100.64.0.0/10 (CGNAT)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_policy_blocks_private_addresses() {
+        let policy = UrlPolicy::default();
+
+        assert!(policy.check("https://docs.rs/serde").is_ok());
+        for url in [
+            "http://127.0.0.1:8080/",
+            "http://10.0.0.5/admin",
+            "http://169.254.169.254/latest/meta-data",
+            "http://[::1]/",
+            "http://[::ffff:192.168.0.1]/",
+            "http://localhost:3000/",
+        ] {
+            assert!(
+                matches!(policy.check(url), Err(PolicyViolation::PrivateAddress(_))),
+                "{} should be blocked",
+                url
+            );
+        }
+
+        assert!(matches!(
+            policy.check("file:///etc/passwd"),
+            Err(PolicyViolation::UnsupportedScheme(_))
+        ));
+    }
+
+    #[test]
+    fn test_allow_and_deny_domains() {
+        let policy = UrlPolicy {
+            allow_domains: vec!["rust-lang.org".to_string()],
+            deny_domains: vec!["blog.rust-lang.org".to_string()],
+            allow_private_networks: false,
+        };
+
+        assert!(policy.check("https://doc.rust-lang.org/std/").is_ok());
+        assert!(policy.check("https://rust-lang.org/").is_ok());
+        assert!(matches!(
+            policy.check("https://blog.rust-lang.org/"),
+            Err(PolicyViolation::DeniedDomain(_))
+        ));
+        assert!(matches!(
+            policy.check("https://evil-rust-lang.org/"),
+            Err(PolicyViolation::NotAllowedDomain(_))
+        ));
+        assert!(matches!(
+            policy.check("https://93.184.216.34/"),
+            Err(PolicyViolation::NotAllowedDomain(_))
+        ));
+    }
+
+    #[test]
+    fn test_check_addrs() {
+        let policy = UrlPolicy::default();
+        let public: SocketAddr = "93.184.216.34:443".parse().unwrap();
+        let metadata: SocketAddr = "169.254.169.254:80".parse().unwrap();
+
+        assert!(policy.check_addrs(&[public]).is_ok());
+        assert!(matches!(
+            policy.check_addrs(&[public, metadata]),
+            Err(PolicyViolation::PrivateAddress(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_resolver_blocks_private_hosts() {
+        let localhost: Name = "localhost".parse().unwrap();
+        let resolver = PolicyResolver::new(UrlPolicy::default());
+        assert!(resolver.resolve(localhost.clone()).await.is_err());
+
+        let resolver = PolicyResolver::new(UrlPolicy {
+            allow_private_networks: true,
+            ..Default::default()
+        });
+        assert!(resolver.resolve(localhost).await.is_ok());
+    }
+
+    #[test]
+    fn test_allow_private_networks() {
+        let policy = UrlPolicy {
+            allow_private_networks: true,
+            ..Default::default()
+        };
+        assert!(policy.check("http://192.168.1.10/wiki").is_ok());
+    }
+}
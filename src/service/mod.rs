@@ -0,0 +1,272 @@
+//! 서비스 등록 모듈 - `service`
+//!
+//! `daemon`(또는 `watch <DIR>`) 명령을 로그인 시 자동으로 실행되도록 등록합니다.
+//! - Linux: systemd 사용자 유닛 (`~/.config/systemd/user/palank-rag.service`)
+//! - Windows: 작업 스케줄러 로그온 작업 (관리자 권한 없이 등록 가능한 방식,
+//!   SCM 서비스가 아니므로 로그인한 동안에만 실행됨)
+//!
+//! 출력은 `~/.palank-rag/logs/daemon.log`에 추가 기록됩니다.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+use crate::knowledge::get_data_dir;
+
+/// 서비스(유닛/작업) 이름
+pub const SERVICE_NAME: &str = "palank-rag";
+
+/// 로그 디렉토리 이름 (데이터 디렉토리 기준)
+pub const LOGS_DIR: &str = "logs";
+
+// ============================================================================
+// Service Spec
+// ============================================================================
+
+/// 등록할 실행 명령
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceSpec {
+    /// 실행 파일 경로
+    pub exe: PathBuf,
+    /// 명령 인자 (예: `["daemon"]`)
+    pub args: Vec<String>,
+    /// 로그 파일 (표준 출력/오류 추가 기록)
+    pub log_path: PathBuf,
+}
+
+impl ServiceSpec {
+    /// 현재 실행 파일로 명령 구성
+    pub fn current(args: Vec<String>) -> Result<Self> {
+        let exe = std::env::current_exe().context("Failed to locate current executable")?;
+        Ok(Self {
+            exe,
+            args,
+            log_path: log_path(),
+        })
+    }
+}
+
+/// 서비스 로그 파일 경로 (`~/.palank-rag/logs/daemon.log`)
+pub fn log_path() -> PathBuf {
+    get_data_dir().join(LOGS_DIR).join("daemon.log")
+}
+
+// ============================================================================
+// systemd (Linux)
+// ============================================================================
+
+/// systemd 사용자 유닛 파일 경로
+pub fn systemd_unit_path() -> Result<PathBuf> {
+    let config_dir = dirs::config_dir().context("Cannot determine config directory")?;
+    Ok(config_dir
+        .join("systemd")
+        .join("user")
+        .join(format!("{}.service", SERVICE_NAME)))
+}
+
+/// systemd 사용자 유닛 내용
+pub fn systemd_unit(spec: &ServiceSpec) -> String {
+    let exec = std::iter::once(spec.exe.to_string_lossy().into_owned())
+        .chain(spec.args.iter().cloned())
+        .map(|arg| systemd_quote(&arg))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let log = spec.log_path.display();
+
+    format!(
+        "[Unit]\n\
+         Description=palank-rag knowledge base maintenance\n\
+         After=network-online.target\n\
+         \n\
+         [Service]\n\
+         Type=simple\n\
+         ExecStart={exec}\n\
+         Restart=on-failure\n\
+         RestartSec=30\n\
+         StandardOutput=append:{log}\n\
+         StandardError=append:{log}\n\
+         \n\
+         [Install]\n\
+         WantedBy=default.target\n"
+    )
+}
+
+/// ExecStart 인자 인용 (공백, 따옴표, `%` 지정자 이스케이프)
+fn systemd_quote(arg: &str) -> String {
+    let escaped = arg
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('%', "%%");
+    if escaped.is_empty() || escaped.contains(char::is_whitespace) || escaped != arg {
+        format!("\"{}\"", escaped)
+    } else {
+        escaped
+    }
+}
+
+// ============================================================================
+// Task Scheduler (Windows)
+// ============================================================================
+
+/// 작업 스케줄러가 실행할 배치 파일 경로 (`~/.palank-rag/palank-rag-service.cmd`)
+pub fn windows_script_path() -> PathBuf {
+    get_data_dir().join(format!("{}-service.cmd", SERVICE_NAME))
+}
+
+/// 배치 파일 내용 (출력을 로그 파일에 추가)
+///
+/// 배치 파일은 OEM 코드 페이지로 읽히므로 주석은 ASCII로 둡니다.
+pub fn windows_script(spec: &ServiceSpec) -> String {
+    // 배치 파일에서 `%`는 변수 확장이므로 `%%`로
+    let quote = |s: &str| format!("\"{}\"", s.replace('%', "%%"));
+    let command = std::iter::once(quote(&spec.exe.to_string_lossy()))
+        .chain(spec.args.iter().map(|arg| quote(arg)))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    format!(
+        "@echo off\r\n\
+         rem Generated by `palank-rag service install`\r\n\
+         {} >> {} 2>&1\r\n",
+        command,
+        quote(&spec.log_path.to_string_lossy())
+    )
+}
+
+// ============================================================================
+// Install / Uninstall
+// ============================================================================
+
+/// 서비스 등록 후 바로 시작. 생성한 유닛/스크립트 파일 경로를 반환합니다.
+pub fn install(spec: &ServiceSpec) -> Result<PathBuf> {
+    if let Some(log_dir) = spec.log_path.parent() {
+        std::fs::create_dir_all(log_dir)
+            .with_context(|| format!("Failed to create log directory: {:?}", log_dir))?;
+    }
+
+    if cfg!(target_os = "linux") {
+        let unit_path = systemd_unit_path()?;
+        write_file(&unit_path, &systemd_unit(spec))?;
+        systemctl(&["daemon-reload"])?;
+        systemctl(&["enable", "--now", &format!("{}.service", SERVICE_NAME)])?;
+        Ok(unit_path)
+    } else if cfg!(windows) {
+        let script_path = windows_script_path();
+        write_file(&script_path, &windows_script(spec))?;
+        let script = format!("\"{}\"", script_path.display());
+        run(
+            "schtasks",
+            &[
+                "/Create",
+                "/F",
+                "/SC",
+                "ONLOGON",
+                "/RL",
+                "LIMITED",
+                "/TN",
+                SERVICE_NAME,
+                "/TR",
+                &script,
+            ],
+        )?;
+        run("schtasks", &["/Run", "/TN", SERVICE_NAME])?;
+        Ok(script_path)
+    } else {
+        anyhow::bail!("Service install is supported on Linux (systemd) and Windows only")
+    }
+}
+
+/// 서비스 중지 및 등록 해제. 등록되어 있지 않았으면 false.
+pub fn uninstall() -> Result<bool> {
+    if cfg!(target_os = "linux") {
+        let unit_path = systemd_unit_path()?;
+        if !unit_path.exists() {
+            return Ok(false);
+        }
+        // 이미 중지/비활성 상태여도 파일은 지움
+        if let Err(e) = systemctl(&["disable", "--now", &format!("{}.service", SERVICE_NAME)]) {
+            tracing::warn!("{:#}", e);
+        }
+        std::fs::remove_file(&unit_path)
+            .with_context(|| format!("Failed to remove unit file: {:?}", unit_path))?;
+        systemctl(&["daemon-reload"])?;
+        Ok(true)
+    } else if cfg!(windows) {
+        let script_path = windows_script_path();
+        let _ = run("schtasks", &["/End", "/TN", SERVICE_NAME]);
+        let removed = run("schtasks", &["/Delete", "/F", "/TN", SERVICE_NAME]).is_ok();
+        if script_path.exists() {
+            std::fs::remove_file(&script_path)
+                .with_context(|| format!("Failed to remove script: {:?}", script_path))?;
+        }
+        Ok(removed)
+    } else {
+        anyhow::bail!("Service uninstall is supported on Linux (systemd) and Windows only")
+    }
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {:?}", parent))?;
+    }
+    std::fs::write(path, contents).with_context(|| format!("Failed to write {:?}", path))
+}
+
+fn systemctl(args: &[&str]) -> Result<()> {
+    let mut full = vec!["--user"];
+    full.extend_from_slice(args);
+    run("systemctl", &full)
+}
+
+fn run(program: &str, args: &[&str]) -> Result<()> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .with_context(|| format!("Failed to run {}", program))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "{} {} failed: {}",
+            program,
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec() -> ServiceSpec {
+        ServiceSpec {
+            exe: PathBuf::from("/opt/palank rag/palank-rag"),
+            args: vec!["watch".to_string(), "/home/me/Shots 100%".to_string()],
+            log_path: PathBuf::from("/home/me/.palank-rag/logs/daemon.log"),
+        }
+    }
+
+    #[test]
+    fn test_systemd_unit() {
+        let unit = systemd_unit(&spec());
+        assert!(unit
+            .contains("ExecStart=\"/opt/palank rag/palank-rag\" watch \"/home/me/Shots 100%%\"\n"));
+        assert!(unit.contains("StandardOutput=append:/home/me/.palank-rag/logs/daemon.log\n"));
+        assert!(unit.contains("WantedBy=default.target"));
+    }
+
+    #[test]
+    fn test_windows_script() {
+        let script = windows_script(&spec());
+        assert!(script.starts_with("@echo off\r\n"));
+        assert!(script.contains(
+            "\"/opt/palank rag/palank-rag\" \"watch\" \"/home/me/Shots 100%%\" >> \"/home/me/.palank-rag/logs/daemon.log\" 2>&1\r\n"
+        ));
+    }
+}
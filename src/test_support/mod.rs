@@ -0,0 +1,329 @@
+//! 통합 테스트 지원 모듈 (`test-support` feature)
+//!
+//! 다운스트림 애플리케이션이 Gemini API 호출이나 ~/.palank-rag 오염 없이
+//! 이 크레이트를 대상으로 통합 테스트를 작성할 수 있도록 돕습니다.
+//!
+//! - `MockEmbedding`: 해시 기반 결정적 임베딩 (네트워크 없음)
+//! - `EphemeralRetriever`: 임시 디렉토리에 저장소를 여는 HybridRetriever
+//! - 픽스처 로더: 샘플 문서 / 폴더에서 문서 읽기
+//!
+//! ## 사용법
+//! ```rust,ignore
+//! let rag = EphemeralRetriever::with_fixtures(sample_documents()).await?;
+//! let results = rag.search("rust ownership", 3).await?;
+//! ```
+
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use tempfile::TempDir;
+
+use crate::embedding::EmbeddingProvider;
+use crate::knowledge::{HybridRetriever, NewDocument, EMBEDDING_DIMENSION};
+
+// ============================================================================
+// MockEmbedding
+// ============================================================================
+
+/// 결정적 Mock 임베딩 프로바이더
+///
+/// 단어를 해시하여 차원 버킷에 누적하는 bag-of-words 방식입니다.
+/// 같은 단어를 공유하는 텍스트끼리 유사도가 높아지므로
+/// 검색 순위를 검증하는 테스트에도 사용할 수 있습니다.
+#[derive(Debug, Clone)]
+pub struct MockEmbedding {
+    dimension: usize,
+    calls: Arc<AtomicUsize>,
+    /// embed 호출마다 지연 (밀리초, 복제본과 공유)
+    latency_ms: Arc<AtomicU64>,
+}
+
+impl MockEmbedding {
+    /// 기본 차원(EMBEDDING_DIMENSION)으로 생성
+    pub fn new() -> Self {
+        Self::with_dimension(EMBEDDING_DIMENSION as usize)
+    }
+
+    /// 차원을 지정하여 생성
+    pub fn with_dimension(dimension: usize) -> Self {
+        Self {
+            dimension,
+            calls: Arc::new(AtomicUsize::new(0)),
+            latency_ms: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// embed 호출마다 지연 설정 (API 지연 재현용, 이미 넘긴 복제본에도 적용)
+    pub fn set_latency(&self, latency: Duration) {
+        self.latency_ms.store(latency.as_millis() as u64, Ordering::SeqCst);
+    }
+
+    /// 지금까지의 embed 호출 횟수
+    pub fn call_count(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+
+    /// 텍스트를 결정적 벡터로 변환 (L2 정규화)
+    pub fn embed_sync(&self, text: &str) -> Vec<f32> {
+        let mut vector = vec![0.0f32; self.dimension];
+
+        for word in text.split_whitespace() {
+            let word: String = word
+                .chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect();
+            if word.is_empty() {
+                continue;
+            }
+
+            let hash = Sha256::digest(word.as_bytes());
+            let bucket = u64::from_le_bytes(hash[..8].try_into().unwrap_or_default()) as usize
+                % self.dimension;
+            vector[bucket] += 1.0;
+        }
+
+        let norm: f32 = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            vector.iter_mut().for_each(|x| *x /= norm);
+        }
+
+        vector
+    }
+}
+
+impl Default for MockEmbedding {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for MockEmbedding {
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        let latency = self.latency_ms.load(Ordering::SeqCst);
+        if latency > 0 {
+            tokio::time::sleep(Duration::from_millis(latency)).await;
+        }
+        Ok(self.embed_sync(text))
+    }
+
+    fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    fn name(&self) -> &str {
+        "mock-embedding"
+    }
+}
+
+// ============================================================================
+// EphemeralRetriever
+// ============================================================================
+
+/// 임시 디렉토리 기반 HybridRetriever
+///
+/// Drop 시 임시 디렉토리(SQLite + LanceDB)가 함께 삭제됩니다.
+/// `Deref`로 HybridRetriever의 모든 메서드를 그대로 사용할 수 있습니다.
+pub struct EphemeralRetriever {
+    retriever: HybridRetriever,
+    embedder: MockEmbedding,
+    dir: TempDir,
+}
+
+impl EphemeralRetriever {
+    /// MockEmbedding을 사용하는 빈 저장소 생성
+    pub async fn new() -> Result<Self> {
+        Self::with_embedder(MockEmbedding::new()).await
+    }
+
+    /// Mock 임베딩 인스턴스를 지정하여 생성
+    pub async fn with_embedder(embedder: MockEmbedding) -> Result<Self> {
+        let dir = TempDir::new().context("Failed to create temp directory")?;
+        let retriever = HybridRetriever::with_embedder(dir.path(), Box::new(embedder.clone()))
+            .await
+            .context("Failed to open ephemeral retriever")?;
+
+        Ok(Self {
+            retriever,
+            embedder,
+            dir,
+        })
+    }
+
+    /// 픽스처 문서를 미리 넣은 저장소 생성
+    pub async fn with_fixtures(docs: Vec<NewDocument>) -> Result<Self> {
+        let rag = Self::new().await?;
+        rag.retriever.add_documents(&docs).await?;
+        Ok(rag)
+    }
+
+    /// 임시 데이터 디렉토리 경로
+    pub fn data_dir(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// 사용 중인 Mock 임베딩 (호출 횟수 확인용)
+    pub fn embedder(&self) -> &MockEmbedding {
+        &self.embedder
+    }
+
+    /// 내부 HybridRetriever 접근
+    pub fn retriever(&self) -> &HybridRetriever {
+        &self.retriever
+    }
+}
+
+impl Deref for EphemeralRetriever {
+    type Target = HybridRetriever;
+
+    fn deref(&self) -> &Self::Target {
+        &self.retriever
+    }
+}
+
+// ============================================================================
+// Fixture Loaders
+// ============================================================================
+
+/// 기본 샘플 문서 세트
+///
+/// 서로 다른 주제의 짧은 문서 3개 (rust, react, sqlite)
+pub fn sample_documents() -> Vec<NewDocument> {
+    vec![
+        NewDocument {
+            url: "fixture://rust-ownership".to_string(),
+            title: Some("Rust Ownership".to_string()),
+            content: "# Rust Ownership\n\n\
+                      Ownership is a set of rules that govern how a Rust program manages memory. \
+                      Each value in Rust has an owner and borrowing lets code reference a value \
+                      without taking ownership."
+                .to_string(),
+            framework: Some("rust".to_string()),
+            ..Default::default()
+        },
+        NewDocument {
+            url: "fixture://react-hooks".to_string(),
+            title: Some("React Hooks".to_string()),
+            content: "# React Hooks\n\n\
+                      Hooks let you use state and other React features in function components. \
+                      useState declares a state variable and useEffect synchronizes a component \
+                      with an external system."
+                .to_string(),
+            framework: Some("react".to_string()),
+            ..Default::default()
+        },
+        NewDocument {
+            url: "fixture://sqlite-fts5".to_string(),
+            title: Some("SQLite FTS5".to_string()),
+            content: "# SQLite FTS5\n\n\
+                      FTS5 is an SQLite virtual table module that provides full-text search \
+                      functionality. The bm25 function ranks matches by relevance."
+                .to_string(),
+            framework: Some("sqlite".to_string()),
+            ..Default::default()
+        },
+    ]
+}
+
+/// 폴더의 텍스트 픽스처(.md, .txt)를 문서로 로드
+///
+/// URL은 `fixture://<파일명>`, 제목은 확장자를 뺀 파일명입니다.
+/// 파일명 순으로 정렬되어 반환됩니다.
+pub fn load_fixture_dir(dir: &Path) -> Result<Vec<NewDocument>> {
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read fixture directory: {:?}", dir))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.is_file()
+                && matches!(
+                    path.extension().and_then(|e| e.to_str()),
+                    Some("md") | Some("txt")
+                )
+        })
+        .collect();
+    paths.sort();
+
+    paths.iter().map(|path| load_fixture_file(path)).collect()
+}
+
+/// 단일 텍스트 픽스처 파일을 문서로 로드
+pub fn load_fixture_file(path: &Path) -> Result<NewDocument> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read fixture: {:?}", path))?;
+
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("fixture");
+    let title = path
+        .file_stem()
+        .and_then(|n| n.to_str())
+        .map(|s| s.to_string());
+
+    Ok(NewDocument {
+        url: format!("fixture://{}", file_name),
+        title,
+        content,
+        framework: None,
+        ..Default::default()
+    })
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::cosine_similarity;
+
+    #[test]
+    fn test_mock_embedding_deterministic() {
+        let embedder = MockEmbedding::new();
+        let a = embedder.embed_sync("Rust ownership rules");
+        let b = embedder.embed_sync("Rust ownership rules");
+        assert_eq!(a, b);
+        assert_eq!(a.len(), EMBEDDING_DIMENSION as usize);
+
+        let similar = embedder.embed_sync("ownership in Rust");
+        let unrelated = embedder.embed_sync("React hooks state");
+        assert!(cosine_similarity(&a, &similar) > cosine_similarity(&a, &unrelated));
+    }
+
+    #[tokio::test]
+    async fn test_ephemeral_retriever_search() {
+        let rag = EphemeralRetriever::with_fixtures(sample_documents())
+            .await
+            .unwrap();
+
+        let stats = rag.stats().await.unwrap();
+        assert_eq!(stats.document_count, 3);
+        assert!(rag.embedder().call_count() >= 3);
+
+        let results = rag.search("rust ownership borrowing", 3).await.unwrap();
+        assert!(!results.is_empty());
+        assert_eq!(results[0].url, "fixture://rust-ownership");
+    }
+
+    #[test]
+    fn test_load_fixture_dir() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("b.md"), "# B").unwrap();
+        std::fs::write(dir.path().join("a.txt"), "A").unwrap();
+        std::fs::write(dir.path().join("skip.bin"), "x").unwrap();
+
+        let docs = load_fixture_dir(dir.path()).unwrap();
+        assert_eq!(docs.len(), 2);
+        assert_eq!(docs[0].url, "fixture://a.txt");
+        assert_eq!(docs[1].title, Some("b".to_string()));
+    }
+}
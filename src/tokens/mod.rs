@@ -0,0 +1,155 @@
+//! 토큰 수 계산 - 프롬프트/임베딩 입력 예산용 근사치
+//!
+//! Gemini API 호출 없이 텍스트의 토큰 수를 추정합니다.
+//! 청크 미리보기, 임베딩 입력 한도 검사, ask 컨텍스트 구성에서 같은 기준을 쓰며,
+//! 라이브러리 사용자도 자체 프롬프트 예산을 잡는 데 쓸 수 있습니다.
+//!
+//! 기본 추정기(`GeminiEstimator`)는 실제 토크나이저보다 크게 잡아 한도 초과를 피합니다.
+//! 다른 모델이나 실제 토크나이저를 쓰려면 `Tokenizer`를 구현하여
+//! `ChunkConfig::tokenizer`(임베딩 입력 한도)나 `ContextOptions::tokenizer`(컨텍스트 예산)에 넘기면 됩니다.
+//!
+//! ## 사용법
+//! ```rust,ignore
+//! use std::sync::Arc;
+//! use palank_rag::knowledge::ContextOptions;
+//! use palank_rag::tokens::{count_tokens, CharRatio, Tokenizer};
+//!
+//! let tokens = count_tokens("Hello, world!");
+//! let openai_like = CharRatio::new("cl100k-approx", 4.0);
+//! let tokens = openai_like.count("Hello, world!");
+//!
+//! let options = ContextOptions {
+//!     max_tokens: Some(4000),
+//!     tokenizer: Arc::new(openai_like),
+//!     ..Default::default()
+//! };
+//! ```
+
+use std::fmt::Debug;
+use std::sync::Arc;
+
+// ============================================================================
+// Tokenizer Trait
+// ============================================================================
+
+/// 토큰 수 계산기 트레이트
+///
+/// 잘라내기는 이분 탐색으로 길이를 찾으므로, 텍스트가 길어질 때 토큰 수가 줄지 않아야 합니다.
+pub trait Tokenizer: Debug + Send + Sync {
+    /// 텍스트의 토큰 수
+    fn count(&self, text: &str) -> usize;
+
+    /// 토크나이저 이름
+    fn name(&self) -> &str;
+
+    /// 토큰 예산 이내인지
+    fn fits(&self, text: &str, budget: usize) -> bool {
+        self.count(text) <= budget
+    }
+}
+
+// ============================================================================
+// GeminiEstimator
+// ============================================================================
+
+/// Gemini 모델용 보수적 추정기 (ASCII 3자당 1토큰, 그 외 문자는 1자당 1토큰)
+///
+/// 영문은 실제로 4자 안팎에 1토큰이고 한글/한자는 1자에 1토큰 이하이므로
+/// 대부분의 텍스트에서 실제보다 크게 나옵니다.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GeminiEstimator;
+
+impl Tokenizer for GeminiEstimator {
+    fn count(&self, text: &str) -> usize {
+        let (ascii, other) = text.chars().fold((0usize, 0usize), |(ascii, other), c| {
+            if c.is_ascii() {
+                (ascii + 1, other)
+            } else {
+                (ascii, other + 1)
+            }
+        });
+        ascii.div_ceil(3) + other
+    }
+
+    fn name(&self) -> &str {
+        "gemini-estimate"
+    }
+}
+
+// ============================================================================
+// CharRatio
+// ============================================================================
+
+/// 문자 수 비율 추정기 (모델별 평균 문자/토큰 비율)
+#[derive(Debug, Clone)]
+pub struct CharRatio {
+    name: String,
+    chars_per_token: f32,
+}
+
+impl CharRatio {
+    /// 이름과 토큰당 평균 문자 수로 생성 (0 이하이면 1로 처리)
+    pub fn new(name: impl Into<String>, chars_per_token: f32) -> Self {
+        Self {
+            name: name.into(),
+            chars_per_token: if chars_per_token > 0.0 {
+                chars_per_token
+            } else {
+                1.0
+            },
+        }
+    }
+}
+
+impl Tokenizer for CharRatio {
+    fn count(&self, text: &str) -> usize {
+        (text.chars().count() as f32 / self.chars_per_token).ceil() as usize
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+// ============================================================================
+// Functions
+// ============================================================================
+
+/// 기본 추정기로 토큰 수 계산
+pub fn count_tokens(text: &str) -> usize {
+    GeminiEstimator.count(text)
+}
+
+/// 기본 토크나이저 생성 (`ChunkConfig`, `ContextOptions`의 기본값)
+pub fn default_tokenizer() -> Arc<dyn Tokenizer> {
+    Arc::new(GeminiEstimator)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gemini_estimator() {
+        assert_eq!(count_tokens(""), 0);
+        assert_eq!(count_tokens("abcd"), 2);
+        assert_eq!(count_tokens("한국어"), 3);
+        assert!(GeminiEstimator.fits("abcdef", 2));
+        assert!(!GeminiEstimator.fits("abcdefg", 2));
+    }
+
+    #[test]
+    fn test_char_ratio() {
+        let tokenizer = CharRatio::new("approx", 4.0);
+        assert_eq!(tokenizer.count("abcdefgh"), 2);
+        assert_eq!(tokenizer.count("abcdefghi"), 3);
+        assert_eq!(tokenizer.name(), "approx");
+
+        // 잘못된 비율은 문자당 1토큰
+        assert_eq!(CharRatio::new("bad", 0.0).count("abc"), 3);
+    }
+}
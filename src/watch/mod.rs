@@ -0,0 +1,590 @@
+//! 폴더 감시 모듈 - `watch`
+//!
+//! 스크린샷 폴더를 주기적으로 확인하여 새 이미지를 Gemini Vision으로 텍스트 추출한 뒤
+//! `screenshot` 태그로 수집합니다. 파일 내용 해시로 중복(복사본, 이름 변경)을 걸러내고,
+//! 처리한 해시는 상태 파일에 기록하여 재시작 후에도 다시 추출하지 않습니다.
+//! 실패한 파일은 해시별로 시도 횟수를 기록하여 점점 긴 간격으로 다시 시도하고, 영구 오류
+//! (4xx 응답, 지원하지 않는 형식)이거나 재시도 한도를 넘으면 처리한 것으로 기록합니다.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::collector::FileType;
+use crate::daemon::Shutdown;
+use crate::embedding::EmbeddingApiError;
+use crate::extractor::{ContentExtractor, ContentFilter, UnsupportedFormat};
+use crate::knowledge::{HybridRetriever, NewDocument, Provenance};
+use crate::notify::NotifyEvent;
+
+/// 스크린샷 문서 기본 태그
+pub const SCREENSHOT_TAG: &str = "screenshot";
+
+/// 감시 상태 디렉토리 이름 (데이터 디렉토리 기준, 감시 폴더마다 상태 파일 1개)
+pub const WATCH_STATE_DIR: &str = "watch";
+
+/// 쓰기 중인 파일을 피하기 위해 마지막 수정 후 기다리는 시간
+const DEFAULT_SETTLE_TIME: Duration = Duration::from_secs(2);
+
+/// 실패한 파일의 최대 시도 횟수 (넘으면 처리한 것으로 기록)
+pub const MAX_ATTEMPTS: u32 = 5;
+
+/// 첫 재시도까지 대기 시간 (이후 시도마다 2배)
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(60);
+
+// ============================================================================
+// Watch State
+// ============================================================================
+
+/// 처리한 파일 해시 기록
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WatchState {
+    /// 내용 해시(sha256) → 처음 수집한 파일 경로
+    pub seen: BTreeMap<String, PathBuf>,
+    /// 내용 해시 → 실패 기록 (성공하거나 포기하면 제거)
+    #[serde(default)]
+    pub failures: BTreeMap<String, FailureRecord>,
+}
+
+/// 실패한 파일의 재시도 기록
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FailureRecord {
+    /// 시도 횟수
+    pub attempts: u32,
+    /// 이 시각 이전에는 다시 시도하지 않음
+    pub retry_after: DateTime<Utc>,
+    /// 마지막 오류
+    pub error: String,
+}
+
+impl FailureRecord {
+    /// 시도 횟수에 따른 재시도 대기 시간 (1분, 2분, 4분, ...)
+    pub fn backoff(attempts: u32) -> Duration {
+        RETRY_BASE_DELAY * 2u32.pow(attempts.saturating_sub(1).min(10))
+    }
+}
+
+impl WatchState {
+    /// 상태 파일 로드 (없으면 빈 상태)
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read watch state: {:?}", path))?;
+        serde_json::from_str(&text)
+            .with_context(|| format!("Failed to parse watch state: {:?}", path))
+    }
+
+    /// 상태 파일 저장 (임시 파일에 쓴 뒤 교체)
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {:?}", parent))?;
+        }
+
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write watch state: {:?}", tmp))?;
+        std::fs::rename(&tmp, path)
+            .with_context(|| format!("Failed to replace watch state: {:?}", path))
+    }
+}
+
+// ============================================================================
+// Screenshot Watcher
+// ============================================================================
+
+/// 새로 발견된 스크린샷
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingScreenshot {
+    pub path: PathBuf,
+    /// 파일 내용 해시 (sha256 hex)
+    pub hash: String,
+}
+
+/// 한 번의 감시 패스 결과
+#[derive(Debug, Clone, Default)]
+pub struct WatchPass {
+    /// 수집된 파일과 문서 ID
+    pub added: Vec<(PathBuf, i64)>,
+    /// 실패한 파일과 오류 (재시도 대기 후 다시 시도)
+    pub failed: Vec<(PathBuf, String)>,
+    /// 다시 시도하지 않을 파일과 오류 (영구 오류 또는 재시도 한도 초과, 한 번만 보고)
+    pub gave_up: Vec<(PathBuf, String)>,
+    /// 추출된 본문이 너무 짧아 건너뛴 파일 (다시 추출하지 않음)
+    pub too_short: Vec<PathBuf>,
+}
+
+impl WatchPass {
+    /// 알림 이벤트로 변환 (추가/실패가 없으면 빈 목록)
+    ///
+    /// 재시도할 실패와 포기한 파일을 함께 하나의 실패 알림으로 보냅니다.
+    pub fn events(&self, dir: &Path) -> Vec<NotifyEvent> {
+        let source = dir.display().to_string();
+        let mut events = Vec::new();
+
+        if !self.added.is_empty() {
+            events.push(NotifyEvent::DocumentsAdded {
+                source: source.clone(),
+                count: self.added.len(),
+            });
+        }
+        if let Some((path, error)) = self.failed.iter().chain(&self.gave_up).next() {
+            events.push(NotifyEvent::IngestFailed {
+                source,
+                count: self.failed.len() + self.gave_up.len(),
+                error: format!("{}: {}", path.display(), error),
+            });
+        }
+
+        events
+    }
+}
+
+/// 스크린샷 폴더 감시기
+pub struct ScreenshotWatcher {
+    dir: PathBuf,
+    state_path: PathBuf,
+    state: WatchState,
+    tag: String,
+    settle_time: Duration,
+    /// 이미 확인한 파일의 (크기, 수정 시각) - 변경되지 않은 파일은 다시 해시하지 않음
+    checked: HashMap<PathBuf, (u64, SystemTime)>,
+    /// 종료 요청 시 다음 파일부터 처리하지 않음
+    shutdown: Option<Shutdown>,
+    /// 최소 본문 길이 필터
+    content_filter: ContentFilter,
+}
+
+impl ScreenshotWatcher {
+    /// 감시기 생성 (상태 파일이 있으면 로드)
+    pub fn new(dir: impl Into<PathBuf>, state_path: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        if !dir.is_dir() {
+            anyhow::bail!("Watch directory not found: {:?}", dir);
+        }
+
+        let state_path = state_path.into();
+        let state = WatchState::load(&state_path)?;
+
+        Ok(Self {
+            dir,
+            state_path,
+            state,
+            tag: SCREENSHOT_TAG.to_string(),
+            settle_time: DEFAULT_SETTLE_TIME,
+            checked: HashMap::new(),
+            shutdown: None,
+            content_filter: ContentFilter::default(),
+        })
+    }
+
+    /// 수집 문서 태그 지정 (기본: `screenshot`)
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = tag.into();
+        self
+    }
+
+    /// 마지막 수정 후 대기 시간 지정
+    pub fn with_settle_time(mut self, settle_time: Duration) -> Self {
+        self.settle_time = settle_time;
+        self
+    }
+
+    /// 최소 본문 길이 필터 지정 (기본: `ContentFilter::default()`)
+    pub fn with_content_filter(mut self, content_filter: ContentFilter) -> Self {
+        self.content_filter = content_filter;
+        self
+    }
+
+    /// 종료 요청 핸들 지정 (요청되면 처리 중인 파일까지만 수집)
+    pub fn with_shutdown(mut self, shutdown: Shutdown) -> Self {
+        self.shutdown = Some(shutdown);
+        self
+    }
+
+    /// 감시 폴더
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// 수집 기록 수
+    pub fn seen_count(&self) -> usize {
+        self.state.seen.len()
+    }
+
+    /// 아직 수집하지 않은 이미지 찾기 (하위 폴더 제외)
+    ///
+    /// 내용이 같은 파일은 하나만 반환합니다. 읽을 수 없는 파일은 로그만 남기고 건너뛰고,
+    /// 재시도 대기 중인 파일은 대기 시간이 지난 뒤의 패스에서 반환합니다.
+    pub fn scan(&mut self) -> Result<Vec<PendingScreenshot>> {
+        let entries = std::fs::read_dir(&self.dir)
+            .with_context(|| format!("Failed to read watch directory: {:?}", self.dir))?;
+
+        let now = SystemTime::now();
+        let mut pending: Vec<PendingScreenshot> = Vec::new();
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if FileType::from_path(&path) != Some(FileType::Image) {
+                continue;
+            }
+
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if !metadata.is_file() {
+                continue;
+            }
+            let modified = metadata.modified().unwrap_or(now);
+            if now.duration_since(modified).unwrap_or_default() < self.settle_time {
+                continue;
+            }
+            if self.checked.get(&path) == Some(&(metadata.len(), modified)) {
+                continue;
+            }
+
+            // 읽을 수 없는 파일은 기록해 두고 건너뜀 (크기/수정 시각이 바뀌면 다시 확인)
+            self.checked
+                .insert(path.clone(), (metadata.len(), modified));
+            let hash = match file_hash(&path) {
+                Ok(hash) => hash,
+                Err(e) => {
+                    tracing::warn!("Skipping unreadable screenshot: {:#}", e);
+                    continue;
+                }
+            };
+
+            if self.state.seen.contains_key(&hash) || pending.iter().any(|p| p.hash == hash) {
+                continue;
+            }
+            if self.is_backing_off(&hash) {
+                // 대기 시간이 지나면 다시 확인
+                self.checked.remove(&path);
+                continue;
+            }
+            pending.push(PendingScreenshot { path, hash });
+        }
+
+        pending.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(pending)
+    }
+
+    /// 수집 완료 기록 (상태 파일 즉시 저장)
+    pub fn mark_done(&mut self, screenshot: &PendingScreenshot) -> Result<()> {
+        self.state.failures.remove(&screenshot.hash);
+        self.state
+            .seen
+            .insert(screenshot.hash.clone(), screenshot.path.clone());
+        self.state.save(&self.state_path)
+    }
+
+    /// 실패 기록 (상태 파일 즉시 저장)
+    ///
+    /// 다시 시도할 수 있으면 `true`를 반환하고 재시도 대기 시간을 설정합니다.
+    /// 재시도 한도를 넘으면 처리한 것으로 기록하고 `false`를 반환합니다.
+    pub fn record_failure(&mut self, screenshot: &PendingScreenshot, error: &str) -> Result<bool> {
+        let attempts = self
+            .state
+            .failures
+            .get(&screenshot.hash)
+            .map_or(0, |f| f.attempts)
+            + 1;
+        if attempts >= MAX_ATTEMPTS {
+            self.mark_done(screenshot)?;
+            return Ok(false);
+        }
+
+        let backoff = chrono::Duration::from_std(FailureRecord::backoff(attempts))?;
+        self.state.failures.insert(
+            screenshot.hash.clone(),
+            FailureRecord {
+                attempts,
+                retry_after: Utc::now() + backoff,
+                error: error.to_string(),
+            },
+        );
+        self.checked.remove(&screenshot.path);
+        self.state.save(&self.state_path)?;
+        Ok(true)
+    }
+
+    /// 재시도 대기 중인 해시인지
+    fn is_backing_off(&self, hash: &str) -> bool {
+        self.state
+            .failures
+            .get(hash)
+            .is_some_and(|f| f.retry_after > Utc::now())
+    }
+
+    /// 한 번의 감시 패스: 새 이미지를 추출하여 수집
+    pub async fn ingest_new(
+        &mut self,
+        retriever: &HybridRetriever,
+        extractor: &ContentExtractor,
+    ) -> Result<WatchPass> {
+        let mut pass = WatchPass::default();
+
+        for screenshot in self.scan()? {
+            if self.shutdown.as_ref().is_some_and(|s| s.is_requested()) {
+                // 남은 파일은 재시작 후 다시 발견됨
+                break;
+            }
+
+            let result = match extractor.extract(&screenshot.path, FileType::Image).await {
+                Ok(contents) => {
+                    let text = contents
+                        .into_iter()
+                        .map(|c| c.text)
+                        .collect::<Vec<_>>()
+                        .join("\n\n");
+                    if !self.content_filter.accepts(&text) {
+                        // 글자가 거의 없는 이미지: 다시 추출해도 같으므로 처리한 것으로 기록
+                        self.mark_done(&screenshot)?;
+                        pass.too_short.push(screenshot.path);
+                        continue;
+                    }
+                    let doc = screenshot_document(&screenshot.path, &text, &self.tag);
+                    retriever.add_document(doc).await
+                }
+                Err(e) => Err(e),
+            };
+
+            match result {
+                Ok(doc_id) => {
+                    self.mark_done(&screenshot)?;
+                    pass.added.push((screenshot.path, doc_id));
+                }
+                Err(e) => {
+                    let error = e.to_string();
+                    if is_permanent_failure(&e) {
+                        // 다시 시도해도 같은 결과: 처리한 것으로 기록하고 한 번만 보고
+                        self.mark_done(&screenshot)?;
+                        pass.gave_up.push((screenshot.path, error));
+                    } else if self.record_failure(&screenshot, &error)? {
+                        pass.failed.push((screenshot.path, error));
+                    } else {
+                        pass.gave_up.push((screenshot.path, error));
+                    }
+                }
+            }
+        }
+
+        Ok(pass)
+    }
+}
+
+/// 다시 시도해도 같은 결과인 오류 (지원하지 않는 형식, 4xx 응답)
+///
+/// 429(요청 한도)와 API 키 오류는 파일 문제가 아니므로 재시도 대상입니다.
+pub fn is_permanent_failure(error: &anyhow::Error) -> bool {
+    if UnsupportedFormat::is_cause_of(error) {
+        return true;
+    }
+    EmbeddingApiError::find(error)
+        .is_some_and(|e| e.is_client_error() && e.status != 429 && !e.is_auth_error())
+}
+
+/// 감시 폴더의 상태 파일 경로 (`<data_dir>/watch/<폴더 경로 해시>.json`)
+pub fn watch_state_path(data_dir: &Path, dir: &Path) -> PathBuf {
+    let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    let hash: String = Sha256::digest(dir.to_string_lossy().as_bytes())
+        .iter()
+        .take(8)
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    data_dir
+        .join(WATCH_STATE_DIR)
+        .join(format!("{}.json", hash))
+}
+
+/// 스크린샷 문서 생성
+pub fn screenshot_document(path: &Path, text: &str, tag: &str) -> NewDocument {
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string());
+
+    NewDocument {
+        url: format!("file://{}", path.display()),
+        content: format!("# {}\n\n{}", file_name, text.trim()),
+        title: Some(file_name),
+        framework: Some(tag.to_string()),
+        provenance: Some(Provenance::current("watch").with_extractor("vision")),
+        ..Default::default()
+    }
+}
+
+/// 파일 내용 해시 (sha256 hex)
+pub fn file_hash(path: &Path) -> Result<String> {
+    let bytes = std::fs::read(path).with_context(|| format!("Failed to read file: {:?}", path))?;
+    Ok(Sha256::digest(&bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_scan_dedups_by_content() {
+        let dir = TempDir::new().unwrap();
+        let shots = dir.path().join("shots");
+        std::fs::create_dir(&shots).unwrap();
+        std::fs::write(shots.join("a.png"), b"image-a").unwrap();
+        std::fs::write(shots.join("a copy.png"), b"image-a").unwrap();
+        std::fs::write(shots.join("b.jpg"), b"image-b").unwrap();
+        std::fs::write(shots.join("notes.txt"), b"not an image").unwrap();
+
+        let state_path = watch_state_path(dir.path(), &shots);
+        let mut watcher = ScreenshotWatcher::new(&shots, &state_path)
+            .unwrap()
+            .with_settle_time(Duration::ZERO);
+
+        let pending = watcher.scan().unwrap();
+        assert_eq!(pending.len(), 2);
+        for screenshot in &pending {
+            watcher.mark_done(screenshot).unwrap();
+        }
+
+        // 변경 없는 파일은 다시 나오지 않음
+        assert!(watcher.scan().unwrap().is_empty());
+
+        // 재시작 후에도 같은 내용의 새 파일은 중복으로 처리
+        std::fs::write(shots.join("renamed.png"), b"image-b").unwrap();
+        let mut watcher = ScreenshotWatcher::new(&shots, &state_path)
+            .unwrap()
+            .with_settle_time(Duration::ZERO);
+        assert_eq!(watcher.seen_count(), 2);
+        assert!(watcher.scan().unwrap().is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_scan_skips_unreadable() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new().unwrap();
+        let shots = dir.path().join("shots");
+        std::fs::create_dir(&shots).unwrap();
+        std::fs::write(shots.join("a.png"), b"image-a").unwrap();
+        let locked = shots.join("locked.png");
+        std::fs::write(&locked, b"image-b").unwrap();
+        std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o000)).unwrap();
+        if std::fs::read(&locked).is_ok() {
+            // root 권한에서는 권한과 관계없이 읽힘
+            return;
+        }
+
+        let state_path = watch_state_path(dir.path(), &shots);
+        let mut watcher = ScreenshotWatcher::new(&shots, &state_path)
+            .unwrap()
+            .with_settle_time(Duration::ZERO);
+
+        let pending = watcher.scan().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].path, shots.join("a.png"));
+    }
+
+    #[test]
+    fn test_record_failure_backs_off_and_gives_up() {
+        let dir = TempDir::new().unwrap();
+        let shots = dir.path().join("shots");
+        std::fs::create_dir(&shots).unwrap();
+        std::fs::write(shots.join("a.png"), b"image-a").unwrap();
+
+        let state_path = watch_state_path(dir.path(), &shots);
+        let mut watcher = ScreenshotWatcher::new(&shots, &state_path)
+            .unwrap()
+            .with_settle_time(Duration::ZERO);
+
+        let pending = watcher.scan().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert!(watcher.record_failure(&pending[0], "error").unwrap());
+
+        // 대기 시간 동안은 다시 나오지 않음 (재시작 후에도)
+        assert!(watcher.scan().unwrap().is_empty());
+        let mut watcher = ScreenshotWatcher::new(&shots, &state_path)
+            .unwrap()
+            .with_settle_time(Duration::ZERO);
+        assert!(watcher.scan().unwrap().is_empty());
+
+        // 대기 시간이 지나면 다시 시도
+        let hash = pending[0].hash.clone();
+        watcher.state.failures.get_mut(&hash).unwrap().retry_after = Utc::now();
+        assert_eq!(watcher.scan().unwrap(), pending);
+
+        for _ in 2..MAX_ATTEMPTS {
+            assert!(watcher.record_failure(&pending[0], "error").unwrap());
+        }
+        assert_eq!(watcher.state.failures[&hash].attempts, MAX_ATTEMPTS - 1);
+
+        // 한도에 도달하면 처리한 것으로 기록
+        assert!(!watcher.record_failure(&pending[0], "error").unwrap());
+        assert!(watcher.state.failures.is_empty());
+        assert_eq!(watcher.seen_count(), 1);
+        assert!(WatchState::load(&state_path).unwrap().failures.is_empty());
+    }
+
+    #[test]
+    fn test_backoff_doubles() {
+        assert_eq!(FailureRecord::backoff(1), Duration::from_secs(60));
+        assert_eq!(FailureRecord::backoff(2), Duration::from_secs(120));
+        assert_eq!(FailureRecord::backoff(4), Duration::from_secs(480));
+    }
+
+    #[test]
+    fn test_is_permanent_failure() {
+        let api = |status: u16, message: &str| {
+            anyhow::Error::new(EmbeddingApiError {
+                status,
+                message: message.to_string(),
+            })
+            .context("Vision API error")
+        };
+
+        assert!(is_permanent_failure(&api(400, "INVALID_ARGUMENT")));
+        assert!(is_permanent_failure(
+            &anyhow::Error::new(UnsupportedFormat("image/heic".into())).context("extract")
+        ));
+        // 요청 한도, 키 문제, 서버 오류, 네트워크 오류는 다시 시도
+        assert!(!is_permanent_failure(&api(429, "RESOURCE_EXHAUSTED")));
+        assert!(!is_permanent_failure(&api(403, "PERMISSION_DENIED")));
+        assert!(!is_permanent_failure(&api(400, "API key not valid")));
+        assert!(!is_permanent_failure(&api(503, "UNAVAILABLE")));
+        assert!(!is_permanent_failure(&anyhow::anyhow!("connection reset")));
+    }
+
+    #[test]
+    fn test_old_state_file_loads() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("state.json");
+        std::fs::write(&path, r#"{"seen":{"abc":"/shots/a.png"}}"#).unwrap();
+        let state = WatchState::load(&path).unwrap();
+        assert_eq!(state.seen.len(), 1);
+        assert!(state.failures.is_empty());
+    }
+
+    #[test]
+    fn test_screenshot_document() {
+        let doc = screenshot_document(Path::new("/shots/error.png"), " E0382 ", SCREENSHOT_TAG);
+        assert_eq!(doc.url, "file:///shots/error.png");
+        assert_eq!(doc.title.as_deref(), Some("error.png"));
+        assert_eq!(doc.content, "# error.png\n\nE0382");
+        assert_eq!(doc.framework.as_deref(), Some("screenshot"));
+    }
+}